@@ -643,6 +643,41 @@ fn testBorderCompact4Case() {
     assert_eq!(4, aztecCode.getLayers());
 }
 
+#[test]
+fn testEncodeBytesSymbolSize() {
+    // The smallest symbol: compact with a single layer
+    let aztec = aztec_encoder::encode_bytes(b"ABC", 23, 0).expect("should encode");
+    assert!(aztec.isCompact());
+    assert_eq!(1, aztec.getLayers());
+    assert_eq!(15, aztec.getSize());
+    assert_eq!(15, aztec.getMatrix().getWidth());
+    assert_eq!(15, aztec.getMatrix().getHeight());
+    assert_eq!(28, aztec.getModeMessage().getSize());
+    assert_eq!(
+        &aztec_encoder::generateModeMessage(true, 1, aztec.getCodeWords()).expect("mode message"),
+        aztec.getModeMessage()
+    );
+
+    // Too much data for a compact symbol goes full range
+    let data = [0x80u8; 100];
+    let aztec = aztec_encoder::encode_bytes(&data, 23, 0).expect("should encode");
+    assert!(!aztec.isCompact());
+    assert_eq!(aztec.getSize(), aztec.getMatrix().getWidth());
+    assert_eq!(40, aztec.getModeMessage().getSize());
+    assert_eq!(
+        &aztec_encoder::generateModeMessage(false, aztec.getLayers(), aztec.getCodeWords())
+            .expect("mode message"),
+        aztec.getModeMessage()
+    );
+
+    // A full range symbol of 5 layers has one alignment line on each side of the bull's eye
+    let aztec = aztec_encoder::encode_bytes(b"ABC", 23, 5).expect("should encode");
+    assert!(!aztec.isCompact());
+    assert_eq!(5, aztec.getLayers());
+    assert_eq!(37, aztec.getSize());
+    assert_eq!(37, aztec.getMatrix().getWidth());
+}
+
 // Helper routines
 
 fn testEncode(data: &str, compact: bool, layers: u32, expected: &str) {
@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use crate::common::BitMatrix;
+use crate::common::{BitArray, BitMatrix};
 
 /**
  * Aztec 2D code representation
@@ -27,6 +27,7 @@ pub struct AztecCode {
     layers: u32,
     code_words: u32,
     matrix: BitMatrix,
+    mode_message: BitArray,
 }
 
 impl AztecCode {
@@ -37,6 +38,7 @@ impl AztecCode {
             layers,
             code_words,
             matrix,
+            mode_message: BitArray::new(),
         }
     }
 
@@ -94,4 +96,16 @@ impl AztecCode {
     pub fn setMatrix(&mut self, matrix: BitMatrix) {
         self.matrix = matrix;
     }
+
+    /**
+     * @return the mode message drawn around the bull's eye: the layer count and the number of
+     *  data codewords, with their error correction (28 bits when compact, otherwise 40)
+     */
+    pub fn getModeMessage(&self) -> &BitArray {
+        &self.mode_message
+    }
+
+    pub fn setModeMessage(&mut self, mode_message: BitArray) {
+        self.mode_message = mode_message;
+    }
 }
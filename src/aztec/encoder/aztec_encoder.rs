@@ -310,7 +310,7 @@ pub fn encode_bytes_with_charset(
     // dbg!(matrix.to_string());
 
     // draw mode message
-    drawModeMessage(&mut matrix, compact, matrixSize, modeMessage);
+    drawModeMessage(&mut matrix, compact, matrixSize, &modeMessage);
 
     // dbg!(matrix.to_string());
 
@@ -325,10 +325,14 @@ pub fn encode_bytes_with_charset(
             let mut k = (matrixSize / 2) & 1;
             while k < matrixSize {
                 // for (int k = (matrixSize / 2) & 1; k < matrixSize; k += 2) {
-                matrix.set(matrixSize / 2 - j, k);
-                matrix.set(matrixSize / 2 + j, k);
-                matrix.set(k, matrixSize / 2 - j);
-                matrix.set(k, matrixSize / 2 + j);
+                // SAFETY: k < matrixSize, and matrixSize was grown above to hold every
+                // alignment line at matrixSize / 2 +- j
+                unsafe {
+                    matrix.set_unchecked(matrixSize / 2 - j, k);
+                    matrix.set_unchecked(matrixSize / 2 + j, k);
+                    matrix.set_unchecked(k, matrixSize / 2 - j);
+                    matrix.set_unchecked(k, matrixSize / 2 + j);
+                }
 
                 k += 2;
             }
@@ -348,7 +352,8 @@ pub fn encode_bytes_with_charset(
 
     // dbg!(matrix.to_string());
 
-    let aztec = AztecCode::new(compact, matrixSize, layers, messageSizeInWords, matrix);
+    let mut aztec = AztecCode::new(compact, matrixSize, layers, messageSizeInWords, matrix);
+    aztec.setModeMessage(modeMessage);
     // aztec.setCompact(compact);
     // aztec.setSize(matrixSize);
     // aztec.setLayers(layers);
@@ -363,10 +368,14 @@ fn drawBullsEye(matrix: &mut BitMatrix, center: u32, size: u32) {
         // for (int i = 0; i < size; i += 2) {
         for j in (center - i)..=(center + i) {
             // for (int j = center - i; j <= center + i; j++) {
-            matrix.set(j, center - i);
-            matrix.set(j, center + i);
-            matrix.set(center - i, j);
-            matrix.set(center + i, j);
+            // SAFETY: the bull's eye is drawn around the center of a symbol at least 2 * size + 1
+            // modules wide, so center +- i stays inside the matrix
+            unsafe {
+                matrix.set_unchecked(j, center - i);
+                matrix.set_unchecked(j, center + i);
+                matrix.set_unchecked(center - i, j);
+                matrix.set_unchecked(center + i, j);
+            }
         }
         i += 2;
     }
@@ -404,7 +413,7 @@ pub fn generateModeMessage(
     Ok(mode_message)
 }
 
-fn drawModeMessage(matrix: &mut BitMatrix, compact: bool, matrixSize: u32, modeMessage: BitArray) {
+fn drawModeMessage(matrix: &mut BitMatrix, compact: bool, matrixSize: u32, modeMessage: &BitArray) {
     let center = matrixSize / 2;
     if compact {
         for i in 0..7usize {
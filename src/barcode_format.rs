@@ -62,6 +62,9 @@ pub enum BarcodeFormat {
     /** PDF417 format. */
     PDF_417,
 
+    /** Pharmacode 1D format, as used on pharmaceutical packaging. */
+    PHARMA_CODE,
+
     /** QR Code 2D barcode format. */
     QR_CODE,
 
@@ -71,6 +74,9 @@ pub enum BarcodeFormat {
     /** RSS EXPANDED */
     RSS_EXPANDED,
 
+    /** Telepen 1D format. */
+    TELEPEN,
+
     /** UPC-A 1D format. */
     UPC_A,
 
@@ -101,9 +107,11 @@ impl Display for BarcodeFormat {
                 BarcodeFormat::ITF => "itf",
                 BarcodeFormat::MAXICODE => "maxicode",
                 BarcodeFormat::PDF_417 => "pdf 417",
+                BarcodeFormat::PHARMA_CODE => "pharmacode",
                 BarcodeFormat::QR_CODE => "qrcode",
                 BarcodeFormat::RSS_14 => "rss 14",
                 BarcodeFormat::RSS_EXPANDED => "rss expanded",
+                BarcodeFormat::TELEPEN => "telepen",
                 BarcodeFormat::UPC_A => "upc a",
                 BarcodeFormat::UPC_E => "upc e",
                 BarcodeFormat::UPC_EAN_EXTENSION => "upc/ean extension",
@@ -139,10 +147,12 @@ impl From<&str> for BarcodeFormat {
             }
             "maxicode" | "maxi_code" => BarcodeFormat::MAXICODE,
             "pdf 417" | "pdf_417" | "pdf417" | "iso 15438" | "iso_15438" => BarcodeFormat::PDF_417,
+            "pharmacode" | "pharma code" | "pharma_code" => BarcodeFormat::PHARMA_CODE,
             "qrcode" | "qr_code" | "qr code" => BarcodeFormat::QR_CODE,
             "rss 14" | "rss_14" | "rss14" | "gs1 databar" | "gs1 databar coupon"
             | "gs1_databar_coupon" => BarcodeFormat::RSS_14,
             "rss expanded" | "expanded rss" | "rss_expanded" => BarcodeFormat::RSS_EXPANDED,
+            "telepen" => BarcodeFormat::TELEPEN,
             "upc a" | "upc_a" | "upca" => BarcodeFormat::UPC_A,
             "upc e" | "upc_e" | "upce" => BarcodeFormat::UPC_E,
             "upc ean extension" | "upc extension" | "ean extension" | "upc/ean extension"
@@ -16,11 +16,11 @@
 
 //package com.google.zxing;
 
-use std::{borrow::Cow, fmt, rc::Rc};
+use std::{borrow::Cow, cell::RefCell, collections::VecDeque, fmt, rc::Rc};
 
 use crate::{
-    common::{BitArray, BitMatrix},
-    Binarizer, Exceptions,
+    common::{BitArray, BitMatrix, HybridBinarizer},
+    Binarizer, Exceptions, GammaLuminanceSource, Luma8LuminanceSource, LuminanceSource,
 };
 
 /**
@@ -33,16 +33,105 @@ use crate::{
 pub struct BinaryBitmap {
     binarizer: Rc<dyn Binarizer>,
     matrix: Option<BitMatrix>,
+    // Rows recently computed by a binarizer without a cache of its own, keyed by y, least
+    // recently used first. At most BLACK_ROW_CACHE_SIZE rows are kept. The rows only depend on
+    // the binarizer, which never changes, so they are never invalidated: cropping, rotating and
+    // scaling produce a new BinaryBitmap with an empty cache instead.
+    black_row_cache: RefCell<VecDeque<(usize, BitArray)>>,
 }
 
+/// The most black rows a BinaryBitmap keeps, enough for the rows around the one being scanned
+pub(crate) const BLACK_ROW_CACHE_SIZE: usize = 32;
+
 impl BinaryBitmap {
     pub fn new(binarizer: Rc<dyn Binarizer>) -> Self {
         Self {
             matrix: None,
+            black_row_cache: RefCell::new(VecDeque::with_capacity(BLACK_ROW_CACHE_SIZE)),
             binarizer,
         }
     }
 
+    /**
+     * Creates a bitmap from a boxed binarizer, for callers which supply their own {@link Binarizer}
+     * implementation rather than one of the built in ones.
+     */
+    pub fn from_boxed_binarizer(binarizer: Box<dyn Binarizer>) -> Self {
+        Self::new(Rc::from(binarizer))
+    }
+
+    /**
+     * Creates a bitmap from 8 bit grayscale pixels, one byte per pixel in row major order, and
+     * binarizes it with a {@link HybridBinarizer}. Use {@link #new} to choose another binarizer.
+     *
+     * @throws IllegalArgumentException if there are not exactly width * height pixels
+     */
+    pub fn from_luma8(pixels: Vec<u8>, width: u32, height: u32) -> Result<Self, Exceptions> {
+        if pixels.len() != width as usize * height as usize {
+            return Err(Exceptions::IllegalArgumentException(Some(format!(
+                "expected {} luma pixels for {width}x{height}, got {}",
+                width as usize * height as usize,
+                pixels.len()
+            ))));
+        }
+        Ok(Self::new(Rc::new(HybridBinarizer::new(Box::new(
+            Luma8LuminanceSource::new(pixels, width, height),
+        )))))
+    }
+
+    /**
+     * Creates a bitmap from RGBA pixels, four bytes per pixel in row major order, and binarizes it
+     * with a {@link HybridBinarizer}. Colors are weighted like {@link BufferedImageLuminanceSource}
+     * does, and transparent areas are composited over white.
+     *
+     * ```
+     * use rxing::{qrcode::{QRCodeReader, QRCodeWriter}, BarcodeFormat, BinaryBitmap, Reader, Writer};
+     *
+     * let matrix = QRCodeWriter.encode("hello", &BarcodeFormat::QR_CODE, 100, 100)?;
+     * let mut rgba = Vec::new();
+     * for y in 0..matrix.getHeight() {
+     *     for x in 0..matrix.getWidth() {
+     *         let v = if matrix.get(x, y) { 0 } else { 255 };
+     *         rgba.extend([v, v, v, 255]);
+     *     }
+     * }
+     *
+     * let mut image = BinaryBitmap::from_rgba(&rgba, matrix.getWidth(), matrix.getHeight())?;
+     * assert_eq!("hello", QRCodeReader.decode(&mut image)?.getText());
+     * # Ok::<(), rxing::Exceptions>(())
+     * ```
+     *
+     * @throws IllegalArgumentException if there are not exactly width * height * 4 bytes
+     */
+    pub fn from_rgba(pixels: &[u8], width: u32, height: u32) -> Result<Self, Exceptions> {
+        if pixels.len() != width as usize * height as usize * 4 {
+            return Err(Exceptions::IllegalArgumentException(Some(format!(
+                "expected {} RGBA bytes for {width}x{height}, got {}",
+                width as usize * height as usize * 4,
+                pixels.len()
+            ))));
+        }
+        Self::from_luma8(rgba_to_luma(pixels), width, height)
+    }
+
+    /**
+     * Creates a bitmap from a grayscale image, binarized with a {@link HybridBinarizer}.
+     */
+    #[cfg(feature = "image")]
+    pub fn from_gray_image(image: image::GrayImage) -> Self {
+        let (width, height) = image.dimensions();
+        Self::new(Rc::new(HybridBinarizer::new(Box::new(
+            Luma8LuminanceSource::new(image.into_raw(), width, height),
+        ))))
+    }
+
+    /**
+     * @return The luminance data the bitmap is binarized from.
+     */
+    pub fn getLuminanceSource(&self) -> &dyn LuminanceSource {
+        self.binarizer.getLuminanceSource().as_ref()
+    }
+
     /**
      * @return The width of the bitmap.
      */
@@ -69,7 +158,56 @@ impl BinaryBitmap {
      * @throws NotFoundException if row can't be binarized
      */
     pub fn getBlackRow(&self, y: usize) -> Result<Cow<BitArray>, Exceptions> {
-        self.binarizer.getBlackRow(y)
+        // Binarizers which already keep their own cache hand back borrowed rows, which are
+        // passed straight through. Freshly computed rows are retained here, so repeated scans of
+        // recent rows (for instance when trying several readers, or when voting across
+        // neighbouring rows) don't binarize them again.
+        if let Some(row) = self.withCachedBlackRow(y, BitArray::clone) {
+            return Ok(Cow::Owned(row));
+        }
+        match self.binarizer.getBlackRow(y)? {
+            Cow::Owned(row) => {
+                let mut cache = self.black_row_cache.borrow_mut();
+                if cache.len() == BLACK_ROW_CACHE_SIZE {
+                    cache.pop_front();
+                }
+                cache.push_back((y, row.clone()));
+                Ok(Cow::Owned(row))
+            }
+            borrowed => Ok(borrowed),
+        }
+    }
+
+    /**
+     * Applies useRow to row y if it's cached, marking it as the most recently used row.
+     */
+    fn withCachedBlackRow<T>(&self, y: usize, useRow: impl FnOnce(&BitArray) -> T) -> Option<T> {
+        let mut cache = self.black_row_cache.borrow_mut();
+        let index = cache.iter().position(|(cachedY, _)| *cachedY == y)?;
+        let entry = cache.remove(index)?;
+        let result = useRow(&entry.1);
+        cache.push_back(entry);
+        Some(result)
+    }
+
+    /**
+     * Converts one row of luminance data to 1 bit data, writing it into a caller provided
+     * buffer. Callers which scan many rows should reuse the same BitArray for each call to avoid
+     * allocating a new row every time.
+     *
+     * @param y The row to fetch, which must be in [0, bitmap height)
+     * @param row The array to overwrite with the bits for this row (true means black).
+     * @throws NotFoundException if row can't be binarized
+     */
+    pub fn getBlackRowInto(&self, y: usize, row: &mut BitArray) -> Result<(), Exceptions> {
+        if self
+            .withCachedBlackRow(y, |cached| row.clone_from(cached))
+            .is_some()
+        {
+            return Ok(());
+        }
+        row.clone_from(self.getBlackRow(y)?.as_ref());
+        Ok(())
     }
 
     /**
@@ -146,6 +284,69 @@ impl BinaryBitmap {
         // }
     }
 
+    /**
+     * Returns a new object with the image data scaled down by an integer factor, each pixel being
+     * the average luminance of a factor x factor block. Rows and columns at the right and bottom
+     * edges which don't fill a whole block are dropped.
+     *
+     * @param factor How many pixels in each direction are combined into one.
+     * @return A scaled down copy of this object.
+     */
+    pub fn scaleDown(&self, factor: usize) -> BinaryBitmap {
+        let factor = factor.max(1);
+        let source = self.binarizer.getLuminanceSource();
+        let width = source.getWidth() / factor;
+        let height = source.getHeight() / factor;
+
+        let mut pixels = Vec::with_capacity(width * height);
+        let mut sums = vec![0u32; width];
+        for y in 0..height {
+            sums.fill(0);
+            for row in y * factor..(y + 1) * factor {
+                let luminances = source.getRow(row);
+                let mut offset = 0;
+                for sum in sums.iter_mut() {
+                    for luminance in &luminances[offset..offset + factor] {
+                        *sum += *luminance as u32;
+                    }
+                    offset += factor;
+                }
+            }
+            let blockSize = (factor * factor) as u32;
+            pixels.extend(sums.iter().map(|sum| (sum / blockSize) as u8));
+        }
+
+        BinaryBitmap::new(
+            self.binarizer
+                .createBinarizer(Box::new(Luma8LuminanceSource::new(
+                    pixels,
+                    width as u32,
+                    height as u32,
+                ))),
+        )
+    }
+
+    /**
+     * Returns a new object with the luminance of the image data corrected by a gamma curve, see
+     * {@link GammaLuminanceSource}. It is binarized the same way as this one.
+     *
+     * @param gamma exponent applied to each luminance value, scaled to [0,1]
+     * @return A gamma corrected copy of this object.
+     */
+    pub fn gammaCorrected(&self, gamma: f32) -> BinaryBitmap {
+        let source = self.binarizer.getLuminanceSource();
+        let copy = Luma8LuminanceSource::new(
+            source.getMatrix(),
+            source.getWidth() as u32,
+            source.getHeight() as u32,
+        );
+
+        BinaryBitmap::new(
+            self.binarizer
+                .createBinarizer(Box::new(GammaLuminanceSource::new(Box::new(copy), gamma))),
+        )
+    }
+
     /**
      * @return Whether this bitmap supports counter-clockwise rotation.
      */
@@ -197,3 +398,63 @@ impl fmt::Display for BinaryBitmap {
         write!(f, "{:?}", self.matrix)
     }
 }
+
+/**
+ * Converts an image of any color type to luminance as {@link BinaryBitmap#from_rgba} does,
+ * compositing transparent areas over white, and binarizes it with a {@link HybridBinarizer}.
+ *
+ * ```
+ * use rxing::{qrcode::{QRCodeReader, QRCodeWriter}, BarcodeFormat, BinaryBitmap, Reader, Writer};
+ *
+ * let matrix = QRCodeWriter.encode("hello", &BarcodeFormat::QR_CODE, 100, 100)?;
+ * let img = image::DynamicImage::from(&matrix);
+ *
+ * let mut bitmap = BinaryBitmap::try_from(img)?;
+ * assert_eq!("hello", QRCodeReader.decode(&mut bitmap)?.getText());
+ * # Ok::<(), rxing::Exceptions>(())
+ * ```
+ *
+ * @throws IllegalArgumentException if the image is empty
+ */
+#[cfg(feature = "image")]
+impl TryFrom<image::DynamicImage> for BinaryBitmap {
+    type Error = Exceptions;
+
+    fn try_from(value: image::DynamicImage) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+#[cfg(feature = "image")]
+impl TryFrom<&image::DynamicImage> for BinaryBitmap {
+    type Error = Exceptions;
+
+    fn try_from(value: &image::DynamicImage) -> Result<Self, Self::Error> {
+        if value.width() == 0 || value.height() == 0 {
+            return Err(Exceptions::IllegalArgumentException(Some(format!(
+                "cannot decode an empty {}x{} image",
+                value.width(),
+                value.height()
+            ))));
+        }
+        let rgba = value.to_rgba8();
+        Self::from_rgba(rgba.as_raw(), rgba.width(), rgba.height())
+    }
+}
+
+/**
+ * Converts RGBA pixels to luminance, weighting colors like {@link BufferedImageLuminanceSource}
+ * and compositing transparent pixels over white, as they are often the "white" area of a barcode.
+ */
+fn rgba_to_luma(pixels: &[u8]) -> Vec<u8> {
+    pixels
+        .chunks_exact(4)
+        .map(|pixel| {
+            let [red, green, blue, alpha] = [pixel[0], pixel[1], pixel[2], pixel[3]].map(u32::from);
+            // .299R + 0.587G + 0.114B, rounded, then blended with white in proportion to the
+            // transparency
+            let luminance = (306 * red + 601 * green + 117 * blue + 0x200) >> 10;
+            ((luminance * alpha + 0xFF * (0xFF - alpha) + 0x7F) / 0xFF) as u8
+        })
+        .collect()
+}
@@ -0,0 +1,266 @@
+use std::{borrow::Cow, cell::Cell, rc::Rc};
+
+use crate::{
+    binary_bitmap::BLACK_ROW_CACHE_SIZE,
+    common::{BitArray, BitMatrix},
+    Binarizer, BinaryBitmap, Exceptions, Luma8LuminanceSource, LuminanceSource,
+};
+
+/**
+ * A binarizer which keeps no cache of its own and counts how often each row is computed.
+ */
+struct CountingBinarizer {
+    source: Box<dyn LuminanceSource>,
+    row_computations: Rc<Cell<usize>>,
+}
+
+impl Binarizer for CountingBinarizer {
+    fn getLuminanceSource(&self) -> &Box<dyn LuminanceSource> {
+        &self.source
+    }
+
+    fn getBlackRow(&self, y: usize) -> Result<Cow<'_, BitArray>, Exceptions> {
+        self.row_computations.set(self.row_computations.get() + 1);
+        let mut row = BitArray::with_size(self.source.getWidth());
+        for (x, lum) in self.source.getRow(y).iter().enumerate() {
+            if *lum < 128 {
+                row.set(x);
+            }
+        }
+        Ok(Cow::Owned(row))
+    }
+
+    fn getBlackMatrix(&self) -> Result<&BitMatrix, Exceptions> {
+        Err(Exceptions::UnsupportedOperationException(None))
+    }
+
+    fn createBinarizer(&self, source: Box<dyn LuminanceSource>) -> Rc<dyn Binarizer> {
+        Rc::new(CountingBinarizer {
+            source,
+            row_computations: self.row_computations.clone(),
+        })
+    }
+
+    fn getWidth(&self) -> usize {
+        self.source.getWidth()
+    }
+
+    fn getHeight(&self) -> usize {
+        self.source.getHeight()
+    }
+}
+
+fn counting_bitmap() -> (BinaryBitmap, Rc<Cell<usize>>) {
+    let pixels = vec![0, 255, 0, 255, 255, 0, 255, 0, 0, 0, 255, 255];
+    counting_bitmap_of(pixels, 4, 3)
+}
+
+fn counting_bitmap_of(pixels: Vec<u8>, width: u32, height: u32) -> (BinaryBitmap, Rc<Cell<usize>>) {
+    let counter = Rc::new(Cell::new(0));
+    let binarizer = CountingBinarizer {
+        source: Box::new(Luma8LuminanceSource::new(pixels, width, height)),
+        row_computations: counter.clone(),
+    };
+    (BinaryBitmap::new(Rc::new(binarizer)), counter)
+}
+
+#[test]
+fn test_black_row_is_computed_once() {
+    let (bitmap, counter) = counting_bitmap();
+
+    let first = bitmap.getBlackRow(1).expect("row").into_owned();
+    assert_eq!(1, counter.get());
+    for _ in 0..5 {
+        assert_eq!(first, *bitmap.getBlackRow(1).expect("row"));
+    }
+    assert_eq!(1, counter.get());
+
+    bitmap.getBlackRow(2).expect("row");
+    assert_eq!(2, counter.get());
+}
+
+#[test]
+fn test_black_row_cache_is_bounded() {
+    let height = BLACK_ROW_CACHE_SIZE + 1;
+    let (bitmap, counter) = counting_bitmap_of(vec![0; 4 * height], 4, height as u32);
+
+    for y in 0..height {
+        bitmap.getBlackRow(y).expect("row");
+    }
+    assert_eq!(height, counter.get());
+
+    // Row 0 was evicted to make room for the last row, the rest are still cached
+    for y in (1..height).rev() {
+        bitmap.getBlackRow(y).expect("row");
+    }
+    assert_eq!(height, counter.get());
+    bitmap.getBlackRow(0).expect("row");
+    assert_eq!(height + 1, counter.get());
+
+    // Rows are evicted least recently used first: row height - 1 was read longest ago, row 1
+    // just before row 0
+    bitmap.getBlackRow(1).expect("row");
+    assert_eq!(height + 1, counter.get());
+    bitmap.getBlackRow(height - 1).expect("row");
+    assert_eq!(height + 2, counter.get());
+}
+
+#[test]
+fn test_black_row_into_reuses_buffer() {
+    let (bitmap, counter) = counting_bitmap();
+
+    let mut row = BitArray::new();
+    bitmap.getBlackRowInto(0, &mut row).expect("row");
+    assert_eq!(4, row.getSize());
+    assert!(row.get(0) && !row.get(1) && row.get(2) && !row.get(3));
+
+    bitmap.getBlackRowInto(2, &mut row).expect("row");
+    assert!(row.get(0) && row.get(1) && !row.get(2) && !row.get(3));
+
+    bitmap.getBlackRowInto(0, &mut row).expect("row");
+    assert!(row.get(0) && !row.get(1));
+    assert_eq!(2, counter.get());
+}
+
+#[test]
+fn test_crop_starts_with_fresh_cache() {
+    let (mut bitmap, counter) = counting_bitmap();
+    bitmap.getBlackRow(0).expect("row");
+    assert_eq!(1, counter.get());
+
+    let cropped = bitmap.crop(1, 0, 3, 3);
+    let row = cropped.getBlackRow(0).expect("row");
+    assert_eq!(3, row.getSize());
+    assert!(!row.get(0) && row.get(1) && !row.get(2));
+    assert_eq!(2, counter.get());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_try_from_dynamic_image() {
+    use crate::{qrcode::QRCodeReader, Reader};
+
+    let img = image::open("test_resources/blackbox/qrcode-1/1.png").expect("load fixture");
+    let mut bitmap = BinaryBitmap::try_from(&img).expect("convert");
+    assert_eq!(
+        "MEBKM:URL:http\\://en.wikipedia.org/wiki/Main_Page;;",
+        QRCodeReader.decode(&mut bitmap).expect("decode").getText()
+    );
+
+    let mut bitmap = BinaryBitmap::try_from(img).expect("convert");
+    assert_eq!(
+        "MEBKM:URL:http\\://en.wikipedia.org/wiki/Main_Page;;",
+        QRCodeReader.decode(&mut bitmap).expect("decode").getText()
+    );
+
+    assert!(matches!(
+        BinaryBitmap::try_from(image::DynamicImage::new_luma8(0, 10)),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_try_from_dynamic_image_composites_over_white() {
+    use image::{DynamicImage, Rgba, RgbaImage};
+
+    let pixels = [
+        Rgba([0x10, 0x20, 0x80, 0xFF]),
+        Rgba([0, 0, 0, 0]),
+        Rgba([0, 0, 0, 0x80]),
+        Rgba([0xFF, 0xFF, 0xFF, 0x80]),
+    ];
+    let img = RgbaImage::from_fn(4, 1, |x, _| pixels[x as usize]);
+    let raw = BinaryBitmap::from_rgba(img.as_raw(), 4, 1).expect("convert");
+    let bitmap = BinaryBitmap::try_from(DynamicImage::ImageRgba8(img)).expect("convert");
+    assert_eq!(
+        vec![0x26, 0xFF, 0x7F, 0xFF],
+        bitmap.getLuminanceSource().getRow(0)
+    );
+    // the raw RGBA entry point converts the same pixels the same way
+    assert_eq!(
+        bitmap.getLuminanceSource().getRow(0),
+        raw.getLuminanceSource().getRow(0)
+    );
+}
+
+/**
+ * A binarizer which takes every pixel darker than a fixed threshold to be black.
+ */
+struct FixedThresholdBinarizer {
+    source: Box<dyn LuminanceSource>,
+    threshold: u8,
+    matrix: BitMatrix,
+}
+
+impl FixedThresholdBinarizer {
+    fn new(source: Box<dyn LuminanceSource>, threshold: u8) -> Self {
+        let (width, height) = (source.getWidth(), source.getHeight());
+        let mut matrix = BitMatrix::new(width as u32, height as u32).unwrap();
+        let pixels = source.getMatrix();
+        for y in 0..height {
+            for x in 0..width {
+                if pixels[y * width + x] < threshold {
+                    matrix.set(x as u32, y as u32);
+                }
+            }
+        }
+        Self {
+            source,
+            threshold,
+            matrix,
+        }
+    }
+}
+
+impl Binarizer for FixedThresholdBinarizer {
+    fn getLuminanceSource(&self) -> &Box<dyn LuminanceSource> {
+        &self.source
+    }
+
+    fn getBlackRow(&self, y: usize) -> Result<Cow<'_, BitArray>, Exceptions> {
+        Ok(Cow::Owned(self.matrix.getRow(y as u32)))
+    }
+
+    fn getBlackMatrix(&self) -> Result<&BitMatrix, Exceptions> {
+        Ok(&self.matrix)
+    }
+
+    fn createBinarizer(&self, source: Box<dyn LuminanceSource>) -> Rc<dyn Binarizer> {
+        Rc::new(FixedThresholdBinarizer::new(source, self.threshold))
+    }
+
+    fn getWidth(&self) -> usize {
+        self.source.getWidth()
+    }
+
+    fn getHeight(&self) -> usize {
+        self.source.getHeight()
+    }
+}
+
+#[test]
+fn test_custom_boxed_binarizer() {
+    use crate::{
+        qrcode::QRCodeReader, qrcode::QRCodeWriter, shared_test_methods::render_luma,
+        BarcodeFormat, Reader, Writer,
+    };
+
+    // Dark modules are drawn at 150 and light ones at 220, which a threshold of 180 separates
+    let symbol = QRCodeWriter
+        .encode("custom binarizer", &BarcodeFormat::QR_CODE, 120, 120)
+        .expect("encode");
+    let (width, height) = (symbol.getWidth(), symbol.getHeight());
+    let pixels = render_luma(width, height, 150, 220, |x, y| symbol.get(x, y));
+    let binarizer: Box<dyn Binarizer> = Box::new(FixedThresholdBinarizer::new(
+        Box::new(Luma8LuminanceSource::new(pixels, width, height)),
+        180,
+    ));
+
+    let mut bitmap = BinaryBitmap::from_boxed_binarizer(binarizer);
+    assert_eq!(symbol, *bitmap.getBlackMatrix());
+    assert_eq!(
+        "custom binarizer",
+        QRCodeReader.decode(&mut bitmap).expect("decode").getText()
+    );
+}
@@ -334,6 +334,44 @@ fn testVCardTypes() {
     );
 }
 
+#[test]
+fn testQuotedPrintableNameAndTwoPhones() {
+    let contents = "BEGIN:VCARD\r\n\
+                    VERSION:2.1\r\n\
+                    N;CHARSET=UTF-8;ENCODING=QUOTED-PRINTABLE:M=C3=BCller;J=C3=BCrgen\r\n\
+                    TEL;CELL:+49 170 1234567\r\n\
+                    TEL;WORK;VOICE:+49 30 123456\r\n\
+                    EMAIL;INTERNET:juergen@example.de\r\n\
+                    ORG:M\u{fc}ller\\, Schmidt \\; Partner\r\n\
+                    TITLE:Head of\r\n  Sales\r\n\
+                    URL:https://example.de\r\n\
+                    GEO:52.52;13.40\r\n\
+                    END:VCARD";
+    doTest(
+        contents,
+        "Head of Sales",
+        &["J\u{fc}rgen M\u{fc}ller"],
+        "",
+        &Vec::new(),
+        &["juergen@example.de"],
+        &["+49 170 1234567", "+49 30 123456"],
+        &["CELL", "WORK"],
+        "M\u{fc}ller, Schmidt ; Partner",
+        &["https://example.de"],
+        "",
+        "",
+    );
+
+    let result = ResultParser::parseRXingResult(&RXingResult::new(
+        contents,
+        Vec::new(),
+        Vec::new(),
+        BarcodeFormat::QR_CODE,
+    ));
+    let addressRXingResult = result.as_address_book().expect("address book");
+    assert_eq!(&["52.52", "13.40"], addressRXingResult.getGeo().as_slice());
+}
+
 fn doTest(
     contents: &str,
     title: &str,
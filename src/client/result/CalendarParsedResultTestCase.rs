@@ -175,6 +175,65 @@ fn testAllDayValueDate() {
     );
 }
 
+#[test]
+fn testRealisticVEvent() {
+    let contents = "BEGIN:VCALENDAR\r\n\
+                    VERSION:2.0\r\n\
+                    PRODID:-//Example Corp//Calendar 1.0//EN\r\n\
+                    BEGIN:VEVENT\r\n\
+                    UID:20240101T120000-4821@example.org\r\n\
+                    DTSTAMP:20231220T080000Z\r\n\
+                    DTSTART:20240101T120000\r\n\
+                    DTEND:20240101T133000Z\r\n\
+                    SUMMARY:New Year planning lunch\r\n\
+                    LOCATION:Cafe Central\\, Herrengasse 14\r\n\
+                    ORGANIZER;CN=Bob Smith:mailto:bob@example.org\r\n\
+                    ATTENDEE;ROLE=REQ-PARTICIPANT;CN=Alice:mailto:alice@example.org\r\n\
+                    ATTENDEE;RSVP=TRUE:mailto:carol@example.org\r\n\
+                    DESCRIPTION:Agenda: budget\\, hiring and the offsite.\\nBring your lap\r\n \
+                    top.\r\n\
+                    END:VEVENT\r\n\
+                    END:VCALENDAR";
+    doTest(
+        contents,
+        "Agenda: budget, hiring and the offsite.\nBring your laptop.",
+        "New Year planning lunch",
+        "Cafe Central, Herrengasse 14",
+        "20240101T120000Z",
+        "20240101T133000Z",
+        "bob@example.org",
+        &["alice@example.org", "carol@example.org"],
+        f64::NAN,
+        f64::NAN,
+    );
+
+    let result = ResultParser::parseRXingResult(&RXingResult::new(
+        contents,
+        Vec::new(),
+        Vec::new(),
+        BarcodeFormat::QR_CODE,
+    ));
+    let calRXingResult = result.as_calendar_event().expect("calendar event");
+    assert!(!calRXingResult.isStartAllDay());
+    assert!(!calRXingResult.isEndAllDay());
+    let display = result.getDisplayRXingResult();
+    assert!(display.starts_with("New Year planning lunch\n"));
+    assert!(display.contains("Cafe Central, Herrengasse 14"));
+}
+
+#[test]
+fn testAllDayFlag() {
+    let result = ResultParser::parseRXingResult(&RXingResult::new(
+        "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20240101\nDTEND:20240102T090000Z\nEND:VEVENT",
+        Vec::new(),
+        Vec::new(),
+        BarcodeFormat::QR_CODE,
+    ));
+    let calRXingResult = result.as_calendar_event().expect("calendar event");
+    assert!(calRXingResult.isStartAllDay());
+    assert!(!calRXingResult.isEndAllDay());
+}
+
 fn doTestShort(
     contents: &str,
     description: &str,
@@ -132,6 +132,30 @@ fn testEmailDocomo() {
     );
 }
 
+#[test]
+fn testEmailDocomoMultipleTos() {
+    do_test(
+        "MATMSG:TO:srowen@example.org, bob@example.org;SUB:Stuff;;",
+        &["srowen@example.org", "bob@example.org"],
+        &Vec::new(),
+        &Vec::new(),
+        "Stuff",
+        "",
+    );
+}
+
+#[test]
+fn testPercentEncodedSubjectAndBody() {
+    do_test(
+        "mailto:srowen%40example.org?subject=Hello%20there&body=Line%201%0ALine%202",
+        &["srowen@example.org"],
+        &Vec::new(),
+        &Vec::new(),
+        "Hello there",
+        "Line 1\nLine 2",
+    );
+}
+
 #[test]
 fn testSMTP() {
     do_test_single("smtp:srowen@example.org", "srowen@example.org", "", "");
@@ -34,7 +34,7 @@ static ATEXT_ALPHANUMERIC: Lazy<Regex> =
 /**
  * Implements the "MATMSG" email message entry format.
  *
- * Supported keys: TO, SUB, BODY
+ * Supported keys: TO, SUB, BODY. TO may list several comma-separated recipients.
  *
  * @author Sean Owen
  */
@@ -43,7 +43,12 @@ pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
     if !rawText.starts_with("MATMSG:") {
         return None;
     }
-    let tos = ResultParser::match_do_co_mo_prefixed_field("TO:", &rawText)?;
+    // Several recipients may share one TO field, separated by commas
+    let tos: Vec<String> = ResultParser::match_do_co_mo_prefixed_field("TO:", &rawText)?
+        .iter()
+        .flat_map(|to| to.split(','))
+        .map(|to| to.trim().to_owned())
+        .collect();
 
     for to in &tos {
         if !isBasicallyValidEmailAddress(to, &ATEXT_ALPHANUMERIC) {
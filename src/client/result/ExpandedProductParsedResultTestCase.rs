@@ -44,7 +44,7 @@ use std::collections::HashMap;
 
 use crate::{client::result::ParsedClientResult, BarcodeFormat, RXingResult};
 
-use super::ExpandedProductResultParser;
+use super::{ExpandedProductParsedRXingResult, ExpandedProductResultParser, ResultParser};
 
 #[test]
 fn testRSSExpanded() {
@@ -89,4 +89,48 @@ fn testRSSExpanded() {
         panic!("Should have found a result");
     }
 }
+
+#[test]
+fn testWeightPriceAndExpiration() {
+    let result = RXingResult::new(
+        "(01)98898765432106(3202)012345(17)251231(3921)1099(10)AB-123(99)XYZ",
+        Vec::new(),
+        Vec::new(),
+        BarcodeFormat::RSS_EXPANDED,
+    );
+    let ParsedClientResult::ExpandedProductResult(epr_res) =
+        ResultParser::parseRXingResult(&result)
+    else {
+        panic!("Should have gotten a expanded product");
+    };
+    assert_eq!("98898765432106", epr_res.getProductID());
+    assert_eq!("AB-123", epr_res.getLotNumber());
+    assert_eq!("251231", epr_res.getExpirationDate());
+    assert_eq!("012345", epr_res.getWeight());
+    assert_eq!(
+        ExpandedProductParsedRXingResult::POUND,
+        epr_res.getWeightType()
+    );
+    assert_eq!("2", epr_res.getWeightIncrement());
+    assert_eq!("1099", epr_res.getPrice());
+    assert_eq!("1", epr_res.getPriceIncrement());
+    assert!(epr_res.getPriceCurrency().is_empty());
+    assert_eq!(1, epr_res.getUncommonAIs().len());
+    assert_eq!(Some(&"XYZ".to_owned()), epr_res.getUncommonAIs().get("99"));
+}
+
+#[test]
+fn testOnlyRSSExpanded() {
+    let result = RXingResult::new(
+        "(01)98898765432106(3103)001750(17)251231",
+        Vec::new(),
+        Vec::new(),
+        BarcodeFormat::CODE_128,
+    );
+    assert!(ExpandedProductResultParser::parse(&result).is_none());
+    assert!(!matches!(
+        ResultParser::parseRXingResult(&result),
+        ParsedClientResult::ExpandedProductResult(_)
+    ));
+}
 // }
@@ -16,7 +16,7 @@
 
 // package com.google.zxing.client.result;
 
-use super::{ParsedRXingResult, ParsedRXingResultType};
+use super::{ParsedRXingResult, ParsedRXingResultType, ResultParser};
 
 /**
  * Represents a parsed result that encodes a geographic coordinate, with latitude,
@@ -28,7 +28,7 @@ use super::{ParsedRXingResult, ParsedRXingResultType};
 pub struct GeoParsedRXingResult {
     latitude: f64,
     longitude: f64,
-    altitude: f64,
+    altitude: Option<f64>,
     query: String,
 }
 
@@ -43,17 +43,15 @@ impl ParsedRXingResult for GeoParsedRXingResult {
         // result.push_str(", ");
         // result.push_str(self.longitude);
         result.push_str(&format!("{}, {}", self.latitude, self.longitude));
-        if self.altitude > 0.0 {
-            result.push_str(&format!(", {}m", self.altitude));
+        if let Some(altitude) = self.altitude {
+            result.push_str(&format!(", {altitude}m"));
             // result.push_str(", ");
             // result.push_str(self.altitude);
             // result.push('m');
         }
         if !self.query.is_empty() {
-            result.push_str(&format!(" ({})", self.query));
-            // result.push_str(" (");
-            // result.push_str(self.query);
-            // result.push(')');
+            result.push('\n');
+            result.push_str(&self.getQueryLabel());
         }
         result
     }
@@ -61,6 +59,23 @@ impl ParsedRXingResult for GeoParsedRXingResult {
 
 impl GeoParsedRXingResult {
     pub fn new(latitude: f64, longitude: f64, altitude: f64, query: String) -> Self {
+        Self::with_optional_altitude(
+            latitude,
+            longitude,
+            Some(altitude).filter(|altitude| *altitude > 0.0),
+            query,
+        )
+    }
+
+    /**
+     * @param altitude altitude in meters, or None if the geo URI didn't specify one
+     */
+    pub fn with_optional_altitude(
+        latitude: f64,
+        longitude: f64,
+        altitude: Option<f64>,
+        query: String,
+    ) -> Self {
         Self {
             latitude,
             longitude,
@@ -76,8 +91,8 @@ impl GeoParsedRXingResult {
         // result.append(latitude);
         // result.append(',');
         // result.append(longitude);
-        if self.altitude > 0.0 {
-            result.push_str(&format!(",{}", self.altitude));
+        if let Some(altitude) = self.altitude {
+            result.push_str(&format!(",{altitude}"));
             // result.append(',');
             // result.append(altitude);
         }
@@ -107,7 +122,14 @@ impl GeoParsedRXingResult {
      * @return altitude in meters. If not specified, in the geo URI, returns 0.0
      */
     pub fn getAltitude(&self) -> f64 {
-        self.altitude
+        self.altitude.unwrap_or(0.0)
+    }
+
+    /**
+     * @return true if the geo URI specified an altitude, even one of 0
+     */
+    pub fn hasAltitude(&self) -> bool {
+        self.altitude.is_some()
     }
 
     /**
@@ -116,6 +138,35 @@ impl GeoParsedRXingResult {
     pub fn getQuery(&self) -> &str {
         &self.query
     }
+
+    /**
+     * @return the URL decoded label given by the "q" parameter of the query, such as the name of
+     * a place, or else the whole query
+     */
+    pub fn getQueryLabel(&self) -> String {
+        self.getQueryParameter("q")
+            .and_then(|label| ResultParser::urlDecode(label).ok())
+            .unwrap_or_else(|| self.query.clone())
+    }
+
+    /**
+     * @return the map zoom level given by the "z" parameter of the query, from 0 for the whole
+     * world up to 23, or None if there is none or it isn't valid
+     */
+    pub fn getZoom(&self) -> Option<u32> {
+        self.getQueryParameter("z")
+            .and_then(|zoom| zoom.parse().ok())
+            .filter(|zoom| *zoom <= 23)
+    }
+
+    fn getQueryParameter(&self, name: &str) -> Option<&str> {
+        self.query.split('&').find_map(|parameter| {
+            parameter
+                .split_once('=')
+                .filter(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+        })
+    }
 }
 
 impl PartialEq for GeoParsedRXingResult {
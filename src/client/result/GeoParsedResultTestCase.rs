@@ -73,6 +73,85 @@ pub fn testGeo4() {
     );
 }
 
+#[test]
+pub fn testGeoAltitudeAndQuery() {
+    doTest(
+        "geo:37.5,-122.3,30?q=Coffee",
+        37.5,
+        -122.3,
+        30.0,
+        "q=Coffee",
+        "geo:37.5,-122.3,30?q=Coffee",
+    );
+    let result = parse("geo:37.5,-122.3,30?q=Coffee%20%26%20Tea");
+    let ParsedClientResult::GeoResult(geoRXingResult) = &result else {
+        panic!("Expected ParsedClientResult::GeoResult");
+    };
+    assert!(geoRXingResult.hasAltitude());
+    assert_eq!("Coffee & Tea", geoRXingResult.getQueryLabel());
+    assert_eq!(
+        "37.5, -122.3, 30m\nCoffee & Tea",
+        result.getDisplayRXingResult()
+    );
+
+    // An explicit altitude of 0 is still shown
+    let result = parse("geo:37.5,-122.3,0");
+    assert_eq!("37.5, -122.3, 0m", result.getDisplayRXingResult());
+}
+
+#[test]
+pub fn testGeoWithoutAltitude() {
+    doTest("geo:37.5,-122.3", 37.5, -122.3, 0.0, "", "geo:37.5,-122.3");
+    let result = parse("geo:37.5,-122.3");
+    let ParsedClientResult::GeoResult(geoRXingResult) = &result else {
+        panic!("Expected ParsedClientResult::GeoResult");
+    };
+    assert!(!geoRXingResult.hasAltitude());
+    assert_eq!(0.0, geoRXingResult.getAltitude());
+    assert_eq!("37.5, -122.3", result.getDisplayRXingResult());
+
+    // A query other than a label is shown as is
+    let result = parse("geo:37.5,-122.3?z=12");
+    assert_eq!("37.5, -122.3\nz=12", result.getDisplayRXingResult());
+}
+
+#[test]
+pub fn testGeoLabelAndZoom() {
+    doTest(
+        "geo:37.77,-122.41?q=Office&z=15",
+        37.77,
+        -122.41,
+        0.0,
+        "q=Office&z=15",
+        "geo:37.77,-122.41?q=Office&z=15",
+    );
+    let result = parse("geo:37.77,-122.41?q=Office&z=15");
+    let ParsedClientResult::GeoResult(geoRXingResult) = &result else {
+        panic!("Expected ParsedClientResult::GeoResult");
+    };
+    assert_eq!("Office", geoRXingResult.getQueryLabel());
+    assert_eq!(Some(15), geoRXingResult.getZoom());
+    assert_eq!("37.77, -122.41\nOffice", result.getDisplayRXingResult());
+
+    for contents in [
+        "geo:37.77,-122.41?q=Office",
+        "geo:37.77,-122.41?z=24",
+        "geo:37.77,-122.41?z=far",
+        "geo:37.77,-122.41?zz=15",
+    ] {
+        let ParsedClientResult::GeoResult(geoRXingResult) = parse(contents) else {
+            panic!("Expected ParsedClientResult::GeoResult");
+        };
+        assert_eq!(None, geoRXingResult.getZoom(), "{contents}");
+    }
+}
+
+fn parse(contents: &str) -> ParsedClientResult {
+    let fakeRXingResult =
+        RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    ResultParser::parseRXingResult(&fakeRXingResult)
+}
+
 fn doTest(contents: &str, latitude: f64, longitude: f64, altitude: f64, query: &str, uri: &str) {
     let fakeRXingResult =
         RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
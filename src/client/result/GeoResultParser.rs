@@ -27,7 +27,7 @@ use once_cell::sync::Lazy;
 
 static GEO_URL: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(GEO_URL_PATTERN).unwrap());
 
-const GEO_URL_PATTERN: &str = "geo:([\\-0-9.]+),([\\-0-9.]+)(?:,([\\-0-9.]+))?(?:\\?(.*))?";
+const GEO_URL_PATTERN: &str = "(?i)geo:([\\-0-9.]+),([\\-0-9.]+)(?:,([\\-0-9.]+))?(?:\\?(.*))?";
 
 /**
  * Parses a "geo:" URI result, which specifies a location on the surface of
@@ -43,7 +43,7 @@ const GEO_URL_PATTERN: &str = "geo:([\\-0-9.]+),([\\-0-9.]+)(?:,([\\-0-9.]+))?(?
 pub fn parse(theRXingResult: &crate::RXingResult) -> Option<super::ParsedClientResult> {
     let rawText = ResultParser::getMassagedText(theRXingResult);
 
-    if let Some(captures) = GEO_URL.captures(&rawText.to_lowercase()) {
+    if let Some(captures) = GEO_URL.captures(&rawText) {
         let query = if let Some(q) = captures.get(4) {
             q.as_str()
         } else {
@@ -81,12 +81,12 @@ pub fn parse(theRXingResult: &crate::RXingResult) -> Option<super::ParsedClientR
                 if alf64 < 0.0 {
                     return None;
                 }
-                alf64
+                Some(alf64)
             } else {
                 return None;
             }
         } else {
-            0.0
+            None
         };
         // let longitude;
         // let altitude;
@@ -110,12 +110,14 @@ pub fn parse(theRXingResult: &crate::RXingResult) -> Option<super::ParsedClientR
         // } catch (NumberFormatException ignored) {
         //   return null;
         // }
-        Some(ParsedClientResult::GeoResult(GeoParsedRXingResult::new(
-            latitude,
-            longitude,
-            altitude,
-            String::from(query),
-        )))
+        Some(ParsedClientResult::GeoResult(
+            GeoParsedRXingResult::with_optional_altitude(
+                latitude,
+                longitude,
+                altitude,
+                String::from(query),
+            ),
+        ))
     } else {
         None
     }
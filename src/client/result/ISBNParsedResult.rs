@@ -16,6 +16,8 @@
 
 // package com.google.zxing.client.result;
 
+use crate::oned::gtin_mod10;
+
 use super::ParsedRXingResult;
 
 /**
@@ -42,7 +44,32 @@ impl ISBNParsedRXingResult {
         Self { isbn }
     }
 
+    /**
+     * @return the ISBN as encoded, 13 digits, or 10 characters for an ISBN-10
+     */
     pub fn getISBN(&self) -> &str {
         &self.isbn
     }
+
+    /**
+     * @return true if this is a 10 character ISBN, whose check character may be "X"
+     */
+    pub fn isISBN10(&self) -> bool {
+        self.isbn.len() == 10
+    }
+
+    /**
+     * @return the 13 digit form of the ISBN: an ISBN-10 is prefixed with "978" and given a new
+     * EAN-13 check digit
+     */
+    pub fn getISBN13(&self) -> String {
+        if !self.isISBN10() {
+            return self.isbn.clone();
+        }
+        let mut isbn13 = format!("978{}", &self.isbn[..9]);
+        if let Some(checkDigit) = gtin_mod10(&isbn13).and_then(|d| char::from_digit(d, 10)) {
+            isbn13.push(checkDigit);
+        }
+        isbn13
+    }
 }
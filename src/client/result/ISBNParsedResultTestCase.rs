@@ -28,7 +28,9 @@
  */
 // public final class ISBNParsedRXingResultTestCase extends Assert {
 use crate::{
-    client::result::{ParsedClientResult, ParsedRXingResult, ParsedRXingResultType},
+    client::result::{
+        ISBNParsedRXingResult, ParsedClientResult, ParsedRXingResult, ParsedRXingResultType,
+    },
     BarcodeFormat, RXingResult,
 };
 
@@ -39,6 +41,49 @@ fn testISBN() {
     doTest("9784567890123");
 }
 
+#[test]
+fn testISBN10() {
+    let result = parse("097522980X", BarcodeFormat::CODE_39);
+    assert!(result.isISBN10());
+    assert_eq!("097522980X", result.getISBN());
+    assert_eq!("9780975229804", result.getISBN13());
+
+    let result = parse("ISBN 0-9752298-0-x", BarcodeFormat::QR_CODE);
+    assert_eq!("097522980X", result.getISBN());
+
+    let result = parse("ISBN-13: 978-0-9752298-0-4", BarcodeFormat::QR_CODE);
+    assert!(!result.isISBN10());
+    assert_eq!("9780975229804", result.getISBN13());
+}
+
+#[test]
+fn testInvalidISBN10() {
+    // Wrong check character
+    doTestNotISBN("0975229801", BarcodeFormat::CODE_39);
+    // "X" only stands in for the check digit
+    doTestNotISBN("09752298X0", BarcodeFormat::CODE_39);
+    // Wrong check digit
+    doTestNotISBN("ISBN 978-0-9752298-0-5", BarcodeFormat::QR_CODE);
+    // Digits in other symbols than Code 39 need the prefix
+    doTestNotISBN("097522980X", BarcodeFormat::QR_CODE);
+    doTestNotISBN("097522980X", BarcodeFormat::CODE_93);
+    doTestNotISBN("097522980X", BarcodeFormat::CODE_128);
+}
+
+fn parse(contents: &str, format: BarcodeFormat) -> ISBNParsedRXingResult {
+    let fakeRXingResult = RXingResult::new(contents, vec![0; 0], vec![], format);
+    match ResultParser::parseRXingResult(&fakeRXingResult) {
+        ParsedClientResult::ISBNResult(res) => res,
+        _ => panic!("expected ISBNResult"),
+    }
+}
+
+fn doTestNotISBN(contents: &str, format: BarcodeFormat) {
+    let fakeRXingResult = RXingResult::new(contents, vec![0; 0], vec![], format);
+    let result = ResultParser::parseRXingResult(&fakeRXingResult);
+    assert_ne!(ParsedRXingResultType::ISBN, result.getType());
+}
+
 fn doTest(contents: &str) {
     let fakeRXingResult = RXingResult::new(contents, vec![0; 0], vec![], BarcodeFormat::EAN_13);
     let result = ResultParser::parseRXingResult(&fakeRXingResult);
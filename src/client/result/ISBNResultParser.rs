@@ -19,12 +19,14 @@
 // import com.google.zxing.BarcodeFormat;
 // import com.google.zxing.RXingResult;
 
-use crate::BarcodeFormat;
+use crate::{oned::gtin_mod10, BarcodeFormat};
 
 use super::{ISBNParsedRXingResult, ParsedClientResult, ResultParser};
 
 /**
- * Parses strings of digits that represent a ISBN.
+ * Parses strings of digits that represent a ISBN: the 13 digit Bookland EAN-13 form, or the
+ * older 10 character form, whose check character may be an "X", as found in Code 39 symbols or
+ * after an "ISBN" prefix in text.
  *
  * @author jbreiden@google.com (Jeff Breidenbach)
  */
@@ -36,20 +38,85 @@ use super::{ISBNParsedRXingResult, ParsedClientResult, ResultParser};
  */
 pub fn parse(theRXingResult: &crate::RXingResult) -> Option<super::ParsedClientResult> {
     let format = theRXingResult.getBarcodeFormat();
-    if *format != BarcodeFormat::EAN_13 {
-        return None;
-    }
     let rawText = ResultParser::getMassagedText(theRXingResult);
-    let length = rawText.len();
-    if length != 13 {
+    if *format == BarcodeFormat::EAN_13 {
+        // The EAN-13 reader has already checked the check digit
+        if rawText.len() != 13 || !isBookland(&rawText) {
+            return None;
+        }
+        return Some(ParsedClientResult::ISBNResult(ISBNParsedRXingResult::new(
+            rawText,
+        )));
+    }
+
+    let rawText = rawText.trim();
+    let (prefixed, number) = match stripPrefix(rawText) {
+        Some(number) => (true, number),
+        None => (false, rawText),
+    };
+    // Other symbologies carry all sorts of numbers which happen to have a valid check character
+    if !prefixed && *format != BarcodeFormat::CODE_39 {
         return None;
     }
-    if !rawText.starts_with("978") && !rawText.starts_with("979") {
+    let isbn: String = number
+        .chars()
+        .filter(|c| *c != '-' && *c != ' ')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let valid = match isbn.len() {
+        10 => isValidISBN10(&isbn),
+        13 => isBookland(&isbn) && isValidISBN13(&isbn),
+        _ => false,
+    };
+    if !valid {
         return None;
     }
 
     Some(ParsedClientResult::ISBNResult(ISBNParsedRXingResult::new(
-        rawText,
+        isbn,
     )))
 }
 // }
+
+fn isBookland(isbn: &str) -> bool {
+    isbn.starts_with("978") || isbn.starts_with("979")
+}
+
+/**
+ * Strips an "ISBN", "ISBN-10" or "ISBN-13" prefix, optionally followed by a colon.
+ */
+fn stripPrefix(text: &str) -> Option<&str> {
+    if !text.get(..4)?.eq_ignore_ascii_case("ISBN") {
+        return None;
+    }
+    let rest = &text[4..];
+    let rest = rest
+        .strip_prefix("-10")
+        .or_else(|| rest.strip_prefix("-13"))
+        .unwrap_or(rest);
+    let rest = rest.trim_start();
+    Some(rest.strip_prefix(':').unwrap_or(rest).trim_start())
+}
+
+/**
+ * @param isbn 9 digits followed by a check digit or "X", standing for 10
+ * @return true if the sum of the characters weighted 10 down to 1 is a multiple of 11
+ */
+fn isValidISBN10(isbn: &str) -> bool {
+    let mut sum = 0;
+    for (i, c) in isbn.chars().enumerate() {
+        let value = match c {
+            'X' if i == 9 => 10,
+            _ => match c.to_digit(10) {
+                Some(digit) => digit,
+                None => return false,
+            },
+        };
+        sum += (10 - i as u32) * value;
+    }
+    sum % 11 == 0
+}
+
+fn isValidISBN13(isbn: &str) -> bool {
+    isbn.chars().all(|c| c.is_ascii_digit()) && gtin_mod10(&isbn[..12]) == isbn[12..].parse().ok()
+}
@@ -0,0 +1,71 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{ParsedRXingResult, ParsedRXingResultType};
+
+/**
+ * Represents a parsed result made of simple key-value pairs, such as {@code a=1;b=2}, which don't
+ * follow any more specific scheme. Pairs are kept in the order they were encoded in.
+ */
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct KeyValueParsedRXingResult {
+    pairs: Vec<(String, String)>,
+}
+
+impl ParsedRXingResult for KeyValueParsedRXingResult {
+    fn getType(&self) -> ParsedRXingResultType {
+        ParsedRXingResultType::KEY_VALUE
+    }
+
+    fn getDisplayRXingResult(&self) -> String {
+        let mut result = String::new();
+        for (key, value) in &self.pairs {
+            self.maybe_append(&format!("{key}={value}"), &mut result);
+        }
+        result
+    }
+}
+
+impl KeyValueParsedRXingResult {
+    pub fn new(pairs: Vec<(String, String)>) -> Self {
+        Self { pairs }
+    }
+
+    /**
+     * @return all pairs, in encoded order
+     */
+    pub fn getPairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+
+    /**
+     * @return the keys, in encoded order
+     */
+    pub fn getKeys(&self) -> Vec<&str> {
+        self.pairs.iter().map(|(key, _)| key.as_str()).collect()
+    }
+
+    /**
+     * @param key key to look up
+     * @return the value of the first pair with that key, if any
+     */
+    pub fn getValue(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
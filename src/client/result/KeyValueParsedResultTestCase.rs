@@ -0,0 +1,83 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    client::result::{
+        parse_key_value_with_delimiters, ParsedClientResult, ParsedRXingResult,
+        ParsedRXingResultType,
+    },
+    BarcodeFormat, RXingResult,
+};
+
+use super::ResultParser;
+
+#[test]
+fn testKeyValue() {
+    let result = parse("a=1;b=2;c=3");
+    assert_eq!(ParsedRXingResultType::KEY_VALUE, result.getType());
+    let ParsedClientResult::KeyValueResult(kv) = result else {
+        panic!("expected key-value result");
+    };
+    assert_eq!(
+        &[
+            ("a".to_owned(), "1".to_owned()),
+            ("b".to_owned(), "2".to_owned()),
+            ("c".to_owned(), "3".to_owned())
+        ],
+        kv.getPairs()
+    );
+    assert_eq!(vec!["a", "b", "c"], kv.getKeys());
+    assert_eq!(Some("2"), kv.getValue("b"));
+    assert_eq!(None, kv.getValue("d"));
+    assert_eq!("a=1\nb=2\nc=3", kv.getDisplayRXingResult());
+}
+
+#[test]
+fn testKeyValueDelimiters() {
+    // Values keep everything after the first '=', and empty pieces are skipped
+    let ParsedClientResult::KeyValueResult(kv) = parse("id=x=y&name=Jane Doe;;") else {
+        panic!("expected key-value result");
+    };
+    assert_eq!(Some("x=y"), kv.getValue("id"));
+    assert_eq!(Some("Jane Doe"), kv.getValue("name"));
+
+    let rxing_result = RXingResult::new("a=1|b=2", Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    let ParsedClientResult::KeyValueResult(kv) =
+        parse_key_value_with_delimiters(&rxing_result, &['|']).expect("parse")
+    else {
+        panic!("expected key-value result");
+    };
+    assert_eq!(vec!["a", "b"], kv.getKeys());
+}
+
+#[test]
+fn testNotKeyValue() {
+    for text in [
+        "a=1",
+        "a=1;just some text",
+        "two words=1;b=2",
+        "=1;b=2",
+        "1 + 1 = 2; 2 + 2 = 4",
+    ] {
+        assert_eq!(ParsedRXingResultType::TEXT, parse(text).getType(), "{text}");
+    }
+}
+
+fn parse(contents: &str) -> ParsedClientResult {
+    let fakeRXingResult =
+        RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    ResultParser::parseRXingResult(&fakeRXingResult)
+}
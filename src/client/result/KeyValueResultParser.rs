@@ -0,0 +1,74 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::RXingResult;
+
+use super::{KeyValueParsedRXingResult, ParsedClientResult, ResultParser};
+
+/**
+ * Delimiters separating pairs when none are specified.
+ */
+pub const DEFAULT_KEY_VALUE_DELIMITERS: [char; 3] = [';', '&', '\n'];
+
+/**
+ * Parses results made of {@code key=value} pairs separated by any of
+ * {@link #DEFAULT_KEY_VALUE_DELIMITERS}. See {@link #parse_key_value_with_delimiters}.
+ */
+pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
+    parse_key_value_with_delimiters(result, &DEFAULT_KEY_VALUE_DELIMITERS)
+}
+
+/**
+ * Parses results made of {@code key=value} pairs separated by any of the given delimiters. To
+ * avoid claiming free text, this only matches when there are at least two pairs and every
+ * non-empty piece is a pair whose key is a single word.
+ *
+ * @param result the raw {@link RXingResult} to parse
+ * @param delimiters characters which separate pairs
+ */
+pub fn parse_key_value_with_delimiters(
+    result: &RXingResult,
+    delimiters: &[char],
+) -> Option<ParsedClientResult> {
+    let raw_text = ResultParser::getMassagedText(result);
+
+    let mut pairs = Vec::new();
+    for piece in raw_text.split(delimiters) {
+        let piece = piece.trim_end_matches('\r');
+        if piece.is_empty() {
+            continue;
+        }
+        let (key, value) = piece.split_once('=')?;
+        if !is_key(key) {
+            return None;
+        }
+        pairs.push((key.to_owned(), value.to_owned()));
+    }
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    Some(ParsedClientResult::KeyValueResult(
+        KeyValueParsedRXingResult::new(pairs),
+    ))
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{ParsedRXingResult, ParsedRXingResultType};
+
+/**
+ * Represents a parsed result that provisions a one-time password generator, as encoded by
+ * {@code otpauth://} URIs for authenticator apps.
+ */
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct OtpAuthParsedRXingResult {
+    otpType: String,
+    label: String,
+    issuer: String,
+    secret: String,
+    algorithm: String,
+    digits: u32,
+    period: u32,
+    counter: Option<u64>,
+}
+
+impl ParsedRXingResult for OtpAuthParsedRXingResult {
+    fn getType(&self) -> ParsedRXingResultType {
+        ParsedRXingResultType::OTP_AUTH
+    }
+
+    fn getDisplayRXingResult(&self) -> String {
+        // The secret is left out on purpose
+        let mut result = String::with_capacity(40);
+        self.maybe_append(&self.issuer, &mut result);
+        self.maybe_append(&self.label, &mut result);
+        result
+    }
+}
+
+impl OtpAuthParsedRXingResult {
+    pub const DEFAULT_ALGORITHM: &'static str = "SHA1";
+    pub const DEFAULT_DIGITS: u32 = 6;
+    pub const DEFAULT_PERIOD: u32 = 30;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        otpType: String,
+        label: String,
+        issuer: String,
+        secret: String,
+        algorithm: String,
+        digits: u32,
+        period: u32,
+        counter: Option<u64>,
+    ) -> Self {
+        Self {
+            otpType,
+            label,
+            issuer,
+            secret,
+            algorithm,
+            digits,
+            period,
+            counter,
+        }
+    }
+
+    /**
+     * @return "totp" for time based or "hotp" for counter based one-time passwords
+     */
+    pub fn getOtpType(&self) -> &str {
+        &self.otpType
+    }
+
+    /**
+     * @return the label, usually the account name prefixed with the issuer, such as
+     *  "Example:alice@example.com"
+     */
+    pub fn getLabel(&self) -> &str {
+        &self.label
+    }
+
+    /**
+     * @return the provider of the account, from the issuer parameter or else the prefix of the label
+     */
+    pub fn getIssuer(&self) -> &str {
+        &self.issuer
+    }
+
+    /**
+     * @return the shared secret, Base32 encoded
+     */
+    pub fn getSecret(&self) -> &str {
+        &self.secret
+    }
+
+    /**
+     * @return the HMAC hash algorithm, "SHA1" unless specified
+     */
+    pub fn getAlgorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    /**
+     * @return the number of digits of each password, 6 unless specified
+     */
+    pub fn getDigits(&self) -> u32 {
+        self.digits
+    }
+
+    /**
+     * @return the number of seconds each time based password is valid for, 30 unless specified
+     */
+    pub fn getPeriod(&self) -> u32 {
+        self.period
+    }
+
+    /**
+     * @return the initial counter value of a counter based generator, None for time based ones
+     */
+    pub fn getCounter(&self) -> Option<u64> {
+        self.counter
+    }
+}
@@ -0,0 +1,100 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    client::result::{ParsedClientResult, ParsedRXingResult, ParsedRXingResultType},
+    BarcodeFormat, RXingResult,
+};
+
+use super::{OtpAuthParsedRXingResult, ResultParser};
+
+#[test]
+fn testTotp() {
+    let result = parse(
+        "otpauth://totp/ACME%20Co:john.doe@email.com?secret=HXDMVJECJJWSRB3HWIZR4IFUGFTMXBOZ&issuer=ACME%20Co&algorithm=sha256&digits=8&period=60",
+    );
+    assert_eq!(ParsedRXingResultType::OTP_AUTH, result.getType());
+    let ParsedClientResult::OtpAuthResult(otp) = result else {
+        panic!("expected otpauth result");
+    };
+    assert_eq!("totp", otp.getOtpType());
+    assert_eq!("ACME Co:john.doe@email.com", otp.getLabel());
+    assert_eq!("ACME Co", otp.getIssuer());
+    assert_eq!("HXDMVJECJJWSRB3HWIZR4IFUGFTMXBOZ", otp.getSecret());
+    assert_eq!("SHA256", otp.getAlgorithm());
+    assert_eq!(8, otp.getDigits());
+    assert_eq!(60, otp.getPeriod());
+    assert_eq!(None, otp.getCounter());
+    assert_eq!(
+        "ACME Co\nACME Co:john.doe@email.com",
+        otp.getDisplayRXingResult()
+    );
+}
+
+#[test]
+fn testDefaults() {
+    // Without an issuer parameter the issuer is the prefix of the label
+    let ParsedClientResult::OtpAuthResult(otp) =
+        parse("OTPAUTH://TOTP/Example:alice@example.com?secret=JBSWY3DPEHPK3PXP")
+    else {
+        panic!("expected otpauth result");
+    };
+    assert_eq!("totp", otp.getOtpType());
+    assert_eq!("Example", otp.getIssuer());
+    assert_eq!(
+        OtpAuthParsedRXingResult::DEFAULT_ALGORITHM,
+        otp.getAlgorithm()
+    );
+    assert_eq!(OtpAuthParsedRXingResult::DEFAULT_DIGITS, otp.getDigits());
+    assert_eq!(OtpAuthParsedRXingResult::DEFAULT_PERIOD, otp.getPeriod());
+}
+
+#[test]
+fn testHotp() {
+    let ParsedClientResult::OtpAuthResult(otp) =
+        parse("otpauth://hotp/alice@example.com?secret=JBSWY3DPEHPK3PXP&counter=42")
+    else {
+        panic!("expected otpauth result");
+    };
+    assert_eq!("hotp", otp.getOtpType());
+    assert_eq!("alice@example.com", otp.getLabel());
+    assert!(otp.getIssuer().is_empty());
+    assert_eq!(Some(42), otp.getCounter());
+}
+
+#[test]
+fn testNotOtpAuth() {
+    for contents in [
+        // No secret
+        "otpauth://totp/Example:alice@example.com?issuer=Example",
+        // Counter based without a counter
+        "otpauth://hotp/alice@example.com?secret=JBSWY3DPEHPK3PXP",
+        "otpauth://totp/alice@example.com?secret=JBSWY3DPEHPK3PXP&digits=six",
+        "otpauth://remoteaccess?devaddr=00%a1b2%c3d4&devname=foo&key=bar",
+    ] {
+        assert_ne!(
+            ParsedRXingResultType::OTP_AUTH,
+            parse(contents).getType(),
+            "{contents}"
+        );
+    }
+}
+
+fn parse(contents: &str) -> ParsedClientResult {
+    let fakeRXingResult =
+        RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    ResultParser::parseRXingResult(&fakeRXingResult)
+}
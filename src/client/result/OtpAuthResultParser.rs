@@ -0,0 +1,84 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::RXingResult;
+
+use super::{OtpAuthParsedRXingResult, ParsedClientResult, ResultParser};
+
+const OTPAUTH_SCHEME: &str = "otpauth://";
+
+/**
+ * <p>Parses one-time password provisioning URIs, as used by authenticator apps. They look like:</p>
+ *
+ * <p>{@code otpauth://totp/Example:alice@example.com?secret=JBSWY3DPEHPK3PXP&issuer=Example&period=30}</p>
+ *
+ * <p>The type is "totp" or "hotp", the label and the parameter values are URL encoded, and
+ * "secret" is required, as is "counter" for "hotp". Other {@code otpauth://} URIs are left to
+ * the URI parser.</p>
+ */
+pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
+    let rawText = ResultParser::getMassagedText(result);
+    let rawText = rawText.trim();
+    if !rawText
+        .get(..OTPAUTH_SCHEME.len())?
+        .eq_ignore_ascii_case(OTPAUTH_SCHEME)
+    {
+        return None;
+    }
+    let rest = &rawText[OTPAUTH_SCHEME.len()..];
+    let (path, _) = rest.split_once('?').unwrap_or((rest, ""));
+    let (otpType, label) = path.split_once('/').unwrap_or((path, ""));
+    let otpType = otpType.to_ascii_lowercase();
+    if otpType != "totp" && otpType != "hotp" {
+        return None;
+    }
+    let label = ResultParser::urlDecode(label).ok()?;
+
+    let parameters = ResultParser::parseNameValuePairs(rawText).unwrap_or_default();
+    let parameter = |name: &str| parameters.get(name).filter(|value| !value.is_empty());
+
+    let secret = parameter("secret")?.clone();
+    let issuer = match parameter("issuer") {
+        Some(issuer) => issuer.clone(),
+        None => label
+            .split_once(':')
+            .map(|(issuer, _)| issuer.trim().to_owned())
+            .unwrap_or_default(),
+    };
+    let algorithm = parameter("algorithm").map_or_else(
+        || OtpAuthParsedRXingResult::DEFAULT_ALGORITHM.to_owned(),
+        |algorithm| algorithm.to_ascii_uppercase(),
+    );
+    let digits = match parameter("digits") {
+        Some(digits) => digits.parse().ok()?,
+        None => OtpAuthParsedRXingResult::DEFAULT_DIGITS,
+    };
+    let period = match parameter("period") {
+        Some(period) => period.parse().ok()?,
+        None => OtpAuthParsedRXingResult::DEFAULT_PERIOD,
+    };
+    let counter = if otpType == "hotp" {
+        Some(parameter("counter")?.parse().ok()?)
+    } else {
+        None
+    };
+
+    Some(ParsedClientResult::OtpAuthResult(
+        OtpAuthParsedRXingResult::new(
+            otpType, label, issuer, secret, algorithm, digits, period, counter,
+        ),
+    ))
+}
@@ -29,7 +29,10 @@
 
 use chrono::{LocalResult, TimeZone, Utc};
 
-use crate::{client::result::ParsedRXingResult, BarcodeFormat, RXingResult};
+use crate::{
+    client::result::ParsedRXingResult, BarcodeFormat, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue,
+};
 
 use super::{ParsedRXingResultType, ResultParser};
 
@@ -67,6 +70,49 @@ fn test_text_type() {
     );
 }
 
+#[test]
+fn test_text_language() {
+    // "Hello, world" in Japanese, read from a UTF-8 byte segment
+    let text = "\u{3053}\u{3093}\u{306B}\u{3061}\u{306F}\u{4E16}\u{754C}";
+    let mut fake_rxing_result =
+        RXingResult::new(text, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    fake_rxing_result.putMetadata(
+        RXingResultMetadataType::BYTE_SEGMENTS,
+        RXingResultMetadataValue::ByteSegments(vec![text.as_bytes().to_vec()]),
+    );
+    let result = ResultParser::parseRXingResult(&fake_rxing_result);
+    let Some(text_result) = result.as_text() else {
+        panic!("expected text result");
+    };
+    assert_eq!("ja", text_result.getLanguage());
+    assert_eq!("UTF-8", text_result.getCharset().to_uppercase());
+
+    for (text, language) in [
+        // Korean and Greek
+        ("\u{C548}\u{B155}\u{D558}\u{C138}\u{C694}", "ko"),
+        (
+            "\u{039A}\u{03B1}\u{03BB}\u{03B7}\u{03BC}\u{03AD}\u{03C1}\u{03B1}",
+            "el",
+        ),
+        // Latin and Cyrillic script are shared by too many languages, as is mostly Latin text
+        ("Bonjour tout le monde", ""),
+        ("\u{041F}\u{0440}\u{0438}\u{0432}\u{0435}\u{0442}", ""),
+        // Han alone may as well be Japanese or Korean as Chinese
+        ("\u{4F60}\u{597D}\u{4E16}\u{754C}", ""),
+        ("Model \u{578B}\u{756A} 12", ""),
+        ("12345", ""),
+    ] {
+        let fake_rxing_result =
+            RXingResult::new(text, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+        let result = ResultParser::parseRXingResult(&fake_rxing_result);
+        let Some(text_result) = result.as_text() else {
+            panic!("expected text result for {text}");
+        };
+        assert_eq!(language, text_result.getLanguage(), "{text}");
+        assert!(text_result.getCharset().is_empty());
+    }
+}
+
 #[test]
 fn test_bookmark_type() {
     do_test_rxing_result(
@@ -261,8 +307,8 @@ fn test_upca() {
         BarcodeFormat::UPC_A,
     );
     do_test_rxing_result_long(
-        "1234567890123",
-        "1234567890123",
+        "1234567890128",
+        "1234567890128",
         ParsedRXingResultType::PRODUCT,
         BarcodeFormat::UPC_A,
     );
@@ -36,4 +36,6 @@ pub enum ParsedRXingResultType {
     WIFI,
     ISBN,
     VIN,
+    KEY_VALUE,
+    OTP_AUTH,
 }
@@ -42,6 +42,26 @@ fn test_product() {
     do_test("01234565", "012345000065", BarcodeFormat::UPC_E);
 }
 
+#[test]
+fn test_upc_e_expansion() {
+    do_test("04252614", "042100005264", BarcodeFormat::UPC_E);
+    do_test("12345670", "123456000070", BarcodeFormat::UPC_E);
+}
+
+#[test]
+fn test_invalid_check_digit() {
+    do_test_not_product("123456789013", BarcodeFormat::UPC_A);
+    do_test_not_product("00393158", BarcodeFormat::EAN_8);
+    do_test_not_product("5051140178490", BarcodeFormat::EAN_13);
+    do_test_not_product("01234566", BarcodeFormat::UPC_E);
+}
+
+fn do_test_not_product(contents: &str, format: BarcodeFormat) {
+    let fake_rxing_result = RXingResult::new(contents, Vec::new(), Vec::new(), format);
+    let result = ResultParser::parseRXingResult(&fake_rxing_result);
+    assert_ne!(ParsedRXingResultType::PRODUCT, result.getType());
+}
+
 fn do_test(contents: &str, normalized: &str, format: BarcodeFormat) {
     let fake_rxing_result = RXingResult::new(contents, Vec::new(), Vec::new(), format);
     let result = ResultParser::parseRXingResult(&fake_rxing_result);
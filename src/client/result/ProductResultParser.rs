@@ -25,7 +25,8 @@ use crate::{BarcodeFormat, RXingResult};
 use super::{ParsedClientResult, ProductParsedRXingResult, ResultParser};
 
 /**
- * Parses strings of digits that represent a UPC code.
+ * Parses strings of digits that represent a UPC code. Codes with an invalid check digit are
+ * not treated as products.
  *
  * @author dswitkin@google.com (Daniel Switkin)
  */
@@ -55,6 +56,12 @@ pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
          rawText.clone()
     };
 
+    // The check digit covers the expanded UPC-A form of a UPC-E code
+    let (data, check) = normalizedProductID.split_at(normalizedProductID.len() - 1);
+    if crate::oned::gtin_mod10(data)? != check.parse().ok()? {
+        return None;
+    }
+
     Some(ParsedClientResult::ProductResult(
         ProductParsedRXingResult::with_normalized_id(rawText, normalizedProductID),
     ))
@@ -33,15 +33,19 @@ use urlencoding::decode;
 
 use once_cell::sync::Lazy;
 
-use crate::{exceptions::Exceptions, RXingResult};
+use crate::{
+    common::StringUtils, exceptions::Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue,
+};
 
 use super::{
     AddressBookAUResultParser, AddressBookDoCoMoResultParser, BizcardResultParser,
     BookmarkDoCoMoResultParser, EmailAddressResultParser, EmailDoCoMoResultParser,
-    ExpandedProductResultParser, GeoResultParser, ISBNResultParser, ParsedClientResult,
-    ProductResultParser, SMSMMSResultParser, SMSTOMMSTOResultParser, SMTPResultParser,
-    TelResultParser, TextParsedRXingResult, URIResultParser, URLTOResultParser, VCardResultParser,
-    VEventResultParser, VINResultParser, WifiResultParser,
+    ExpandedProductResultParser, GeoResultParser, ISBNResultParser, KeyValueResultParser,
+    OtpAuthResultParser, ParsedClientResult, ProductResultParser, SMSMMSResultParser,
+    SMSTOMMSTOResultParser, SMTPResultParser, TelResultParser, TextParsedRXingResult,
+    URIResultParser, URLTOResultParser, VCardResultParser, VEventResultParser, VINResultParser,
+    WifiResultParser,
 };
 
 /**
@@ -130,7 +134,7 @@ pub fn parse_result_with_parser<F: Fn(&RXingResult) -> Option<ParsedClientResult
 }
 
 pub fn parseRXingResult(the_rxing_result: &RXingResult) -> ParsedClientResult {
-    let PARSERS: [&ParserFunction; 20] = [
+    let PARSERS: [&ParserFunction; 22] = [
         &BookmarkDoCoMoResultParser::parse,
         &AddressBookDoCoMoResultParser::parse,
         &EmailDoCoMoResultParser::parse,
@@ -145,12 +149,14 @@ pub fn parseRXingResult(the_rxing_result: &RXingResult) -> ParsedClientResult {
         &SMSTOMMSTOResultParser::parse,
         &GeoResultParser::parse,
         &WifiResultParser::parse,
+        &OtpAuthResultParser::parse,
         &URLTOResultParser::parse,
         &URIResultParser::parse,
         &ISBNResultParser::parse,
         &ProductResultParser::parse,
         &ExpandedProductResultParser::parse,
         &VINResultParser::parse,
+        &KeyValueResultParser::parse,
     ];
 
     for parser in PARSERS {
@@ -165,12 +171,30 @@ pub fn parseRXingResult(the_rxing_result: &RXingResult) -> ParsedClientResult {
     //   }
     // }
 
-    ParsedClientResult::TextResult(TextParsedRXingResult::new(
+    ParsedClientResult::TextResult(TextParsedRXingResult::with_detected_language(
         the_rxing_result.getText().to_owned(),
-        String::default(),
+        guessCharset(the_rxing_result),
     ))
 }
 
+/**
+ * @return name of the character set the byte segments of the result most likely were in, or an
+ * empty string if the result has none
+ */
+fn guessCharset(the_rxing_result: &RXingResult) -> String {
+    match the_rxing_result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::BYTE_SEGMENTS)
+    {
+        Some(RXingResultMetadataValue::ByteSegments(segments)) if !segments.is_empty() => {
+            StringUtils::guessCharset(&segments.concat(), &HashMap::new())
+                .name()
+                .to_owned()
+        }
+        _ => String::default(),
+    }
+}
+
 pub fn maybe_append_string(value: &str, result: &mut String) {
     if !value.is_empty() {
         if !result.is_empty() {
@@ -203,18 +227,12 @@ pub fn maybeWrap(value: Option<String>) -> Option<Vec<String>> {
 }
 
 pub fn unescapeBackslash(escaped: &str) -> String {
-    let backslash = escaped.find('\\');
-    if backslash.is_none() {
+    if !escaped.contains('\\') {
         return escaped.to_owned();
     }
-    let max = escaped.len();
-    let mut unescaped = String::with_capacity(max - 1);
-    let backslash = backslash.unwrap_or(0);
-    unescaped.push_str(&escaped[0..backslash]);
+    let mut unescaped = String::with_capacity(escaped.len() - 1);
     let mut nextIsEscaped = false;
-    for i in backslash..max {
-        // for (int i = backslash; i < max; i++) {
-        let c = escaped.chars().nth(i).unwrap();
+    for c in escaped.chars() {
         if nextIsEscaped || c != '\\' {
             unescaped.push(c);
             nextIsEscaped = false;
@@ -388,16 +406,12 @@ pub fn matchPrefixedField(
 }
 
 pub fn countPrecedingBackslashes(s: &str, pos: usize) -> u32 {
-    let mut count = 0;
-    for i in (0..pos).rev() {
-        // for (int i = pos - 1; i >= 0; i--) {
-        if s.chars().nth(i).unwrap() == '\\' {
-            count += 1;
-        } else {
-            break;
-        }
-    }
-    count
+    // pos is a byte offset, and a backslash is never part of a multi-byte character
+    s.as_bytes()[..pos]
+        .iter()
+        .rev()
+        .take_while(|b| **b == b'\\')
+        .count() as u32
 }
 
 pub fn matchSinglePrefixedField(
@@ -74,6 +74,43 @@ fn test_mms() {
     );
 }
 
+#[test]
+fn test_multiple_numbers() {
+    do_test_numbers(
+        "sms:+15551212,+15551213,+15551214?body=bar",
+        &["+15551212", "+15551213", "+15551214"],
+        "bar",
+        "+15551212\n+15551213\n+15551214\nbar",
+    );
+    do_test_numbers(
+        "sms:+15551212;+15551213",
+        &["+15551212", "+15551213"],
+        "",
+        "+15551212\n+15551213",
+    );
+    do_test_numbers(
+        "SMSTO:+15551212;+15551213:bar",
+        &["+15551212", "+15551213"],
+        "bar",
+        "+15551212\n+15551213\nbar",
+    );
+    do_test_numbers("smsto:15551212", &["15551212"], "", "15551212");
+}
+
+fn do_test_numbers(contents: &str, numbers: &[&str], body: &str, display: &str) {
+    let fake_rxing_result =
+        RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    let result = ResultParser::parseRXingResult(&fake_rxing_result);
+    assert_eq!(display, result.getDisplayRXingResult());
+
+    if let ParsedClientResult::SMSResult(smsRXingResult) = result {
+        assert_eq!(numbers, smsRXingResult.getNumbers());
+        assert_eq!(body, smsRXingResult.getBody());
+    } else {
+        panic!("Expected ParsedClientResult::SMSResult");
+    }
+}
+
 fn do_test(contents: &str, number: &str, subject: &str, body: &str, via: &str, parsedURI: &str) {
     let fake_rxing_result =
         RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
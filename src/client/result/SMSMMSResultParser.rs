@@ -81,30 +81,11 @@ pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
          &raw_text[4..4 + query_start.unwrap_or(0)]
     };
 
-    let mut last_comma: i32 = -1;
-    let mut comma: i32 = sms_uriwithout_query[(last_comma + 1) as usize..]
-        .find(',')
-        .unwrap_or(0) as i32;
     let mut numbers = Vec::with_capacity(1);
     let mut vias = Vec::with_capacity(1);
-    while comma > last_comma {
-        comma = sms_uriwithout_query[(last_comma + 1) as usize..]
-            .find(',')
-            .unwrap_or(0) as i32; //sms_uriwithout_query.indexOf(',', lastComma + 1);
-        let number_part =
-            &sms_uriwithout_query[(last_comma + 1) as usize..(last_comma + 1 + comma) as usize];
+    for number_part in sms_uriwithout_query.split(',') {
         add_number_via(&mut numbers, &mut vias, number_part);
-        last_comma = comma;
     }
-    add_number_via(
-        &mut numbers,
-        &mut vias,
-        &sms_uriwithout_query[(if last_comma > 0 {
-            last_comma + 1
-        } else {
-            last_comma
-        }) as usize..],
-    );
 
     Some(ParsedClientResult::SMSResult(
         SMSParsedRXingResult::with_arrays(numbers, vias, subject, body),
@@ -117,24 +98,15 @@ pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
 }
 
 fn add_number_via(numbers: &mut Vec<String>, vias: &mut Vec<String>, number_part: &str) {
-    if number_part.is_empty() {
-        return;
-    }
-    if let Some(number_end) = number_part.find(';') {
-        // if numberEnd < 0 {
-        numbers.push(number_part[..number_end].to_string());
-        let maybe_via = &number_part[number_end + 1..];
-        let via = if let Some(stripped) = maybe_via.strip_prefix("via=") {
-            stripped
-        } else {
-            ""
-        };
-        if !via.is_empty() {
-            vias.push(via.to_owned());
+    // Besides a via parameter, several numbers may be separated by semicolons
+    for part in number_part.split(';') {
+        if let Some(via) = part.strip_prefix("via=") {
+            if !via.is_empty() {
+                vias.push(via.to_owned());
+            }
+        } else if !part.is_empty() && !part.contains('=') {
+            numbers.push(part.to_owned());
         }
-    } else {
-        numbers.push(number_part.to_owned());
-        //vias.push(String::default());
     }
 }
 
@@ -82,9 +82,9 @@ impl SMSParsedRXingResult {
                 result.push(',');
             }
             result.push_str(&self.numbers[i]);
-            if !self.vias.is_empty() {
+            if let Some(via) = self.vias.get(i).filter(|via| !via.is_empty()) {
                 result.push_str(";via=");
-                result.push_str(&self.vias[i]);
+                result.push_str(via);
             }
         }
         let has_body = !self.body.is_empty();
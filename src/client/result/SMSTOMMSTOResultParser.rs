@@ -54,13 +54,14 @@ pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
     //   body = number.substring(bodyStart + 1);
     //   number = number.substring(0, bodyStart);
     // }
+    // Several recipients may be separated by semicolons or commas
+    let numbers = number
+        .split([';', ','])
+        .filter(|number| !number.is_empty())
+        .map(|number| number.to_owned())
+        .collect();
     Some(ParsedClientResult::SMSResult(
-        SMSParsedRXingResult::with_singles(
-            number.to_owned(),
-            String::default(),
-            String::default(),
-            body.to_owned(),
-        ),
+        SMSParsedRXingResult::with_arrays(numbers, Vec::new(), String::default(), body.to_owned()),
     ))
     // return new SMSParsedRXingResult(number, null, null, body);
 }
@@ -55,4 +55,28 @@ fn doTest(contents: &str, number: &str, title: &str) {
     }
 }
 
+#[test]
+fn testAsTel() {
+    let telRXingResult = ResultParser::parseRXingResult(&RXingResult::new(
+        "tel:+15551212",
+        Vec::new(),
+        Vec::new(),
+        BarcodeFormat::QR_CODE,
+    ));
+    assert_eq!(
+        Some("+15551212"),
+        telRXingResult.as_tel().map(|tel| tel.getNumber())
+    );
+    assert!(telRXingResult.as_geo().is_none());
+
+    let textRXingResult = ResultParser::parseRXingResult(&RXingResult::new(
+        "This is not a phone number",
+        Vec::new(),
+        Vec::new(),
+        BarcodeFormat::QR_CODE,
+    ));
+    assert!(textRXingResult.as_tel().is_none());
+    assert!(textRXingResult.as_text().is_some());
+}
+
 // }
@@ -28,6 +28,7 @@ use super::{ParsedRXingResult, ParsedRXingResultType};
 pub struct TextParsedRXingResult {
     text: String,
     language: String,
+    charset: String,
 }
 
 impl ParsedRXingResult for TextParsedRXingResult {
@@ -42,7 +43,27 @@ impl ParsedRXingResult for TextParsedRXingResult {
 
 impl TextParsedRXingResult {
     pub fn new(text: String, language: String) -> Self {
-        Self { text, language }
+        Self {
+            text,
+            language,
+            charset: String::default(),
+        }
+    }
+
+    /**
+     * Creates a result whose language is guessed from the text with {@link #guessLanguage}, and
+     * is left empty when that can't tell.
+     *
+     * @param text the decoded text
+     * @param charset name of the character set the text was decoded from, or empty if unknown
+     */
+    pub fn with_detected_language(text: String, charset: String) -> Self {
+        let language = Self::guessLanguage(&text).unwrap_or_default().to_owned();
+        Self {
+            text,
+            language,
+            charset,
+        }
     }
 
     pub fn getText(&self) -> &str {
@@ -52,4 +73,55 @@ impl TextParsedRXingResult {
     pub fn getLanguage(&self) -> &str {
         &self.language
     }
+
+    /**
+     * @return name of the character set the text was decoded from, or empty if unknown
+     */
+    pub fn getCharset(&self) -> &str {
+        &self.charset
+    }
+
+    /**
+     * Guesses the ISO 639-1 code of the language of a text from the scripts its letters are
+     * written in. This only answers for scripts which are (nearly) unique to one language, when
+     * most letters are in that script: kana for "ja", Hangul for "ko", and Greek, Hebrew and Thai.
+     * Latin and Cyrillic text, among others, gives None, as does Han alone, which is written in
+     * Chinese as well as in Japanese and Korean.
+     */
+    pub fn guessLanguage(text: &str) -> Option<&'static str> {
+        let (mut letters, mut kana, mut han, mut hangul, mut greek, mut hebrew, mut thai) =
+            (0, 0, 0, 0, 0, 0, 0);
+        for c in text.chars().filter(|c| c.is_alphabetic()) {
+            letters += 1;
+            match c {
+                '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+                    kana += 1
+                }
+                '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => {
+                    han += 1
+                }
+                '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+                    hangul += 1
+                }
+                '\u{0370}'..='\u{03FF}' => greek += 1,
+                '\u{0590}'..='\u{05FF}' => hebrew += 1,
+                '\u{0E00}'..='\u{0E7F}' => thai += 1,
+                _ => {}
+            }
+        }
+        let most = |count: i32| count * 2 > letters;
+        if kana > 0 && most(kana + han) {
+            Some("ja")
+        } else if most(hangul + han) && hangul > 0 {
+            Some("ko")
+        } else if most(greek) {
+            Some("el")
+        } else if most(hebrew) {
+            Some("he")
+        } else if most(thai) {
+            Some("th")
+        } else {
+            None
+        }
+    }
 }
@@ -59,11 +59,26 @@ impl URIParsedRXingResult {
     /**
      * @return true if the URI contains suspicious patterns that may suggest it intends to
      *  mislead the user about its true nature
-     * @deprecated see {@link URIRXingResultParser#isPossiblyMaliciousURI(String)}
+     * @deprecated see {@link #possiblyMaliciousURI()}
      */
     #[deprecated]
     pub fn is_possibly_malicious_uri(&self) -> bool {
+        self.possiblyMaliciousURI()
+    }
+
+    /**
+     * URIs parsed from plain text aren't returned as URIs if they look malicious, but those
+     * marked with a "URL:" or "URI:" prefix are.
+     *
+     * @return true if the URI contains suspicious patterns that may suggest it intends to
+     *  mislead the user about its true nature, like user info before the host or punycode
+     *  host labels
+     * @see URIResultParser#is_possibly_malicious_uri(String)
+     * @see URIResultParser#has_punycode_host(String)
+     */
+    pub fn possiblyMaliciousURI(&self) -> bool {
         URIResultParser::is_possibly_malicious_uri(&self.uri)
+            || URIResultParser::has_punycode_host(&self.uri)
     }
 
     /**
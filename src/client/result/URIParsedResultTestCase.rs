@@ -108,6 +108,39 @@ fn test_is_possibly_malicious() {
     do_test_is_possibly_malicious("http://google.com/@@", false);
 }
 
+#[test]
+fn test_punycode_host() {
+    do_test_punycode_host("http://xn--pple-43d.com/", true);
+    do_test_punycode_host("https://www.XN--pple-43d.com", true);
+    do_test_punycode_host("xn--pple-43d.com", true);
+    do_test_punycode_host("http://xn--mnchen-3ya.de", true);
+    do_test_punycode_host("http://example.com/xn--pple-43d", false);
+    do_test_punycode_host("http://example.com/?q=xn--pple-43d.com", false);
+}
+
+#[test]
+fn test_possibly_malicious_uri() {
+    let parse = |contents: &str| {
+        ResultParser::parseRXingResult(&RXingResult::new(
+            contents,
+            Vec::new(),
+            Vec::new(),
+            BarcodeFormat::QR_CODE,
+        ))
+    };
+
+    let benign = parse("http://good.com/user@home");
+    assert!(!benign.as_uri().expect("uri").possiblyMaliciousURI());
+    assert_eq!(
+        ParsedRXingResultType::TEXT,
+        parse("http://user@evil.com@good.com").getType()
+    );
+
+    // An explicit URL: prefix is taken at its word, but still flagged
+    let malicious = parse("URL:http://user@evil.com@good.com");
+    assert!(malicious.as_uri().expect("uri").possiblyMaliciousURI());
+}
+
 #[test]
 fn test_malicious_unicode() {
     do_test_is_possibly_malicious("https://google.com\u{2215}.evil.com/stuff", true);
@@ -164,4 +197,14 @@ fn do_test_is_possibly_malicious(uri: &str, malicious: bool) {
     );
 }
 
+fn do_test_punycode_host(uri: &str, flagged: bool) {
+    let fake_rxing_result = RXingResult::new(uri, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    let result = ResultParser::parseRXingResult(&fake_rxing_result);
+    assert_eq!(ParsedRXingResultType::URI, result.getType());
+    assert_eq!(
+        flagged,
+        result.as_uri().expect("uri").possiblyMaliciousURI()
+    );
+}
+
 // }
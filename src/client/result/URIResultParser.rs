@@ -39,6 +39,10 @@ static ALLOWED_URI_CHARS: Lazy<Regex> = Lazy::new(|| {
 });
 static USER_IN_HOST: Lazy<Regex> =
     Lazy::new(|| Regex::new(":/*([^/@]+)@[^/]+").expect("Regex patterns should always copile"));
+static PUNYCODE_IN_HOST: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?i)^(?:[a-z][a-z0-9+.\\-]*://)?(?:[^/?#@]*@)?(?:[^/?#:@.]*\\.)*xn--")
+        .expect("Regex patterns should always copile")
+});
 static URL_WITH_PROTOCOL_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new("[a-zA-Z][a-zA-Z0-9+-.]+:").unwrap());
 static URL_WITHOUT_PROTOCOL_PATTERN: Lazy<Regex> =
@@ -92,6 +96,16 @@ pub fn is_possibly_malicious_uri(uri: &str) -> bool {
     !allowed || user
 }
 
+/**
+ * @return true if a label of the URI's host is punycode, which starts with "xn--". Such
+ *  hosts may display as look-alikes of other domains, like xn--pple-43d.com for аpple.com
+ *  with a Cyrillic "а". Internationalized domains are legitimate too, so this doesn't keep
+ *  a URI from being parsed; it only flags it.
+ */
+pub fn has_punycode_host(uri: &str) -> bool {
+    PUNYCODE_IN_HOST.is_match(uri)
+}
+
 pub fn is_basically_valid_uri(uri: &str) -> bool {
     if uri.contains(' ') {
         // Quick hack check for a common case
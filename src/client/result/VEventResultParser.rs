@@ -152,7 +152,8 @@ fn matchVCardPrefixedField(prefix: &str, rawText: &str) -> Vec<String> {
             for (i, res) in result.iter_mut().enumerate().take(size) {
                 // for i in 0..size {
                 // for (int i = 0; i < size; i++) {
-                *res = values.get(i).unwrap().get(0).unwrap().clone();
+                // Parameters such as CN= come before the value itself
+                *res = values.get(i).unwrap().last().unwrap().clone();
             }
             result
         }
@@ -89,6 +89,45 @@ fn test_vin() {
     );
 }
 
+#[test]
+fn testNorthAmericanVIN() {
+    do_test(
+        "1HGCM82633A004352",
+        "1HG",
+        "CM8263",
+        "3A004352",
+        "US",
+        "CM826",
+        2003,
+        'A',
+        "004352",
+    );
+}
+
+#[test]
+fn testIncorrectCheckDigit() {
+    for contents in ["1HGCM82643A004352", "1HGCM826X3A004352"] {
+        let fakeRXingResult =
+            RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::CODE_39);
+        let result = ResultParser::parseRXingResult(&fakeRXingResult);
+        assert_eq!(ParsedRXingResultType::TEXT, result.getType());
+    }
+}
+
+#[test]
+fn testInvalidCharacters() {
+    for contents in [
+        "1hgcm82633a004352",
+        "1HGCM82633A00435-",
+        "1HGCM82633A0043521",
+    ] {
+        let fakeRXingResult =
+            RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::CODE_39);
+        let result = ResultParser::parseRXingResult(&fakeRXingResult);
+        assert_eq!(ParsedRXingResultType::TEXT, result.getType());
+    }
+}
+
 fn do_test(
     contents: &str,
     wmi: &str,
@@ -47,7 +47,9 @@ pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
     let raw_text_res = result.getText().trim();
     let raw_text = IOQ_MATCHER.replace_all(raw_text_res, "").to_string();
     // rawText = IOQ.matcher(rawText).replaceAll("").trim();
-    AZ09_MATCHER.find(&raw_text)?;
+    if !AZ09_MATCHER.is_match(&raw_text) {
+        return None;
+    }
     // if !AZ09.matcher(rawText).matches() {
     //   return null;
     // }
@@ -92,7 +94,7 @@ pub fn parse(result: &RXingResult) -> Option<ParsedClientResult> {
 }
 
 const IOQ: &str = "[IOQ]";
-const AZ09: &str = "[A-Z0-9]{17}";
+const AZ09: &str = "^[A-Z0-9]{17}$";
 
 fn check_checksum(vin: &str) -> Result<bool, Exceptions> {
     let mut sum = 0;
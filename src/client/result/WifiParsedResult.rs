@@ -18,6 +18,40 @@
 
 use super::{ParsedRXingResult, ParsedRXingResultType, ResultParser};
 
+/**
+ * The kind of authentication a wifi network asks for, as given by the "T:" field of a wifi
+ * configuration barcode.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WifiAuthType {
+    /** No password, "nopass" or an empty type */
+    OPEN,
+    WEP,
+    /** WPA or WPA2 personal; barcodes use "WPA" for either */
+    WPA,
+    WPA2,
+    /** WPA3 personal, "SAE" */
+    WPA3,
+    /** WPA or WPA2 enterprise, such as "WPA2-EAP", with the EAP details of the result */
+    WPA2_EAP,
+    /** Any other type, which is left in the raw string */
+    UNKNOWN,
+}
+
+impl From<&str> for WifiAuthType {
+    fn from(value: &str) -> Self {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "" | "NOPASS" | "NONE" | "OPEN" => WifiAuthType::OPEN,
+            "WEP" => WifiAuthType::WEP,
+            "WPA" => WifiAuthType::WPA,
+            "WPA2" => WifiAuthType::WPA2,
+            "SAE" | "WPA3" => WifiAuthType::WPA3,
+            "WPA2-EAP" | "WPA-EAP" | "EAP" => WifiAuthType::WPA2_EAP,
+            _ => WifiAuthType::UNKNOWN,
+        }
+    }
+}
+
 /**
  * Represents a parsed result that encodes wifi network information, like SSID and password.
  *
@@ -46,6 +80,11 @@ impl ParsedRXingResult for WifiParsedRXingResult {
         ResultParser::maybe_append_string(&self.networkEncryption, &mut result);
         ResultParser::maybe_append_string(&self.password, &mut result);
         ResultParser::maybe_append_string(&self.hidden.to_string(), &mut result);
+        // Only enterprise networks have any of these
+        ResultParser::maybe_append_string(&self.eapMethod, &mut result);
+        ResultParser::maybe_append_string(&self.phase2Method, &mut result);
+        ResultParser::maybe_append_string(&self.anonymousIdentity, &mut result);
+        ResultParser::maybe_append_string(&self.identity, &mut result);
 
         result
     }
@@ -105,6 +144,14 @@ impl WifiParsedRXingResult {
         &self.networkEncryption
     }
 
+    /**
+     * @return the authentication type, parsed from {@link #getNetworkEncryption()} regardless of
+     * case, which stays available for types {@link WifiAuthType} doesn't know
+     */
+    pub fn getAuthType(&self) -> WifiAuthType {
+        WifiAuthType::from(self.networkEncryption.as_str())
+    }
+
     pub fn getPassword(&self) -> &str {
         &self.password
     }
@@ -28,7 +28,10 @@
  */
 // public final class WifiParsedRXingResultTestCase extends Assert {
 use crate::{
-    client::result::{ParsedClientResult, ParsedRXingResult, ParsedRXingResultType},
+    client::result::{
+        ParsedClientResult, ParsedRXingResult, ParsedRXingResultType, WifiAuthType,
+        WifiParsedRXingResult,
+    },
     BarcodeFormat, RXingResult,
 };
 
@@ -143,6 +146,106 @@ fn testEscape() {
     );
 }
 
+#[test]
+fn testEscapedReservedCharacters() {
+    // The password is p;a\s:s,wörd\;
+    doTest(
+        "WIFI:T:WPA;S:test;P:p\\;a\\\\s\\:s\\,wörd\\\\\\;;;",
+        "test",
+        "p;a\\s:s,wörd\\;",
+        "WPA",
+    );
+    // Prefixes within other fields aren't fields of their own
+    doTest(
+        "WIFI:S:AP:Home\\;T:X;T:WPA;P:S:pass;;",
+        "AP:Home;T:X",
+        "S:pass",
+        "WPA",
+    );
+}
+
+#[test]
+fn testHidden() {
+    for (contents, hidden) in [
+        ("WIFI:S:Hidden;T:WPA;P:secret;H:true;;", true),
+        ("WIFI:S:Hidden;T:WPA;P:secret;H:TRUE;;", true),
+        ("WIFI:S:Shown;T:WPA;P:secret;H:false;;", false),
+        ("WIFI:S:Shown;T:WPA;P:secret;;", false),
+        // With a phase 2 method present "H:" can only be the hidden flag
+        ("WIFI:S:Shown;T:WPA2-EAP;PH2:MSCHAPV2;H:yes;;", false),
+    ] {
+        let wifiRXingResult = parseWifi(contents);
+        assert_eq!(hidden, wifiRXingResult.isHidden(), "{contents}");
+    }
+}
+
+#[test]
+fn testEap() {
+    let wifiRXingResult = parseWifi(
+        "WIFI:T:WPA2-EAP;S:Corp;H:true;E:TTLS;PH2:MSCHAPV2;A:anon@corp.example;I:jdoe;P:hunter2;X:ignored;;",
+    );
+    assert_eq!("Corp", wifiRXingResult.getSsid());
+    assert_eq!("WPA2-EAP", wifiRXingResult.getNetworkEncryption());
+    assert_eq!("hunter2", wifiRXingResult.getPassword());
+    assert!(wifiRXingResult.isHidden());
+    assert_eq!("TTLS", wifiRXingResult.getEapMethod());
+    assert_eq!("MSCHAPV2", wifiRXingResult.getPhase2Method());
+    assert_eq!("anon@corp.example", wifiRXingResult.getAnonymousIdentity());
+    assert_eq!("jdoe", wifiRXingResult.getIdentity());
+    assert_eq!(
+        "Corp\nWPA2-EAP\nhunter2\ntrue\nTTLS\nMSCHAPV2\nanon@corp.example\njdoe",
+        wifiRXingResult.getDisplayRXingResult()
+    );
+
+    // Older encoders put the phase 2 method in "H:"
+    let wifiRXingResult = parseWifi("WIFI:T:WPA2-EAP;S:Corp;E:PEAP;H:GTC;I:jdoe;P:hunter2;;");
+    assert!(!wifiRXingResult.isHidden());
+    assert_eq!("GTC", wifiRXingResult.getPhase2Method());
+    assert!(wifiRXingResult.getAnonymousIdentity().is_empty());
+
+    // Personal networks show none of the enterprise lines
+    let wifiRXingResult = parseWifi("WIFI:T:WPA;S:Home;P:secret;;");
+    assert!(wifiRXingResult.getEapMethod().is_empty());
+    assert_eq!(
+        "Home\nWPA\nsecret\nfalse",
+        wifiRXingResult.getDisplayRXingResult()
+    );
+}
+
+#[test]
+fn testAuthType() {
+    let authType = |contents: &str| parseWifi(contents).getAuthType();
+    assert_eq!(WifiAuthType::OPEN, authType("WIFI:S:Cafe;T:nopass;;"));
+    assert_eq!(WifiAuthType::OPEN, authType("WIFI:S:Cafe;;"));
+    assert_eq!(
+        WifiAuthType::WEP,
+        authType("WIFI:S:Home;T:wep;P:0123456789;;")
+    );
+    assert_eq!(WifiAuthType::WPA, authType("WIFI:S:Home;T:WPA;P:secret;;"));
+    assert_eq!(
+        WifiAuthType::WPA2,
+        authType("WIFI:S:Home;T:WPA2;P:secret;;")
+    );
+    assert_eq!(WifiAuthType::WPA3, authType("WIFI:S:Home;T:SAE;P:secret;;"));
+    assert_eq!(
+        WifiAuthType::WPA2_EAP,
+        authType("WIFI:S:Corp;T:WPA2-EAP;E:PEAP;I:jdoe;P:hunter2;;")
+    );
+
+    let wifiRXingResult = parseWifi("WIFI:S:Home;T:WPA4;P:secret;;");
+    assert_eq!(WifiAuthType::UNKNOWN, wifiRXingResult.getAuthType());
+    assert_eq!("WPA4", wifiRXingResult.getNetworkEncryption());
+}
+
+fn parseWifi(contents: &str) -> WifiParsedRXingResult {
+    let fakeRXingResult =
+        RXingResult::new(contents, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+    match ResultParser::parseRXingResult(&fakeRXingResult) {
+        ParsedClientResult::WiFiResult(wifiRXingResult) => wifiRXingResult,
+        _ => panic!("Expected WIFI"),
+    }
+}
+
 /**
  * Given the string contents for the barcode, check that it matches our expectations
  */
@@ -34,7 +34,8 @@ use super::ResultParser;
  *
  * <p>"EAP method" can e.g. be "TTLS" or "PWD" or one of the other fields in <a href="https://developer.android.com/reference/android/net/wifi/WifiEnterpriseConfig.Eap.html">WifiEnterpriseConfig.Eap</a> and "Phase 2 method" can e.g. be "MSCHAPV2" or any of the other fields in <a href="https://developer.android.com/reference/android/net/wifi/WifiEnterpriseConfig.Phase2.html">WifiEnterpriseConfig.Phase2</a></p>
  *
- * <p>The fields can appear in any order. Only "S:" is required.</p>
+ * <p>The fields can appear in any order. Only "S:" is required. Within values the reserved
+ * characters {@code \\}, {@code ;}, {@code ,}, {@code :} and {@code "} are escaped with a backslash.</p>
  *
  * @author Vikram Aggarwal
  * @author Sean Owen
@@ -51,31 +52,26 @@ pub fn parse(theRXingResult: &crate::RXingResult) -> Option<super::ParsedClientR
         return None;
     }
     let rawText = rawText_unstripped[WIFI_TEST.len()..].to_owned();
-    let ssid =
-        ResultParser::matchSinglePrefixedField("S:", &rawText, ';', false).unwrap_or_default();
+    let ssid = matchField("S:", &rawText).unwrap_or_default();
     if ssid.is_empty() {
         return None;
     }
-    let pass =
-        ResultParser::matchSinglePrefixedField("P:", &rawText, ';', false).unwrap_or_default();
-    let n_type =
-        if let Some(nt) = ResultParser::matchSinglePrefixedField("T:", &rawText, ';', false) {
-            nt
-        } else {
-            String::from("nopass")
-        };
+    let pass = matchField("P:", &rawText).unwrap_or_default();
+    let n_type = if let Some(nt) = matchField("T:", &rawText) {
+        nt
+    } else {
+        String::from("nopass")
+    };
 
     // Unfortunately, in the past, H: was not just used for boolean 'hidden', but 'phase 2 method'.
     // To try to retain backwards compatibility, we set one or the other based on whether the string
     // is 'true' or 'false':
     let mut hidden = false;
-    let mut phase2Method = ResultParser::matchSinglePrefixedField("PH2:", &rawText, ';', false);
-    let _hValue = if let Some(hv) =
-        ResultParser::matchSinglePrefixedField("H:", &rawText, ';', false)
-    {
+    let mut phase2Method = matchField("PH2:", &rawText);
+    let _hValue = if let Some(hv) = matchField("H:", &rawText) {
         // If PH2 was specified separately, or if the value is clearly boolean, interpret it as 'hidden'
         if phase2Method.is_some() || "true" == hv.to_lowercase() || "false" == hv.to_lowercase() {
-            hidden = hv.parse().unwrap(); //Boolean.parseBoolean(hValue);
+            hidden = hv.eq_ignore_ascii_case("true"); //Boolean.parseBoolean(hValue);
         } else {
             phase2Method = Some(hv.clone());
         }
@@ -84,12 +80,9 @@ pub fn parse(theRXingResult: &crate::RXingResult) -> Option<super::ParsedClientR
         String::default()
     };
 
-    let identity =
-        ResultParser::matchSinglePrefixedField("I:", &rawText, ';', false).unwrap_or_default();
-    let anonymousIdentity =
-        ResultParser::matchSinglePrefixedField("A:", &rawText, ';', false).unwrap_or_default();
-    let eapMethod =
-        ResultParser::matchSinglePrefixedField("E:", &rawText, ';', false).unwrap_or_default();
+    let identity = matchField("I:", &rawText).unwrap_or_default();
+    let anonymousIdentity = matchField("A:", &rawText).unwrap_or_default();
+    let eapMethod = matchField("E:", &rawText).unwrap_or_default();
 
     Some(ParsedClientResult::WiFiResult(
         WifiParsedRXingResult::with_details(
@@ -105,3 +98,28 @@ pub fn parse(theRXingResult: &crate::RXingResult) -> Option<super::ParsedClientR
     ))
 }
 // }
+
+/**
+ * Finds the unescaped value of the field with the given prefix. Fields are only separated by
+ * semicolons which aren't escaped, so that the prefix of a field showing up within the value of
+ * another, such as "P:" within an SSID, isn't taken for that field.
+ */
+fn matchField(prefix: &str, rawText: &str) -> Option<String> {
+    let mut start = 0;
+    let mut nextIsEscaped = false;
+    for (i, c) in rawText.char_indices().chain([(rawText.len(), ';')]) {
+        if nextIsEscaped {
+            nextIsEscaped = false;
+        } else if c == '\\' {
+            nextIsEscaped = true;
+        } else if c == ';' {
+            if let Some(value) = rawText[start..i].trim_start().strip_prefix(prefix) {
+                if !value.is_empty() {
+                    return Some(ResultParser::unescapeBackslash(value));
+                }
+            }
+            start = i + 1;
+        }
+    }
+    None
+}
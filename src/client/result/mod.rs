@@ -14,6 +14,10 @@ mod GeoParsedResult;
 mod GeoResultParser;
 mod ISBNParsedResult;
 mod ISBNResultParser;
+mod KeyValueParsedResult;
+mod KeyValueResultParser;
+mod OtpAuthParsedResult;
+mod OtpAuthResultParser;
 mod ParsedResult;
 mod ParsedResultType;
 mod ProductParsedResult;
@@ -55,6 +59,9 @@ pub use CalendarParsedResult::*;
 pub use CalendarParsedResult::*;
 pub use EmailAddressParsedResult::*;
 pub use ExpandedProductParsedResult::*;
+pub use KeyValueParsedResult::*;
+pub use KeyValueResultParser::{parse_key_value_with_delimiters, DEFAULT_KEY_VALUE_DELIMITERS};
+pub use OtpAuthParsedResult::*;
 pub use ProductParsedResult::*;
 pub use SMSParsedResult::*;
 pub use URIParsedResult::*;
@@ -73,6 +80,10 @@ mod GeoParsedResultTestCase;
 #[cfg(test)]
 mod ISBNParsedResultTestCase;
 #[cfg(test)]
+mod KeyValueParsedResultTestCase;
+#[cfg(test)]
+mod OtpAuthParsedResultTestCase;
+#[cfg(test)]
 mod ParsedReaderResultTestCase;
 #[cfg(test)]
 mod ProductParsedResultTestCase;
@@ -102,6 +113,130 @@ pub enum ParsedClientResult {
     AddressBookResult(AddressBookParsedRXingResult),
     CalendarEventResult(CalendarParsedRXingResult),
     ExpandedProductResult(ExpandedProductParsedRXingResult),
+    KeyValueResult(KeyValueParsedRXingResult),
+    OtpAuthResult(OtpAuthParsedRXingResult),
+}
+
+impl ParsedClientResult {
+    /** @return the parsed text, or None if this result is of another type */
+    pub fn as_text(&self) -> Option<&TextParsedRXingResult> {
+        match self {
+            ParsedClientResult::TextResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed telephone number, or None if this result is of another type */
+    pub fn as_tel(&self) -> Option<&TelParsedRXingResult> {
+        match self {
+            ParsedClientResult::TelResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed ISBN, or None if this result is of another type */
+    pub fn as_isbn(&self) -> Option<&ISBNParsedRXingResult> {
+        match self {
+            ParsedClientResult::ISBNResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed Wi-Fi network configuration, or None if this result is of another type */
+    pub fn as_wifi(&self) -> Option<&WifiParsedRXingResult> {
+        match self {
+            ParsedClientResult::WiFiResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed geographic location, or None if this result is of another type */
+    pub fn as_geo(&self) -> Option<&GeoParsedRXingResult> {
+        match self {
+            ParsedClientResult::GeoResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed SMS or MMS message, or None if this result is of another type */
+    pub fn as_sms(&self) -> Option<&SMSParsedRXingResult> {
+        match self {
+            ParsedClientResult::SMSResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed product code, or None if this result is of another type */
+    pub fn as_product(&self) -> Option<&ProductParsedRXingResult> {
+        match self {
+            ParsedClientResult::ProductResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed URI, or None if this result is of another type */
+    pub fn as_uri(&self) -> Option<&URIParsedRXingResult> {
+        match self {
+            ParsedClientResult::URIResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed email message, or None if this result is of another type */
+    pub fn as_email(&self) -> Option<&EmailAddressParsedRXingResult> {
+        match self {
+            ParsedClientResult::EmailResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed vehicle identification number, or None if this result is of another type */
+    pub fn as_vin(&self) -> Option<&VINParsedRXingResult> {
+        match self {
+            ParsedClientResult::VINResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed contact, or None if this result is of another type */
+    pub fn as_address_book(&self) -> Option<&AddressBookParsedRXingResult> {
+        match self {
+            ParsedClientResult::AddressBookResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed calendar event, or None if this result is of another type */
+    pub fn as_calendar_event(&self) -> Option<&CalendarParsedRXingResult> {
+        match self {
+            ParsedClientResult::CalendarEventResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed RSS expanded product, or None if this result is of another type */
+    pub fn as_expanded_product(&self) -> Option<&ExpandedProductParsedRXingResult> {
+        match self {
+            ParsedClientResult::ExpandedProductResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed list of key/value pairs, or None if this result is of another type */
+    pub fn as_key_value(&self) -> Option<&KeyValueParsedRXingResult> {
+        match self {
+            ParsedClientResult::KeyValueResult(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /** @return the parsed one-time password provisioning URI, or None if this result is of another type */
+    pub fn as_otp_auth(&self) -> Option<&OtpAuthParsedRXingResult> {
+        match self {
+            ParsedClientResult::OtpAuthResult(a) => Some(a),
+            _ => None,
+        }
+    }
 }
 
 impl ParsedRXingResult for ParsedClientResult {
@@ -120,6 +255,8 @@ impl ParsedRXingResult for ParsedClientResult {
             ParsedClientResult::AddressBookResult(a) => a.getType(),
             ParsedClientResult::CalendarEventResult(a) => a.getType(),
             ParsedClientResult::ExpandedProductResult(a) => a.getType(),
+            ParsedClientResult::KeyValueResult(a) => a.getType(),
+            ParsedClientResult::OtpAuthResult(a) => a.getType(),
         }
     }
 
@@ -138,6 +275,8 @@ impl ParsedRXingResult for ParsedClientResult {
             ParsedClientResult::AddressBookResult(a) => a.getDisplayRXingResult(),
             ParsedClientResult::CalendarEventResult(a) => a.getDisplayRXingResult(),
             ParsedClientResult::ExpandedProductResult(a) => a.getDisplayRXingResult(),
+            ParsedClientResult::KeyValueResult(a) => a.getDisplayRXingResult(),
+            ParsedClientResult::OtpAuthResult(a) => a.getDisplayRXingResult(),
         }
     }
 }
@@ -297,3 +297,34 @@ fn arrays_are_equal(left: &[u32], right: &[u32], size: usize) -> bool {
 }
 
 // }
+
+#[test]
+fn test_count_ones() {
+    let mut r = rand::thread_rng();
+    for size in [0, 1, 31, 32, 33, 100, 128] {
+        let mut array = BitArray::with_size(size);
+        for i in 0..size {
+            if r.gen_bool(0.5) {
+                array.set(i);
+            }
+        }
+        let naive = (0..size).filter(|i| array.get(*i)).count();
+        assert_eq!(naive, array.count_ones());
+        // Reversing shifts bits between ints, but mustn't change the count
+        if size > 0 {
+            array.reverse();
+            assert_eq!(naive, array.count_ones());
+        }
+    }
+}
+
+#[test]
+fn test_density() {
+    let mut array = BitArray::with_size(40);
+    assert_eq!(0.0, array.density());
+    array.setRange(0, 10).expect("set");
+    assert_eq!(0.25, array.density());
+    array.setRange(10, 40).expect("set");
+    assert_eq!(1.0, array.density());
+    assert_eq!(0.0, BitArray::new().density());
+}
@@ -0,0 +1,54 @@
+use super::{sample_grid, BitMatrix, PerspectiveTransform};
+
+const DIMENSION: u32 = 7;
+
+/** Corners of the warped symbol in the image, clockwise from the top left. */
+const CORNERS: [f32; 8] = [10.0, 12.0, 85.0, 8.0, 90.0, 92.0, 6.0, 80.0];
+
+#[test]
+fn test_sample_warped_grid() {
+    let mut modules = BitMatrix::with_single_dimension(DIMENSION);
+    for y in 0..DIMENSION {
+        for x in 0..DIMENSION {
+            if (x * 3 + y * 5 + x * y) % 4 < 2 {
+                modules.set(x, y);
+            }
+        }
+    }
+
+    let size = DIMENSION as f32;
+    let [x0, y0, x1, y1, x2, y2, x3, y3] = CORNERS;
+    let imageToModules = PerspectiveTransform::quadrilateralToQuadrilateral(
+        x0, y0, x1, y1, x2, y2, x3, y3, 0.0, 0.0, size, 0.0, size, size, 0.0, size,
+    );
+    let modulesToImage = PerspectiveTransform::quadrilateralToQuadrilateral(
+        0.0, 0.0, size, 0.0, size, size, 0.0, size, x0, y0, x1, y1, x2, y2, x3, y3,
+    );
+
+    // Draw the modules in perspective
+    let mut image = BitMatrix::with_single_dimension(100);
+    for y in 0..image.getHeight() {
+        for x in 0..image.getWidth() {
+            let mut point = [x as f32 + 0.5, y as f32 + 0.5];
+            imageToModules.transform_points_single(&mut point);
+            let [moduleX, moduleY] = point;
+            if (0.0..size).contains(&moduleX)
+                && (0.0..size).contains(&moduleY)
+                && modules.get(moduleX as u32, moduleY as u32)
+            {
+                image.set(x, y);
+            }
+        }
+    }
+
+    let sampled = sample_grid(&image, DIMENSION, DIMENSION, &modulesToImage).unwrap();
+    assert_eq!(modules, sampled);
+}
+
+#[test]
+fn test_sample_outside_image() {
+    let image = BitMatrix::with_single_dimension(20);
+    let transform =
+        PerspectiveTransform::squareToQuadrilateral(0.0, 0.0, 40.0, 0.0, 40.0, 40.0, 0.0, 40.0);
+    assert!(sample_grid(&image, DIMENSION, DIMENSION, &transform).is_err());
+}
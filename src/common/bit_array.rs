@@ -29,7 +29,7 @@ static LOAD_FACTOR: f32 = 0.75f32;
  *
  * @author Sean Owen
  */
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub struct BitArray {
     bits: Vec<u32>,
     size: usize,
@@ -331,6 +331,33 @@ impl BitArray {
         &self.bits
     }
 
+    /**
+     * @return number of bits which are set in the array
+     */
+    pub fn count_ones(&self) -> usize {
+        let full = self.size / 32;
+        let mut count: usize = self.bits[..full]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+        // ignore anything beyond size in the last, partially used int
+        let remainder = self.size & 0x1F;
+        if remainder != 0 {
+            count += (self.bits[full] & ((1 << remainder) - 1)).count_ones() as usize;
+        }
+        count
+    }
+
+    /**
+     * @return fraction of the bits which are set, between 0 and 1; 0 for an empty array
+     */
+    pub fn density(&self) -> f32 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        self.count_ones() as f32 / self.size as f32
+    }
+
     /**
      * Reverses all bits in the array.
      */
@@ -402,3 +429,19 @@ impl Default for BitArray {
         Self::new()
     }
 }
+
+impl Clone for BitArray {
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits.clone(),
+            size: self.size,
+        }
+    }
+
+    // Reuses the existing allocation, which matters when the same row buffer is refilled
+    // for every scanned row of an image.
+    fn clone_from(&mut self, source: &Self) {
+        self.bits.clone_from(&source.bits);
+        self.size = source.size;
+    }
+}
@@ -24,6 +24,9 @@ use crate::{Exceptions, RXingResultPoint};
 
 use super::BitArray;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /**
  * <p>Represents a 2D matrix of bits. In function arguments below, and throughout the common
  * module, x is the column position, and y is the row position. The ordering is always x, y.
@@ -39,6 +42,7 @@ use super::BitArray;
  * @author Sean Owen
  * @author dswitkin@google.com (Daniel Switkin)
  */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BitMatrix {
     width: u32,
@@ -263,6 +267,29 @@ impl BitMatrix {
         self.bits[offset] |= 1 << (x & 0x1f);
     }
 
+    /**
+     * <p>Sets the given bit to true, without checking that it's inside the matrix. Meant for
+     * encoders placing many modules whose positions are known to be valid.</p>
+     *
+     * # Safety
+     *
+     * The caller guarantees that x is less than the width and y less than the height of the
+     * matrix. This is only asserted in debug builds.
+     *
+     * @param x The horizontal component (i.e. which column)
+     * @param y The vertical component (i.e. which row)
+     */
+    pub unsafe fn set_unchecked(&mut self, x: u32, y: u32) {
+        debug_assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) outside of {}x{} matrix",
+            self.width,
+            self.height
+        );
+        let offset = self.get_offset(y, x);
+        *self.bits.get_unchecked_mut(offset) |= 1 << (x & 0x1f);
+    }
+
     pub fn set_bool(&mut self, x: u32, y: u32, value: bool) {
         if value {
             self.set(x, y)
@@ -282,11 +309,18 @@ impl BitMatrix {
      * @param x The horizontal component (i.e. which column)
      * @param y The vertical component (i.e. which row)
      */
-    pub fn flip_coords(&mut self, x: u32, y: u32) {
+    pub fn flip(&mut self, x: u32, y: u32) {
         let offset = self.get_offset(y, x);
         self.bits[offset] ^= 1 << (x & 0x1f);
     }
 
+    /**
+     * Same as {@link #flip(int, int)}.
+     */
+    pub fn flip_coords(&mut self, x: u32, y: u32) {
+        self.flip(x, y)
+    }
+
     /**
      * <p>Flips every bit in the matrix.</p>
      */
@@ -295,6 +329,7 @@ impl BitMatrix {
         for bit_set in self.bits.iter_mut().take(max) {
             *bit_set = !*bit_set;
         }
+        self.clear_trailing_bits();
     }
 
     /**
@@ -322,9 +357,35 @@ impl BitMatrix {
                 self.bits[offset + x] ^= *row_x;
             }
         }
+        self.clear_trailing_bits();
         Ok(())
     }
 
+    /**
+     * @return mask of the bits of the last word of each row which lie inside the matrix. Bits
+     * past {@code width} are always clear, so that operations on whole words, and comparisons
+     * of matrices, never see them.
+     */
+    fn row_mask(&self) -> u32 {
+        match self.width % 32 {
+            0 => u32::MAX,
+            used => (1 << used) - 1,
+        }
+    }
+
+    /**
+     * Clears the bits past {@code width} in the last word of each row, after an operation on
+     * whole words may have set them.
+     */
+    fn clear_trailing_bits(&mut self) {
+        let mask = self.row_mask();
+        if mask != u32::MAX {
+            for row in self.bits.chunks_exact_mut(self.row_size) {
+                row[self.row_size - 1] &= mask;
+            }
+        }
+    }
+
     /**
      * Clears all bits (sets to false).
      */
@@ -413,9 +474,11 @@ impl BitMatrix {
      * @param row {@link BitArray} to copy from
      */
     pub fn setRow(&mut self, y: u32, row: &BitArray) {
-        self.bits[y as usize * self.row_size..y as usize * self.row_size + self.row_size]
-            .clone_from_slice(&row.getBitArray()[0..self.row_size])
+        let offset = y as usize * self.row_size;
+        self.bits[offset..offset + self.row_size]
+            .clone_from_slice(&row.getBitArray()[0..self.row_size]);
         //System.arraycopy(row.getBitArray(), 0, self.bits, y * self.rowSize, self.rowSize);
+        self.bits[offset + self.row_size - 1] &= self.row_mask();
     }
 
     /**
@@ -490,6 +553,31 @@ impl BitMatrix {
         self.bits = newBits;
     }
 
+    /**
+     * Modifies this {@code BitMatrix} to represent its mirror image across the main diagonal,
+     * so that the bit at (x, y) moves to (y, x). This is how a symbol appears when it is read
+     * through glass or in a mirror, then rotated back upright.
+     */
+    pub fn mirror(&mut self) {
+        let newWidth = self.height;
+        let newHeight = self.width;
+        let newRowSize = newWidth.div_ceil(32);
+        let mut newBits = vec![0; (newRowSize * newHeight) as usize];
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.get(x, y) {
+                    let newOffset = (x * newRowSize + (y / 32)) as usize;
+                    newBits[newOffset] |= 1 << (y & 0x1f);
+                }
+            }
+        }
+        self.width = newWidth;
+        self.height = newHeight;
+        self.row_size = newRowSize as usize;
+        self.bits = newBits;
+    }
+
     /**
      * This is useful in detecting the enclosing rectangle of a 'pure' barcode.
      *
@@ -735,41 +823,149 @@ impl From<BitMatrix> for image::DynamicImage {
 #[cfg(feature = "image")]
 impl From<&BitMatrix> for image::DynamicImage {
     fn from(value: &BitMatrix) -> Self {
-        let mut pixels = image::ImageBuffer::new(value.width, value.height);
-
-        for (x, y, pixel) in pixels.enumerate_pixels_mut() {
-            let new_pixel = if value.get(x, y) {
-                image::Rgb([0, 0, 0])
-            } else {
-                image::Rgb([u8::MAX, u8::MAX, u8::MAX])
-            };
-            *pixel = new_pixel
-        }
-
-        pixels.into()
+        value.renderImage(&super::RenderConfig::default())
     }
 }
 
 #[cfg(feature = "svg_write")]
 impl From<&BitMatrix> for svg::Document {
     fn from(value: &BitMatrix) -> Self {
-        let block_size = 1;
-        let mut document = svg::Document::new().set(
-            "viewBox",
-            (0, 0, value.width * block_size, value.height * block_size),
-        );
-        for x in 0..value.width {
-            for y in 0..value.height {
-                if value.get(x, y) {
-                    let block = svg::node::element::Rectangle::new()
-                        .set("x", x * block_size)
-                        .set("y", y * block_size)
-                        .set("width", block_size)
-                        .set("height", block_size);
+        value.renderSvg(&super::RenderConfig::default())
+    }
+}
+
+impl BitMatrix {
+    /**
+     * Renders the matrix as an image, in the colors and with the module size and margin of the
+     * given config. The image only has an alpha channel if one of the colors isn't opaque.
+     *
+     * @throws IllegalArgumentException if the scale of the config is 0
+     */
+    #[cfg(feature = "image")]
+    pub fn render_image(
+        &self,
+        config: &super::RenderConfig,
+    ) -> Result<image::DynamicImage, Exceptions> {
+        config.validate()?;
+        Ok(self.renderImage(config))
+    }
+
+    #[cfg(feature = "image")]
+    fn renderImage(&self, config: &super::RenderConfig) -> image::DynamicImage {
+        let (scale, margin) = (config.scale, config.margin);
+        let color = |x: u32, y: u32| {
+            let argb = if self.isSetScaled(x, y, scale, margin) {
+                config.fg
+            } else {
+                config.bg
+            };
+            argb.to_be_bytes()
+        };
+
+        let width = (self.width + 2 * margin) * scale;
+        let height = (self.height + 2 * margin) * scale;
+        if config.isOpaque() {
+            image::RgbImage::from_fn(width, height, |x, y| {
+                let [_, red, green, blue] = color(x, y);
+                image::Rgb([red, green, blue])
+            })
+            .into()
+        } else {
+            image::RgbaImage::from_fn(width, height, |x, y| {
+                let [alpha, red, green, blue] = color(x, y);
+                image::Rgba([red, green, blue, alpha])
+            })
+            .into()
+        }
+    }
+
+    /**
+     * Renders the matrix as SVG, in the colors and with the module size and margin of the given
+     * config. Black modules are left at the SVG default fill, and a white or fully transparent
+     * background is left out, so the default config gives the same document as it always has.
+     *
+     * @throws IllegalArgumentException if the scale of the config is 0
+     */
+    #[cfg(feature = "svg_write")]
+    pub fn render_svg(&self, config: &super::RenderConfig) -> Result<svg::Document, Exceptions> {
+        config.validate()?;
+        Ok(self.renderSvg(config))
+    }
+
+    #[cfg(feature = "svg_write")]
+    fn renderSvg(&self, config: &super::RenderConfig) -> svg::Document {
+        use super::RenderConfig;
+        use svg::node::element::Rectangle;
+
+        fn filled(rectangle: Rectangle, argb: u32) -> Rectangle {
+            let [alpha, red, green, blue] = argb.to_be_bytes();
+            let rectangle = rectangle.set("fill", format!("#{red:02x}{green:02x}{blue:02x}"));
+            if alpha == u8::MAX {
+                rectangle
+            } else {
+                rectangle.set("fill-opacity", alpha as f32 / u8::MAX as f32)
+            }
+        }
+
+        let (scale, margin) = (config.scale, config.margin);
+        let width = (self.width + 2 * margin) * scale;
+        let height = (self.height + 2 * margin) * scale;
+        let mut document = svg::Document::new().set("viewBox", (0, 0, width, height));
+        if config.bg >> 24 != 0 && config.bg != RenderConfig::WHITE {
+            let background = Rectangle::new().set("width", width).set("height", height);
+            document = document.add(filled(background, config.bg));
+        }
+        for x in 0..self.width {
+            for y in 0..self.height {
+                if self.get(x, y) {
+                    let mut block = Rectangle::new()
+                        .set("x", (x + margin) * scale)
+                        .set("y", (y + margin) * scale)
+                        .set("width", scale)
+                        .set("height", scale);
+                    if config.fg != RenderConfig::BLACK {
+                        block = filled(block, config.fg);
+                    }
                     document = document.add(block);
                 }
             }
         }
         document
     }
+
+    /**
+     * Scales the matrix up by the module size of the given config and surrounds it with its
+     * margin, for writing pixels to a target that takes a matrix. The colors are not used.
+     *
+     * @throws IllegalArgumentException if the scale of the config is 0
+     */
+    pub fn render_matrix(&self, config: &super::RenderConfig) -> Result<BitMatrix, Exceptions> {
+        config.validate()?;
+        let (scale, margin) = (config.scale, config.margin);
+        let mut result = BitMatrix::new(
+            (self.width + 2 * margin) * scale,
+            (self.height + 2 * margin) * scale,
+        )?;
+        for y in 0..result.height {
+            for x in 0..result.width {
+                if self.isSetScaled(x, y, scale, margin) {
+                    result.set(x, y);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /**
+     * @return whether pixel (x, y) of the matrix scaled up by scale, with margin blank modules
+     * around it, belongs to a set module
+     */
+    fn isSetScaled(&self, x: u32, y: u32, scale: u32, margin: u32) -> bool {
+        let (x, y) = (x / scale, y / scale);
+        x >= margin
+            && y >= margin
+            && x - margin < self.width
+            && y - margin < self.height
+            && self.get(x - margin, y - margin)
+    }
 }
@@ -27,7 +27,7 @@
 //  */
 // public final class BitMatrixTestCase extends Assert {
 
-use super::BitMatrix;
+use super::{BitArray, BitMatrix, RenderConfig};
 
 static BIT_MATRIX_POINTS: [u32; 6] = [1, 2, 2, 0, 3, 1];
 
@@ -124,6 +124,41 @@ fn test_rectangular_matrix() {
     assert!(!matrix.get(51, 3));
 }
 
+#[test]
+fn test_flip_twice_restores() {
+    let mut matrix = BitMatrix::new(75, 20).unwrap();
+    matrix.set(10, 0);
+    matrix.set(74, 19);
+    let original = matrix.clone();
+    for (x, y) in [(10, 0), (74, 19), (0, 0), (31, 7), (32, 7)] {
+        let was = matrix.get(x, y);
+        matrix.flip(x, y);
+        assert_eq!(!was, matrix.get(x, y));
+        matrix.flip(x, y);
+    }
+    assert_eq!(original, matrix);
+}
+
+#[test]
+fn test_set_unchecked_matches_set() {
+    let mut checked = BitMatrix::new(75, 20).unwrap();
+    let mut unchecked = BitMatrix::new(75, 20).unwrap();
+    for (x, y) in [
+        (0, 0),
+        (31, 0),
+        (32, 0),
+        (74, 0),
+        (10, 5),
+        (63, 12),
+        (74, 19),
+    ] {
+        checked.set(x, y);
+        // SAFETY: all inside the 75x20 matrix
+        unsafe { unchecked.set_unchecked(x, y) };
+    }
+    assert_eq!(checked, unchecked);
+}
+
 #[test]
 fn test_rectangular_set_region() {
     let mut matrix = BitMatrix::new(320, 240).unwrap();
@@ -209,6 +244,32 @@ fn test_rotate180_simple() {
     assert!(matrix.get(0, 1));
 }
 
+#[test]
+fn test_mirror() {
+    let mut matrix = BitMatrix::new(3, 3).unwrap();
+    matrix.set(0, 0);
+    matrix.set(0, 1);
+    matrix.set(1, 2);
+    matrix.set(2, 1);
+
+    matrix.mirror();
+
+    assert!(matrix.get(0, 0));
+    assert!(matrix.get(1, 0));
+    assert!(matrix.get(2, 1));
+    assert!(matrix.get(1, 2));
+    assert!(!matrix.get(0, 1));
+
+    let mut rectangular = BitMatrix::new(40, 2).unwrap();
+    rectangular.set(35, 1);
+    rectangular.mirror();
+    assert_eq!(2, rectangular.getWidth());
+    assert_eq!(40, rectangular.getHeight());
+    assert!(rectangular.get(1, 35));
+    rectangular.mirror();
+    assert!(rectangular.get(35, 1));
+}
+
 #[test]
 fn test_rotate180_case() {
     test_rotate_180(7, 4);
@@ -342,6 +403,54 @@ fn test_xor_case() {
     // }
 }
 
+#[test]
+fn test_word_operations_clear_trailing_bits() {
+    // One and two bits past a word boundary, so most of the last word of each row is unused
+    for width in [33, 65] {
+        let height = 3;
+        let empty = BitMatrix::new(width, height).unwrap();
+        let mut full = empty.clone();
+        full.setRegion(0, 0, width, height).expect("must set");
+
+        let mut flipped = empty.clone();
+        flipped.flip_self();
+        assert_eq!(full, flipped, "flip {width}");
+        assert_eq!(
+            Some([0, 0, width, height]),
+            flipped.getEnclosingRectangle(),
+            "enclosing {width}"
+        );
+        assert_eq!(
+            Some([width - 1, height - 1]),
+            flipped.getBottomRightOnBit(),
+            "bottom right {width}"
+        );
+        assert_eq!(width as usize, flipped.getRow(1).getNextUnset(0));
+
+        let mut xored = empty.clone();
+        xored.xor(&flipped).expect("must xor");
+        assert_eq!(full, xored, "xor {width}");
+        xored.xor(&flipped).expect("must xor");
+        assert_eq!(empty, xored, "xor twice {width}");
+
+        // A row which is wider than the matrix
+        let mut row = BitArray::with_size(96);
+        row.setRange(0, 96).expect("must set");
+        let mut copied = empty.clone();
+        for y in 0..height {
+            copied.setRow(y, &row);
+        }
+        assert_eq!(full, copied, "set row {width}");
+
+        let mut rotated = flipped.clone();
+        rotated.rotate180();
+        assert_eq!(full, rotated, "rotate 180 {width}");
+        rotated.rotate(270).expect("must rotate");
+        rotated.rotate(90).expect("must rotate");
+        assert_eq!(full, rotated, "rotate 270 and 90 {width}");
+    }
+}
+
 pub fn matrix_to_string(result: &BitMatrix) -> String {
     assert_eq!(1, result.getHeight());
     let mut builder = String::with_capacity(result.getWidth().try_into().unwrap());
@@ -396,3 +505,120 @@ fn get_input(width: u32, height: u32) -> BitMatrix {
     }
     result
 }
+
+#[test]
+fn test_render_config_scale() {
+    let matrix = get_input(4, 3);
+    let config = RenderConfig {
+        scale: 0,
+        ..RenderConfig::default()
+    };
+    assert!(matrix.render_matrix(&config).is_err());
+    let config = RenderConfig {
+        scale: 2,
+        margin: 1,
+        ..RenderConfig::default()
+    };
+    let scaled = matrix.render_matrix(&config).expect("scale");
+    assert_eq!((12, 10), (scaled.getWidth(), scaled.getHeight()));
+    for y in 0..scaled.getHeight() {
+        for x in 0..scaled.getWidth() {
+            let (mx, my) = (x / 2, y / 2);
+            let isSet = (1..5).contains(&mx) && (1..4).contains(&my) && matrix.get(mx - 1, my - 1);
+            assert_eq!(isSet, scaled.get(x, y), "({x}, {y})");
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_render_image_with_config() {
+    use crate::{qrcode::QRCodeWriter, BarcodeFormat, Writer};
+
+    let matrix = QRCodeWriter
+        .encode("render", &BarcodeFormat::QR_CODE, 0, 0)
+        .expect("encode");
+    let (fg, bg) = (0xFF1E3A8A, 0xFFFFEE00);
+    let config = RenderConfig {
+        fg,
+        bg,
+        scale: 3,
+        margin: 2,
+    };
+    let image = matrix.render_image(&config).expect("render").to_rgb8();
+    assert_eq!((matrix.getWidth() + 4) * 3, image.width());
+    assert_eq!((matrix.getHeight() + 4) * 3, image.height());
+
+    let rgb = |argb: u32| {
+        let [_, red, green, blue] = argb.to_be_bytes();
+        image::Rgb([red, green, blue])
+    };
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let (mx, my) = (x / 3, y / 3);
+        let isSet = (2..matrix.getWidth() + 2).contains(&mx)
+            && (2..matrix.getHeight() + 2).contains(&my)
+            && matrix.get(mx - 2, my - 2);
+        assert_eq!(&rgb(if isSet { fg } else { bg }), pixel, "({x}, {y})");
+    }
+
+    // Transparency needs an alpha channel
+    let config = RenderConfig {
+        bg: 0,
+        scale: 1,
+        margin: 0,
+        ..config
+    };
+    let image = matrix.render_image(&config).expect("render");
+    assert!(image.color().has_alpha());
+    assert_eq!(image::Rgba([0, 0, 0, 0]), image.to_rgba8()[(7, 0)]);
+}
+
+#[cfg(feature = "svg_write")]
+#[test]
+fn test_render_svg_with_config() {
+    let matrix = BitMatrix::parse_strings("X  \n X \n", "X", " ").expect("parse");
+    let config = RenderConfig {
+        fg: 0x801E3A8A,
+        bg: 0xFFFFEE00,
+        scale: 4,
+        margin: 1,
+    };
+    let svg = matrix.render_svg(&config).expect("render").to_string();
+    assert!(svg.contains("viewBox=\"0 0 20 16\""), "{svg}");
+    assert!(svg.contains("fill=\"#ffee00\""), "{svg}");
+    assert_eq!(2, svg.matches("fill=\"#1e3a8a\"").count(), "{svg}");
+    assert!(svg.contains("x=\"8\""), "{svg}");
+}
+
+#[cfg(feature = "svg_write")]
+#[test]
+fn test_render_svg_default_unchanged() {
+    use svg::node::element::Rectangle;
+
+    let matrix = BitMatrix::parse_strings("X  \n X \n", "X", " ").expect("parse");
+    let expected = svg::Document::new()
+        .set("viewBox", (0, 0, 3, 2))
+        .add(
+            Rectangle::new()
+                .set("x", 0)
+                .set("y", 0)
+                .set("width", 1)
+                .set("height", 1),
+        )
+        .add(
+            Rectangle::new()
+                .set("x", 1)
+                .set("y", 1)
+                .set("width", 1)
+                .set("height", 1),
+        );
+    let svg: svg::Document = (&matrix).into();
+    assert_eq!(expected.to_string(), svg.to_string());
+    assert_eq!(
+        expected.to_string(),
+        matrix
+            .render_svg(&RenderConfig::default())
+            .expect("render")
+            .to_string()
+    );
+}
@@ -20,6 +20,8 @@
 
 use std::{any::Any, rc::Rc};
 
+use crate::qrcode::decoder::QRCodeDecodedSegment;
+
 /**
  * <p>Encapsulates the result of decoding a matrix of bits. This typically
  * applies to 2D barcode formats. For now it contains the raw bytes obtained,
@@ -41,6 +43,14 @@ pub struct DecoderRXingResult {
     symbologyModifier: u32,
     contentType: String,
     isMirrored: bool,
+    shiftJisBytes: Vec<Vec<u8>>,
+    dataCodewords: Vec<u32>,
+    ambiguousCodewords: Vec<(u32, Vec<u32>)>,
+    unplacedCodewords: Vec<u32>,
+    qrDecodedSegments: Vec<QRCodeDecodedSegment>,
+    timingPatternMismatches: Option<u32>,
+    unusedErrorCorrection: Option<f32>,
+    fixedPatternDamage: Option<f32>,
 }
 
 impl DecoderRXingResult {
@@ -131,6 +141,14 @@ impl DecoderRXingResult {
             symbologyModifier,
             contentType,
             isMirrored,
+            shiftJisBytes: Vec::new(),
+            dataCodewords: Vec::new(),
+            ambiguousCodewords: Vec::new(),
+            unplacedCodewords: Vec::new(),
+            qrDecodedSegments: Vec::new(),
+            timingPatternMismatches: None,
+            unusedErrorCorrection: None,
+            fixedPatternDamage: None,
         }
     }
 
@@ -242,4 +260,99 @@ impl DecoderRXingResult {
     pub fn setIsMirrored(&mut self, is_mirrored: bool) {
         self.isMirrored = is_mirrored
     }
+
+    /**
+     * @return raw bytes of each segment decoded as Shift_JIS, if they were requested
+     */
+    pub fn getShiftJisBytes(&self) -> &Vec<Vec<u8>> {
+        &self.shiftJisBytes
+    }
+
+    pub fn setShiftJisBytes(&mut self, shift_jis_bytes: Vec<Vec<u8>>) {
+        self.shiftJisBytes = shift_jis_bytes
+    }
+
+    /**
+     * @return the error corrected data codewords the result was decoded from, if the format
+     * records them. See {@link RXingResultMetadataType#DATA_CODEWORDS} for their order.
+     */
+    pub fn getDataCodewords(&self) -> &Vec<u32> {
+        &self.dataCodewords
+    }
+
+    pub fn setDataCodewords(&mut self, data_codewords: Vec<u32>) {
+        self.dataCodewords = data_codewords
+    }
+
+    /**
+     * @return the codeword indexes read with several equally likely values, and those values. See
+     * {@link RXingResultMetadataType#PDF417_AMBIGUOUS_CODEWORDS}.
+     */
+    pub fn getAmbiguousCodewords(&self) -> &Vec<(u32, Vec<u32>)> {
+        &self.ambiguousCodewords
+    }
+
+    pub fn setAmbiguousCodewords(&mut self, ambiguous_codewords: Vec<(u32, Vec<u32>)>) {
+        self.ambiguousCodewords = ambiguous_codewords
+    }
+
+    /**
+     * @return the values of codewords which were read but couldn't be placed in the symbol. See
+     * {@link RXingResultMetadataType#PDF417_UNPLACED_CODEWORDS}.
+     */
+    pub fn getUnplacedCodewords(&self) -> &Vec<u32> {
+        &self.unplacedCodewords
+    }
+
+    pub fn setUnplacedCodewords(&mut self, unplaced_codewords: Vec<u32>) {
+        self.unplacedCodewords = unplaced_codewords
+    }
+
+    /**
+     * @return the data segments of a QR Code, if they were requested. See
+     * {@link RXingResultMetadataType#QR_DECODED_SEGMENTS}.
+     */
+    pub fn getQRDecodedSegments(&self) -> &Vec<QRCodeDecodedSegment> {
+        &self.qrDecodedSegments
+    }
+
+    pub fn setQRDecodedSegments(&mut self, qr_decoded_segments: Vec<QRCodeDecodedSegment>) {
+        self.qrDecodedSegments = qr_decoded_segments
+    }
+
+    /**
+     * @return the number of timing pattern modules of a QR Code which were sampled with the wrong
+     * color. See {@link RXingResultMetadataType#QR_TIMING_PATTERN_MISMATCHES}.
+     */
+    pub fn getTimingPatternMismatches(&self) -> Option<u32> {
+        self.timingPatternMismatches
+    }
+
+    pub fn setTimingPatternMismatches(&mut self, timing_pattern_mismatches: Option<u32>) {
+        self.timingPatternMismatches = timing_pattern_mismatches
+    }
+
+    /**
+     * @return the fraction of the error correction capacity left over in the block which needed
+     * the most correction, if the decoder reports it
+     */
+    pub fn getUnusedErrorCorrection(&self) -> Option<f32> {
+        self.unusedErrorCorrection
+    }
+
+    pub fn setUnusedErrorCorrection(&mut self, unused_error_correction: Option<f32>) {
+        self.unusedErrorCorrection = unused_error_correction
+    }
+
+    /**
+     * @return the fraction of the fixed pattern modules which were sampled with the wrong color,
+     * if the decoder reports it
+     */
+    pub fn getFixedPatternDamage(&self) -> Option<f32> {
+        self.fixedPatternDamage
+    }
+
+    pub fn setFixedPatternDamage(&mut self, fixed_pattern_damage: Option<f32>) {
+        self.fixedPatternDamage = fixed_pattern_damage
+    }
 }
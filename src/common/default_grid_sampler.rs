@@ -116,3 +116,25 @@ impl GridSampler for DefaultGridSampler {
         Ok(bits)
     }
 }
+
+/**
+ * Samples an image for a rectangular matrix of bits with the {@link DefaultGridSampler}, as the
+ * QR Code and Data Matrix detectors do. This is meant for custom 2D formats: once their symbol
+ * is located, the transform maps module coordinates, where (0.5, 0.5) is the center of the top
+ * left module, to image coordinates.
+ *
+ * @param image image to sample
+ * @param dimension_x width of {@link BitMatrix} to sample from image
+ * @param dimension_y height of {@link BitMatrix} to sample from image
+ * @param transform mapping from module space to image space
+ * @return {@link BitMatrix} representing a grid of points sampled from the image
+ * @throws NotFoundException if the transform samples outside the image boundaries
+ */
+pub fn sample_grid(
+    image: &BitMatrix,
+    dimension_x: u32,
+    dimension_y: u32,
+    transform: &PerspectiveTransform,
+) -> Result<BitMatrix, Exceptions> {
+    DefaultGridSampler.sample_grid(image, dimension_x, dimension_y, transform)
+}
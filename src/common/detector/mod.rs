@@ -5,3 +5,6 @@ pub use monochrome_rectangle_detector::*;
 
 mod white_rectangle_detector;
 pub use white_rectangle_detector::*;
+
+#[cfg(test)]
+mod monochrome_rectangle_detector_test_case;
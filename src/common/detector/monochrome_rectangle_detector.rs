@@ -27,15 +27,36 @@ use crate::{common::BitMatrix, Exceptions, RXingResultPoint, ResultPoint};
  * @author Sean Owen
  * @deprecated without replacement since 3.3.0
  */
-const MAX_MODULES: i32 = 32;
+pub const MAX_MODULES: i32 = 32;
 #[deprecated]
 pub struct MonochromeRectangleDetector<'a> {
     image: &'a BitMatrix,
+    maxModules: i32,
+    maxWhiteRun: Option<i32>,
 }
 
 impl<'a> MonochromeRectangleDetector<'_> {
     pub fn new(image: &'a BitMatrix) -> MonochromeRectangleDetector<'a> {
-        MonochromeRectangleDetector { image }
+        Self::with_params(image, MAX_MODULES, None)
+    }
+
+    /**
+     * @param image image to search
+     * @param maxModules the most modules the barcode is expected to have along one side; this
+     *  determines how finely the image is stepped through while looking for corners
+     * @param maxWhiteRun largest run of white pixels that can still be considered part of the
+     *  barcode region. If not given, it is derived from the image dimensions.
+     */
+    pub fn with_params(
+        image: &'a BitMatrix,
+        maxModules: i32,
+        maxWhiteRun: Option<i32>,
+    ) -> MonochromeRectangleDetector<'a> {
+        MonochromeRectangleDetector {
+            image,
+            maxModules: maxModules.max(1),
+            maxWhiteRun,
+        }
     }
 
     /**
@@ -53,8 +74,9 @@ impl<'a> MonochromeRectangleDetector<'_> {
         let width = self.image.getWidth() as i32;
         let halfHeight = height / 2;
         let halfWidth = width / 2;
-        let deltaY = 1.max(height / (MAX_MODULES * 8));
-        let deltaX = 1.max(width / (MAX_MODULES * 8));
+        let deltaY = 1.max(height / (self.maxModules * 8));
+        let deltaX = 1.max(width / (self.maxModules * 8));
+        let maxWhiteRun = |derived: i32| self.maxWhiteRun.unwrap_or(derived);
 
         let mut top = 0;
         let mut bottom = height;
@@ -69,7 +91,7 @@ impl<'a> MonochromeRectangleDetector<'_> {
             -deltaY,
             top,
             bottom,
-            halfWidth / 2,
+            maxWhiteRun(halfWidth / 2),
         )?;
         top = (pointA.getY() - 1f32) as i32;
         let pointB = self.findCornerFromCenter(
@@ -81,7 +103,7 @@ impl<'a> MonochromeRectangleDetector<'_> {
             0,
             top,
             bottom,
-            halfHeight / 2,
+            maxWhiteRun(halfHeight / 2),
         )?;
         left = (pointB.getX() - 1f32) as i32;
         let pointC = self.findCornerFromCenter(
@@ -93,7 +115,7 @@ impl<'a> MonochromeRectangleDetector<'_> {
             0,
             top,
             bottom,
-            halfHeight / 2,
+            maxWhiteRun(halfHeight / 2),
         )?;
         right = (pointC.getX() + 1f32) as i32;
         let pointD = self.findCornerFromCenter(
@@ -105,7 +127,7 @@ impl<'a> MonochromeRectangleDetector<'_> {
             deltaY,
             top,
             bottom,
-            halfWidth / 2,
+            maxWhiteRun(halfWidth / 2),
         )?;
         bottom = (pointD.getY() + 1f32) as i32;
 
@@ -119,7 +141,7 @@ impl<'a> MonochromeRectangleDetector<'_> {
             -deltaY,
             top,
             bottom,
-            halfWidth / 4,
+            maxWhiteRun(halfWidth / 4),
         )?;
 
         Ok([pointA, pointB, pointC, pointD])
@@ -168,39 +190,38 @@ impl<'a> MonochromeRectangleDetector<'_> {
                 self.blackWhiteRange(x, maxWhiteRun, top, bottom, false)
             };
             if range.is_none() {
-                if let Some(lastRange) = lastRange_z {
-                    // lastRange was found
-                    if deltaX == 0 {
-                        let lastY = y - deltaY;
-                        if lastRange[0] < centerX {
-                            if lastRange[1] > centerX {
-                                // straddle, choose one or the other based on direction
-                                return Ok(RXingResultPoint::new(
-                                    lastRange[usize::from(deltaY <= 0)] as f32,
-                                    lastY as f32,
-                                ));
-                            }
-                            return Ok(RXingResultPoint::new(lastRange[0] as f32, lastY as f32));
-                        } else {
-                            return Ok(RXingResultPoint::new(lastRange[1] as f32, lastY as f32));
+                let Some(lastRange) = lastRange_z else {
+                    return Err(Exceptions::NotFoundException(None));
+                };
+                // lastRange was found
+                if deltaX == 0 {
+                    let lastY = y - deltaY;
+                    if lastRange[0] < centerX {
+                        if lastRange[1] > centerX {
+                            // straddle, choose one or the other based on direction
+                            return Ok(RXingResultPoint::new(
+                                lastRange[usize::from(deltaY <= 0)] as f32,
+                                lastY as f32,
+                            ));
                         }
+                        return Ok(RXingResultPoint::new(lastRange[0] as f32, lastY as f32));
                     } else {
-                        let lastX = x - deltaX;
-                        if lastRange[0] < centerY {
-                            if lastRange[1] > centerY {
-                                return Ok(RXingResultPoint::new(
-                                    lastX as f32,
-                                    lastRange[usize::from(deltaX >= 0)] as f32,
-                                ));
-                            }
-                            return Ok(RXingResultPoint::new(lastX as f32, lastRange[0] as f32));
-                        } else {
-                            return Ok(RXingResultPoint::new(lastX as f32, lastRange[1] as f32));
+                        return Ok(RXingResultPoint::new(lastRange[1] as f32, lastY as f32));
+                    }
+                } else {
+                    let lastX = x - deltaX;
+                    if lastRange[0] < centerY {
+                        if lastRange[1] > centerY {
+                            return Ok(RXingResultPoint::new(
+                                lastX as f32,
+                                lastRange[usize::from(deltaX >= 0)] as f32,
+                            ));
                         }
+                        return Ok(RXingResultPoint::new(lastX as f32, lastRange[0] as f32));
+                    } else {
+                        return Ok(RXingResultPoint::new(lastX as f32, lastRange[1] as f32));
                     }
                 }
-            } else {
-                return Err(Exceptions::NotFoundException(None));
             }
             lastRange_z = range;
             y += deltaY;
@@ -0,0 +1,78 @@
+#![allow(deprecated)]
+
+use crate::{common::BitMatrix, ResultPoint};
+
+use super::{MonochromeRectangleDetector, MAX_MODULES};
+
+/**
+ * A square outline 10 pixels thick, from (20, 20) to (179, 179). The inside is white, so every
+ * scan line through the middle contains a white run of 140 pixels.
+ */
+fn frame() -> BitMatrix {
+    let mut matrix = BitMatrix::with_single_dimension(200);
+    matrix.setRegion(20, 20, 160, 10).unwrap();
+    matrix.setRegion(20, 170, 160, 10).unwrap();
+    matrix.setRegion(20, 20, 10, 160).unwrap();
+    matrix.setRegion(170, 20, 10, 160).unwrap();
+    matrix
+}
+
+#[test]
+fn test_default_white_run_rejects_sparse_region() {
+    let matrix = frame();
+    assert!(MonochromeRectangleDetector::new(&matrix).detect().is_err());
+}
+
+#[test]
+fn test_wide_white_run_detects_sparse_region() {
+    let matrix = frame();
+    let points = MonochromeRectangleDetector::with_params(&matrix, MAX_MODULES, Some(160))
+        .detect()
+        .expect("detect");
+    let corners: Vec<(f32, f32)> = points.iter().map(|p| (p.getX(), p.getY())).collect();
+    assert_eq!(
+        vec![(179.0, 20.0), (20.0, 20.0), (179.0, 179.0), (20.0, 179.0)],
+        corners
+    );
+}
+
+#[test]
+fn test_detects_region_found_at_center() {
+    // Scanning starts with a range found at the center, and must carry on out to the edges of
+    // the region rather than give up there
+    let mut matrix = BitMatrix::with_single_dimension(200);
+    matrix.setRegion(60, 60, 80, 80).unwrap();
+    let points = MonochromeRectangleDetector::new(&matrix)
+        .detect()
+        .expect("detect");
+    for point in points {
+        assert!((59.0..=140.0).contains(&point.getX()));
+        assert!((59.0..=140.0).contains(&point.getY()));
+    }
+    assert!(points[0].getY() < 62.0);
+    assert!(points[1].getX() < 62.0);
+    assert!(points[2].getX() > 137.0);
+    assert!(points[3].getY() > 137.0);
+}
+
+#[test]
+fn test_detect_returns_corners_in_order() {
+    // A solid diamond centred on (100, 100), so each extreme is a distinct point
+    let mut matrix = BitMatrix::with_single_dimension(200);
+    for y in 40..=160u32 {
+        let halfWidth = 60 - y.abs_diff(100);
+        matrix
+            .setRegion(100 - halfWidth, y, 2 * halfWidth + 1, 1)
+            .unwrap();
+    }
+    let points = MonochromeRectangleDetector::new(&matrix)
+        .detect()
+        .expect("detect");
+    assert_eq!(4, points.len());
+    let [top, left, right, bottom] = points;
+    // The corners are only found to within a few pixels, but must come in this order
+    assert!(top.getY() < 45.0 && (top.getX() - 100.0).abs() < 5.0);
+    assert!(left.getX() < 45.0 && left.getY() > top.getY() && left.getY() < bottom.getY());
+    assert!(right.getX() > 155.0 && right.getY() > top.getY() && right.getY() < bottom.getY());
+    assert!(bottom.getY() > 155.0 && (bottom.getX() - 100.0).abs() < 5.0);
+}
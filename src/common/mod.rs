@@ -18,6 +18,9 @@ mod BitSourceTestCase;
 #[cfg(test)]
 mod PerspectiveTransformTestCase;
 
+#[cfg(test)]
+mod GridSamplerTestCase;
+
 mod string_utils;
 pub use string_utils::*;
 
@@ -65,6 +68,9 @@ pub trait DetectorRXingResult {
 mod bit_matrix;
 pub use bit_matrix::*;
 
+mod render_config;
+pub use render_config::*;
+
 mod eci_input;
 pub use eci_input::*;
 
@@ -104,6 +110,9 @@ pub use global_histogram_binarizer::*;
 mod hybrid_binarizer;
 pub use hybrid_binarizer::*;
 
+mod print_quality;
+pub use print_quality::*;
+
 #[cfg(feature = "otsu_level")]
 mod otsu_level_binarizer;
 #[cfg(feature = "otsu_level")]
@@ -0,0 +1,117 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::LuminanceSource;
+
+/**
+ * Grades the symbol contrast, the spread between the darkest and lightest luminance in the symbol
+ * as a fraction of the full range.
+ */
+pub fn gradeSymbolContrast(symbolContrast: f32) -> char {
+    gradeAtLeast(symbolContrast, [0.70, 0.55, 0.40, 0.20])
+}
+
+/**
+ * Grades the damage to the fixed patterns, as the fraction of timing pattern modules which were
+ * sampled with the wrong color.
+ */
+pub fn gradeFixedPatternDamage(damagedFraction: f32) -> char {
+    if damagedFraction <= 0.02 {
+        'A'
+    } else if damagedFraction <= 0.05 {
+        'B'
+    } else if damagedFraction <= 0.10 {
+        'C'
+    } else if damagedFraction <= 0.15 {
+        'D'
+    } else {
+        'F'
+    }
+}
+
+/**
+ * Grades the unused error correction, the fraction of the error correction capacity of the worst
+ * block which was left over after correcting it.
+ */
+pub fn gradeUnusedErrorCorrection(unusedErrorCorrection: f32) -> char {
+    gradeAtLeast(unusedErrorCorrection, [0.62, 0.50, 0.37, 0.25])
+}
+
+/**
+ * Computes a simplified print quality grade of a 2D symbol, loosely following ISO/IEC 15415. Each
+ * parameter is graded from A (best) to F (fail) and the overall grade is the worst of them. Unlike
+ * the standard, the parameters are measured on the captured image rather than with a calibrated
+ * reflectance scan, so the grade is only an indication of how well a symbol was printed.
+ *
+ * @return the overall grade, 'A' to 'D' or 'F'
+ */
+pub fn gradePrintQuality(
+    symbolContrast: f32,
+    fixedPatternDamage: f32,
+    unusedErrorCorrection: f32,
+) -> char {
+    // 'A' sorts before 'F', so the worst grade is the largest
+    gradeSymbolContrast(symbolContrast)
+        .max(gradeFixedPatternDamage(fixedPatternDamage))
+        .max(gradeUnusedErrorCorrection(unusedErrorCorrection))
+}
+
+/**
+ * Measures the symbol contrast of a region of an image. The 5th and 95th percentile luminances
+ * stand in for the darkest and lightest values, so that a few specks don't decide the result.
+ *
+ * @return the contrast, between 0 and 1; 0 for an empty region
+ */
+pub fn measureSymbolContrast(
+    source: &dyn LuminanceSource,
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+) -> f32 {
+    let right = right.min(source.getWidth());
+    let bottom = bottom.min(source.getHeight());
+    if left >= right || top >= bottom {
+        return 0.0;
+    }
+    let mut histogram = [0usize; 256];
+    for y in top..bottom {
+        for luminance in &source.getRow(y)[left..right] {
+            histogram[*luminance as usize] += 1;
+        }
+    }
+    let total = (right - left) * (bottom - top);
+    let percentile = |fraction: f32| {
+        let target = (total as f32 * fraction) as usize;
+        let mut seen = 0;
+        for (luminance, count) in histogram.iter().enumerate() {
+            seen += count;
+            if seen > target {
+                return luminance;
+            }
+        }
+        255
+    };
+    (percentile(0.95) - percentile(0.05)) as f32 / 255.0
+}
+
+fn gradeAtLeast(value: f32, thresholds: [f32; 4]) -> char {
+    thresholds
+        .iter()
+        .zip(['A', 'B', 'C', 'D'])
+        .find(|(threshold, _)| value >= **threshold)
+        .map_or('F', |(_, grade)| grade)
+}
@@ -0,0 +1,56 @@
+use crate::Exceptions;
+
+/**
+ * Colors and scaling for rendering a {@link BitMatrix} as an image, as SVG or as a larger
+ * matrix, shared by all of the renderers. Colors are ARGB values, 0xAARRGGBB, as in
+ * {@code MatrixToImageConfig}. The renderers check that the scale is at least 1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderConfig {
+    /// color of set modules
+    pub fg: u32,
+    /// color of unset modules and of the margin
+    pub bg: u32,
+    /// width and height in pixels of each module
+    pub scale: u32,
+    /// blank modules to add on every side
+    pub margin: u32,
+}
+
+impl RenderConfig {
+    pub const BLACK: u32 = 0xFF000000;
+    pub const WHITE: u32 = 0xFFFFFFFF;
+
+    /**
+     * @throws IllegalArgumentException if scale is 0
+     */
+    pub fn validate(&self) -> Result<(), Exceptions> {
+        if self.scale < 1 {
+            return Err(Exceptions::IllegalArgumentException(Some(
+                "scale must be at least 1".to_owned(),
+            )));
+        }
+        Ok(())
+    }
+
+    /**
+     * @return whether neither color is transparent at all
+     */
+    pub fn isOpaque(&self) -> bool {
+        self.fg >> 24 == 0xFF && self.bg >> 24 == 0xFF
+    }
+}
+
+impl Default for RenderConfig {
+    /**
+     * Black on white, one pixel per module and no margin.
+     */
+    fn default() -> Self {
+        Self {
+            fg: Self::BLACK,
+            bg: Self::WHITE,
+            scale: 1,
+            margin: 0,
+        }
+    }
+}
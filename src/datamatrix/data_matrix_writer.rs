@@ -20,16 +20,10 @@ use std::collections::HashMap;
 use encoding::EncodingRef;
 
 use crate::{
-    common::BitMatrix, qrcode::encoder::ByteMatrix, BarcodeFormat, EncodeHintType, EncodeHintValue,
-    Exceptions, Writer,
+    common::BitMatrix, BarcodeFormat, EncodeHintType, EncodeHintValue, Exceptions, Writer,
 };
 
-use super::encoder::{
-    high_level_encoder, minimal_encoder, DefaultPlacement, SymbolInfo, SymbolInfoLookup,
-    SymbolShapeHint,
-};
-
-use super::encoder::error_correction;
+use super::encoder::{encodeCodewords, high_level_encoder, minimal_encoder, SymbolShapeHint};
 
 /**
  * This object renders a Data Matrix code as a BitMatrix 2D array of greyscale values.
@@ -94,7 +88,7 @@ impl Writer for DataMatrixWriter {
             }
             // @SuppressWarnings("deprecation")
             let requestedMaxSize = hints.get(&EncodeHintType::MAX_SIZE);
-            if let Some(EncodeHintValue::MinSize(rq)) = requestedMaxSize {
+            if let Some(EncodeHintValue::MaxSize(rq)) = requestedMaxSize {
                 maxSize = Some(*rq);
             }
         }
@@ -155,26 +149,12 @@ impl Writer for DataMatrixWriter {
             )?;
         }
 
-        let symbol_lookup = SymbolInfoLookup::new();
-        let Some(symbolInfo) = symbol_lookup.lookup_with_codewords_shape_size_fail(encoded.chars().count() as u32, *shape, &minSize, &maxSize, true)? else {
-      return Err(Exceptions::NotFoundException(Some("symbol info is bad".to_owned())))
-    };
+        //2. step: ECC generation and module placement
+        let symbol = encodeCodewords(&encoded, *shape, minSize, maxSize)?;
 
-        //2. step: ECC generation
-        let codewords = error_correction::encodeECC200(&encoded, symbolInfo)?;
-
-        //3. step: Module placement in Matrix
-        let mut placement = DefaultPlacement::new(
-            codewords,
-            symbolInfo.getSymbolDataWidth()? as usize,
-            symbolInfo.getSymbolDataHeight()? as usize,
-        );
-        placement.place();
-
-        //4. step: low-level encoding
-        Self::encodeLowLevel(
-            &placement,
-            symbolInfo,
+        //3. step: scaling to the requested size
+        Self::convertSymbolToBitMatrix(
+            &symbol,
             width.try_into().unwrap(),
             height.try_into().unwrap(),
         )
@@ -183,81 +163,15 @@ impl Writer for DataMatrixWriter {
 
 impl DataMatrixWriter {
     /**
-     * Encode the given symbol info to a bit matrix.
-     *
-     * @param placement  The DataMatrix placement.
-     * @param symbolInfo The symbol info to encode.
-     * @return The bit matrix generated.
-     */
-    fn encodeLowLevel(
-        placement: &DefaultPlacement,
-        symbolInfo: &SymbolInfo,
-        width: u32,
-        height: u32,
-    ) -> Result<BitMatrix, Exceptions> {
-        let symbolWidth = symbolInfo.getSymbolDataWidth()?;
-        let symbolHeight = symbolInfo.getSymbolDataHeight()?;
-
-        let mut matrix =
-            ByteMatrix::new(symbolInfo.getSymbolWidth()?, symbolInfo.getSymbolHeight()?);
-
-        let mut matrixY = 0;
-
-        for y in 0..symbolHeight {
-            // for (int y = 0; y < symbolHeight; y++) {
-            // Fill the top edge with alternate 0 / 1
-            let mut matrixX;
-            if (y % symbolInfo.matrixHeight) == 0 {
-                matrixX = 0;
-                for x in 0..symbolInfo.getSymbolWidth()? {
-                    // for (int x = 0; x < symbolInfo.getSymbolWidth(); x++) {
-                    matrix.set_bool(matrixX, matrixY, (x % 2) == 0);
-                    matrixX += 1;
-                }
-                matrixY += 1;
-            }
-            matrixX = 0;
-            for x in 0..symbolWidth {
-                // for (int x = 0; x < symbolWidth; x++) {
-                // Fill the right edge with full 1
-                if (x % symbolInfo.matrixWidth) == 0 {
-                    matrix.set_bool(matrixX, matrixY, true);
-                    matrixX += 1;
-                }
-                matrix.set_bool(matrixX, matrixY, placement.getBit(x as usize, y as usize));
-                matrixX += 1;
-                // Fill the right edge with alternate 0 / 1
-                if (x % symbolInfo.matrixWidth) == symbolInfo.matrixWidth - 1 {
-                    matrix.set_bool(matrixX, matrixY, (y % 2) == 0);
-                    matrixX += 1;
-                }
-            }
-            matrixY += 1;
-            // Fill the bottom edge with full 1
-            if (y % symbolInfo.matrixHeight) == symbolInfo.matrixHeight - 1 {
-                matrixX = 0;
-                for _x in 0..symbolInfo.getSymbolWidth()? {
-                    // for (int x = 0; x < symbolInfo.getSymbolWidth(); x++) {
-                    matrix.set_bool(matrixX, matrixY, true);
-                    matrixX += 1;
-                }
-                matrixY += 1;
-            }
-        }
-
-        Self::convertByteMatrixToBitMatrix(&matrix, width, height)
-    }
-
-    /**
-     * Convert the ByteMatrix to BitMatrix.
+     * Scale the symbol up to the requested size, centering it.
      *
      * @param reqHeight The requested height of the image (in pixels) with the Datamatrix code
      * @param reqWidth The requested width of the image (in pixels) with the Datamatrix code
      * @param matrix The input matrix.
      * @return The output matrix.
      */
-    fn convertByteMatrixToBitMatrix(
-        matrix: &ByteMatrix,
+    fn convertSymbolToBitMatrix(
+        matrix: &BitMatrix,
         reqWidth: u32,
         reqHeight: u32,
     ) -> Result<BitMatrix, Exceptions> {
@@ -292,7 +206,7 @@ impl DataMatrixWriter {
             let mut outputX = leftPadding;
             while inputX < matrixWidth {
                 // for (int inputX = 0, outputX = leftPadding; inputX < matrixWidth; inputX++, outputX += multiple) {
-                if matrix.get(inputX, inputY) == 1 {
+                if matrix.get(inputX, inputY) {
                     output.setRegion(outputX, outputY, multiple, multiple)?;
                 }
 
@@ -313,7 +227,7 @@ mod tests {
 
     use crate::{
         datamatrix::{encoder::SymbolShapeHint, DataMatrixWriter},
-        BarcodeFormat, EncodeHintType, EncodeHintValue, Writer,
+        BarcodeFormat, Dimension, EncodeHintType, EncodeHintValue, Writer,
     };
 
     #[test]
@@ -380,4 +294,23 @@ mod tests {
         assert!(tooSmall < matrix.getWidth() as i32);
         assert!(tooSmall < matrix.getHeight() as i32);
     }
+
+    #[test]
+    fn testDataMatrixMaxSize() {
+        let mut hints = HashMap::new();
+        hints.insert(
+            EncodeHintType::MAX_SIZE,
+            EncodeHintValue::MaxSize(Dimension::new(10, 10).expect("dimension")),
+        );
+        let writer = DataMatrixWriter {};
+        assert!(writer
+            .encode_with_hints(
+                "http://www.google.com/",
+                &BarcodeFormat::DATA_MATRIX,
+                0,
+                0,
+                &hints,
+            )
+            .is_err());
+    }
 }
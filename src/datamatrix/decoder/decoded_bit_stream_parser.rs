@@ -14,8 +14,6 @@
  * limitations under the License.
  */
 
-use encoding::Encoding;
-
 use crate::{
     common::{BitSource, DecoderRXingResult, ECIStringBuilder},
     Exceptions,
@@ -734,13 +732,11 @@ fn decodeBase256Segment(
         *byte = unrandomize255State(bits.readBits(8)?, codewordPosition) as u8;
         codewordPosition += 1;
     }
-    result.append_string(
-        &encoding::all::ISO_8859_1
-            .decode(&bytes, encoding::DecoderTrap::Strict)
-            .expect("decode"),
-    );
+    // The bytes are kept as they are, to be decoded with the current ECI, ISO-8859-1 by default
+    for byte in &bytes {
+        result.append_byte(*byte);
+    }
     byteSegments.push(bytes);
-    // result.append_string(&encoding::all::ISO_8859_1.decode(&bytes, encoding::DecoderTrap::Strict).expect("decode"));
 
     Ok(())
 }
@@ -852,7 +848,9 @@ fn unrandomize255State(randomizedBase256Codeword: u32, base256CodewordPosition:
 
 #[cfg(test)]
 mod tests {
-    use crate::datamatrix::decoder::decoded_bit_stream_parser;
+    use crate::datamatrix::{
+        decoder::decoded_bit_stream_parser, encoder::high_level_encoder::encodeHighLevel,
+    };
 
     #[test]
     fn testAsciiStandardDecode() {
@@ -885,6 +883,92 @@ mod tests {
         assert_eq!("00019899", decodedString);
     }
 
+    /**
+     * Builds a Base 256 segment starting at the given 1-indexed codeword position: the latch,
+     * followed by the length field and the bytes, all randomized by their position.
+     */
+    fn base256Segment(position: usize, length: &[u32], data: &[u8]) -> Vec<u8> {
+        let mut codewords = vec![231];
+        for (i, value) in length
+            .iter()
+            .copied()
+            .chain(data.iter().map(|b| *b as u32))
+            .enumerate()
+        {
+            let pseudoRandom = ((149 * (position + 1 + i) as u32) % 255) + 1;
+            codewords.push(((value + pseudoRandom) % 256) as u8);
+        }
+        codewords
+    }
+
+    fn binaryData(length: usize) -> Vec<u8> {
+        (0..length).map(|i| (i * 97 + 13) as u8).collect()
+    }
+
+    #[test]
+    fn testBase256SingleByteLength() {
+        let data = binaryData(249);
+        let bytes = base256Segment(1, &[249], &data);
+        let result = decoded_bit_stream_parser::decode(&bytes, false).expect("decode");
+        assert_eq!(&vec![data], result.getByteSegments());
+    }
+
+    #[test]
+    fn testBase256TwoByteLength() {
+        for length in [250, 256, 1555] {
+            let data = binaryData(length);
+            let header = [(length / 250 + 249) as u32, (length % 250) as u32];
+            let bytes = base256Segment(1, &header, &data);
+            let result = decoded_bit_stream_parser::decode(&bytes, false).expect("decode");
+            assert_eq!(&vec![data], result.getByteSegments(), "length {length}");
+        }
+    }
+
+    #[test]
+    fn testBase256RemainderOfSymbol() {
+        // A length of 0 means the segment runs to the end of the symbol
+        let data: Vec<u8> = (0..=255).collect();
+        let mut bytes = vec![b'A' + 1];
+        bytes.extend(base256Segment(2, &[0], &data));
+        let result = decoded_bit_stream_parser::decode(&bytes, false).expect("decode");
+        assert_eq!(&vec![data], result.getByteSegments());
+        assert!(result.getText().starts_with('A'));
+    }
+
+    #[test]
+    fn testBase256FollowedByAscii() {
+        let data = [0, 255, 0x80, 231, 129];
+        let mut bytes = base256Segment(1, &[data.len() as u32], &data);
+        bytes.extend([b'O' + 1, b'K' + 1]);
+        let result = decoded_bit_stream_parser::decode(&bytes, false).expect("decode");
+        assert_eq!(&vec![data.to_vec()], result.getByteSegments());
+        assert!(result.getText().ends_with("OK"));
+    }
+
+    #[test]
+    fn testBase256Truncated() {
+        let bytes = base256Segment(1, &[10], &binaryData(9));
+        assert!(decoded_bit_stream_parser::decode(&bytes, false).is_err());
+    }
+
+    #[test]
+    fn testBase256EncoderRoundTrip() {
+        for length in [20, 249, 250, 300] {
+            let message: String = binaryData(length)
+                .into_iter()
+                .map(|b| char::from(b | 0x80))
+                .collect();
+            let codewords: Vec<u8> = encodeHighLevel(&message)
+                .expect("encode")
+                .chars()
+                .map(|c| c as u8)
+                .collect();
+            assert_eq!(231, codewords[0], "length {length}");
+            let result = decoded_bit_stream_parser::decode(&codewords, false).expect("decode");
+            assert_eq!(message, result.getText(), "length {length}");
+        }
+    }
+
     // TODO(bbrown): Add test cases for each encoding type
     // TODO(bbrown): Add test cases for switching encoding types
 }
@@ -50,7 +50,7 @@ impl Encoder for Base256Encoder {
         let dataCount = buffer.chars().count() - 1;
         let lengthFieldSize = 1;
         let currentSize = context.getCodewordCount() + dataCount + lengthFieldSize;
-        context.updateSymbolInfoWithLength(currentSize);
+        context.updateSymbolInfoWithLength(currentSize)?;
         let mustPad = (context.getSymbolInfo().unwrap().getDataCapacity() - currentSize as u32) > 0;
         if context.hasMoreCharacters() || mustPad {
             if dataCount <= 249 {
@@ -62,7 +62,7 @@ impl C40Encoder {
             let unwritten = (buffer.chars().count() / 3) * 2;
 
             let curCodewordCount = context.getCodewordCount() + unwritten;
-            context.updateSymbolInfoWithLength(curCodewordCount);
+            context.updateSymbolInfoWithLength(curCodewordCount)?;
             let available =
                 context.getSymbolInfo().unwrap().getDataCapacity() as usize - curCodewordCount;
 
@@ -107,7 +107,7 @@ impl C40Encoder {
         handleEOD(context, &mut buffer)
     }
 
-    pub fn encodeMaximalC40(&self, context: &mut EncoderContext) {
+    pub fn encodeMaximalC40(&self, context: &mut EncoderContext) -> Result<(), Exceptions> {
         self.encodeMaximal(context, &Self::encodeChar_c40, &Self::handleEOD_c40)
     }
 
@@ -116,7 +116,7 @@ impl C40Encoder {
         context: &mut EncoderContext,
         encodeChar: &dyn Fn(char, &mut String) -> u32,
         handleEOD: &dyn Fn(&mut EncoderContext, &mut String) -> Result<(), Exceptions>,
-    ) {
+    ) -> Result<(), Exceptions> {
         let mut buffer = String::new();
         let mut lastCharSize = 0;
         let mut backtrackStartPosition = context.pos;
@@ -134,7 +134,7 @@ impl C40Encoder {
             let unwritten = (buffer.chars().count() / 3) * 2;
 
             let curCodewordCount = context.getCodewordCount() + unwritten + 1; // +1 for the latch to C40
-            context.updateSymbolInfoWithLength(curCodewordCount);
+            context.updateSymbolInfoWithLength(curCodewordCount)?;
             let available =
                 context.getSymbolInfo().unwrap().getDataCapacity() as usize - curCodewordCount;
             let rest = buffer.chars().count() % 3;
@@ -149,7 +149,7 @@ impl C40Encoder {
             context.writeCodeword(LATCH_TO_C40);
         }
 
-        handleEOD(context, &mut buffer).expect("eod");
+        handleEOD(context, &mut buffer)
     }
 
     fn backtrackOneCharacter(
@@ -190,7 +190,7 @@ impl C40Encoder {
         let rest = buffer.chars().count() % 3;
 
         let curCodewordCount = context.getCodewordCount() + unwritten;
-        context.updateSymbolInfoWithLength(curCodewordCount);
+        context.updateSymbolInfoWithLength(curCodewordCount)?;
         let available =
             context.getSymbolInfo().unwrap().getDataCapacity() as usize - curCodewordCount;
 
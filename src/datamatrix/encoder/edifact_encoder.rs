@@ -73,13 +73,13 @@ impl EdifactEncoder {
             }
             if count == 1 {
                 //Only an unlatch at the end
-                context.updateSymbolInfo();
+                context.updateSymbolInfo()?;
                 let mut available = context.getSymbolInfo().unwrap().getDataCapacity()
                     - context.getCodewordCount() as u32;
                 let remaining = context.getRemainingCharacters();
                 // The following two lines are a hack inspired by the 'fix' from https://sourceforge.net/p/barcode4j/svn/221/
                 if remaining > available {
-                    context.updateSymbolInfoWithLength(context.getCodewordCount() + 1);
+                    context.updateSymbolInfoWithLength(context.getCodewordCount() + 1)?;
                     available = context.getSymbolInfo().unwrap().getDataCapacity()
                         - context.getCodewordCount() as u32;
                 }
@@ -99,14 +99,14 @@ impl EdifactEncoder {
             let mut restInAscii = endOfSymbolReached && restChars <= 2;
 
             if restChars <= 2 {
-                context.updateSymbolInfoWithLength(context.getCodewordCount() + restChars);
+                context.updateSymbolInfoWithLength(context.getCodewordCount() + restChars)?;
                 let available = context.getSymbolInfo().unwrap().getDataCapacity()
                     - context.getCodewordCount() as u32;
                 if available >= 3 {
                     restInAscii = false;
                     context.updateSymbolInfoWithLength(
                         context.getCodewordCount() + encoded.chars().count(),
-                    );
+                    )?;
                     //available = context.symbolInfo.dataCapacity - context.getCodewordCount();
                 }
             }
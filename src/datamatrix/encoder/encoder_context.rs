@@ -153,27 +153,27 @@ impl<'a> EncoderContext<'_> {
         &self.symbolInfo
     }
 
-    pub fn updateSymbolInfo(&mut self) {
-        self.updateSymbolInfoWithLength(self.getCodewordCount());
+    pub fn updateSymbolInfo(&mut self) -> Result<(), Exceptions> {
+        self.updateSymbolInfoWithLength(self.getCodewordCount())
     }
 
-    pub fn updateSymbolInfoWithLength(&mut self, len: usize) {
+    /**
+     * @throws IllegalArgumentException if no symbol within the shape and size constraints can
+     *  hold len data codewords
+     */
+    pub fn updateSymbolInfoWithLength(&mut self, len: usize) -> Result<(), Exceptions> {
         if self.symbolInfo.is_none()
             || len > self.symbolInfo.as_ref().unwrap().getDataCapacity() as usize
         {
-            self.symbolInfo = Some(
-                self.symbol_lookup
-                    .lookup_with_codewords_shape_size_fail(
-                        len as u32,
-                        self.shape,
-                        &self.minSize,
-                        &self.maxSize,
-                        true,
-                    )
-                    .unwrap()
-                    .unwrap(),
-            );
+            self.symbolInfo = self.symbol_lookup.lookup_with_codewords_shape_size_fail(
+                len as u32,
+                self.shape,
+                &self.minSize,
+                &self.maxSize,
+                true,
+            )?;
         }
+        Ok(())
     }
 
     pub fn resetSymbolInfo(&mut self) {
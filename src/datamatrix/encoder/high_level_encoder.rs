@@ -219,7 +219,7 @@ pub fn encodeHighLevelWithDimensionForceC40WithSymbolInfoLookup(
     let mut encodingMode = ASCII_ENCODATION; //Default mode
 
     if forceC40 {
-        c40Encoder.encodeMaximalC40(&mut context);
+        c40Encoder.encodeMaximalC40(&mut context)?;
         encodingMode = context.getNewEncoding().unwrap();
         context.resetEncoderSignal();
     }
@@ -232,7 +232,7 @@ pub fn encodeHighLevelWithDimensionForceC40WithSymbolInfoLookup(
         }
     }
     let len = context.getCodewordCount();
-    context.updateSymbolInfo();
+    context.updateSymbolInfo()?;
     let capacity = context.getSymbolInfo().unwrap().getDataCapacity();
     if len < capacity as usize
         && encodingMode != ASCII_ENCODATION
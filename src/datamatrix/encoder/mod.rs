@@ -31,5 +31,10 @@ pub use edifact_encoder::*;
 mod base256_encoder;
 pub use base256_encoder::*;
 
+mod symbol_encoder;
+pub use symbol_encoder::*;
+
 #[cfg(test)]
 mod high_level_encode_test_case;
+#[cfg(test)]
+mod symbol_encoder_test_case;
@@ -0,0 +1,131 @@
+use crate::{common::BitMatrix, Dimension, Exceptions};
+
+use super::{
+    error_correction, high_level_encoder, DefaultPlacement, SymbolInfo, SymbolInfoLookup,
+    SymbolShapeHint,
+};
+
+/**
+ * Encodes contents as a Data Matrix symbol: high-level encodation in ASCII, C40, Text, X12,
+ * EDIFACT and Base 256 modes, Reed-Solomon error correction and module placement. The smallest
+ * symbol the codewords fit in is chosen, within the given constraints.
+ *
+ * @param contents the contents to encode
+ * @param shape whether the symbol must be square or rectangular
+ * @param minSize the minimum symbol size, in modules, or None for no constraint
+ * @param maxSize the maximum symbol size, in modules, or None for no constraint
+ * @return the symbol, with one bit per module and no quiet zone
+ */
+pub fn encode(
+    contents: &str,
+    shape: SymbolShapeHint,
+    minSize: Option<Dimension>,
+    maxSize: Option<Dimension>,
+) -> Result<BitMatrix, Exceptions> {
+    let encoded = high_level_encoder::encodeHighLevelWithDimensionForceC40(
+        contents, shape, minSize, maxSize, false,
+    )?;
+    encodeCodewords(&encoded, shape, minSize, maxSize)
+}
+
+/**
+ * Lays out already high-level encoded data codewords in the smallest fitting symbol, adding its
+ * error correction codewords.
+ *
+ * @param encoded the data codewords, one per char
+ * @return the symbol, with one bit per module and no quiet zone
+ */
+pub fn encodeCodewords(
+    encoded: &str,
+    shape: SymbolShapeHint,
+    minSize: Option<Dimension>,
+    maxSize: Option<Dimension>,
+) -> Result<BitMatrix, Exceptions> {
+    let symbol_lookup = SymbolInfoLookup::new();
+    let Some(symbolInfo) = symbol_lookup.lookup_with_codewords_shape_size_fail(
+        encoded.chars().count() as u32,
+        shape,
+        &minSize,
+        &maxSize,
+        true,
+    )?
+    else {
+        return Err(Exceptions::NotFoundException(Some(
+            "symbol info is bad".to_owned(),
+        )));
+    };
+
+    let codewords = error_correction::encodeECC200(encoded, symbolInfo)?;
+
+    let mut placement = DefaultPlacement::new(
+        codewords,
+        symbolInfo.getSymbolDataWidth()? as usize,
+        symbolInfo.getSymbolDataHeight()? as usize,
+    );
+    placement.place();
+
+    placeModules(&placement, symbolInfo)
+}
+
+/**
+ * Adds the finder and timing patterns around each data region of the placement.
+ *
+ * @param placement  The DataMatrix placement.
+ * @param symbolInfo The symbol info to encode.
+ * @return The bit matrix generated.
+ */
+fn placeModules(
+    placement: &DefaultPlacement,
+    symbolInfo: &SymbolInfo,
+) -> Result<BitMatrix, Exceptions> {
+    let symbolWidth = symbolInfo.getSymbolDataWidth()?;
+    let symbolHeight = symbolInfo.getSymbolDataHeight()?;
+
+    let mut matrix = BitMatrix::new(symbolInfo.getSymbolWidth()?, symbolInfo.getSymbolHeight()?)?;
+
+    let mut matrixY = 0;
+
+    for y in 0..symbolHeight {
+        // Fill the top edge with alternate 0 / 1
+        let mut matrixX;
+        if (y % symbolInfo.matrixHeight) == 0 {
+            matrixX = 0;
+            for x in 0..symbolInfo.getSymbolWidth()? {
+                if x % 2 == 0 {
+                    matrix.set(matrixX, matrixY);
+                }
+                matrixX += 1;
+            }
+            matrixY += 1;
+        }
+        matrixX = 0;
+        for x in 0..symbolWidth {
+            // Fill the right edge with full 1
+            if (x % symbolInfo.matrixWidth) == 0 {
+                matrix.set(matrixX, matrixY);
+                matrixX += 1;
+            }
+            if placement.getBit(x as usize, y as usize) {
+                matrix.set(matrixX, matrixY);
+            }
+            matrixX += 1;
+            // Fill the right edge with alternate 0 / 1
+            if (x % symbolInfo.matrixWidth) == symbolInfo.matrixWidth - 1 {
+                if y % 2 == 0 {
+                    matrix.set(matrixX, matrixY);
+                }
+                matrixX += 1;
+            }
+        }
+        matrixY += 1;
+        // Fill the bottom edge with full 1
+        if (y % symbolInfo.matrixHeight) == symbolInfo.matrixHeight - 1 {
+            for x in 0..symbolInfo.getSymbolWidth()? {
+                matrix.set(x, matrixY);
+            }
+            matrixY += 1;
+        }
+    }
+
+    Ok(matrix)
+}
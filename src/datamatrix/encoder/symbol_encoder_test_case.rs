@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitMatrix, datamatrix::DataMatrixReader, shared_test_methods::render_bitmap,
+    DecodeHintType, DecodeHintValue, Dimension, Reader,
+};
+
+use super::{encode, SymbolShapeHint};
+
+const MODULE_SIZE: u32 = 4;
+const QUIET_ZONE: u32 = 2;
+
+fn read(symbol: &BitMatrix) -> String {
+    let width = (symbol.getWidth() + 2 * QUIET_ZONE) * MODULE_SIZE;
+    let height = (symbol.getHeight() + 2 * QUIET_ZONE) * MODULE_SIZE;
+    let mut image = render_bitmap(width, height, |x, y| {
+        let (mx, my) = (x / MODULE_SIZE, y / MODULE_SIZE);
+        mx >= QUIET_ZONE
+            && my >= QUIET_ZONE
+            && symbol.try_get(mx - QUIET_ZONE, my - QUIET_ZONE) == Some(true)
+    });
+    let hints = HashMap::from([(
+        DecodeHintType::PURE_BARCODE,
+        DecodeHintValue::PureBarcode(true),
+    )]);
+    DataMatrixReader
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode")
+        .getText()
+        .to_owned()
+}
+
+fn roundTrip(contents: &str, shape: SymbolShapeHint) -> BitMatrix {
+    let symbol = encode(contents, shape, None, None).expect("encode");
+    assert_eq!(contents, read(&symbol));
+    symbol
+}
+
+#[test]
+fn testRoundTripEncodationModes() {
+    // ASCII, with digit pairs
+    roundTrip("Hello, World! 0123456789", SymbolShapeHint::FORCE_NONE);
+    // C40
+    roundTrip("ABCDEFGHIJKLMNOPQRSTUVWXYZ", SymbolShapeHint::FORCE_NONE);
+    // Text
+    roundTrip(
+        "the quick brown fox jumps over",
+        SymbolShapeHint::FORCE_NONE,
+    );
+    // Base 256
+    roundTrip(
+        "\u{00e0}\u{00e9}\u{00ee}\u{00f5}\u{00fc}\u{00e7}\u{00f1}\u{00e5}\u{00f8}\u{00e6}",
+        SymbolShapeHint::FORCE_NONE,
+    );
+}
+
+#[test]
+fn testSmallestSymbol() {
+    let symbol = roundTrip("123456", SymbolShapeHint::FORCE_NONE);
+    assert_eq!((10, 10), (symbol.getWidth(), symbol.getHeight()));
+
+    let symbol = roundTrip("Hello", SymbolShapeHint::FORCE_SQUARE);
+    assert_eq!((12, 12), (symbol.getWidth(), symbol.getHeight()));
+}
+
+#[test]
+fn testRectangularSymbol() {
+    let symbol = roundTrip("Hello", SymbolShapeHint::FORCE_RECTANGLE);
+    assert_eq!((18, 8), (symbol.getWidth(), symbol.getHeight()));
+
+    // Rectangles with more than one data region
+    let symbol = roundTrip(
+        "A rectangular symbol with two data regions",
+        SymbolShapeHint::FORCE_RECTANGLE,
+    );
+    assert!(symbol.getWidth() > symbol.getHeight());
+}
+
+#[test]
+fn testSizeConstraints() {
+    let minSize = Dimension::new(26, 26).expect("dimension");
+    let symbol = encode("Hello", SymbolShapeHint::FORCE_NONE, Some(minSize), None).expect("encode");
+    assert_eq!((26, 26), (symbol.getWidth(), symbol.getHeight()));
+    assert_eq!("Hello", read(&symbol));
+
+    let maxSize = Dimension::new(10, 10).expect("dimension");
+    assert!(encode(
+        "This does not fit in ten by ten modules",
+        SymbolShapeHint::FORCE_NONE,
+        None,
+        Some(maxSize),
+    )
+    .is_err());
+}
@@ -78,7 +78,7 @@ impl X12Encoder {
     }
 
     fn handleEOD(context: &mut EncoderContext, buffer: &mut str) -> Result<(), Exceptions> {
-        context.updateSymbolInfo();
+        context.updateSymbolInfo()?;
         let available =
             context.getSymbolInfo().unwrap().getDataCapacity() - context.getCodewordCount() as u32;
         let count = buffer.chars().count();
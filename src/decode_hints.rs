@@ -18,7 +18,7 @@
 
 use std::collections::HashSet;
 
-use crate::{BarcodeFormat, RXingResultPointCallback};
+use crate::{BarcodeFormat, DecodingHintDictionary, Exceptions, RXingResultPointCallback};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -109,6 +109,225 @@ pub enum DecodeHintType {
      */
     ALSO_INVERTED,
 
+    /**
+     * If true, 1D readers also scan the image column by column, so that barcodes printed
+     * vertically can be read without rotating the image first or setting {@link #TRY_HARDER}.
+     * The orientation the barcode was found in is reported in the result metadata. Doesn't
+     * matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    TRY_VERTICAL,
+
+    /**
+     * The expected width in pixels of the narrowest bar of 1D barcodes, when it's known from the
+     * capture setup. 1D readers then reject start patterns which don't match this module width
+     * before trying to decode any further, which is faster and avoids reads of other barcodes
+     * printed at a different scale. Maps to an {@code f32}; by default the module width is
+     * estimated from each candidate pattern.
+     */
+    X_DIMENSION,
+
+    /**
+     * If true, {@link MultiFormatReader} attaches a copy of the binarized image which the barcode
+     * was decoded from to the result, as {@link RXingResultMetadataType#BINARIZED_MATRIX}. That
+     * allows inspecting or decoding it again without binarizing the image once more. 1D barcodes
+     * are decoded from single rows, not from a binarized image, so their results don't get one.
+     * Off by default, as it clones the whole matrix. Doesn't matter what it maps to; use
+     * {@link Boolean#TRUE}.
+     */
+    RETURN_BINARIZED_MATRIX,
+
+    /**
+     * If true, 1D readers scan rows across the whole height of the image, as closely as when
+     * trying harder, instead of only around the middle. That's needed to collect every row of
+     * tall stacked GS1 DataBar symbols, which the DataBar readers combine into one result, without
+     * also trying rotated images. The Expanded reader then also doesn't join a pair to the previous
+     * one in a row when it's too far away, as it belongs to another symbol next to this one. Maps
+     * to a {@code bool}.
+     */
+    RSS_STACKED,
+
+    /**
+     * If true, Telepen symbols are decoded in numeric mode from their first character on, as
+     * printed by encoders of numeric only Telepen. Otherwise they start out in full ASCII mode.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    TELEPEN_AS_NUMERIC,
+
+    /**
+     * The highest QR Code version the decoder will accept. Symbols which claim a larger version
+     * (and therefore more codewords) are rejected before their codewords are read. Maps to a
+     * {@code u32} in the range [1,40]; defaults to 40, which imposes no limit.
+     */
+    QR_MAX_VERSION,
+
+    /**
+     * If true, the raw bytes of any QR Code segment which is decoded as Shift_JIS are made
+     * available in the result metadata in addition to the transcoded text. Doesn't matter what it
+     * maps to; use {@link Boolean#TRUE}.
+     */
+    QR_PRESERVE_SHIFT_JIS_BYTES,
+
+    /**
+     * The error correction level QR Codes are known to use. Format information is then only
+     * matched against the patterns for that level, which tolerates more damage to it. Maps to a
+     * {@link String} naming the level: "L", "M", "Q" or "H". By default the level is read from the
+     * symbol.
+     */
+    QR_ASSUME_EC_LEVEL,
+
+    /**
+     * The minimum confidence, in the range [0,1], that the located finder patterns belong to a
+     * QR Code. Detection gives up with a NotFoundException below it instead of trying to decode,
+     * which avoids false positives when continuously scanning at the cost of missing weak reads.
+     * Confidence drops when the module sizes of the finder patterns disagree, or when they don't
+     * form an isosceles right triangle. Maps to an {@code f32}; by default any candidate is accepted.
+     */
+    QR_MIN_DETECTION_CONFIDENCE,
+
+    /**
+     * If true, PDF417 results report the codewords the decoder couldn't settle on: positions
+     * read with several equally likely values, and codewords read whose row couldn't be placed in
+     * the symbol. They're returned in the result metadata, which helps to find out why a symbol
+     * only decodes some of the time. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    PDF417_REPORT_AMBIGUOUS_CODEWORDS,
+
+    /**
+     * If true, a PDF417 symbol is only decoded if every codeword was read with a single most
+     * likely value and could be placed in the symbol, rather than leaving ambiguous codewords to
+     * error correction. Decoding fails with a FormatException otherwise. Doesn't matter what it
+     * maps to; use {@link Boolean#TRUE}.
+     */
+    PDF417_STRICT_CODEWORDS,
+
+    /**
+     * If true, barcodes are read even if they lack the quiet zone their specification requires,
+     * as in images cropped flush to the symbol or laid out tightly against other content. UPC/EAN
+     * readers then accept guard patterns whose quiet zone runs off the edge of the image, Code 39,
+     * ITF and Codabar readers accept symbols with content right next to them, and if the QR Code
+     * detector fails it measures finder patterns cut by the edge of the image again from their
+     * intact half. This trades more false positives for reading cropped images; by default
+     * quiet zones are enforced. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    RELAX_QUIET_ZONE,
+
+    /**
+     * How FNC1 characters after the first are rendered in GS1-128 results, which are read with
+     * {@link #ASSUME_GS1}. Each is replaced by the string this maps to: a single character such as
+     * "~" for systems which expect a visible placeholder, or the empty string to strip them.
+     * Defaults to ASCII 29 (GS) as specified by GS1. Maps to a {@link String}.
+     */
+    CODE_128_FNC1_SEPARATOR,
+
+    /**
+     * If true, UPC/EAN results carry a result point at each edge of their start and end guard
+     * patterns, and one at the center of the middle guard pattern of symbols which have one, all
+     * from left to right. This lets an overlay outline the symbol itself instead of the centers of
+     * its guards. By default only the centers of the start and end guard patterns are returned.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    RETURN_UPC_EAN_GUARD_POINTS,
+
+    /**
+     * The most result points MultiFormatReader returns with a result. Readers list the corners
+     * of a symbol, or its finder patterns, before any other points, so those are kept and the
+     * rest are dropped. Helps callers which copy the points into a buffer of fixed size. Maps to
+     * a {@code u32}; by default all points are returned.
+     */
+    MAX_RESULT_POINTS,
+
+    /**
+     * Appends a UPC/EAN supplement to the text of the result, after a space, as in
+     * {@code "9780735200449 51299"}. The supplement is only taken if it starts close enough to
+     * the end of the main symbol, allowing somewhat more than the 7 to 12 module gap the
+     * specification requires, so that a neighbouring barcode is not read as one. By default the
+     * supplement is only reported through {@link RXingResultMetadataType#UPC_EAN_EXTENSION}.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    APPEND_UPC_EAN_EXTENSION,
+
+    /**
+     * How many quadrants 1D readers try beyond the upright image, from 0 to 3: 0 for none, 1 to
+     * also scan the image turned 90 degrees counterclockwise, which catches barcodes printed
+     * vertically at about twice the cost, and 3 for all quadrants. 2 and 3 cost the same as 1 and
+     * find the same barcodes, as 1D readers read every row in both directions: upside down
+     * barcodes are found without turning the image, and turned 180 or 270 degrees it holds no rows
+     * which weren't scanned already. Maps to a {@code u32}; without it {@link #TRY_HARDER} and
+     * {@link #TRY_VERTICAL} make a single attempt.
+     */
+    ROTATION_ATTEMPTS,
+
+    /**
+     * If true, the data segments of a QR Code are reported in the result metadata, each with its
+     * mode, the characters of the text it decoded to, the bits of the data codewords it was read
+     * from and its ECI designator. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    QR_RETURN_DECODED_SEGMENTS,
+
+    /**
+     * Restricts 1D readers to the rows of a horizontal band of the image, such as the area around
+     * the scan line a viewfinder shows. Maps to the top and bottom of the band, as fractions of
+     * the image height from 0.0 at the top to 1.0 at the bottom; rows are scanned from the middle
+     * of the band outward. The band applies to each rotation of the image that is tried. By
+     * default the rows around the middle of the whole image are scanned.
+     */
+    ONED_ROW_BAND,
+
+    /**
+     * If true, a reader which located a barcode but failed to decode it returns a partial result
+     * instead of the error: it has no text, the points the detector found and
+     * {@link RXingResultMetadataType#DETECTION_BOUNDS}, and it reports why decoding failed in
+     * {@link RXingResultMetadataType#DECODE_FAILURE}. Viewfinders can use it to guide the user
+     * closer. Only the QR Code reader supports it. {@link MultiFormatReader} only returns a partial
+     * result when none of its readers decodes the image, and readers of multiple barcodes leave
+     * them out, see {@link RXingResult#isPartial}. Doesn't matter what it maps to; use
+     * {@link Boolean#TRUE}.
+     */
+    RETURN_DETECTION_ON_FAILURE,
+
+    /**
+     * Decodes the bytes of byte mode segments with this character set, ignoring any ECI in the
+     * symbol, to read symbols from encoders which declare the wrong character set, such as
+     * ISO-8859-1 for UTF-8 bytes. By default the ECI is honored, and {@link #CHARACTER_SET} only
+     * applies to segments without one. Maps to a {@link String} naming the character set. Only the
+     * QR Code decoder supports it.
+     */
+    OVERRIDE_ECI_CHARSET,
+
+    /**
+     * The most results a {@link MultipleBarcodeReader} returns. It stops looking for further
+     * barcodes once it has found as many. Maps to a {@code u32}; by default every barcode found
+     * is returned.
+     */
+    MAX_RESULTS,
+
+    /**
+     * Asks the reader to grade the print quality of the symbol, see
+     * {@link RXingResultMetadataType#PRINT_QUALITY_GRADE}. Only the QR Code reader supports it.
+     * Maps to a {@code bool}.
+     */
+    PRINT_QUALITY_GRADE,
+
+    /**
+     * Corrects the luminance of the image with a gamma curve before decoding it, see
+     * {@link GammaLuminanceSource}. A gamma above 1, around 2.2 for sRGB, linearizes gamma encoded
+     * camera images, which can help the binarizer with bright, washed out captures. Maps to an
+     * {@code f32}; by default the image is decoded as given. Only {@link MultiFormatReader}
+     * supports it.
+     */
+    GAMMA_PREFILTER,
+
+    /**
+     * Images with at least this many pixels are searched for a QR Code on a copy scaled down to
+     * around a million pixels first, and only a crop around the symbol found there is binarized
+     * at full resolution. That is much faster on multi-megapixel photos, but a symbol with modules
+     * under about two pixels wide at full resolution may be lost on the scaled down copy; the
+     * native resolution search then still runs, at the cost of both passes. Maps to a
+     * {@code u32}; {@code u32::MAX} turns the scaled down search off. By default images of four
+     * million pixels or more are searched this way.
+     */
+    QR_COARSE_DETECTION_MIN_PIXELS,
+
     /**
      * Specifies that the codes are expected to be in conformance with the specification
      * ISO/IEC 18004 regading the interpretation of character encoding. Values encoded in BYTE mode
@@ -219,6 +438,225 @@ pub enum DecodeHintValue {
      */
     AlsoInverted(bool),
 
+    /**
+     * If true, 1D readers also scan the image column by column, so that barcodes printed
+     * vertically can be read without rotating the image first or setting {@link #TRY_HARDER}.
+     * The orientation the barcode was found in is reported in the result metadata. Doesn't
+     * matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    TryVertical(bool),
+
+    /**
+     * The expected width in pixels of the narrowest bar of 1D barcodes, when it's known from the
+     * capture setup. 1D readers then reject start patterns which don't match this module width
+     * before trying to decode any further, which is faster and avoids reads of other barcodes
+     * printed at a different scale. Maps to an {@code f32}; by default the module width is
+     * estimated from each candidate pattern.
+     */
+    XDimension(f32),
+
+    /**
+     * If true, {@link MultiFormatReader} attaches a copy of the binarized image which the barcode
+     * was decoded from to the result, as {@link RXingResultMetadataType#BINARIZED_MATRIX}. That
+     * allows inspecting or decoding it again without binarizing the image once more. 1D barcodes
+     * are decoded from single rows, not from a binarized image, so their results don't get one.
+     * Off by default, as it clones the whole matrix. Doesn't matter what it maps to; use
+     * {@link Boolean#TRUE}.
+     */
+    ReturnBinarizedMatrix(bool),
+
+    /**
+     * If true, 1D readers scan rows across the whole height of the image, as closely as when
+     * trying harder, instead of only around the middle. That's needed to collect every row of
+     * tall stacked GS1 DataBar symbols, which the DataBar readers combine into one result, without
+     * also trying rotated images. The Expanded reader then also doesn't join a pair to the previous
+     * one in a row when it's too far away, as it belongs to another symbol next to this one. Maps
+     * to a {@code bool}.
+     */
+    RssStacked(bool),
+
+    /**
+     * If true, Telepen symbols are decoded in numeric mode from their first character on, as
+     * printed by encoders of numeric only Telepen. Otherwise they start out in full ASCII mode.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    TelepenAsNumeric(bool),
+
+    /**
+     * The highest QR Code version the decoder will accept. Symbols which claim a larger version
+     * (and therefore more codewords) are rejected before their codewords are read. Maps to a
+     * {@code u32} in the range [1,40]; defaults to 40, which imposes no limit.
+     */
+    QrMaxVersion(u32),
+
+    /**
+     * If true, the raw bytes of any QR Code segment which is decoded as Shift_JIS are made
+     * available in the result metadata in addition to the transcoded text. Doesn't matter what it
+     * maps to; use {@link Boolean#TRUE}.
+     */
+    QrPreserveShiftJisBytes(bool),
+
+    /**
+     * The error correction level QR Codes are known to use. Format information is then only
+     * matched against the patterns for that level, which tolerates more damage to it. Maps to a
+     * {@link String} naming the level: "L", "M", "Q" or "H". By default the level is read from the
+     * symbol.
+     */
+    QrAssumeEcLevel(String),
+
+    /**
+     * The minimum confidence, in the range [0,1], that the located finder patterns belong to a
+     * QR Code. Detection gives up with a NotFoundException below it instead of trying to decode,
+     * which avoids false positives when continuously scanning at the cost of missing weak reads.
+     * Confidence drops when the module sizes of the finder patterns disagree, or when they don't
+     * form an isosceles right triangle. Maps to an {@code f32}; by default any candidate is accepted.
+     */
+    QrMinDetectionConfidence(f32),
+
+    /**
+     * If true, PDF417 results report the codewords the decoder couldn't settle on: positions
+     * read with several equally likely values, and codewords read whose row couldn't be placed in
+     * the symbol. They're returned in the result metadata, which helps to find out why a symbol
+     * only decodes some of the time. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    Pdf417ReportAmbiguousCodewords(bool),
+
+    /**
+     * If true, a PDF417 symbol is only decoded if every codeword was read with a single most
+     * likely value and could be placed in the symbol, rather than leaving ambiguous codewords to
+     * error correction. Decoding fails with a FormatException otherwise. Doesn't matter what it
+     * maps to; use {@link Boolean#TRUE}.
+     */
+    Pdf417StrictCodewords(bool),
+
+    /**
+     * If true, barcodes are read even if they lack the quiet zone their specification requires,
+     * as in images cropped flush to the symbol or laid out tightly against other content. UPC/EAN
+     * readers then accept guard patterns whose quiet zone runs off the edge of the image, Code 39,
+     * ITF and Codabar readers accept symbols with content right next to them, and if the QR Code
+     * detector fails it measures finder patterns cut by the edge of the image again from their
+     * intact half. This trades more false positives for reading cropped images; by default
+     * quiet zones are enforced. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    RelaxQuietZone(bool),
+
+    /**
+     * How FNC1 characters after the first are rendered in GS1-128 results, which are read with
+     * {@link #ASSUME_GS1}. Each is replaced by the string this maps to: a single character such as
+     * "~" for systems which expect a visible placeholder, or the empty string to strip them.
+     * Defaults to ASCII 29 (GS) as specified by GS1. Maps to a {@link String}.
+     */
+    Code128Fnc1Separator(String),
+
+    /**
+     * If true, UPC/EAN results carry a result point at each edge of their start and end guard
+     * patterns, and one at the center of the middle guard pattern of symbols which have one, all
+     * from left to right. This lets an overlay outline the symbol itself instead of the centers of
+     * its guards. By default only the centers of the start and end guard patterns are returned.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    ReturnUpcEanGuardPoints(bool),
+
+    /**
+     * The most result points MultiFormatReader returns with a result. Readers list the corners
+     * of a symbol, or its finder patterns, before any other points, so those are kept and the
+     * rest are dropped. Helps callers which copy the points into a buffer of fixed size. Maps to
+     * a {@code u32}; by default all points are returned.
+     */
+    MaxResultPoints(u32),
+
+    /**
+     * Appends a UPC/EAN supplement to the text of the result, after a space, as in
+     * {@code "9780735200449 51299"}. The supplement is only taken if it starts close enough to
+     * the end of the main symbol, allowing somewhat more than the 7 to 12 module gap the
+     * specification requires, so that a neighbouring barcode is not read as one. By default the
+     * supplement is only reported through {@link RXingResultMetadataType#UPC_EAN_EXTENSION}.
+     * Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    AppendUpcEanExtension(bool),
+
+    /**
+     * How many quadrants 1D readers try beyond the upright image, from 0 to 3: 0 for none, 1 to
+     * also scan the image turned 90 degrees counterclockwise, which catches barcodes printed
+     * vertically at about twice the cost, and 3 for all quadrants. 2 and 3 cost the same as 1 and
+     * find the same barcodes, as 1D readers read every row in both directions: upside down
+     * barcodes are found without turning the image, and turned 180 or 270 degrees it holds no rows
+     * which weren't scanned already. Maps to a {@code u32}; without it {@link #TRY_HARDER} and
+     * {@link #TRY_VERTICAL} make a single attempt.
+     */
+    RotationAttempts(u32),
+
+    /**
+     * If true, the data segments of a QR Code are reported in the result metadata, each with its
+     * mode, the characters of the text it decoded to, the bits of the data codewords it was read
+     * from and its ECI designator. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    QrReturnDecodedSegments(bool),
+
+    /**
+     * Restricts 1D readers to the rows of a horizontal band of the image, such as the area around
+     * the scan line a viewfinder shows. Maps to the top and bottom of the band, as fractions of
+     * the image height from 0.0 at the top to 1.0 at the bottom; rows are scanned from the middle
+     * of the band outward. The band applies to each rotation of the image that is tried. By
+     * default the rows around the middle of the whole image are scanned.
+     */
+    OneDRowBand(f32, f32),
+
+    /**
+     * If true, a reader which located a barcode but failed to decode it returns a partial result
+     * instead of the error: it has no text, the points the detector found and
+     * {@link RXingResultMetadataType#DETECTION_BOUNDS}, and it reports why decoding failed in
+     * {@link RXingResultMetadataType#DECODE_FAILURE}. Viewfinders can use it to guide the user
+     * closer. Only the QR Code reader supports it. {@link MultiFormatReader} only returns a partial
+     * result when none of its readers decodes the image, and readers of multiple barcodes leave
+     * them out, see {@link RXingResult#isPartial}. Doesn't matter what it maps to; use
+     * {@link Boolean#TRUE}.
+     */
+    ReturnDetectionOnFailure(bool),
+
+    /**
+     * Decodes the bytes of byte mode segments with this character set, ignoring any ECI in the
+     * symbol, to read symbols from encoders which declare the wrong character set, such as
+     * ISO-8859-1 for UTF-8 bytes. By default the ECI is honored, and {@link #CHARACTER_SET} only
+     * applies to segments without one. Maps to a {@link String} naming the character set. Only the
+     * QR Code decoder supports it.
+     */
+    OverrideEciCharset(String),
+
+    /**
+     * The most results a {@link MultipleBarcodeReader} returns. It stops looking for further
+     * barcodes once it has found as many. Maps to a {@code u32}; by default every barcode found
+     * is returned.
+     */
+    MaxResults(u32),
+
+    /**
+     * Asks the reader to grade the print quality of the symbol, see
+     * {@link RXingResultMetadataType#PRINT_QUALITY_GRADE}. Only the QR Code reader supports it.
+     * Maps to a {@code bool}.
+     */
+    PrintQualityGrade(bool),
+
+    /**
+     * Corrects the luminance of the image with a gamma curve before decoding it, see
+     * {@link GammaLuminanceSource}. A gamma above 1, around 2.2 for sRGB, linearizes gamma encoded
+     * camera images, which can help the binarizer with bright, washed out captures. Maps to an
+     * {@code f32}; by default the image is decoded as given. Only {@link MultiFormatReader}
+     * supports it.
+     */
+    GammaPrefilter(f32),
+
+    /**
+     * Images with at least this many pixels are searched for a QR Code on a copy scaled down to
+     * around a million pixels first, and only a crop around the symbol found there is binarized
+     * at full resolution. That is much faster on multi-megapixel photos, but a symbol with modules
+     * under about two pixels wide at full resolution may be lost on the scaled down copy; the
+     * native resolution search then still runs, at the cost of both passes. Maps to a
+     * {@code u32}; {@code u32::MAX} turns the scaled down search off. By default images of four
+     * million pixels or more are searched this way.
+     */
+    QrCoarseDetectionMinPixels(u32),
+
     /**
      * Specifies that the codes are expected to be in conformance with the specification
      * ISO/IEC 18004 regading the interpretation of character encoding. Values encoded in BYTE mode
@@ -232,3 +670,93 @@ pub enum DecodeHintValue {
     #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
     QrAssumeSpecConformInput(bool),
 }
+
+impl DecodeHintValue {
+    /**
+     * @return the {@link DecodeHintType} this value belongs under
+     */
+    pub fn getHintType(&self) -> DecodeHintType {
+        match self {
+            DecodeHintValue::Other(..) => DecodeHintType::OTHER,
+            DecodeHintValue::PureBarcode(..) => DecodeHintType::PURE_BARCODE,
+            DecodeHintValue::PossibleFormats(..) => DecodeHintType::POSSIBLE_FORMATS,
+            DecodeHintValue::TryHarder(..) => DecodeHintType::TRY_HARDER,
+            DecodeHintValue::CharacterSet(..) => DecodeHintType::CHARACTER_SET,
+            DecodeHintValue::AllowedLengths(..) => DecodeHintType::ALLOWED_LENGTHS,
+            DecodeHintValue::AssumeCode39CheckDigit(..) => {
+                DecodeHintType::ASSUME_CODE_39_CHECK_DIGIT
+            }
+            DecodeHintValue::AssumeGs1(..) => DecodeHintType::ASSUME_GS1,
+            DecodeHintValue::ReturnCodabarStartEnd(..) => DecodeHintType::RETURN_CODABAR_START_END,
+            DecodeHintValue::NeedResultPointCallback(..) => {
+                DecodeHintType::NEED_RESULT_POINT_CALLBACK
+            }
+            DecodeHintValue::AllowedEanExtensions(..) => DecodeHintType::ALLOWED_EAN_EXTENSIONS,
+            DecodeHintValue::AlsoInverted(..) => DecodeHintType::ALSO_INVERTED,
+            DecodeHintValue::TryVertical(..) => DecodeHintType::TRY_VERTICAL,
+            DecodeHintValue::XDimension(..) => DecodeHintType::X_DIMENSION,
+            DecodeHintValue::ReturnBinarizedMatrix(..) => DecodeHintType::RETURN_BINARIZED_MATRIX,
+            DecodeHintValue::RssStacked(..) => DecodeHintType::RSS_STACKED,
+            DecodeHintValue::TelepenAsNumeric(..) => DecodeHintType::TELEPEN_AS_NUMERIC,
+            DecodeHintValue::QrMaxVersion(..) => DecodeHintType::QR_MAX_VERSION,
+            DecodeHintValue::QrPreserveShiftJisBytes(..) => {
+                DecodeHintType::QR_PRESERVE_SHIFT_JIS_BYTES
+            }
+            DecodeHintValue::QrAssumeEcLevel(..) => DecodeHintType::QR_ASSUME_EC_LEVEL,
+            DecodeHintValue::QrMinDetectionConfidence(..) => {
+                DecodeHintType::QR_MIN_DETECTION_CONFIDENCE
+            }
+            DecodeHintValue::Pdf417ReportAmbiguousCodewords(..) => {
+                DecodeHintType::PDF417_REPORT_AMBIGUOUS_CODEWORDS
+            }
+            DecodeHintValue::Pdf417StrictCodewords(..) => DecodeHintType::PDF417_STRICT_CODEWORDS,
+            DecodeHintValue::RelaxQuietZone(..) => DecodeHintType::RELAX_QUIET_ZONE,
+            DecodeHintValue::Code128Fnc1Separator(..) => DecodeHintType::CODE_128_FNC1_SEPARATOR,
+            DecodeHintValue::ReturnUpcEanGuardPoints(..) => {
+                DecodeHintType::RETURN_UPC_EAN_GUARD_POINTS
+            }
+            DecodeHintValue::MaxResultPoints(..) => DecodeHintType::MAX_RESULT_POINTS,
+            DecodeHintValue::AppendUpcEanExtension(..) => DecodeHintType::APPEND_UPC_EAN_EXTENSION,
+            DecodeHintValue::RotationAttempts(..) => DecodeHintType::ROTATION_ATTEMPTS,
+            DecodeHintValue::QrReturnDecodedSegments(..) => {
+                DecodeHintType::QR_RETURN_DECODED_SEGMENTS
+            }
+            DecodeHintValue::OneDRowBand(..) => DecodeHintType::ONED_ROW_BAND,
+            DecodeHintValue::ReturnDetectionOnFailure(..) => {
+                DecodeHintType::RETURN_DETECTION_ON_FAILURE
+            }
+            DecodeHintValue::OverrideEciCharset(..) => DecodeHintType::OVERRIDE_ECI_CHARSET,
+            DecodeHintValue::MaxResults(..) => DecodeHintType::MAX_RESULTS,
+            DecodeHintValue::PrintQualityGrade(..) => DecodeHintType::PRINT_QUALITY_GRADE,
+            DecodeHintValue::GammaPrefilter(..) => DecodeHintType::GAMMA_PREFILTER,
+            DecodeHintValue::QrCoarseDetectionMinPixels(..) => {
+                DecodeHintType::QR_COARSE_DETECTION_MIN_PIXELS
+            }
+            #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
+            DecodeHintValue::QrAssumeSpecConformInput(..) => {
+                DecodeHintType::QR_ASSUME_SPEC_CONFORM_INPUT
+            }
+        }
+    }
+}
+
+/**
+ * Inserts a hint, checking that the value is the variant readers expect for its type. Readers
+ * match on the variant, so a value stored under the wrong type would otherwise be ignored.
+ *
+ * @return the value previously stored for the type, if any
+ * @throws IllegalArgumentException if the value doesn't belong to the hint type
+ */
+pub fn insert_decode_hint(
+    hints: &mut DecodingHintDictionary,
+    hintType: DecodeHintType,
+    value: DecodeHintValue,
+) -> Result<Option<DecodeHintValue>, Exceptions> {
+    let valueType = value.getHintType();
+    if valueType != hintType {
+        return Err(Exceptions::IllegalArgumentException(Some(format!(
+            "{valueType:?} value given for hint {hintType:?}"
+        ))));
+    }
+    Ok(hints.insert(hintType, value))
+}
@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+
+use crate::{
+    insert_decode_hint, BarcodeFormat, DecodeHintType, DecodeHintValue, DecodingHintDictionary,
+    Exceptions,
+};
+
+/**
+ * Builds a {@link DecodingHintDictionary} with each hint stored under the value variant its
+ * readers look for, so a hint can't silently be ignored because the wrong variant was used.
+ *
+ * Many readers only check whether a flag hint such as {@link DecodeHintType#TRY_HARDER} is
+ * present, whatever its value, so turning a flag off removes it from the dictionary.
+ *
+ * ```
+ * use rxing::{BarcodeFormat, DecodeHintType, DecodeHintValue, DecodeHintsBuilder};
+ *
+ * let hints = DecodeHintsBuilder::new()
+ *     .try_harder(true)
+ *     .possible_formats(&[BarcodeFormat::QR_CODE])
+ *     .build()?;
+ * assert!(matches!(
+ *     hints.get(&DecodeHintType::TRY_HARDER),
+ *     Some(DecodeHintValue::TryHarder(true))
+ * ));
+ * # Ok::<(), rxing::Exceptions>(())
+ * ```
+ */
+#[derive(Default)]
+pub struct DecodeHintsBuilder {
+    hints: DecodingHintDictionary,
+    error: Option<Exceptions>,
+}
+
+impl DecodeHintsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * See {@link DecodeHintType#TRY_HARDER}.
+     */
+    pub fn try_harder(self, tryHarder: bool) -> Self {
+        self.flag(
+            DecodeHintType::TRY_HARDER,
+            tryHarder,
+            DecodeHintValue::TryHarder,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#PURE_BARCODE}.
+     */
+    pub fn pure_barcode(self, pureBarcode: bool) -> Self {
+        self.flag(
+            DecodeHintType::PURE_BARCODE,
+            pureBarcode,
+            DecodeHintValue::PureBarcode,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#POSSIBLE_FORMATS}.
+     */
+    pub fn possible_formats(self, formats: &[BarcodeFormat]) -> Self {
+        self.set(
+            DecodeHintType::POSSIBLE_FORMATS,
+            DecodeHintValue::PossibleFormats(formats.iter().copied().collect::<HashSet<_>>()),
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#CHARACTER_SET}.
+     */
+    pub fn character_set(self, characterSet: &str) -> Self {
+        self.set(
+            DecodeHintType::CHARACTER_SET,
+            DecodeHintValue::CharacterSet(characterSet.to_owned()),
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#ALLOWED_LENGTHS}.
+     */
+    pub fn allowed_lengths(self, lengths: &[u32]) -> Self {
+        self.set(
+            DecodeHintType::ALLOWED_LENGTHS,
+            DecodeHintValue::AllowedLengths(lengths.to_vec()),
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#ASSUME_CODE_39_CHECK_DIGIT}.
+     */
+    pub fn assume_code_39_check_digit(self, assumeCheckDigit: bool) -> Self {
+        self.flag(
+            DecodeHintType::ASSUME_CODE_39_CHECK_DIGIT,
+            assumeCheckDigit,
+            DecodeHintValue::AssumeCode39CheckDigit,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#ASSUME_GS1}.
+     */
+    pub fn assume_gs1(self, assumeGs1: bool) -> Self {
+        self.flag(
+            DecodeHintType::ASSUME_GS1,
+            assumeGs1,
+            DecodeHintValue::AssumeGs1,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#RETURN_CODABAR_START_END}.
+     */
+    pub fn return_codabar_start_end(self, returnStartEnd: bool) -> Self {
+        self.flag(
+            DecodeHintType::RETURN_CODABAR_START_END,
+            returnStartEnd,
+            DecodeHintValue::ReturnCodabarStartEnd,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#ALLOWED_EAN_EXTENSIONS}.
+     */
+    pub fn allowed_ean_extensions(self, extensions: &[u32]) -> Self {
+        self.set(
+            DecodeHintType::ALLOWED_EAN_EXTENSIONS,
+            DecodeHintValue::AllowedEanExtensions(extensions.to_vec()),
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#ALSO_INVERTED}.
+     */
+    pub fn also_inverted(self, alsoInverted: bool) -> Self {
+        self.flag(
+            DecodeHintType::ALSO_INVERTED,
+            alsoInverted,
+            DecodeHintValue::AlsoInverted,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#TRY_VERTICAL}.
+     */
+    pub fn try_vertical(self, tryVertical: bool) -> Self {
+        self.flag(
+            DecodeHintType::TRY_VERTICAL,
+            tryVertical,
+            DecodeHintValue::TryVertical,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#X_DIMENSION}.
+     */
+    pub fn x_dimension(self, xDimension: f32) -> Self {
+        self.set(
+            DecodeHintType::X_DIMENSION,
+            DecodeHintValue::XDimension(xDimension),
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#RELAX_QUIET_ZONE}.
+     */
+    pub fn relax_quiet_zone(self, relaxQuietZone: bool) -> Self {
+        self.flag(
+            DecodeHintType::RELAX_QUIET_ZONE,
+            relaxQuietZone,
+            DecodeHintValue::RelaxQuietZone,
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#QR_MAX_VERSION}.
+     */
+    pub fn qr_max_version(self, maxVersion: u32) -> Self {
+        self.set(
+            DecodeHintType::QR_MAX_VERSION,
+            DecodeHintValue::QrMaxVersion(maxVersion),
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#MAX_RESULTS}.
+     */
+    pub fn max_results(self, maxResults: u32) -> Self {
+        self.set(
+            DecodeHintType::MAX_RESULTS,
+            DecodeHintValue::MaxResults(maxResults),
+        )
+    }
+
+    /**
+     * See {@link DecodeHintType#GAMMA_PREFILTER}.
+     */
+    pub fn gamma_prefilter(self, gamma: f32) -> Self {
+        self.set(
+            DecodeHintType::GAMMA_PREFILTER,
+            DecodeHintValue::GammaPrefilter(gamma),
+        )
+    }
+
+    /**
+     * Sets a hint which has no typed setter. The value must be the variant belonging to the hint
+     * type, otherwise {@link #build()} fails.
+     */
+    pub fn hint(self, hintType: DecodeHintType, value: DecodeHintValue) -> Self {
+        self.set(hintType, value)
+    }
+
+    /**
+     * Checks the values given and returns the hints.
+     *
+     * @throws IllegalArgumentException if a hint was given a value of the wrong type, no possible
+     *  formats were given, the character set is unknown, the X dimension or the gamma isn't
+     *  positive, or the QR Code version or the rotation attempts are out of range
+     */
+    pub fn build(self) -> Result<DecodingHintDictionary, Exceptions> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let invalid = |message: String| Err(Exceptions::IllegalArgumentException(Some(message)));
+        if let Some(DecodeHintValue::PossibleFormats(formats)) =
+            self.hints.get(&DecodeHintType::POSSIBLE_FORMATS)
+        {
+            if formats.is_empty() {
+                return invalid("no possible formats given".to_owned());
+            }
+        }
+        if let Some(DecodeHintValue::CharacterSet(characterSet)) =
+            self.hints.get(&DecodeHintType::CHARACTER_SET)
+        {
+            if encoding::label::encoding_from_whatwg_label(characterSet).is_none() {
+                return invalid(format!("unknown character set '{characterSet}'"));
+            }
+        }
+        if let Some(DecodeHintValue::XDimension(xDimension)) =
+            self.hints.get(&DecodeHintType::X_DIMENSION)
+        {
+            if !(xDimension.is_finite() && *xDimension > 0.0) {
+                return invalid(format!("X dimension must be positive, got {xDimension}"));
+            }
+        }
+        if let Some(DecodeHintValue::GammaPrefilter(gamma)) =
+            self.hints.get(&DecodeHintType::GAMMA_PREFILTER)
+        {
+            if !(gamma.is_finite() && *gamma > 0.0) {
+                return invalid(format!("gamma must be positive, got {gamma}"));
+            }
+        }
+        if let Some(DecodeHintValue::QrMaxVersion(maxVersion)) =
+            self.hints.get(&DecodeHintType::QR_MAX_VERSION)
+        {
+            if !(1..=40).contains(maxVersion) {
+                return invalid(format!(
+                    "QR Code version must be between 1 and 40, got {maxVersion}"
+                ));
+            }
+        }
+        if let Some(DecodeHintValue::RotationAttempts(attempts)) =
+            self.hints.get(&DecodeHintType::ROTATION_ATTEMPTS)
+        {
+            if *attempts > 3 {
+                return invalid(format!(
+                    "rotation attempts must be between 0 and 3, got {attempts}"
+                ));
+            }
+        }
+        Ok(self.hints)
+    }
+
+    fn flag(
+        mut self,
+        hintType: DecodeHintType,
+        value: bool,
+        variant: fn(bool) -> DecodeHintValue,
+    ) -> Self {
+        if value {
+            self.set(hintType, variant(true))
+        } else {
+            self.hints.remove(&hintType);
+            self
+        }
+    }
+
+    fn set(mut self, hintType: DecodeHintType, value: DecodeHintValue) -> Self {
+        if let Err(error) = insert_decode_hint(&mut self.hints, hintType, value) {
+            self.error.get_or_insert(error);
+        }
+        self
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    insert_decode_hint,
+    oned::{Code39Writer, MultiFormatOneDReader},
+    shared_test_methods::bitmap_for,
+    BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue, DecodeHintsBuilder, Exceptions,
+    Reader, Writer,
+};
+
+fn code_39_bitmap(contents: &str) -> BinaryBitmap {
+    let matrix = Code39Writer
+        .encode(contents, &BarcodeFormat::CODE_39, 300, 60)
+        .expect("encode");
+    bitmap_for(&matrix)
+}
+
+#[test]
+fn test_builds_expected_variants() {
+    let hints = DecodeHintsBuilder::new()
+        .try_harder(true)
+        .pure_barcode(true)
+        .possible_formats(&[BarcodeFormat::QR_CODE, BarcodeFormat::CODE_39])
+        .character_set("UTF-8")
+        .assume_code_39_check_digit(true)
+        .allowed_lengths(&[8, 13])
+        .qr_max_version(10)
+        .build()
+        .expect("valid hints");
+
+    assert_eq!(7, hints.len());
+    assert!(matches!(
+        hints.get(&DecodeHintType::TRY_HARDER),
+        Some(DecodeHintValue::TryHarder(true))
+    ));
+    assert!(matches!(
+        hints.get(&DecodeHintType::PURE_BARCODE),
+        Some(DecodeHintValue::PureBarcode(true))
+    ));
+    let Some(DecodeHintValue::PossibleFormats(formats)) =
+        hints.get(&DecodeHintType::POSSIBLE_FORMATS)
+    else {
+        panic!("expected PossibleFormats");
+    };
+    assert_eq!(
+        &HashSet::from([BarcodeFormat::QR_CODE, BarcodeFormat::CODE_39]),
+        formats
+    );
+    assert!(matches!(
+        hints.get(&DecodeHintType::CHARACTER_SET),
+        Some(DecodeHintValue::CharacterSet(name)) if name == "UTF-8"
+    ));
+    assert!(matches!(
+        hints.get(&DecodeHintType::ASSUME_CODE_39_CHECK_DIGIT),
+        Some(DecodeHintValue::AssumeCode39CheckDigit(true))
+    ));
+    assert!(matches!(
+        hints.get(&DecodeHintType::ALLOWED_LENGTHS),
+        Some(DecodeHintValue::AllowedLengths(lengths)) if lengths == &[8, 13]
+    ));
+    assert!(matches!(
+        hints.get(&DecodeHintType::QR_MAX_VERSION),
+        Some(DecodeHintValue::QrMaxVersion(10))
+    ));
+}
+
+#[test]
+fn test_disabled_flag_is_absent() {
+    // readers test flags with contains_key, so a false value must not be stored
+    let hints = DecodeHintsBuilder::new()
+        .try_harder(true)
+        .try_harder(false)
+        .also_inverted(false)
+        .build()
+        .expect("valid hints");
+    assert!(hints.is_empty());
+}
+
+#[test]
+fn test_rejects_invalid_values() {
+    let rejected = [
+        DecodeHintsBuilder::new().possible_formats(&[]).build(),
+        DecodeHintsBuilder::new()
+            .character_set("no-such-charset")
+            .build(),
+        DecodeHintsBuilder::new().x_dimension(0.0).build(),
+        DecodeHintsBuilder::new().x_dimension(f32::NAN).build(),
+        DecodeHintsBuilder::new().gamma_prefilter(-1.0).build(),
+        DecodeHintsBuilder::new().qr_max_version(0).build(),
+        DecodeHintsBuilder::new().qr_max_version(41).build(),
+        DecodeHintsBuilder::new()
+            .hint(
+                DecodeHintType::ROTATION_ATTEMPTS,
+                DecodeHintValue::RotationAttempts(4),
+            )
+            .build(),
+    ];
+    for result in rejected {
+        assert!(matches!(
+            result,
+            Err(Exceptions::IllegalArgumentException(_))
+        ));
+    }
+    // every quadrant is a valid number of rotation attempts
+    assert!(DecodeHintsBuilder::new()
+        .hint(
+            DecodeHintType::ROTATION_ATTEMPTS,
+            DecodeHintValue::RotationAttempts(3),
+        )
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn test_multi_format_one_d_reader_honours_hints() {
+    let matching = DecodeHintsBuilder::new()
+        .possible_formats(&[BarcodeFormat::CODE_39])
+        .build()
+        .expect("valid hints");
+    let result = MultiFormatOneDReader::new(&matching)
+        .decode_with_hints(&mut code_39_bitmap("HINTS1"), &matching)
+        .expect("decode");
+    assert_eq!("HINTS1", result.getText());
+    assert_eq!(&BarcodeFormat::CODE_39, result.getBarcodeFormat());
+
+    let other = DecodeHintsBuilder::new()
+        .possible_formats(&[BarcodeFormat::CODE_128])
+        .build()
+        .expect("valid hints");
+    assert!(MultiFormatOneDReader::new(&other)
+        .decode_with_hints(&mut code_39_bitmap("HINTS1"), &other)
+        .is_err());
+
+    // "HINTS1" has no valid check digit, so assuming one must make decoding fail
+    let checked = DecodeHintsBuilder::new()
+        .possible_formats(&[BarcodeFormat::CODE_39])
+        .assume_code_39_check_digit(true)
+        .build()
+        .expect("valid hints");
+    assert!(MultiFormatOneDReader::new(&checked)
+        .decode_with_hints(&mut code_39_bitmap("HINTS1"), &checked)
+        .is_err());
+}
+
+#[test]
+fn test_insert_rejects_mismatched_variant() {
+    let mut hints = HashMap::new();
+    assert!(matches!(
+        insert_decode_hint(
+            &mut hints,
+            DecodeHintType::TRY_HARDER,
+            DecodeHintValue::CharacterSet("UTF-8".to_owned()),
+        ),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        insert_decode_hint(
+            &mut hints,
+            DecodeHintType::TRY_HARDER,
+            DecodeHintValue::PureBarcode(true),
+        ),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(hints.is_empty());
+
+    assert!(matches!(
+        insert_decode_hint(
+            &mut hints,
+            DecodeHintType::TRY_HARDER,
+            DecodeHintValue::TryHarder(true),
+        ),
+        Ok(None)
+    ));
+    assert!(matches!(
+        hints.get(&DecodeHintType::TRY_HARDER),
+        Some(DecodeHintValue::TryHarder(true))
+    ));
+}
+
+#[test]
+fn test_builder_rejects_mismatched_hint() {
+    let result = DecodeHintsBuilder::new()
+        .hint(DecodeHintType::TRY_HARDER, DecodeHintValue::MaxResults(1))
+        .build();
+    assert!(matches!(
+        result,
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+
+    let hints = DecodeHintsBuilder::new()
+        .hint(
+            DecodeHintType::RELAX_QUIET_ZONE,
+            DecodeHintValue::RelaxQuietZone(true),
+        )
+        .build()
+        .expect("valid hints");
+    assert_eq!(
+        DecodeHintType::RELAX_QUIET_ZONE,
+        hints[&DecodeHintType::RELAX_QUIET_ZONE].getHintType()
+    );
+}
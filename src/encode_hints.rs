@@ -180,6 +180,15 @@ pub enum EncodeHintType {
      * exclusive.
      */
     CODE128_COMPACT,
+
+    /**
+     * Specifies an explicit segmentation of the content for QR codes, as a list of
+     * {@link com.google.zxing.qrcode.decoder.Mode Mode} and byte range pairs. The ranges must cover
+     * the content in order without gaps, and each range must be encodable in its mode. The encoder
+     * uses the segments as given instead of analyzing the content, which saves time on large
+     * payloads whose optimal segmentation is already known. Takes precedence over {@link #QR_COMPACT}.
+     */
+    QR_SEGMENTS,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -333,4 +342,13 @@ pub enum EncodeHintValue {
      * exclusive.
      */
     Code128Compact(bool),
+
+    /**
+     * Specifies an explicit segmentation of the content for QR codes, as a list of
+     * {@link com.google.zxing.qrcode.decoder.Mode Mode} and byte range pairs. The ranges must cover
+     * the content in order without gaps, and each range must be encodable in its mode. The encoder
+     * uses the segments as given instead of analyzing the content, which saves time on large
+     * payloads whose optimal segmentation is already known. Takes precedence over {@link #QR_COMPACT}.
+     */
+    QrSegments(Vec<(crate::qrcode::decoder::Mode, std::ops::Range<usize>)>),
 }
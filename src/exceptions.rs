@@ -20,6 +20,30 @@ pub enum Exceptions {
     RuntimeException(Option<String>),
     ParseException(Option<String>),
     ReaderDecodeException(),
+    CapacityExceededException(Option<CapacityExceeded>),
+}
+
+/**
+ * How far the content overflows the symbol, for {@link Exceptions#CapacityExceededException}:
+ * {@code required_bits} of data were needed where the chosen size and error correction level
+ * hold {@code available_bits}. Callers can lower the error correction level or allow a larger
+ * size and try again.
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CapacityExceeded {
+    pub required_bits: u32,
+    pub available_bits: u32,
+}
+
+impl fmt::Display for CapacityExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bits needed but {} available",
+            self.required_bits, self.available_bits
+        )
+    }
 }
 
 impl fmt::Display for Exceptions {
@@ -49,6 +73,9 @@ impl fmt::Display for Exceptions {
 
             Exceptions::RuntimeException(Some(a)) => write!(f, "RuntimeException - {a}"),
             Exceptions::ParseException(Some(a)) => write!(f, "ParseException - {a}"),
+            Exceptions::CapacityExceededException(Some(a)) => {
+                write!(f, "CapacityExceededException - {a}")
+            }
 
             Exceptions::IllegalArgumentException(None) => write!(f, "IllegalArgumentException"),
 
@@ -67,6 +94,7 @@ impl fmt::Display for Exceptions {
 
             Exceptions::RuntimeException(None) => write!(f, "RuntimeException"),
             Exceptions::ParseException(None) => write!(f, "ParseException"),
+            Exceptions::CapacityExceededException(None) => write!(f, "CapacityExceededException"),
 
             Exceptions::ReaderDecodeException() => write!(f, "ReaderDecodeException - -"),
         }
@@ -0,0 +1,115 @@
+use crate::{Exceptions, LuminanceSource};
+
+/**
+ * Wraps another {@link LuminanceSource} and applies a gamma curve to every luminance value it
+ * returns, through a 256 entry lookup table computed up front.
+ *
+ * Camera images are usually gamma encoded. A gamma above 1 (around 2.2 for sRGB) linearizes
+ * them, which stretches the contrast between light values and compresses it between dark ones;
+ * this can help the binarizer with glare or bright, washed out captures. A gamma below 1 does
+ * the opposite.
+ */
+pub struct GammaLuminanceSource {
+    source: Box<dyn LuminanceSource>,
+    table: [u8; 256],
+    inverted: bool,
+}
+
+impl GammaLuminanceSource {
+    /**
+     * @param source the luminance source to correct
+     * @param gamma exponent applied to each luminance value, scaled to [0,1]; 1.0 leaves the
+     *  values unchanged
+     */
+    pub fn new(source: Box<dyn LuminanceSource>, gamma: f32) -> Self {
+        let mut table = [0u8; 256];
+        for (value, entry) in table.iter_mut().enumerate() {
+            *entry = ((value as f32 / 255.0).powf(gamma) * 255.0).round() as u8;
+        }
+        Self {
+            source,
+            table,
+            inverted: false,
+        }
+    }
+
+    fn with_table(source: Box<dyn LuminanceSource>, table: [u8; 256], inverted: bool) -> Self {
+        Self {
+            source,
+            table,
+            inverted,
+        }
+    }
+
+    fn correct(&self, luminances: Vec<u8>) -> Vec<u8> {
+        let mut luminances = luminances;
+        for luminance in luminances.iter_mut() {
+            *luminance = self.table[*luminance as usize];
+        }
+        if self.inverted {
+            self.invert_block_of_bytes(luminances)
+        } else {
+            luminances
+        }
+    }
+}
+
+impl LuminanceSource for GammaLuminanceSource {
+    fn getRow(&self, y: usize) -> Vec<u8> {
+        self.correct(self.source.getRow(y))
+    }
+
+    fn getMatrix(&self) -> Vec<u8> {
+        self.correct(self.source.getMatrix())
+    }
+
+    fn getWidth(&self) -> usize {
+        self.source.getWidth()
+    }
+
+    fn getHeight(&self) -> usize {
+        self.source.getHeight()
+    }
+
+    fn isCropSupported(&self) -> bool {
+        self.source.isCropSupported()
+    }
+
+    fn crop(
+        &self,
+        left: usize,
+        top: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Box<dyn LuminanceSource>, Exceptions> {
+        Ok(Box::new(Self::with_table(
+            self.source.crop(left, top, width, height)?,
+            self.table,
+            self.inverted,
+        )))
+    }
+
+    fn isRotateSupported(&self) -> bool {
+        self.source.isRotateSupported()
+    }
+
+    fn invert(&mut self) {
+        self.inverted = !self.inverted;
+    }
+
+    fn rotateCounterClockwise(&self) -> Result<Box<dyn LuminanceSource>, Exceptions> {
+        Ok(Box::new(Self::with_table(
+            self.source.rotateCounterClockwise()?,
+            self.table,
+            self.inverted,
+        )))
+    }
+
+    fn rotateCounterClockwise45(&self) -> Result<Box<dyn LuminanceSource>, Exceptions> {
+        Ok(Box::new(Self::with_table(
+            self.source.rotateCounterClockwise45()?,
+            self.table,
+            self.inverted,
+        )))
+    }
+}
@@ -0,0 +1,58 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{
+    common::HybridBinarizer, qrcode::QRCodeReader, qrcode::QRCodeWriter,
+    shared_test_methods::render_luma, BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue,
+    GammaLuminanceSource, Luma8LuminanceSource, LuminanceSource, Reader, Writer,
+};
+
+#[test]
+fn test_applies_curve() {
+    let source = Luma8LuminanceSource::new(vec![0, 64, 128, 192, 255, 232], 3, 2);
+    let corrected = GammaLuminanceSource::new(Box::new(source), 2.2);
+    assert_eq!(vec![0, 12, 56], corrected.getRow(0));
+    assert_eq!(vec![0, 12, 56, 137, 255, 207], corrected.getMatrix());
+}
+
+#[test]
+fn test_identity_and_invert() {
+    let data = vec![0, 64, 128, 192, 255, 232];
+    let mut corrected =
+        GammaLuminanceSource::new(Box::new(Luma8LuminanceSource::new(data.clone(), 3, 2)), 1.0);
+    assert_eq!(data, corrected.getMatrix());
+
+    corrected.invert();
+    assert_eq!(vec![255, 191, 127, 63, 0, 23], corrected.getMatrix());
+}
+
+#[test]
+fn test_crop_keeps_curve() {
+    let source = Luma8LuminanceSource::new(vec![0, 64, 128, 192, 255, 232], 3, 2);
+    let corrected = GammaLuminanceSource::new(Box::new(source), 2.2);
+    let cropped = corrected.crop(1, 1, 2, 1).expect("crop");
+    assert_eq!(vec![255, 207], cropped.getRow(0));
+}
+
+#[test]
+fn test_rescues_washed_out_decode() {
+    let matrix = QRCodeWriter
+        .encode("washed out", &BarcodeFormat::QR_CODE, 120, 120)
+        .expect("encode");
+    // Dark modules barely darker than light ones, as in an overexposed photo: too little
+    // contrast for the binarizer to tell them apart
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    let pixels = render_luma(width, height, 190, 250, |x, y| matrix.get(x, y));
+    let source = || Luma8LuminanceSource::new(pixels.clone(), width, height);
+    let hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+
+    let mut plain = BinaryBitmap::new(Rc::new(HybridBinarizer::new(Box::new(source()))));
+    assert!(QRCodeReader.decode_with_hints(&mut plain, &hints).is_err());
+
+    let mut corrected = BinaryBitmap::new(Rc::new(HybridBinarizer::new(Box::new(
+        GammaLuminanceSource::new(Box::new(source()), 2.2),
+    ))));
+    let result = QRCodeReader
+        .decode_with_hints(&mut corrected, &hints)
+        .expect("decode");
+    assert_eq!("washed out", result.getText());
+}
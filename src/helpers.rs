@@ -6,10 +6,13 @@ use std::{
 };
 
 use crate::{
-    common::{BitMatrix, HybridBinarizer},
+    client::result::{parseRXingResult, ParsedClientResult},
+    common::{BitMatrix, HybridBinarizer, RenderConfig},
     multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+    oned::MultiFormatOneDReader,
     BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue, DecodingHintDictionary,
-    Exceptions, Luma8LuminanceSource, MultiFormatReader, RXingResult, Reader,
+    Exceptions, Luma8LuminanceSource, MultiFormatReader, MultiFormatWriter, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
 };
 
 #[cfg(feature = "image")]
@@ -183,6 +186,64 @@ pub fn detect_multiple_in_file_with_hints(
     )
 }
 
+/**
+ * Loads an image from a file and decodes a single barcode from it with a {@link MultiFormatReader}.
+ * Unlike {@link #detect_in_file_with_hints}, the hints are used exactly as given.
+ *
+ * @throws IllegalArgumentException if the file cannot be opened or isn't a supported image
+ * @throws NotFoundException if no barcode could be decoded
+ */
+#[cfg(feature = "image")]
+pub fn decode_file(
+    path: impl AsRef<std::path::Path>,
+    hints: &DecodingHintDictionary,
+) -> Result<RXingResult, Exceptions> {
+    MultiFormatReader::default().decode_with_hints(&mut load_bitmap(path.as_ref())?, hints)
+}
+
+/**
+ * Loads an image from a file and decodes every barcode in it with a
+ * {@link GenericMultipleBarcodeReader}.
+ *
+ * @throws IllegalArgumentException if the file cannot be opened or isn't a supported image
+ * @throws NotFoundException if no barcode could be decoded
+ */
+#[cfg(feature = "image")]
+pub fn decode_file_multiple(
+    path: impl AsRef<std::path::Path>,
+    hints: &DecodingHintDictionary,
+) -> Result<Vec<RXingResult>, Exceptions> {
+    GenericMultipleBarcodeReader::new(MultiFormatReader::default())
+        .decode_multiple_with_hints(&mut load_bitmap(path.as_ref())?, hints)
+}
+
+#[cfg(feature = "image")]
+fn load_bitmap(path: &std::path::Path) -> Result<BinaryBitmap, Exceptions> {
+    let img = image::open(path).map_err(|e| {
+        Exceptions::IllegalArgumentException(Some(format!(
+            "cannot load image '{}': {e}",
+            path.display()
+        )))
+    })?;
+    BinaryBitmap::try_from(img)
+}
+
+/**
+ * Decodes an already loaded image of any color type, converted to luminance as by
+ * {@link BinaryBitmap#try_from(DynamicImage)}. Transparent areas are treated as if the image was
+ * drawn over a white background.
+ *
+ * @throws IllegalArgumentException if the image is empty
+ * @throws NotFoundException if no barcode could be decoded
+ */
+#[cfg(feature = "image")]
+pub fn decode_dynamic_image(
+    img: &image::DynamicImage,
+    hints: &DecodingHintDictionary,
+) -> Result<RXingResult, Exceptions> {
+    MultiFormatReader::default().decode_with_hints(&mut BinaryBitmap::try_from(img)?, hints)
+}
+
 pub fn detect_in_luma(
     luma: Vec<u8>,
     width: u32,
@@ -249,9 +310,113 @@ pub fn detect_multiple_in_luma_with_hints(
     )
 }
 
+/**
+ * Decodes the image with a {@link MultiFormatReader}, then parses the decoded text into a
+ * {@link ParsedClientResult}, the same as calling the two steps one after the other.
+ *
+ * @return the raw decode result together with its parsed form
+ * @throws NotFoundException if no barcode could be decoded
+ */
+pub fn decode_and_parse(
+    image: &mut BinaryBitmap,
+    hints: &DecodingHintDictionary,
+) -> Result<(RXingResult, ParsedClientResult), Exceptions> {
+    let result = MultiFormatReader::default().decode_with_hints(image, hints)?;
+    let parsed = parseRXingResult(&result);
+    Ok((result, parsed))
+}
+
+/**
+ * Decodes a 1D barcode, then encodes its value again with the writer for its format, giving a
+ * crisp copy of a noisy symbol to reprint. A GS1-128 symbol keeps its leading FNC1 character,
+ * and the ones separating its element strings when it was decoded with
+ * {@link DecodeHintType#ASSUME_GS1}. The reader only reports that a Code 128 symbol held FNC2
+ * or FNC3, not where, so they are put back at the start of the data; their meaning doesn't
+ * depend on their position.
+ *
+ * @return the decode result together with the newly encoded symbol, at its minimal size
+ * @throws NotFoundException if no 1D barcode could be decoded
+ * @throws IllegalArgumentException if there is no writer for the format of the barcode
+ */
+pub fn decode_then_reencode_1d(
+    image: &mut BinaryBitmap,
+    hints: &DecodingHintDictionary,
+) -> Result<(RXingResult, BitMatrix), Exceptions> {
+    let result = MultiFormatOneDReader::new(hints).decode_with_hints(image, hints)?;
+
+    let mut contents = result.getText().to_owned();
+    if result.getBarcodeFormat() == &BarcodeFormat::CODE_128
+        && matches!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::SYMBOLOGY_IDENTIFIER),
+            Some(RXingResultMetadataValue::SymbologyIdentifier(id)) if id == "]C1"
+        )
+    {
+        // Put back the FNC1 characters, which ASSUME_GS1 turns into "]C1" and separators
+        let data = contents.strip_prefix("]C1").unwrap_or(&contents);
+        contents = format!("\u{00f1}{}", data.replace('\u{1d}', "\u{00f1}"));
+    }
+    if result.getBarcodeFormat() == &BarcodeFormat::CODE_128 {
+        let metadata = result.getRXingResultMetadata();
+        let mut functions = String::new();
+        if let Some(RXingResultMetadataValue::Code128ReaderInitialization(true)) =
+            metadata.get(&RXingResultMetadataType::CODE_128_READER_INITIALIZATION)
+        {
+            functions.push('\u{00f3}');
+        }
+        if let Some(RXingResultMetadataValue::Code128MessageAppend(true)) =
+            metadata.get(&RXingResultMetadataType::CODE_128_MESSAGE_APPEND)
+        {
+            functions.push('\u{00f2}');
+        }
+        // After a leading FNC1, which has to stay first to mark GS1-128
+        let position = if contents.starts_with('\u{00f1}') {
+            '\u{00f1}'.len_utf8()
+        } else {
+            0
+        };
+        contents.insert_str(position, &functions);
+    }
+
+    let matrix = MultiFormatWriter.encode(&contents, result.getBarcodeFormat(), 0, 0)?;
+    Ok((result, matrix))
+}
+
+/**
+ * Encodes the contents with the writer for the format at its minimal size, then scales the
+ * matrix up by the module size of the config and adds its margin. The colors are not used.
+ *
+ * @throws IllegalArgumentException if the scale of the config is 0, or the contents can't be
+ * encoded in the format
+ */
+pub fn encode_to_matrix(
+    contents: &str,
+    format: &BarcodeFormat,
+    config: &RenderConfig,
+) -> Result<BitMatrix, Exceptions> {
+    config.validate()?;
+    MultiFormatWriter
+        .encode(contents, format, 0, 0)?
+        .render_matrix(config)
+}
+
 #[cfg(feature = "image")]
 pub fn save_image(file_name: &str, bit_matrix: &BitMatrix) -> Result<(), Exceptions> {
-    let image: image::DynamicImage = bit_matrix.into();
+    save_image_with_config(file_name, bit_matrix, &RenderConfig::default())
+}
+
+/**
+ * Saves the matrix as an image, in the format given by the file extension, rendered with the
+ * colors, module size and margin of the config.
+ */
+#[cfg(feature = "image")]
+pub fn save_image_with_config(
+    file_name: &str,
+    bit_matrix: &BitMatrix,
+    config: &RenderConfig,
+) -> Result<(), Exceptions> {
+    let image = bit_matrix.render_image(config)?;
     match image.save(file_name) {
         Ok(_) => Ok(()),
         Err(err) => Err(Exceptions::IllegalArgumentException(Some(format!(
@@ -262,7 +427,19 @@ pub fn save_image(file_name: &str, bit_matrix: &BitMatrix) -> Result<(), Excepti
 
 #[cfg(feature = "svg_write")]
 pub fn save_svg(file_name: &str, bit_matrix: &BitMatrix) -> Result<(), Exceptions> {
-    let svg: svg::Document = bit_matrix.into();
+    save_svg_with_config(file_name, bit_matrix, &RenderConfig::default())
+}
+
+/**
+ * Saves the matrix as SVG, rendered with the colors, module size and margin of the config.
+ */
+#[cfg(feature = "svg_write")]
+pub fn save_svg_with_config(
+    file_name: &str,
+    bit_matrix: &BitMatrix,
+    config: &RenderConfig,
+) -> Result<(), Exceptions> {
+    let svg = bit_matrix.render_svg(config)?;
 
     match svg::save(file_name, &svg) {
         Ok(_) => Ok(()),
@@ -274,6 +451,18 @@ pub fn save_svg(file_name: &str, bit_matrix: &BitMatrix) -> Result<(), Exception
 }
 
 pub fn save_file(file_name: &str, bit_matrix: &BitMatrix) -> Result<(), Exceptions> {
+    save_file_with_config(file_name, bit_matrix, &RenderConfig::default())
+}
+
+/**
+ * Saves the matrix as SVG, as an image or as text depending on the file extension, rendered with
+ * the module size and margin of the config. SVG and images use its colors as well.
+ */
+pub fn save_file_with_config(
+    file_name: &str,
+    bit_matrix: &BitMatrix,
+    config: &RenderConfig,
+) -> Result<(), Exceptions> {
     let path = PathBuf::from(file_name);
 
     #[allow(unused_variables)]
@@ -285,18 +474,19 @@ pub fn save_file(file_name: &str, bit_matrix: &BitMatrix) -> Result<(), Exceptio
 
     #[cfg(feature = "svg_write")]
     if ext == "svg" {
-        return save_svg(file_name, bit_matrix);
+        return save_svg_with_config(file_name, bit_matrix, config);
     }
 
     #[cfg(feature = "image")]
     if !ext.is_empty() && ext != "txt" {
-        return save_image(file_name, bit_matrix);
+        return save_image_with_config(file_name, bit_matrix, config);
     }
 
+    let rendered = bit_matrix.render_matrix(config)?;
     match || -> std::io::Result<_> {
         let file = std::fs::File::create(path)?;
         let mut output = std::io::BufWriter::new(file);
-        output.write_all(bit_matrix.to_string().as_bytes())?;
+        output.write_all(rendered.to_string().as_bytes())?;
         output.flush()?;
         Ok(())
     }() {
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use crate::{
+    client::result::{ParsedClientResult, ParsedRXingResult},
+    common::RenderConfig,
+    helpers,
+    oned::Code128Writer,
+    qrcode::QRCodeWriter,
+    shared_test_methods::bitmap_for,
+    BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue, Exceptions, Writer,
+};
+
+fn qr_bitmap(contents: &str) -> BinaryBitmap {
+    let matrix = QRCodeWriter
+        .encode(contents, &BarcodeFormat::QR_CODE, 200, 200)
+        .expect("encode");
+    bitmap_for(&matrix)
+}
+
+#[test]
+fn test_decode_and_parse_tel() {
+    let (result, parsed) =
+        helpers::decode_and_parse(&mut qr_bitmap("tel:+15551234567"), &HashMap::new())
+            .expect("decode");
+    assert_eq!("tel:+15551234567", result.getText());
+    assert_eq!(&BarcodeFormat::QR_CODE, result.getBarcodeFormat());
+    let ParsedClientResult::TelResult(tel) = parsed else {
+        panic!(
+            "expected a TelResult, got {}",
+            parsed.getDisplayRXingResult()
+        );
+    };
+    assert_eq!("+15551234567", tel.getNumber());
+    assert_eq!("tel:+15551234567", tel.getTelURI());
+}
+
+#[test]
+fn test_decode_and_parse_not_found() {
+    let mut blank = BinaryBitmap::from_luma8(vec![255; 100 * 100], 100, 100).expect("bitmap");
+    assert!(helpers::decode_and_parse(&mut blank, &HashMap::new()).is_err());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_decode_dynamic_image_rgba() {
+    use image::{DynamicImage, Rgba, RgbaImage};
+
+    let matrix = QRCodeWriter
+        .encode("rgba contents", &BarcodeFormat::QR_CODE, 200, 200)
+        .expect("encode");
+    // Dark blue modules over a fully transparent black background, with a half transparent
+    // black margin that must still come out light
+    let img = RgbaImage::from_fn(matrix.getWidth(), matrix.getHeight(), |x, y| {
+        if matrix.get(x, y) {
+            Rgba([0x10, 0x20, 0x80, 0xFF])
+        } else if x < 4 || y < 4 {
+            Rgba([0, 0, 0, 0x40])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    });
+
+    let result = helpers::decode_dynamic_image(&DynamicImage::ImageRgba8(img), &HashMap::new())
+        .expect("decode");
+    assert_eq!("rgba contents", result.getText());
+    assert_eq!(&BarcodeFormat::QR_CODE, result.getBarcodeFormat());
+}
+
+#[cfg(feature = "image")]
+const QR_FIXTURE: &str = "test_resources/blackbox/qrcode-1/1.png";
+
+#[cfg(feature = "image")]
+#[test]
+fn test_decode_file() {
+    let result = helpers::decode_file(QR_FIXTURE, &HashMap::new()).expect("decode");
+    assert_eq!(
+        "MEBKM:URL:http\\://en.wikipedia.org/wiki/Main_Page;;",
+        result.getText()
+    );
+    assert_eq!(&BarcodeFormat::QR_CODE, result.getBarcodeFormat());
+
+    let results = helpers::decode_file_multiple(QR_FIXTURE, &HashMap::new()).expect("decode");
+    assert_eq!(1, results.len());
+    assert_eq!(result.getText(), results[0].getText());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_decode_file_missing() {
+    let path = "test_resources/blackbox/qrcode-1/missing.png";
+    assert!(matches!(
+        helpers::decode_file(path, &HashMap::new()),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        helpers::decode_file_multiple(path, &HashMap::new()),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+}
+
+fn noisy_code_128_bitmap(contents: &str) -> BinaryBitmap {
+    let matrix = Code128Writer
+        .encode(contents, &BarcodeFormat::CODE_128, 0, 0)
+        .expect("encode");
+    // Three pixels per module and 40 rows, lit unevenly, with speckles and a quiet zone of 10
+    // modules on either side
+    let width = (matrix.getWidth() + 20) * 3;
+    let height = 40;
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let module = (x / 3)
+                .checked_sub(10)
+                .filter(|module| *module < matrix.getWidth());
+            let shade = (60 * x / width) as u8;
+            pixels.push(if module.is_some_and(|module| matrix.get(module, 0)) {
+                let speckle = (x * 7 + y * 13) % 17 == 0;
+                20 + shade + if speckle { 20 } else { 0 }
+            } else {
+                170 + shade / 2
+            });
+        }
+    }
+    BinaryBitmap::from_luma8(pixels, width, height).expect("bitmap")
+}
+
+#[test]
+fn test_decode_then_reencode_1d() {
+    let (result, matrix) =
+        helpers::decode_then_reencode_1d(&mut noisy_code_128_bitmap("RXING-1275"), &HashMap::new())
+            .expect("decode");
+    assert_eq!("RXING-1275", result.getText());
+    assert_eq!(&BarcodeFormat::CODE_128, result.getBarcodeFormat());
+    let clean = Code128Writer
+        .encode("RXING-1275", &BarcodeFormat::CODE_128, 0, 0)
+        .expect("encode");
+    assert_eq!(clean, matrix);
+}
+
+#[test]
+fn test_decode_then_reencode_1d_keeps_fnc1() {
+    let contents = "\u{00f1}0100012345678905\u{00f1}10ABC";
+    let clean = Code128Writer
+        .encode(contents, &BarcodeFormat::CODE_128, 0, 0)
+        .expect("encode");
+    let hints = HashMap::from([(DecodeHintType::ASSUME_GS1, DecodeHintValue::AssumeGs1(true))]);
+    let (result, matrix) =
+        helpers::decode_then_reencode_1d(&mut noisy_code_128_bitmap(contents), &hints)
+            .expect("decode");
+    assert_eq!("]C10100012345678905\u{1d}10ABC", result.getText());
+    assert_eq!(clean, matrix);
+}
+
+#[test]
+fn test_encode_to_matrix_scales_with_margin() {
+    let clean = QRCodeWriter
+        .encode("scaled", &BarcodeFormat::QR_CODE, 0, 0)
+        .expect("encode");
+    let config = RenderConfig {
+        scale: 3,
+        margin: 2,
+        ..RenderConfig::default()
+    };
+    let matrix =
+        helpers::encode_to_matrix("scaled", &BarcodeFormat::QR_CODE, &config).expect("encode");
+    assert_eq!((clean.getWidth() + 4) * 3, matrix.getWidth());
+    assert_eq!(clean.render_matrix(&config).expect("scale"), matrix);
+    // The first finder pattern module, after the margin and the quiet zone of 4
+    assert!(matrix.get((2 + 4) * 3, (2 + 4) * 3));
+
+    let config = RenderConfig { scale: 0, ..config };
+    assert!(helpers::encode_to_matrix("scaled", &BarcodeFormat::QR_CODE, &config).is_err());
+}
+
+#[test]
+fn test_save_text_file_with_config() {
+    let matrix = QRCodeWriter
+        .encode("saved", &BarcodeFormat::QR_CODE, 0, 0)
+        .expect("encode");
+    let config = RenderConfig {
+        scale: 2,
+        margin: 1,
+        ..RenderConfig::default()
+    };
+    let path = std::env::temp_dir().join("rxing_save_text_file_with_config.txt");
+    let file_name = path.to_str().expect("path");
+    helpers::save_file_with_config(file_name, &matrix, &config).expect("save");
+    let saved = std::fs::read_to_string(&path).expect("read");
+    std::fs::remove_file(&path).expect("remove");
+    // The text is scaled up and has the margin, like the images
+    assert_eq!(
+        matrix.render_matrix(&config).expect("scale").to_string(),
+        saved
+    );
+}
+
+#[test]
+fn test_decode_then_reencode_1d_keeps_fnc2_and_fnc3() {
+    for contents in [
+        "\u{00f3}\u{00f2}RXING",
+        "\u{00f2}RXING",
+        "\u{00f1}\u{00f3}0100012345678905",
+    ] {
+        let clean = Code128Writer
+            .encode(contents, &BarcodeFormat::CODE_128, 0, 0)
+            .expect("encode");
+        let (_, matrix) =
+            helpers::decode_then_reencode_1d(&mut noisy_code_128_bitmap(contents), &HashMap::new())
+                .expect("decode");
+        assert_eq!(clean, matrix, "{contents:?}");
+    }
+}
@@ -10,7 +10,7 @@ pub mod qrcode;
 
 use std::{collections::HashMap, rc::Rc};
 
-pub use exceptions::Exceptions;
+pub use exceptions::{CapacityExceeded, Exceptions};
 
 #[cfg(feature = "image")]
 mod buffered_image_luminance_source;
@@ -24,6 +24,36 @@ mod PlanarYUVLuminanceSourceTestCase;
 #[cfg(test)]
 mod rgb_luminance_source_test_case;
 
+#[cfg(test)]
+mod binary_bitmap_test_case;
+
+#[cfg(test)]
+mod helpers_test_case;
+
+#[cfg(test)]
+mod decode_hints_builder_test_case;
+
+#[cfg(test)]
+mod gamma_luminance_source_test_case;
+
+#[cfg(test)]
+mod luma_luma_source_test_case;
+
+#[cfg(test)]
+mod luminance_source_test_case;
+
+#[cfg(test)]
+mod multi_format_reader_test_case;
+
+#[cfg(test)]
+mod video_decoder_test_case;
+
+#[cfg(test)]
+mod multi_format_writer_test_case;
+
+#[cfg(test)]
+mod shared_test_methods;
+
 pub type EncodingHintDictionary = HashMap<EncodeHintType, EncodeHintValue>;
 pub type DecodingHintDictionary = HashMap<DecodeHintType, DecodeHintValue>;
 pub type MetadataDictionary = HashMap<RXingResultMetadataType, RXingResultMetadataValue>;
@@ -45,6 +75,9 @@ pub type RXingResultPointCallback = Rc<dyn Fn(&dyn ResultPoint)>;
 mod decode_hints;
 pub use decode_hints::*;
 
+mod decode_hints_builder;
+pub use decode_hints_builder::*;
+
 mod writer;
 pub use writer::*;
 
@@ -94,12 +127,18 @@ pub use multi_format_writer::*;
 mod multi_format_reader;
 pub use multi_format_reader::*;
 
+mod video_decoder;
+pub use video_decoder::*;
+
 // Simple methods to help detect barcodes in common situations
 pub mod helpers;
 
 mod luma_luma_source;
 pub use luma_luma_source::*;
 
+mod gamma_luminance_source;
+pub use gamma_luminance_source::*;
+
 #[cfg(feature = "svg_read")]
 mod svg_luminance_source;
 #[cfg(feature = "svg_read")]
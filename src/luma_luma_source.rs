@@ -1,6 +1,6 @@
 use crate::LuminanceSource;
 
-/// A simple luma8 source for bytes, supports cropping but not rotation
+/// A simple luma8 source for bytes, supports cropping and rotation by 90 degrees
 pub struct Luma8LuminanceSource {
     /// image dimension in form (x,y)
     dimensions: (u32, u32),
@@ -15,34 +15,27 @@ pub struct Luma8LuminanceSource {
 }
 impl LuminanceSource for Luma8LuminanceSource {
     fn getRow(&self, y: usize) -> Vec<u8> {
-        self.data
-            .chunks_exact(self.original_dimension.0 as usize)
-            .skip(y + self.origin.1 as usize)
-            .take(1)
-            .flatten()
-            .skip(self.origin.0 as usize)
-            .take(self.dimensions.0 as usize)
-            .map(|byte| Self::invert_if_should(*byte, self.inverted))
-            .collect()
+        let row = self.row(y);
+        if self.inverted {
+            row.iter()
+                .map(|byte| Self::invert_if_should(*byte, true))
+                .collect()
+        } else {
+            row.to_vec()
+        }
     }
 
     fn getMatrix(&self) -> Vec<u8> {
-        self.data
-            .iter()
-            .skip((self.original_dimension.0 * self.origin.1) as usize)
-            .take((self.dimensions.1 * self.original_dimension.0) as usize)
-            .collect::<Vec<&u8>>()
-            .chunks_exact(self.original_dimension.0 as usize)
-            .into_iter()
-            .flat_map(|f| {
-                f.iter()
-                    .skip((self.origin.0) as usize)
-                    .take(self.getWidth())
-                    .copied()
-            }) // flatten this all out
-            .copied() // copy it over so that it's u8
-            .map(|byte| Self::invert_if_should(byte, self.inverted))
-            .collect() // collect into a vec
+        let mut matrix = Vec::with_capacity(self.getWidth() * self.getHeight());
+        for y in 0..self.getHeight() {
+            let row = self.row(y);
+            if self.inverted {
+                matrix.extend(row.iter().map(|byte| Self::invert_if_should(*byte, true)));
+            } else {
+                matrix.extend_from_slice(row);
+            }
+        }
+        matrix
     }
 
     fn getWidth(&self) -> usize {
@@ -68,9 +61,15 @@ impl LuminanceSource for Luma8LuminanceSource {
         width: usize,
         height: usize,
     ) -> Result<Box<dyn LuminanceSource>, crate::Exceptions> {
+        if left + width > self.getWidth() || top + height > self.getHeight() {
+            return Err(crate::Exceptions::IllegalArgumentException(Some(
+                "Crop rectangle does not fit within image data.".to_owned(),
+            )));
+        }
+        // The crop is relative to this, possibly already cropped, source
         Ok(Box::new(Self {
             dimensions: (width as u32, height as u32),
-            origin: (left as u32, top as u32),
+            origin: (self.origin.0 + left as u32, self.origin.1 + top as u32),
             data: self.data.clone(),
             inverted: self.inverted,
             original_dimension: self.original_dimension,
@@ -82,16 +81,26 @@ impl LuminanceSource for Luma8LuminanceSource {
     }
 
     fn rotateCounterClockwise(&self) -> Result<Box<dyn LuminanceSource>, crate::Exceptions> {
-        let mut new_matrix = Self {
-            dimensions: self.dimensions,
-            origin: self.origin,
-            data: self.data.clone(),
+        let width = self.getWidth();
+        let height = self.getHeight();
+        let mut data = Vec::with_capacity(width * height);
+        // The new row y is the old column (width - 1 - y), read top to bottom
+        for x in (0..width).rev() {
+            for y in 0..height {
+                data.push(
+                    self.data[(y + self.origin.1 as usize) * self.original_dimension.0 as usize
+                        + x
+                        + self.origin.0 as usize],
+                );
+            }
+        }
+        Ok(Box::new(Self {
+            dimensions: (height as u32, width as u32),
+            origin: (0, 0),
+            data,
             inverted: self.inverted,
-            original_dimension: self.original_dimension,
-        };
-        new_matrix.transpose();
-        new_matrix.reverseColumns();
-        Ok(Box::new(new_matrix))
+            original_dimension: (height as u32, width as u32),
+        }))
     }
 
     fn rotateCounterClockwise45(&self) -> Result<Box<dyn LuminanceSource>, crate::Exceptions> {
@@ -101,38 +110,6 @@ impl LuminanceSource for Luma8LuminanceSource {
     }
 }
 
-impl Luma8LuminanceSource {
-    fn reverseColumns(&mut self) {
-        for i in 0..self.getWidth() {
-            // for (int i = 0; i < C; i++)
-            let mut j = 0;
-            let mut k = self.getWidth() - 1;
-            while j < k {
-                // for (int j = 0, k = C - 1; j < k; j++, k--)
-                let offset_a = (self.getWidth() * i) + j;
-                let offset_b = (self.getWidth() * i) + k;
-                self.data.swap(offset_a, offset_b);
-                // swap(arr[j][i], arr[k][i]);
-                j += 1;
-                k -= 1;
-            }
-        }
-    }
-
-    fn transpose(&mut self) {
-        for i in 0..self.getHeight() {
-            // for (int i = 0; i < R; i++)
-            for j in i..self.getWidth() {
-                // for (int j = i; j < C; j++)
-                let offset_a = (self.getWidth() * i) + j;
-                let offset_b = (self.getWidth() * j) + i;
-                self.data.swap(offset_a, offset_b);
-                // swap(arr[i][j], arr[j][i]);
-            }
-        }
-    }
-}
-
 impl Luma8LuminanceSource {
     pub fn new(source: Vec<u8>, width: u32, height: u32) -> Self {
         Self {
@@ -144,6 +121,19 @@ impl Luma8LuminanceSource {
         }
     }
 
+    /**
+     * The pixels of row y of the (possibly cropped) image, before any inversion. Empty if y is
+     * outside of the image.
+     */
+    fn row(&self, y: usize) -> &[u8] {
+        if y >= self.getHeight() {
+            return &[];
+        }
+        let start = (y + self.origin.1 as usize) * self.original_dimension.0 as usize
+            + self.origin.0 as usize;
+        &self.data[start..start + self.dimensions.0 as usize]
+    }
+
     #[inline(always)]
     fn invert_if_should(byte: u8, invert: bool) -> u8 {
         if invert {
@@ -0,0 +1,49 @@
+use crate::{Luma8LuminanceSource, LuminanceSource};
+
+#[test]
+fn test_rotate_counter_clockwise() {
+    // 3x2:
+    // 1 2 3
+    // 4 5 6
+    let source = Luma8LuminanceSource::new(vec![1, 2, 3, 4, 5, 6], 3, 2);
+    assert!(source.isRotateSupported());
+    let rotated = source.rotateCounterClockwise().expect("rotate");
+    assert_eq!(2, rotated.getWidth());
+    assert_eq!(3, rotated.getHeight());
+    assert_eq!(vec![3, 6, 2, 5, 1, 4], rotated.getMatrix());
+    assert_eq!(vec![2, 5], rotated.getRow(1));
+}
+
+#[test]
+fn test_rotate_cropped() {
+    let source = Luma8LuminanceSource::new((0..16).collect(), 4, 4);
+    let cropped = source.crop(1, 1, 3, 2).expect("crop");
+    // 5 6 7
+    // 9 10 11
+    let rotated = cropped.rotateCounterClockwise().expect("rotate");
+    assert_eq!(vec![7, 11, 6, 10, 5, 9], rotated.getMatrix());
+}
+
+#[test]
+fn test_row_outside_of_image_is_empty() {
+    let source = Luma8LuminanceSource::new((0..16).collect(), 4, 4);
+    assert!(source.getRow(4).is_empty());
+    let cropped = source.crop(1, 1, 3, 2).expect("crop");
+    assert_eq!(vec![9, 10, 11], cropped.getRow(1));
+    assert!(cropped.getRow(2).is_empty());
+}
+
+#[test]
+fn test_crop_cropped() {
+    let source = Luma8LuminanceSource::new((0..16).collect(), 4, 4);
+    let cropped = source.crop(1, 1, 3, 3).expect("crop");
+    // 5 6 7
+    // 9 10 11
+    // 13 14 15
+    let recropped = cropped.crop(1, 1, 2, 2).expect("crop");
+    assert_eq!(vec![10, 11, 14, 15], recropped.getMatrix());
+    assert_eq!(vec![14, 15], recropped.getRow(1));
+
+    assert!(cropped.crop(2, 0, 2, 1).is_err());
+    assert!(cropped.crop(0, 1, 1, 3).is_err());
+}
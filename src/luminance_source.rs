@@ -16,7 +16,7 @@
 
 //package com.google.zxing;
 
-use crate::Exceptions;
+use crate::{Exceptions, GammaLuminanceSource};
 
 /**
  * The purpose of this class hierarchy is to abstract different bitmap implementations across
@@ -171,3 +171,89 @@ pub trait LuminanceSource {
       return result.toString();
     }*/
 }
+
+/**
+ * Chainable versions of the {@link LuminanceSource} transformations, so that composing them reads
+ * left to right:
+ *
+ * ```
+ * use rxing::{Luma8LuminanceSource, LuminanceSource, LuminanceSourceExt};
+ *
+ * let source = Luma8LuminanceSource::new(vec![0, 64, 128, 192, 255, 232], 3, 2);
+ * let composed = source.cropped(1, 0, 2, 2)?.inverted().gamma(2.2);
+ * assert_eq!(vec![135, 55, 0, 1], composed.getMatrix());
+ * # Ok::<(), rxing::Exceptions>(())
+ * ```
+ *
+ * Each step consumes the source and returns the result boxed, ready for the next one.
+ */
+pub trait LuminanceSourceExt: Sized {
+    /**
+     * @return this source as a trait object
+     */
+    fn boxed(self) -> Box<dyn LuminanceSource>;
+
+    /**
+     * @see LuminanceSource#crop(int, int, int, int)
+     */
+    fn cropped(
+        self,
+        left: usize,
+        top: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Box<dyn LuminanceSource>, Exceptions> {
+        self.boxed().crop(left, top, width, height)
+    }
+
+    /**
+     * @see LuminanceSource#invert()
+     */
+    fn inverted(self) -> Box<dyn LuminanceSource> {
+        let mut source = self.boxed();
+        source.invert();
+        source
+    }
+
+    /**
+     * Rotates counterclockwise by 45 degrees or any multiple of 90 degrees.
+     *
+     * @throws IllegalArgumentException for any other angle
+     * @throws UnsupportedOperationException if the source doesn't support the rotation
+     */
+    fn rotated(self, degrees: u32) -> Result<Box<dyn LuminanceSource>, Exceptions> {
+        let source = self.boxed();
+        match degrees % 360 {
+            0 => Ok(source),
+            45 => source.rotateCounterClockwise45(),
+            90 => source.rotateCounterClockwise(),
+            180 => source.rotateCounterClockwise()?.rotateCounterClockwise(),
+            270 => source
+                .rotateCounterClockwise()?
+                .rotateCounterClockwise()?
+                .rotateCounterClockwise(),
+            _ => Err(Exceptions::IllegalArgumentException(Some(format!(
+                "cannot rotate by {degrees} degrees"
+            )))),
+        }
+    }
+
+    /**
+     * @see GammaLuminanceSource#new(LuminanceSource, float)
+     */
+    fn gamma(self, gamma: f32) -> Box<dyn LuminanceSource> {
+        Box::new(GammaLuminanceSource::new(self.boxed(), gamma))
+    }
+}
+
+impl<T: LuminanceSource + 'static> LuminanceSourceExt for T {
+    fn boxed(self) -> Box<dyn LuminanceSource> {
+        Box::new(self)
+    }
+}
+
+impl LuminanceSourceExt for Box<dyn LuminanceSource> {
+    fn boxed(self) -> Box<dyn LuminanceSource> {
+        self
+    }
+}
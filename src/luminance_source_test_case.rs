@@ -0,0 +1,67 @@
+use crate::{
+    Exceptions, GammaLuminanceSource, Luma8LuminanceSource, LuminanceSource, LuminanceSourceExt,
+};
+
+// 0   64  128
+// 192 255 232
+fn source() -> Luma8LuminanceSource {
+    Luma8LuminanceSource::new(vec![0, 64, 128, 192, 255, 232], 3, 2)
+}
+
+#[test]
+fn test_chain_matches_nested_decorators() {
+    let chained = source()
+        .cropped(0, 0, 2, 2)
+        .expect("crop")
+        .inverted()
+        .gamma(2.2);
+    assert_eq!(vec![255, 135, 12, 0], chained.getMatrix());
+    assert_eq!(vec![12, 0], chained.getRow(1));
+
+    let mut cropped = source().crop(0, 0, 2, 2).expect("crop");
+    cropped.invert();
+    let nested = GammaLuminanceSource::new(cropped, 2.2);
+    assert_eq!(nested.getMatrix(), chained.getMatrix());
+}
+
+#[test]
+fn test_rotated() {
+    let rotated = source().rotated(90).expect("rotate");
+    assert_eq!((2, 3), (rotated.getWidth(), rotated.getHeight()));
+    assert_eq!(vec![128, 232, 64, 255, 0, 192], rotated.getMatrix());
+
+    let upside_down = source().rotated(180).expect("rotate");
+    assert_eq!(vec![232, 255, 192, 128, 64, 0], upside_down.getMatrix());
+    assert_eq!(
+        upside_down.getMatrix(),
+        source()
+            .rotated(90)
+            .and_then(|s| s.rotated(90))
+            .expect("rotate")
+            .getMatrix()
+    );
+    assert_eq!(
+        source().getMatrix(),
+        source().rotated(360).expect("rotate").getMatrix()
+    );
+
+    assert!(matches!(
+        source().rotated(30),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        source().rotated(45),
+        Err(Exceptions::UnsupportedOperationException(_))
+    ));
+}
+
+#[test]
+fn test_rotate_then_crop() {
+    let composed = source()
+        .inverted()
+        .rotated(270)
+        .and_then(|s| s.cropped(0, 1, 2, 2))
+        .expect("compose");
+    // Rotated clockwise: 192 0 / 255 64 / 232 128, inverted
+    assert_eq!(vec![0, 191, 23, 127], composed.getMatrix());
+}
@@ -17,8 +17,8 @@
 use std::collections::HashMap;
 
 use crate::{
-    BinaryBitmap, DecodingHintDictionary, Exceptions, RXingResult, RXingResultPoint, Reader,
-    ResultPoint,
+    BinaryBitmap, DecodeHintType, DecodeHintValue, DecodingHintDictionary, Exceptions, RXingResult,
+    RXingResultPoint, Reader, ResultPoint,
 };
 
 use super::MultipleBarcodeReader;
@@ -78,7 +78,7 @@ impl<T: Reader> GenericMultipleBarcodeReader<T> {
         yOffset: u32,
         currentDepth: u32,
     ) {
-        if currentDepth > Self::MAX_DEPTH {
+        if currentDepth > Self::MAX_DEPTH || results.len() >= Self::maxResults(hints) {
             return;
         }
 
@@ -86,6 +86,10 @@ impl<T: Reader> GenericMultipleBarcodeReader<T> {
         let Ok(result) = self.0.decode_with_hints(image, hints) else {
             return;
         };
+        // A located barcode which couldn't be decoded isn't one of the results
+        if result.isPartial() {
+            return;
+        }
 
         let mut alreadyFound = false;
         for existingRXingResult in results.iter() {
@@ -177,6 +181,19 @@ impl<T: Reader> GenericMultipleBarcodeReader<T> {
         }
     }
 
+    /**
+     * @return the most results to return, see {@link DecodeHintType#MAX_RESULTS}
+     */
+    fn maxResults(hints: &DecodingHintDictionary) -> usize {
+        if let Some(DecodeHintValue::MaxResults(maxResults)) =
+            hints.get(&DecodeHintType::MAX_RESULTS)
+        {
+            *maxResults as usize
+        } else {
+            usize::MAX
+        }
+    }
+
     fn translateRXingResultPoints(result: RXingResult, xOffset: u32, yOffset: u32) -> RXingResult {
         let oldRXingResultPoints = result.getRXingResultPoints();
         if oldRXingResultPoints.is_empty() {
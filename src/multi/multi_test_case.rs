@@ -14,14 +14,21 @@
  * limitations under the License.
  */
 
-use std::{collections::HashSet, path::PathBuf, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    rc::Rc,
+};
 
 use crate::{
-    common::HybridBinarizer, BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource,
-    MultiFormatReader,
+    common::HybridBinarizer,
+    oned::{Code128Reader, Code128Writer, Code39Writer},
+    shared_test_methods::render_bitmap,
+    BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintType, DecodeHintValue,
+    MultiFormatReader, Reader, ResultPoint, Writer,
 };
 
-use super::{GenericMultipleBarcodeReader, MultipleBarcodeReader};
+use super::{ByQuadrantReader, GenericMultipleBarcodeReader, MultipleBarcodeReader};
 
 /**
  * Tests {@link MultipleBarcodeReader}.
@@ -93,3 +100,85 @@ fn testMultiQR() {
         .insert("You get to CREATE OUR JOURNAL PROMPT FOR THE DAY!  Yay!  Way to go!  ".to_owned());
     assert_eq!(expectedContents, barcodeContents);
 }
+
+#[test]
+fn testMultiCode39SideBySide() {
+    // Two Code 39 barcodes next to each other, with a wide gap between them
+    let left = Code39Writer
+        .encode("LEFT", &BarcodeFormat::CODE_39, 0, 0)
+        .expect("encode");
+    let right = Code39Writer
+        .encode("RIGHT", &BarcodeFormat::CODE_39, 0, 0)
+        .expect("encode");
+    let scale = 2;
+    let gap = 150;
+    let rightStart = (left.getWidth() * scale + gap) as usize;
+    let width = rightStart + (right.getWidth() * scale) as usize;
+    let mut bitmap = render_bitmap(width as u32, 60, |x, _| {
+        let x = x as usize;
+        if x < rightStart {
+            let bx = x as u32 / scale;
+            bx < left.getWidth() && left.get(bx, 0)
+        } else {
+            right.get((x - rightStart) as u32 / scale, 0)
+        }
+    });
+
+    let mut reader = GenericMultipleBarcodeReader::new(MultiFormatReader::default());
+    let results = reader
+        .decode_multiple(&mut bitmap)
+        .expect("must decode multi");
+    assert_eq!(2, results.len());
+    assert_eq!("LEFT", results[0].getText());
+    assert_eq!("RIGHT", results[1].getText());
+    for result in &results {
+        assert_eq!(&BarcodeFormat::CODE_39, result.getBarcodeFormat());
+    }
+    // The points of the second barcode were found in a crop, and are moved back into the image
+    for point in results[1].getRXingResultPoints() {
+        assert!(point.getX() >= rightStart as f32);
+        assert!(point.getX() <= width as f32);
+    }
+
+    let hints = HashMap::from([(DecodeHintType::MAX_RESULTS, DecodeHintValue::MaxResults(1))]);
+    let results = reader
+        .decode_multiple_with_hints(&mut bitmap, &hints)
+        .expect("must decode multi");
+    assert_eq!(1, results.len());
+    assert_eq!("LEFT", results[0].getText());
+}
+
+#[test]
+fn testByQuadrantPassesOverFailedChecksum() {
+    // The symbols of "AB", but the check symbol of "AC", which is just as wide
+    let row = |contents: &str| {
+        Code128Writer
+            .encode(contents, &BarcodeFormat::CODE_128, 0, 1)
+            .expect("encode")
+    };
+    let (good, other) = (row("AB"), row("AC"));
+    let width = good.getWidth();
+    let stopEnd = (0..width).rev().find(|x| good.get(*x, 0)).expect("bars");
+    // The check symbol is the 11 modules before the 13 of the stop pattern
+    let checkSymbol = stopEnd - 23..=stopEnd - 13;
+    // The failing symbol fills the top left quadrant and the good one the top right
+    let quadrantWidth = (width + 20) * 3;
+    let mut bitmap = render_bitmap(quadrantWidth * 2, 120, |x, y| {
+        let failing = x < quadrantWidth;
+        let x = (x % quadrantWidth / 3).wrapping_sub(10);
+        if y >= 60 || x >= width {
+            false
+        } else if failing && checkSymbol.contains(&x) {
+            other.get(x, 0)
+        } else {
+            good.get(x, 0)
+        }
+    });
+
+    let mut reader = ByQuadrantReader::new(Code128Reader::default());
+    let result = reader.decode(&mut bitmap).expect("must decode");
+    assert_eq!("AB", result.getText());
+    for point in result.getRXingResultPoints() {
+        assert!(point.getX() >= quadrantWidth as f32);
+    }
+}
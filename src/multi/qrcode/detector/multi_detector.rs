@@ -71,3 +71,117 @@ impl<'a> MultiDetector<'_> {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod multi_detector_test_case {
+    use std::collections::HashMap;
+
+    use crate::{
+        common::{BitMatrix, PerspectiveTransform},
+        multi::{
+            qrcode::{detector::MultiFinderPatternFinder, QRCodeMultiReader},
+            MultipleBarcodeReader,
+        },
+        qrcode::{QRCodeReader, QRCodeWriter},
+        shared_test_methods::bitmap_for,
+        BarcodeFormat, EncodeHintType, EncodeHintValue, Reader, Writer,
+    };
+
+    use super::MultiDetector;
+
+    const SCALE: u32 = 4;
+
+    fn encode(contents: &str) -> BitMatrix {
+        let hints = HashMap::from([(
+            EncodeHintType::MARGIN,
+            EncodeHintValue::Margin("0".to_owned()),
+        )]);
+        QRCodeWriter
+            .encode_with_hints(contents, &BarcodeFormat::QR_CODE, 0, 0, &hints)
+            .expect("encode")
+    }
+
+    fn draw(image: &mut BitMatrix, symbol: &BitMatrix, left: u32, top: u32) {
+        for y in 0..symbol.getHeight() * SCALE {
+            for x in 0..symbol.getWidth() * SCALE {
+                if symbol.get(x / SCALE, y / SCALE) {
+                    image.set(left + x, top + y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn testDetectTwoCodes() {
+        let first = encode("first code");
+        let second = encode("second code");
+        let size = first.getWidth() * SCALE;
+        let mut image = BitMatrix::new(size * 2 + 30 * SCALE, size + 16 * SCALE).unwrap();
+        draw(&mut image, &first, 8 * SCALE, 8 * SCALE);
+        draw(&mut image, &second, size + 22 * SCALE, 8 * SCALE);
+
+        let results = MultiDetector::new(&image)
+            .detectMulti(&HashMap::new())
+            .expect("detect");
+        assert_eq!(2, results.len());
+    }
+
+    #[test]
+    fn testDetectSkewedSingleCode() {
+        // The code as seen turned about 40 degrees away around its vertical axis: the right edge
+        // is farther off, so it's shorter and the top and bottom edges slant towards it
+        let contents = "a code seen at an angle, with an alignment pattern";
+        let symbol = encode(contents);
+        let modules = symbol.getWidth() as f32;
+        let toModules = PerspectiveTransform::quadrilateralToQuadrilateral(
+            40.0, 40.0, 360.0, 70.0, 360.0, 450.0, 40.0, 480.0, 0.0, 0.0, modules, 0.0, modules,
+            modules, 0.0, modules,
+        );
+        let mut image = BitMatrix::new(520, 520).unwrap();
+        for y in 0..image.getHeight() {
+            for x in 0..image.getWidth() {
+                let mut point = [x as f32 + 0.5, y as f32 + 0.5];
+                toModules.transform_points_single(&mut point);
+                let [mx, my] = point;
+                if mx >= 0.0 && my >= 0.0 && symbol.try_get(mx as u32, my as u32) == Some(true) {
+                    image.set(x, y);
+                }
+            }
+        }
+
+        let single = QRCodeReader
+            .decode(&mut bitmap_for(&image))
+            .expect("decode");
+        assert_eq!(contents, single.getText());
+        let results = QRCodeMultiReader::new()
+            .decode_multiple(&mut bitmap_for(&image))
+            .expect("decode");
+        assert_eq!(1, results.len());
+        assert_eq!(contents, results[0].getText());
+    }
+
+    #[test]
+    fn testRejectFinderPatternsInLine() {
+        // Three finder patterns in a row are no QR Code, as there is no right angle between them
+        let mut finder = BitMatrix::new(7, 7).unwrap();
+        for y in 0..7 {
+            for x in 0..7 {
+                if x == 0
+                    || x == 6
+                    || y == 0
+                    || y == 6
+                    || (2..5).contains(&x) && (2..5).contains(&y)
+                {
+                    finder.set(x, y);
+                }
+            }
+        }
+        let mut image = BitMatrix::new(80 * SCALE, 20 * SCALE).unwrap();
+        for i in 0..3 {
+            draw(&mut image, &finder, (6 + i * 28) * SCALE, 6 * SCALE);
+        }
+
+        let mut finder = MultiFinderPatternFinder::new(&image, None);
+        assert!(finder.findMulti(&HashMap::new()).is_err());
+    }
+}
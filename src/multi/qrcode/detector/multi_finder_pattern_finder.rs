@@ -20,7 +20,7 @@ use crate::{
     common::BitMatrix,
     qrcode::detector::{FinderPattern, FinderPatternFinder, FinderPatternInfo},
     result_point_utils, DecodeHintType, DecodingHintDictionary, Exceptions,
-    RXingResultPointCallback,
+    RXingResultPointCallback, ResultPoint,
 };
 
 // max. legal count of modules per QR code edge (177)
@@ -42,6 +42,10 @@ const DIFF_MODSIZE_CUTOFF_PERCENT: f32 = 0.05_f32;
  */
 const DIFF_MODSIZE_CUTOFF: f32 = 0.5_f32;
 
+// min. area of the triangle between the finder patterns of a lone code, relative to the square
+// of its longest edge, below which they are taken to be in line
+const MIN_LAYOUT_AREA_RATIO: f32 = 0.1_f32;
+
 /**
  * <p>This class attempts to find finder patterns in a QR Code. Finder patterns are the square
  * markers at three corners of a QR Code.</p>
@@ -99,11 +103,16 @@ impl MultiFinderPatternFinder {
          * Begin HE modifications to safely detect multiple codes of equal size
          */
         if size == 3 {
-            return Ok(vec![[
-                possibleCenters[0],
-                possibleCenters[1],
-                possibleCenters[2],
-            ]]);
+            // A lone triple is taken as it is, as the edges and the angle of a code seen at an
+            // angle are well beyond what isCodeLayout allows; only patterns in line are rejected
+            let mut test = [possibleCenters[0], possibleCenters[1], possibleCenters[2]];
+            if Self::isCollinear(&test) {
+                return Err(Exceptions::NotFoundException(Some(
+                    "Finder patterns don't form a QR Code".to_owned(),
+                )));
+            }
+            result_point_utils::orderBestPatterns(&mut test);
+            return Ok(vec![test]);
         }
 
         // Sort by estimated module size to speed up the upcoming checks
@@ -171,38 +180,9 @@ impl MultiFinderPatternFinder {
                     let mut test = [*p1, *p2, *p3];
                     result_point_utils::orderBestPatterns(&mut test);
 
-                    // Calculate the distances: a = topleft-bottomleft, b=topleft-topright, c = diagonal
-                    let info = FinderPatternInfo::new(test);
-                    let dA = result_point_utils::distance(info.getTopLeft(), info.getBottomLeft());
-                    let dC = result_point_utils::distance(info.getTopRight(), info.getBottomLeft());
-                    let dB = result_point_utils::distance(info.getTopLeft(), info.getTopRight());
-
-                    // Check the sizes
-                    let estimatedModuleCount = (dA + dB) / (p1.getEstimatedModuleSize() * 2.0);
-                    if !(MIN_MODULE_COUNT_PER_EDGE..=MAX_MODULE_COUNT_PER_EDGE)
-                        .contains(&estimatedModuleCount)
-                    {
-                        continue;
-                    }
-
-                    // Calculate the difference of the edge lengths in percent
-                    let vABBC = ((dA - dB) / dA.min(dB)).abs();
-                    if vABBC >= 0.1 {
-                        continue;
+                    if Self::isCodeLayout(&test, p1.getEstimatedModuleSize()) {
+                        results.push(test);
                     }
-
-                    // Calculate the diagonal length by assuming a 90° angle at topleft
-                    let dCpy =
-                        ((dA as f64) * (dA as f64) + (dB as f64) * (dB as f64)).sqrt() as f32;
-                    // Compare to the real distance in %
-                    let vPyC = ((dC - dCpy) / dC.min(dCpy)).abs();
-
-                    if vPyC >= 0.1 {
-                        continue;
-                    }
-
-                    // All tests passed!
-                    results.push(test);
                 }
             }
         }
@@ -214,6 +194,59 @@ impl MultiFinderPatternFinder {
         }
     }
 
+    /**
+     * @param patterns three finder patterns, in any order
+     * @return true if they lie about on a straight line, so they can't be the corners of a
+     *         QR Code, however much it is skewed
+     */
+    fn isCollinear(patterns: &[FinderPattern; 3]) -> bool {
+        let [a, b, c] = patterns;
+        let cross = (b.getX() - a.getX()) * (c.getY() - a.getY())
+            - (b.getY() - a.getY()) * (c.getX() - a.getX());
+        let longest = [(a, b), (b, c), (c, a)]
+            .iter()
+            .map(|(p, q)| result_point_utils::distance(*p, *q))
+            .fold(0.0, f32::max);
+        // The cross product is twice the area of the triangle, which comes to half the square
+        // of the longest edge for an upright code, and nothing for points in line
+        cross.abs() < MIN_LAYOUT_AREA_RATIO * longest * longest
+    }
+
+    /**
+     * @param patterns three finder patterns, ordered as bottom left, top left and top right
+     * @param moduleSize estimated module size of the code
+     * @return true if they could be the corners of a QR Code: at a distance which gives a module
+     *         count within the specification, with edges of similar length and a 90° angle at
+     *         the top left
+     */
+    fn isCodeLayout(patterns: &[FinderPattern; 3], moduleSize: f32) -> bool {
+        // Calculate the distances: a = topleft-bottomleft, b=topleft-topright, c = diagonal
+        let info = FinderPatternInfo::new(*patterns);
+        let dA = result_point_utils::distance(info.getTopLeft(), info.getBottomLeft());
+        let dC = result_point_utils::distance(info.getTopRight(), info.getBottomLeft());
+        let dB = result_point_utils::distance(info.getTopLeft(), info.getTopRight());
+
+        // Check the sizes
+        let estimatedModuleCount = (dA + dB) / (moduleSize * 2.0);
+        if !(MIN_MODULE_COUNT_PER_EDGE..=MAX_MODULE_COUNT_PER_EDGE).contains(&estimatedModuleCount)
+        {
+            return false;
+        }
+
+        // Calculate the difference of the edge lengths in percent
+        let vABBC = ((dA - dB) / dA.min(dB)).abs();
+        if vABBC >= 0.1 {
+            return false;
+        }
+
+        // Calculate the diagonal length by assuming a 90° angle at topleft
+        let dCpy = ((dA as f64) * (dA as f64) + (dB as f64) * (dB as f64)).sqrt() as f32;
+        // Compare to the real distance in %
+        let vPyC = ((dC - dCpy) / dC.min(dCpy)).abs();
+
+        vPyC < 0.1
+    }
+
     pub fn findMulti(
         &mut self,
         hints: &DecodingHintDictionary,
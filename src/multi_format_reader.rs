@@ -14,15 +14,29 @@
  * limitations under the License.
  */
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+#[cfg(feature = "diagnostics")]
+use crate::oned::OneDReader;
 use crate::{
     aztec::AztecReader, datamatrix::DataMatrixReader, maxicode::MaxiCodeReader,
     oned::MultiFormatOneDReader, pdf417::PDF417Reader, qrcode::QRCodeReader, BarcodeFormat,
     BinaryBitmap, DecodeHintType, DecodeHintValue, DecodingHintDictionary, Exceptions, RXingResult,
-    Reader,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
+/**
+ * The 2D formats decoded when {@link DecodeHintType#POSSIBLE_FORMATS} names none of the supported
+ * formats, along with {@link MultiFormatOneDReader#DEFAULT_FORMATS}.
+ */
+const DEFAULT_2D_FORMATS: [BarcodeFormat; 5] = [
+    BarcodeFormat::QR_CODE,
+    BarcodeFormat::DATA_MATRIX,
+    BarcodeFormat::AZTEC,
+    BarcodeFormat::PDF_417,
+    BarcodeFormat::MAXICODE,
+];
+
 /**
  * MultiFormatReader is a convenience class and the main entry point into the library for most uses.
  * By default it attempts to decode all barcode formats that the library supports. Optionally, you
@@ -101,6 +115,62 @@ impl MultiFormatReader {
         self.decode_internal(image)
     }
 
+    /**
+     * Decodes the first of several orientations of the same frame which contains a barcode, for
+     * capture systems which provide the rotations already. Only the images given are tried, none
+     * of them is rotated any further: the 1D readers don't turn them, whatever
+     * {@link DecodeHintType#TRY_HARDER}, {@link DecodeHintType#TRY_VERTICAL} or
+     * {@link DecodeHintType#ROTATION_ATTEMPTS} say.
+     *
+     * @param images The orientations of the frame, in the order to try them
+     * @param hints The hints to use, clearing the previous state.
+     * @return The contents of the first image a barcode is found in, with its index in
+     *  {@link RXingResultMetadataType#ROTATION_INDEX}. A partial result, see
+     *  {@link DecodeHintType#RETURN_DETECTION_ON_FAILURE}, is only returned if none of the images
+     *  decodes.
+     * @throws NotFoundException if none of the images contains a barcode
+     */
+    pub fn decode_rotations(
+        &mut self,
+        images: &mut [BinaryBitmap],
+        hints: &DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        self.set_ints(hints);
+        // Only for the images given: later calls keep the hints as the caller passed them
+        let mut orientationHints = hints.clone();
+        orientationHints.remove(&DecodeHintType::TRY_VERTICAL);
+        orientationHints.insert(
+            DecodeHintType::ROTATION_ATTEMPTS,
+            DecodeHintValue::RotationAttempts(0),
+        );
+        let hints = std::mem::replace(&mut self.hints, orientationHints);
+
+        let mut partial = None;
+        let mut decoded = None;
+        for (index, image) in images.iter_mut().enumerate() {
+            match self.decode_internal(image) {
+                Ok(result) if result.isPartial() => {
+                    partial.get_or_insert((index, result));
+                }
+                Ok(result) => {
+                    decoded = Some((index, result));
+                    break;
+                }
+                Err(_) => {}
+            }
+        }
+        self.hints = hints;
+
+        let (index, mut result) = decoded
+            .or(partial)
+            .ok_or(Exceptions::NotFoundException(None))?;
+        result.putMetadata(
+            RXingResultMetadataType::ROTATION_INDEX,
+            RXingResultMetadataValue::RotationIndex(index),
+        );
+        Ok(result)
+    }
+
     /**
      * This method adds state to the MultiFormatReader. By setting the hints once, subsequent calls
      * to decodeWithState(image) can reuse the same set of readers without reallocating memory. This
@@ -111,76 +181,224 @@ impl MultiFormatReader {
     pub fn set_ints(&mut self, hints: &DecodingHintDictionary) {
         self.hints = hints.clone(); // {hint} else {HashMap::new()};
 
-        let tryHarder = self.hints.contains_key(&DecodeHintType::TRY_HARDER);
-        //@SuppressWarnings("unchecked")
-        let formats = hints.get(&DecodeHintType::POSSIBLE_FORMATS);
-        let mut readers: Vec<Box<dyn Reader>> = Vec::new();
-        if let Some(DecodeHintValue::PossibleFormats(formats)) = formats {
-            let addOneDReader = formats.contains(&BarcodeFormat::UPC_A)
-                || formats.contains(&BarcodeFormat::UPC_E)
-                || formats.contains(&BarcodeFormat::EAN_13)
-                || formats.contains(&BarcodeFormat::EAN_8)
-                || formats.contains(&BarcodeFormat::CODABAR)
-                || formats.contains(&BarcodeFormat::CODE_39)
-                || formats.contains(&BarcodeFormat::CODE_93)
-                || formats.contains(&BarcodeFormat::CODE_128)
-                || formats.contains(&BarcodeFormat::ITF)
-                || formats.contains(&BarcodeFormat::RSS_14)
-                || formats.contains(&BarcodeFormat::RSS_EXPANDED);
-            // Put 1D readers upfront in "normal" mode
-            if addOneDReader && !tryHarder {
-                readers.push(Box::new(MultiFormatOneDReader::new(hints)));
-            }
-            if formats.contains(&BarcodeFormat::QR_CODE) {
-                readers.push(Box::<QRCodeReader>::default());
-            }
-            if formats.contains(&BarcodeFormat::DATA_MATRIX) {
-                readers.push(Box::<DataMatrixReader>::default());
-            }
-            if formats.contains(&BarcodeFormat::AZTEC) {
-                readers.push(Box::<AztecReader>::default());
-            }
-            if formats.contains(&BarcodeFormat::PDF_417) {
-                readers.push(Box::<PDF417Reader>::default());
-            }
-            if formats.contains(&BarcodeFormat::MAXICODE) {
-                readers.push(Box::<MaxiCodeReader>::default());
-            }
-            // At end in "try harder" mode
-            if addOneDReader && tryHarder {
-                readers.push(Box::new(MultiFormatOneDReader::new(hints)));
-            }
+        let mut readers = Vec::new();
+        if let Some(DecodeHintValue::PossibleFormats(formats)) =
+            hints.get(&DecodeHintType::POSSIBLE_FORMATS)
+        {
+            readers = Self::readersFor(formats, hints);
         }
         if readers.is_empty() {
-            if !tryHarder {
-                readers.push(Box::new(MultiFormatOneDReader::new(hints)));
-            }
+            readers = Self::readersFor(&Self::defaultFormats(), hints);
+        }
+        self.readers = readers; //Vec::new(); //readers.toArray(EMPTY_READER_ARRAY);
+    }
 
+    fn readersFor(
+        formats: &HashSet<BarcodeFormat>,
+        hints: &DecodingHintDictionary,
+    ) -> Vec<Box<dyn Reader>> {
+        let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
+        let addOneDReader = formats.iter().any(Self::isDecodedFromRows);
+        let mut readers: Vec<Box<dyn Reader>> = Vec::new();
+        // Put 1D readers upfront in "normal" mode
+        if addOneDReader && !tryHarder {
+            readers.push(Box::new(MultiFormatOneDReader::new(hints)));
+        }
+        if formats.contains(&BarcodeFormat::QR_CODE) {
             readers.push(Box::<QRCodeReader>::default());
+        }
+        if formats.contains(&BarcodeFormat::DATA_MATRIX) {
             readers.push(Box::<DataMatrixReader>::default());
+        }
+        if formats.contains(&BarcodeFormat::AZTEC) {
             readers.push(Box::<AztecReader>::default());
+        }
+        if formats.contains(&BarcodeFormat::PDF_417) {
             readers.push(Box::<PDF417Reader>::default());
+        }
+        if formats.contains(&BarcodeFormat::MAXICODE) {
             readers.push(Box::<MaxiCodeReader>::default());
-            // unimplemented!("");
+        }
+        // At end in "try harder" mode
+        if addOneDReader && tryHarder {
+            readers.push(Box::new(MultiFormatOneDReader::new(hints)));
+        }
+        readers
+    }
+
+    /**
+     * @return the formats decoded when {@link DecodeHintType#POSSIBLE_FORMATS} names none of the
+     *  supported formats
+     */
+    fn defaultFormats() -> HashSet<BarcodeFormat> {
+        DEFAULT_2D_FORMATS
+            .into_iter()
+            .chain(MultiFormatOneDReader::DEFAULT_FORMATS)
+            .collect()
+    }
 
-            if tryHarder {
-                readers.push(Box::new(MultiFormatOneDReader::new(hints)));
+    /**
+     * Diagnostic counterpart of {@link #decode(BinaryBitmap, Map)}: tries each format on its own,
+     * with the same readers decode() would use for it, and reports how every attempt ended. An
+     * error tells whether nothing was found, or whether something was found which failed to
+     * decode, for instance because of a checksum.
+     *
+     * @param image The pixel data to decode
+     * @param hints The hints to use, clearing the previous state. Only the formats in
+     *  {@link DecodeHintType#POSSIBLE_FORMATS} are attempted, if given, otherwise all the formats
+     *  which are decoded by default.
+     * @return the result, or the error, of every attempted format
+     */
+    #[cfg(feature = "diagnostics")]
+    pub fn decode_with_diagnostics(
+        &mut self,
+        image: &mut BinaryBitmap,
+        hints: &DecodingHintDictionary,
+    ) -> HashMap<BarcodeFormat, Result<RXingResult, Exceptions>> {
+        let formats: Vec<BarcodeFormat> = match hints.get(&DecodeHintType::POSSIBLE_FORMATS) {
+            Some(DecodeHintValue::PossibleFormats(formats)) if !formats.is_empty() => {
+                formats.iter().cloned().collect()
             }
+            _ => Self::defaultFormats().into_iter().collect(),
+        };
+
+        let mut corrected = Self::prefiltered(image, hints);
+        let image = corrected.as_mut().unwrap_or(image);
+        let mut outcomes = HashMap::with_capacity(formats.len());
+        for format in formats {
+            let mut formatHints = hints.clone();
+            formatHints.insert(
+                DecodeHintType::POSSIBLE_FORMATS,
+                DecodeHintValue::PossibleFormats(HashSet::from([format])),
+            );
+            let outcome = if Self::isDecodedFromRows(&format) {
+                // 1D readers only tell why no row decoded when asked to
+                let mut reader = MultiFormatOneDReader::new(&formatHints);
+                Self::decode_keeping_error(image, &formatHints, |image| {
+                    reader.decode_with_row_errors(image, &formatHints)
+                })
+            } else {
+                self.set_ints(&formatHints);
+                let readers = &mut self.readers;
+                Self::decode_keeping_error(image, &formatHints, |image| {
+                    let mut lastError = Exceptions::NotFoundException(None);
+                    for reader in readers.iter_mut() {
+                        match reader.decode_with_hints(image, &formatHints) {
+                            Ok(result) => return Ok(result),
+                            Err(e) => lastError = e,
+                        }
+                    }
+                    Err(lastError)
+                })
+            };
+            outcomes.insert(format, outcome);
         }
-        self.readers = readers; //Vec::new(); //readers.toArray(EMPTY_READER_ARRAY);
+        self.set_ints(hints);
+        outcomes
+    }
+
+    /**
+     * Like {@link #decode_with_readers(BinaryBitmap)}, but returns the error of the decode rather
+     * than NotFoundException, and leaves the image as it was.
+     */
+    #[cfg(feature = "diagnostics")]
+    fn decode_keeping_error<F>(
+        image: &mut BinaryBitmap,
+        hints: &DecodingHintDictionary,
+        mut decode: F,
+    ) -> Result<RXingResult, Exceptions>
+    where
+        F: FnMut(&mut BinaryBitmap) -> Result<RXingResult, Exceptions>,
+    {
+        let result = decode(image);
+        if result.is_ok() || !hints.contains_key(&DecodeHintType::ALSO_INVERTED) {
+            return result;
+        }
+        image.getBlackMatrixMut().flip_self();
+        let inverted = decode(image);
+        image.getBlackMatrixMut().flip_self();
+        inverted
     }
 
     pub fn decode_internal(&mut self, image: &mut BinaryBitmap) -> Result<RXingResult, Exceptions> {
+        let mut corrected = Self::prefiltered(image, &self.hints);
+        let image = corrected.as_mut().unwrap_or(image);
+        let mut result = self.decode_with_readers(image)?;
+        // 1D barcodes are decoded from single rows, binarized on their own, so there's no matrix
+        // they were decoded from
+        if matches!(
+            self.hints.get(&DecodeHintType::RETURN_BINARIZED_MATRIX),
+            Some(DecodeHintValue::ReturnBinarizedMatrix(true))
+        ) && !Self::isDecodedFromRows(result.getBarcodeFormat())
+        {
+            // After ALSO_INVERTED the matrix is left inverted, as the barcode was decoded from it
+            result.putMetadata(
+                RXingResultMetadataType::BINARIZED_MATRIX,
+                RXingResultMetadataValue::BinarizedMatrix(image.getBlackMatrix().clone()),
+            );
+        }
+        if let Some(DecodeHintValue::MaxResultPoints(maxPoints)) =
+            self.hints.get(&DecodeHintType::MAX_RESULT_POINTS)
+        {
+            result
+                .getRXingResultPointsMut()
+                .truncate(*maxPoints as usize);
+        }
+        Ok(result)
+    }
+
+    /**
+     * @return the image corrected as {@link DecodeHintType#GAMMA_PREFILTER} asks, if it does
+     */
+    fn prefiltered(image: &BinaryBitmap, hints: &DecodingHintDictionary) -> Option<BinaryBitmap> {
+        if let Some(DecodeHintValue::GammaPrefilter(gamma)) =
+            hints.get(&DecodeHintType::GAMMA_PREFILTER)
+        {
+            Some(image.gammaCorrected(*gamma))
+        } else {
+            None
+        }
+    }
+
+    /**
+     * @return true for the formats {@link MultiFormatOneDReader} decodes, from single rows of the
+     *  image rather than from its binarized matrix
+     */
+    fn isDecodedFromRows(format: &BarcodeFormat) -> bool {
+        matches!(
+            format,
+            BarcodeFormat::UPC_A
+                | BarcodeFormat::UPC_E
+                | BarcodeFormat::EAN_13
+                | BarcodeFormat::EAN_8
+                | BarcodeFormat::CODABAR
+                | BarcodeFormat::CODE_39
+                | BarcodeFormat::CODE_93
+                | BarcodeFormat::CODE_128
+                | BarcodeFormat::ITF
+                | BarcodeFormat::RSS_14
+                | BarcodeFormat::RSS_EXPANDED
+                | BarcodeFormat::TELEPEN
+                | BarcodeFormat::PHARMA_CODE
+        )
+    }
+
+    fn decode_with_readers(&mut self, image: &mut BinaryBitmap) -> Result<RXingResult, Exceptions> {
         if !self.readers.is_empty() {
+            // A partial result is only returned if no reader can decode the image
+            let mut partial = None;
             for reader in self.readers.iter_mut() {
                 // I'm not sure how to model this in rust
                 // if (Thread.currentThread().isInterrupted()) {
                 //   throw NotFoundException.getNotFoundInstance();
                 // }
                 //try {
-                let res = reader.decode_with_hints(image, &self.hints);
-                if res.is_ok() {
-                    return res;
+                match reader.decode_with_hints(image, &self.hints) {
+                    Ok(result) if result.isPartial() => {
+                        partial.get_or_insert(result);
+                    }
+                    Ok(result) => return Ok(result),
+                    Err(_) => {}
                 }
                 //} catch (ReaderException re) {
                 // continue
@@ -194,9 +412,12 @@ impl MultiFormatReader {
                     // if (Thread.currentThread().isInterrupted()) {
                     //   throw NotFoundException.getNotFoundInstance();
                     // }
-                    let res = reader.decode_with_hints(image, &self.hints);
-                    if res.is_ok() {
-                        return res;
+                    match reader.decode_with_hints(image, &self.hints) {
+                        Ok(result) if result.isPartial() => {
+                            partial.get_or_insert(result);
+                        }
+                        Ok(result) => return Ok(result),
+                        Err(_) => {}
                     }
                     // try {
                     //   return reader.decode(image, hints);
@@ -205,6 +426,9 @@ impl MultiFormatReader {
                     // }
                 }
             }
+            if let Some(partial) = partial {
+                return Ok(partial);
+            }
         }
         Err(Exceptions::NotFoundException(None))
     }
@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+
+use crate::{
+    oned::Code128Writer,
+    pdf417::PDF417Writer,
+    qrcode::QRCodeWriter,
+    shared_test_methods::{
+        bitmap_for, matrix_pixels, render, render_bitmap, render_luma, unreadable_qr_pixels,
+    },
+    BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue, MultiFormatReader,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
+};
+
+fn qr_pixels(contents: &str) -> (Vec<u8>, u32, u32) {
+    let matrix = QRCodeWriter
+        .encode(contents, &BarcodeFormat::QR_CODE, 200, 200)
+        .expect("encode");
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    // Gray rather than black and white, so that binarizing has something to do
+    let pixels = render_luma(width, height, 60, 190, |x, y| matrix.get(x, y));
+    (pixels, width, height)
+}
+
+#[test]
+fn test_binarized_matrix_not_returned_by_default() {
+    let (pixels, width, height) = qr_pixels("binarized");
+    let mut image = BinaryBitmap::from_luma8(pixels, width, height).expect("bitmap");
+    let result = MultiFormatReader::default()
+        .decode(&mut image)
+        .expect("decode");
+    assert!(!result
+        .getRXingResultMetadata()
+        .contains_key(&RXingResultMetadataType::BINARIZED_MATRIX));
+}
+
+#[test]
+fn test_returned_binarized_matrix_decodes_to_same_result() {
+    let (pixels, width, height) = qr_pixels("binarized");
+    let mut image = BinaryBitmap::from_luma8(pixels, width, height).expect("bitmap");
+    let hints = HashMap::from([(
+        DecodeHintType::RETURN_BINARIZED_MATRIX,
+        DecodeHintValue::ReturnBinarizedMatrix(true),
+    )]);
+    let result = MultiFormatReader::default()
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    let Some(RXingResultMetadataValue::BinarizedMatrix(matrix)) = result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::BINARIZED_MATRIX)
+    else {
+        panic!("missing binarized matrix");
+    };
+    assert_eq!(image.getBlackMatrix(), matrix);
+
+    let mut binarized = bitmap_for(matrix);
+    let again = MultiFormatReader::default()
+        .decode(&mut binarized)
+        .expect("decode");
+    assert_eq!(result.getText(), again.getText());
+    assert_eq!(result.getBarcodeFormat(), again.getBarcodeFormat());
+    assert_eq!(result.getRawBytes(), again.getRawBytes());
+}
+
+#[test]
+fn test_binarized_matrix_not_returned_for_1d() {
+    let matrix = Code128Writer
+        .encode("row decoded", &BarcodeFormat::CODE_128, 300, 80)
+        .expect("encode");
+    let mut image = bitmap_for(&matrix);
+    let hints = HashMap::from([(
+        DecodeHintType::RETURN_BINARIZED_MATRIX,
+        DecodeHintValue::ReturnBinarizedMatrix(true),
+    )]);
+    let result = MultiFormatReader::default()
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    assert_eq!(&BarcodeFormat::CODE_128, result.getBarcodeFormat());
+    assert!(!result
+        .getRXingResultMetadata()
+        .contains_key(&RXingResultMetadataType::BINARIZED_MATRIX));
+}
+
+#[test]
+fn test_max_result_points_keeps_corners() {
+    let matrix = PDF417Writer
+        .encode("result points", &BarcodeFormat::PDF_417, 300, 150)
+        .expect("encode");
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    let pixels = matrix_pixels(&matrix);
+    let image = || BinaryBitmap::from_luma8(pixels.clone(), width, height).expect("bitmap");
+
+    let all = MultiFormatReader::default()
+        .decode(&mut image())
+        .expect("decode");
+    assert!(all.getRXingResultPoints().len() > 4);
+
+    let hints = HashMap::from([(
+        DecodeHintType::MAX_RESULT_POINTS,
+        DecodeHintValue::MaxResultPoints(4),
+    )]);
+    let capped = MultiFormatReader::default()
+        .decode_with_hints(&mut image(), &hints)
+        .expect("decode");
+    assert_eq!("result points", capped.getText());
+
+    // The kept points are the corners of the symbol: top left, bottom left, top right and
+    // bottom right
+    let dark: Vec<(u32, u32)> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| matrix.get(x, y))
+        .collect();
+    let left = dark.iter().map(|&(x, _)| x).min().unwrap() as f32;
+    let right = dark.iter().map(|&(x, _)| x).max().unwrap() as f32;
+    let top = dark.iter().map(|&(_, y)| y).min().unwrap() as f32;
+    let bottom = dark.iter().map(|&(_, y)| y).max().unwrap() as f32;
+    let corners = [(left, top), (left, bottom), (right, top), (right, bottom)];
+    let points = capped.getRXingResultPoints();
+    assert_eq!(corners.len(), points.len());
+    for (point, (x, y)) in points.iter().zip(corners) {
+        assert!(
+            (point.x - x).abs() <= 2.0 && (point.y - y).abs() <= 2.0,
+            "{point:?} should be near ({x}, {y})"
+        );
+    }
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn test_diagnostics_report_every_attempted_format() {
+    use crate::Exceptions;
+    use std::collections::HashSet;
+
+    let (pixels, width, height) = qr_pixels("diagnostics");
+    let mut image = BinaryBitmap::from_luma8(pixels, width, height).expect("bitmap");
+    let mut reader = MultiFormatReader::default();
+
+    let outcomes = reader.decode_with_diagnostics(&mut image, &HashMap::new());
+    for format in [
+        BarcodeFormat::AZTEC,
+        BarcodeFormat::CODE_128,
+        BarcodeFormat::DATA_MATRIX,
+        BarcodeFormat::EAN_13,
+        BarcodeFormat::QR_CODE,
+        BarcodeFormat::RSS_EXPANDED,
+        BarcodeFormat::UPC_E,
+    ] {
+        assert!(outcomes.contains_key(&format), "{format} attempted");
+    }
+    // Pharmacode is only tried when asked for
+    assert!(!outcomes.contains_key(&BarcodeFormat::PHARMA_CODE));
+    for (format, outcome) in &outcomes {
+        match outcome {
+            Ok(result) => {
+                assert_eq!(&BarcodeFormat::QR_CODE, format);
+                assert_eq!("diagnostics", result.getText());
+            }
+            Err(_) => assert_ne!(&BarcodeFormat::QR_CODE, format),
+        }
+    }
+
+    let hints = HashMap::from([
+        (
+            DecodeHintType::POSSIBLE_FORMATS,
+            DecodeHintValue::PossibleFormats(HashSet::from([
+                BarcodeFormat::CODE_128,
+                BarcodeFormat::PDF_417,
+            ])),
+        ),
+        (
+            DecodeHintType::ALSO_INVERTED,
+            DecodeHintValue::AlsoInverted(true),
+        ),
+    ]);
+    let outcomes = reader.decode_with_diagnostics(&mut image, &hints);
+    assert_eq!(2, outcomes.len());
+    assert!(matches!(
+        outcomes[&BarcodeFormat::CODE_128],
+        Err(Exceptions::NotFoundException(_))
+    ));
+    assert!(outcomes[&BarcodeFormat::PDF_417].is_err());
+
+    // The image is left as it was, and the reader set up with the given hints
+    assert_eq!(
+        "diagnostics",
+        MultiFormatReader::default()
+            .decode(&mut image)
+            .expect("decode")
+            .getText()
+    );
+    assert!(matches!(
+        reader.decode_with_state(&mut image),
+        Err(Exceptions::NotFoundException(_))
+    ));
+}
+
+#[test]
+fn test_decode_rotations_reports_orientation() {
+    use std::collections::HashSet;
+
+    let matrix = Code128Writer
+        .encode("ROTATIONS", &BarcodeFormat::CODE_128, 200, 60)
+        .expect("encode");
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    // The same frame turned 90 degrees, with the bars running across
+    let turned = render(height, width, |x, y| matrix.get(y, height - 1 - x));
+
+    // Without TRY_HARDER the turned image is not rotated back, so only the upright one decodes
+    let hints = HashMap::from([(
+        DecodeHintType::POSSIBLE_FORMATS,
+        DecodeHintValue::PossibleFormats(HashSet::from([BarcodeFormat::CODE_128])),
+    )]);
+    let mut images = [
+        BinaryBitmap::from_luma8(turned, height, width).expect("bitmap"),
+        bitmap_for(&matrix),
+    ];
+    let mut reader = MultiFormatReader::default();
+    assert!(reader.decode_with_hints(&mut images[0], &hints).is_err());
+
+    let result = reader
+        .decode_rotations(&mut images, &hints)
+        .expect("decode");
+    assert_eq!("ROTATIONS", result.getText());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::RotationIndex(1)),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ROTATION_INDEX)
+    );
+
+    assert!(reader.decode_rotations(&mut images[..1], &hints).is_err());
+    assert!(reader.decode_rotations(&mut [], &hints).is_err());
+}
+
+#[test]
+fn test_decode_rotations_does_not_rotate_further() {
+    let matrix = Code128Writer
+        .encode("ROTATIONS", &BarcodeFormat::CODE_128, 200, 60)
+        .expect("encode");
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    // Turned 90 degrees, with the bars running across
+    let image = || render_bitmap(height, width, |x, y| matrix.get(y, height - 1 - x));
+
+    let hints = HashMap::from([
+        (DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true)),
+        (
+            DecodeHintType::TRY_VERTICAL,
+            DecodeHintValue::TryVertical(true),
+        ),
+        (
+            DecodeHintType::ROTATION_ATTEMPTS,
+            DecodeHintValue::RotationAttempts(3),
+        ),
+    ]);
+    // These hints have the 1D readers turn the image themselves
+    let mut reader = MultiFormatReader::default();
+    assert_eq!(
+        "ROTATIONS",
+        reader
+            .decode_with_hints(&mut image(), &hints)
+            .expect("decode")
+            .getText()
+    );
+    assert!(reader.decode_rotations(&mut [image()], &hints).is_err());
+
+    // The hints are left as given, so the reader turns the image itself again
+    assert_eq!(
+        "ROTATIONS",
+        reader
+            .decode_with_state(&mut image())
+            .expect("decode")
+            .getText()
+    );
+}
+
+#[test]
+fn test_decode_rotations_prefers_full_result_to_partial() {
+    let (unreadable, size) = unreadable_qr_pixels();
+    let (readable, width, height) = qr_pixels("second orientation");
+    let hints = HashMap::from([(
+        DecodeHintType::RETURN_DETECTION_ON_FAILURE,
+        DecodeHintValue::ReturnDetectionOnFailure(true),
+    )]);
+    let mut images = [
+        BinaryBitmap::from_luma8(unreadable.clone(), size, size).expect("bitmap"),
+        BinaryBitmap::from_luma8(readable, width, height).expect("bitmap"),
+    ];
+
+    let mut reader = MultiFormatReader::default();
+    let result = reader
+        .decode_rotations(&mut images, &hints)
+        .expect("decode");
+    assert!(!result.isPartial());
+    assert_eq!("second orientation", result.getText());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::RotationIndex(1)),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ROTATION_INDEX)
+    );
+
+    // With nothing else to decode, the partial result is returned after all
+    let mut images = [BinaryBitmap::from_luma8(unreadable, size, size).expect("bitmap")];
+    let result = reader
+        .decode_rotations(&mut images, &hints)
+        .expect("partial result");
+    assert!(result.isPartial());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::RotationIndex(0)),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ROTATION_INDEX)
+    );
+}
+
+#[test]
+fn test_gamma_prefilter_rescues_washed_out_decode() {
+    let matrix = QRCodeWriter
+        .encode("washed out", &BarcodeFormat::QR_CODE, 120, 120)
+        .expect("encode");
+    // Dark modules barely darker than light ones, as in an overexposed photo
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    let pixels = render_luma(width, height, 190, 250, |x, y| matrix.get(x, y));
+    let image = || BinaryBitmap::from_luma8(pixels.clone(), width, height).expect("bitmap");
+
+    let mut hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+    let mut reader = MultiFormatReader::default();
+    assert!(reader.decode_with_hints(&mut image(), &hints).is_err());
+
+    hints.insert(
+        DecodeHintType::GAMMA_PREFILTER,
+        DecodeHintValue::GammaPrefilter(2.2),
+    );
+    let result = reader
+        .decode_with_hints(&mut image(), &hints)
+        .expect("decode");
+    assert_eq!("washed out", result.getText());
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn test_diagnostics_tell_failed_checksum_from_not_found() {
+    use crate::Exceptions;
+    use std::collections::HashSet;
+
+    // The symbols of "AB", but the check symbol of "AC", which is just as wide
+    let row = |contents: &str| {
+        Code128Writer
+            .encode(contents, &BarcodeFormat::CODE_128, 0, 1)
+            .expect("encode")
+    };
+    let (good, other) = (row("AB"), row("AC"));
+    let width = good.getWidth();
+    let stopEnd = (0..width).rev().find(|x| good.get(*x, 0)).expect("bars");
+    // The check symbol is the 11 modules before the 13 of the stop pattern
+    let checkSymbol = stopEnd - 23..=stopEnd - 13;
+    // With a wider quiet zone than the writer leaves
+    let mut image = render_bitmap((width + 20) * 3, 60, |x, _| {
+        let x = (x / 3).wrapping_sub(10);
+        if x >= width {
+            false
+        } else if checkSymbol.contains(&x) {
+            other.get(x, 0)
+        } else {
+            good.get(x, 0)
+        }
+    });
+
+    let hints = HashMap::from([(
+        DecodeHintType::POSSIBLE_FORMATS,
+        DecodeHintValue::PossibleFormats(HashSet::from([
+            BarcodeFormat::CODE_128,
+            BarcodeFormat::CODE_39,
+        ])),
+    )]);
+    let outcomes = MultiFormatReader::default().decode_with_diagnostics(&mut image, &hints);
+    assert!(matches!(
+        outcomes[&BarcodeFormat::CODE_128],
+        Err(Exceptions::ChecksumException(_))
+    ));
+    assert!(matches!(
+        outcomes[&BarcodeFormat::CODE_39],
+        Err(Exceptions::NotFoundException(_))
+    ));
+}
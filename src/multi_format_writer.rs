@@ -24,7 +24,11 @@ use crate::{
         ITFWriter, UPCAWriter, UPCEWriter,
     },
     pdf417::PDF417Writer,
-    qrcode::QRCodeWriter,
+    qrcode::{
+        decoder::{ErrorCorrectionLevel, Mode, Version},
+        encoder::qrcode_encoder,
+        QRCodeWriter,
+    },
     BarcodeFormat, Exceptions, Writer,
 };
 
@@ -143,3 +147,44 @@ impl Writer for MultiFormatWriter {
 //   }
 
 // }
+
+/**
+ * The most characters a barcode of the given format can hold, so that input can be checked
+ * before it's encoded. For QR Code this is the capacity of the largest version at the given
+ * error correction level, counted in digits, alphanumeric characters, bytes or Kanji characters
+ * according to the mode. Other formats ignore the level and mode: EAN and UPC formats take a
+ * fixed number of digits, not counting the check digit, and the Code 39, Code 93, Code 128 and
+ * ITF writers accept up to 80 characters.
+ *
+ * @param format format of the barcode
+ * @param ec_level QR Code error correction level
+ * @param mode QR Code mode the content is encoded in
+ * @return the maximum number of characters
+ * @throws UnsupportedOperationException for formats without a fixed limit, or QR Code modes
+ *  which don't encode characters
+ */
+pub fn max_capacity(
+    format: BarcodeFormat,
+    ec_level: ErrorCorrectionLevel,
+    mode: Mode,
+) -> Result<usize, Exceptions> {
+    let capacity = match format {
+        BarcodeFormat::QR_CODE => (1..=40)
+            .filter_map(|number| Version::getVersionForNumber(number).ok())
+            .filter_map(|version| qrcode_encoder::getMaxCharacterCount(version, &ec_level, mode))
+            .max(),
+        BarcodeFormat::EAN_13 => Some(12),
+        BarcodeFormat::UPC_A => Some(11),
+        BarcodeFormat::EAN_8 | BarcodeFormat::UPC_E => Some(7),
+        BarcodeFormat::CODE_39
+        | BarcodeFormat::CODE_93
+        | BarcodeFormat::CODE_128
+        | BarcodeFormat::ITF => Some(80),
+        _ => None,
+    };
+    capacity.map(|capacity| capacity as usize).ok_or_else(|| {
+        Exceptions::UnsupportedOperationException(Some(format!(
+            "No capacity known for format {format:?} in mode {mode:?}"
+        )))
+    })
+}
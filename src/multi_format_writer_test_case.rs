@@ -0,0 +1,56 @@
+use crate::{
+    max_capacity,
+    qrcode::{
+        decoder::{ErrorCorrectionLevel, Mode},
+        encoder::qrcode_encoder,
+    },
+    BarcodeFormat, CapacityExceeded, Exceptions, MultiFormatWriter, Writer,
+};
+
+#[test]
+fn test_qr_code_max_capacity() {
+    let capacity = |ec_level, mode| max_capacity(BarcodeFormat::QR_CODE, ec_level, mode);
+    // The capacities of version 40 from the specification
+    assert_eq!(Ok(1273), capacity(ErrorCorrectionLevel::H, Mode::BYTE));
+    assert_eq!(Ok(2953), capacity(ErrorCorrectionLevel::L, Mode::BYTE));
+    assert_eq!(Ok(7089), capacity(ErrorCorrectionLevel::L, Mode::NUMERIC));
+    assert_eq!(
+        Ok(4296),
+        capacity(ErrorCorrectionLevel::L, Mode::ALPHANUMERIC)
+    );
+    assert_eq!(Ok(1817), capacity(ErrorCorrectionLevel::L, Mode::KANJI));
+    assert!(matches!(
+        capacity(ErrorCorrectionLevel::L, Mode::ECI),
+        Err(Exceptions::UnsupportedOperationException(_))
+    ));
+
+    // Exactly as much as fits
+    let bytes = "a".repeat(1273);
+    let code = qrcode_encoder::encode(&bytes, ErrorCorrectionLevel::H).expect("encode");
+    assert_eq!(40, code.getVersion().unwrap().getVersionNumber());
+    // 4 mode bits, 16 length bits and 8 bits per byte, where version 40-H holds 1276 data bytes
+    assert_eq!(
+        Err(Exceptions::CapacityExceededException(Some(
+            CapacityExceeded {
+                required_bits: 4 + 16 + 1274 * 8,
+                available_bits: 1276 * 8,
+            }
+        ))),
+        qrcode_encoder::encode(&format!("{bytes}a"), ErrorCorrectionLevel::H).map(|_| ())
+    );
+}
+
+#[test]
+fn test_one_d_max_capacity() {
+    let capacity = |format| max_capacity(format, ErrorCorrectionLevel::L, Mode::BYTE);
+    assert_eq!(Ok(12), capacity(BarcodeFormat::EAN_13));
+    assert!(MultiFormatWriter
+        .encode("123456789012", &BarcodeFormat::EAN_13, 0, 0)
+        .is_ok());
+    assert_eq!(Ok(7), capacity(BarcodeFormat::EAN_8));
+    assert_eq!(Ok(80), capacity(BarcodeFormat::CODE_128));
+    assert!(matches!(
+        capacity(BarcodeFormat::CODABAR),
+        Err(Exceptions::UnsupportedOperationException(_))
+    ));
+}
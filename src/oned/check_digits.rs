@@ -0,0 +1,121 @@
+use crate::Exceptions;
+
+use super::Code39Reader;
+
+const CODE_128_START_A: u8 = 103;
+const CODE_128_START_C: u8 = 105;
+
+/**
+ * Computes the EAN-13 check digit for the first 12 digits of a GTIN-13.
+ *
+ * @param digits the 12 data digits, without check digit
+ * @return the check digit, in the range [0,9]
+ * @throws IllegalArgumentException if the input is not exactly 12 ASCII digits
+ */
+pub fn ean13_check_digit(digits: &str) -> Result<u32, Exceptions> {
+    gtin_check_digit(digits, 12, "EAN-13")
+}
+
+/**
+ * Computes the UPC-A check digit for the first 11 digits of a GTIN-12.
+ *
+ * @param digits the 11 data digits, without check digit
+ * @return the check digit, in the range [0,9]
+ * @throws IllegalArgumentException if the input is not exactly 11 ASCII digits
+ */
+pub fn upc_a_check_digit(digits: &str) -> Result<u32, Exceptions> {
+    gtin_check_digit(digits, 11, "UPC-A")
+}
+
+/**
+ * Computes the ITF-14 check digit for the first 13 digits of a GTIN-14.
+ *
+ * @param digits the 13 data digits, without check digit
+ * @return the check digit, in the range [0,9]
+ * @throws IllegalArgumentException if the input is not exactly 13 ASCII digits
+ */
+pub fn itf14_check_digit(digits: &str) -> Result<u32, Exceptions> {
+    gtin_check_digit(digits, 13, "ITF-14")
+}
+
+/**
+ * Computes the optional Code 39 modulo 43 check character.
+ *
+ * @param contents the data characters, excluding start/stop characters
+ * @return the check character
+ * @throws IllegalArgumentException if the input contains a character outside the
+ * Code 39 alphabet
+ */
+pub fn code39_mod43(contents: &str) -> Result<char, Exceptions> {
+    let mut total = 0;
+    for c in contents.chars() {
+        let Some(pos) = Code39Reader::ALPHABET_STRING.find(c) else {
+            return Err(Exceptions::IllegalArgumentException(Some(format!(
+                "'{c}' is not a valid Code 39 character"
+            ))));
+        };
+        total += pos;
+    }
+    Ok(Code39Reader::ALPHABET_STRING
+        .chars()
+        .nth(total % 43)
+        .unwrap())
+}
+
+/**
+ * Computes the Code 128 modulo 103 checksum symbol value.
+ *
+ * @param symbol_values the symbol values of the start code followed by every data symbol,
+ * in order, excluding the checksum and stop symbols
+ * @return the value of the checksum symbol, in the range [0,102]
+ * @throws IllegalArgumentException if no start code is given, the first value is not a
+ * start code, or any value is not a valid symbol value
+ */
+pub fn code128_checksum(symbol_values: &[u8]) -> Result<u8, Exceptions> {
+    let Some(start) = symbol_values.first() else {
+        return Err(Exceptions::IllegalArgumentException(Some(
+            "Code 128 checksum requires a start code".to_owned(),
+        )));
+    };
+    if !(CODE_128_START_A..=CODE_128_START_C).contains(start) {
+        return Err(Exceptions::IllegalArgumentException(Some(format!(
+            "{start} is not a Code 128 start code"
+        ))));
+    }
+    let mut sum = *start as usize;
+    for (weight, value) in symbol_values.iter().enumerate().skip(1) {
+        if *value >= CODE_128_START_A {
+            return Err(Exceptions::IllegalArgumentException(Some(format!(
+                "{value} is not a Code 128 data symbol value"
+            ))));
+        }
+        sum += weight * *value as usize;
+    }
+    Ok((sum % 103) as u8)
+}
+
+fn gtin_check_digit(digits: &str, length: usize, format: &str) -> Result<u32, Exceptions> {
+    if digits.len() != length || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Exceptions::IllegalArgumentException(Some(format!(
+            "{format} check digit requires exactly {length} digits, got '{digits}'"
+        ))));
+    }
+    // Callers have already validated the input, so this cannot fail
+    gtin_mod10(digits).ok_or(Exceptions::IllegalStateException(None))
+}
+
+/**
+ * Standard GS1 modulo 10 checksum shared by the UPC/EAN and ITF-14 symbologies: digits are
+ * weighted 3 and 1 alternately starting from the rightmost digit.
+ *
+ * @return the check digit, or None if the input contains anything other than ASCII digits
+ */
+pub(crate) fn gtin_mod10(digits: &str) -> Option<u32> {
+    let mut sum = 0;
+    let mut weight = 3;
+    for c in digits.chars().rev() {
+        sum += c.to_digit(10)? * weight;
+        weight = 4 - weight;
+    }
+    Some((10 - sum % 10) % 10)
+}
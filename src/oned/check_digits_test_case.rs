@@ -0,0 +1,74 @@
+use crate::Exceptions;
+
+use super::{
+    code128_checksum, code39_mod43, ean13_check_digit, itf14_check_digit, upc_a_check_digit,
+};
+
+#[test]
+fn test_ean13_check_digit() {
+    assert_eq!(Ok(1), ean13_check_digit("400638133393"));
+    assert_eq!(Ok(7), ean13_check_digit("978030640615"));
+    assert_eq!(Ok(0), ean13_check_digit("501234567890"));
+}
+
+#[test]
+fn test_upc_a_check_digit() {
+    assert_eq!(Ok(2), upc_a_check_digit("03600029145"));
+    assert_eq!(Ok(2), upc_a_check_digit("12345678901"));
+}
+
+#[test]
+fn test_itf14_check_digit() {
+    assert_eq!(Ok(7), itf14_check_digit("1001234512345"));
+    // Same GTIN as the UPC-A above, zero padded
+    assert_eq!(Ok(2), itf14_check_digit("0003600029145"));
+}
+
+#[test]
+fn test_gtin_rejects_bad_input() {
+    assert!(matches!(
+        ean13_check_digit("40063813339"),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        ean13_check_digit("40063813339A"),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        upc_a_check_digit("036000291452"),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        itf14_check_digit("１００１２３４５１２３４５"),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+}
+
+#[test]
+fn test_code39_mod43() {
+    // C=12, O=24, D=13, E=14, space=38, 3=3, 9=9; 113 % 43 = 27 -> 'R'
+    assert_eq!(Ok('R'), code39_mod43("CODE 39"));
+    assert_eq!(Ok('0'), code39_mod43(""));
+    assert!(matches!(
+        code39_mod43("code 39"),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+}
+
+#[test]
+fn test_code128_checksum() {
+    // "PJJ123C" in code set B: start B, then the data symbols
+    assert_eq!(Ok(55), code128_checksum(&[104, 48, 42, 42, 17, 18, 19, 35]));
+    assert!(matches!(
+        code128_checksum(&[]),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        code128_checksum(&[48, 42]),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+    assert!(matches!(
+        code128_checksum(&[104, 106]),
+        Err(Exceptions::IllegalArgumentException(_))
+    ));
+}
@@ -14,14 +14,16 @@
  * limitations under the License.
  */
 
-use rxing_one_d_proc_derive::OneDReader;
-
 use crate::common::BitArray;
 use crate::BarcodeFormat;
+use crate::DecodeHintType;
 use crate::Exceptions;
 use crate::RXingResult;
+use crate::RXingResultMetadataType;
+use crate::RXingResultMetadataValue;
+use crate::RXingResultPoint;
 
-use super::OneDReader;
+use super::{one_d_reader, OneDReader};
 
 /**
  * <p>Decodes Codabar barcodes.</p>
@@ -29,7 +31,6 @@ use super::OneDReader;
  * @author Bas Vijfwinkel
  * @author David Walker
  */
-#[derive(OneDReader)]
 pub struct CodaBarReader {
     // Keep some instance variables to avoid reallocations
     decodeRowRXingResult: String,
@@ -57,7 +58,15 @@ impl OneDReader for CodaBarReader {
         self.counters.fill(0);
         // Arrays.fill(counters, 0);
         self.setCounters(row)?;
-        let startOffset = self.findStartPattern()? as usize;
+        let relaxQuietZone = one_d_reader::relaxesQuietZone(hints);
+        let startOffset = self.findStartPattern(relaxQuietZone)? as usize;
+        // 4 narrow and 3 wide elements, wide ones being 2 to 3 times as wide
+        let startPatternSize = self.counters[startOffset..startOffset + 7]
+            .iter()
+            .sum::<u32>();
+        if !one_d_reader::matchesXDimension(hints, startPatternSize as usize, 10, 13) {
+            return Err(Exceptions::NotFoundException(None));
+        }
         let mut nextStart = startOffset;
 
         self.decodeRowRXingResult.clear();
@@ -96,8 +105,11 @@ impl OneDReader for CodaBarReader {
 
         // We need to see whitespace equal to 50% of the last pattern size,
         // otherwise this is probably a false positive. The exception is if we are
-        // at the end of the row. (I.e. the barcode barely fits.)
-        if nextStart < self.counterLength && trailingWhitespace < lastPatternSize / 2 {
+        // at the end of the row (I.e. the barcode barely fits.), or if the quiet zone is relaxed.
+        if nextStart < self.counterLength
+            && !relaxQuietZone
+            && trailingWhitespace < lastPatternSize / 2
+        {
             return Err(Exceptions::NotFoundException(None));
         }
 
@@ -170,6 +182,9 @@ impl OneDReader for CodaBarReader {
         Ok(result)
     }
 }
+
+impl_one_d_reader!(CodaBarReader);
+
 impl CodaBarReader {
     // These values are critical for determining how permissive the decoding
     // will be. All stripe sizes must be within the window these define, as
@@ -319,7 +334,7 @@ impl CodaBarReader {
         }
     }
 
-    fn findStartPattern(&mut self) -> Result<u32, Exceptions> {
+    fn findStartPattern(&mut self, relaxQuietZone: bool) -> Result<u32, Exceptions> {
         let mut i = 1;
         while i < self.counterLength {
             // for (int i = 1; i < counterLength; i += 2) {
@@ -331,13 +346,14 @@ impl CodaBarReader {
                 )
             {
                 // Look for whitespace before start pattern, >= 50% of width of start pattern
-                // We make an exception if the whitespace is the first element.
+                // We make an exception if the whitespace is the first element, or if the quiet
+                // zone is relaxed.
                 let mut patternSize = 0;
                 for j in i..(i + 7) {
                     // for (int j = i; j < i + 7; j++) {
                     patternSize += self.counters[j];
                 }
-                if i == 1 || self.counters[i - 1] >= patternSize / 2 {
+                if i == 1 || relaxQuietZone || self.counters[i - 1] >= patternSize / 2 {
                     return Ok(i as u32);
                 }
             }
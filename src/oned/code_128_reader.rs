@@ -14,18 +14,19 @@
  * limitations under the License.
  */
 
-use rxing_one_d_proc_derive::OneDReader;
+use crate::{
+    common::BitArray, BarcodeFormat, DecodeHintType, DecodeHintValue, Exceptions, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint,
+};
 
-use crate::{common::BitArray, BarcodeFormat, Exceptions, RXingResult};
-
-use super::{one_d_reader, OneDReader};
+use super::{check_digits, one_d_reader, OneDReader};
 
 /**
  * <p>Decodes Code 128 barcodes.</p>
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, Default)]
+#[derive(Default)]
 pub struct Code128Reader;
 
 impl OneDReader for Code128Reader {
@@ -36,10 +37,28 @@ impl OneDReader for Code128Reader {
         hints: &crate::DecodingHintDictionary,
     ) -> Result<crate::RXingResult, crate::Exceptions> {
         let convertFNC1 = hints.contains_key(&DecodeHintType::ASSUME_GS1);
+        let fnc1Separator = if let Some(DecodeHintValue::Code128Fnc1Separator(separator)) =
+            hints.get(&DecodeHintType::CODE_128_FNC1_SEPARATOR)
+        {
+            separator.as_str()
+        } else {
+            "\u{1d}"
+        };
 
         let mut symbologyModifier = 0;
+        // FNC2 (message append) and FNC3 (reader initialization) don't show in the text
+        let mut messageAppend = false;
+        let mut readerInitialization = false;
 
         let startPatternInfo = self.findStartPattern(row)?;
+        if !one_d_reader::matchesXDimension(
+            hints,
+            startPatternInfo[1] - startPatternInfo[0],
+            11,
+            11,
+        ) {
+            return Err(Exceptions::NotFoundException(None));
+        }
         let startCode = startPatternInfo[2] as u8;
 
         let mut rawCodes: Vec<u8> = Vec::with_capacity(20); //new ArrayList<>(20);
@@ -62,10 +81,6 @@ impl OneDReader for Code128Reader {
         let mut nextStart = startPatternInfo[1];
         let mut counters = [0_u32; 6]; //new int[6];
 
-        let mut lastCode = 0;
-        let mut code = 0;
-        let mut checksumTotal = startCode as usize;
-        let mut multiplier = 0;
         let mut lastCharacterWasPrintable = true;
         let mut upperMode = false;
         let mut shiftUpperMode = false;
@@ -74,11 +89,8 @@ impl OneDReader for Code128Reader {
             let unshift = isNextShifted;
             isNextShifted = false;
 
-            // Save off last code
-            lastCode = code;
-
             // Decode another code from image
-            code = self.decodeCode(row, &mut counters, nextStart)?;
+            let code = self.decodeCode(row, &mut counters, nextStart)?;
 
             rawCodes.push(code);
 
@@ -87,12 +99,6 @@ impl OneDReader for Code128Reader {
                 lastCharacterWasPrintable = true;
             }
 
-            // Add to checksum computation (if not CODE_STOP of course)
-            if code != CODE_STOP {
-                multiplier += 1;
-                checksumTotal += multiplier as usize * code as usize;
-            }
-
             // Advance to where the next code will to start
             lastStart = nextStart;
 
@@ -148,14 +154,16 @@ impl OneDReader for Code128Reader {
                                         result.push_str("]C1");
                                     } else {
                                         // GS1 specification 5.4.7.5. Every subsequent FNC1 is returned as ASCII 29 (GS)
-                                        result.push(29 as char);
+                                        // unless another separator was asked for
+                                        result.push_str(fnc1Separator);
                                     }
                                 }
                             }
-                            CODE_FNC_2 => symbologyModifier = 4,
-                            CODE_FNC_3 =>
-                                // do nothing?
-                                {}
+                            CODE_FNC_2 => {
+                                symbologyModifier = 4;
+                                messageAppend = true;
+                            }
+                            CODE_FNC_3 => readerInitialization = true,
                             CODE_FNC_4_A => {
                                 if !upperMode && shiftUpperMode {
                                     upperMode = true;
@@ -205,14 +213,16 @@ impl OneDReader for Code128Reader {
                                         result.push_str("]C1");
                                     } else {
                                         // GS1 specification 5.4.7.5. Every subsequent FNC1 is returned as ASCII 29 (GS)
-                                        result.push(29 as char);
+                                        // unless another separator was asked for
+                                        result.push_str(fnc1Separator);
                                     }
                                 }
                             }
-                            CODE_FNC_2 => symbologyModifier = 4,
-                            CODE_FNC_3 =>
-                                // do nothing?
-                                {}
+                            CODE_FNC_2 => {
+                                symbologyModifier = 4;
+                                messageAppend = true;
+                            }
+                            CODE_FNC_3 => readerInitialization = true,
                             CODE_FNC_4_B => {
                                 if !upperMode && shiftUpperMode {
                                     upperMode = true;
@@ -263,7 +273,8 @@ impl OneDReader for Code128Reader {
                                         result.push_str("]C1");
                                     } else {
                                         // GS1 specification 5.4.7.5. Every subsequent FNC1 is returned as ASCII 29 (GS)
-                                        result.push(29 as char);
+                                        // unless another separator was asked for
+                                        result.push_str(fnc1Separator);
                                     }
                                 }
                             }
@@ -304,11 +315,13 @@ impl OneDReader for Code128Reader {
             return Err(Exceptions::NotFoundException(None));
         }
 
-        // Pull out from sum the value of the penultimate check code
-        checksumTotal -= multiplier as usize * lastCode as usize;
-        // lastCode is the checksum then:
-        if (checksumTotal % 103) as u8 != lastCode {
-            return Err(Exceptions::ChecksumException(None));
+        // The penultimate code is the checksum over the start code and everything after it
+        let checksumIndex = rawCodes.len() - 2;
+        match check_digits::code128_checksum(&rawCodes[..checksumIndex]) {
+            Ok(checksum) if checksum == rawCodes[checksumIndex] => {}
+            Ok(_) => return Err(Exceptions::ChecksumException(None)),
+            // only the start and stop codes were read
+            Err(_) => return Err(Exceptions::NotFoundException(None)),
         }
 
         // Need to pull out the check digits from string
@@ -358,11 +371,56 @@ impl OneDReader for Code128Reader {
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier(format!("]C{symbologyModifier}")),
         );
+        if messageAppend {
+            resultObject.putMetadata(
+                RXingResultMetadataType::CODE_128_MESSAGE_APPEND,
+                RXingResultMetadataValue::Code128MessageAppend(true),
+            );
+        }
+        if readerInitialization {
+            resultObject.putMetadata(
+                RXingResultMetadataType::CODE_128_READER_INITIALIZATION,
+                RXingResultMetadataValue::Code128ReaderInitialization(true),
+            );
+        }
 
         Ok(resultObject)
     }
 }
+
+impl_one_d_reader!(Code128Reader);
+
 impl Code128Reader {
+    /**
+     * Combines the texts of symbols read in scanning order according to the FNC2 message append
+     * convention: the text of a symbol containing FNC2 is buffered and prepended to the text of
+     * the next symbol. Results which aren't Code 128 are passed through as they are.
+     *
+     * @param results symbols in the order they were scanned
+     * @return the complete messages, followed by any text still buffered at the end
+     */
+    pub fn joinAppendedMessages(results: &[RXingResult]) -> Vec<String> {
+        let mut messages = Vec::new();
+        let mut buffered = String::new();
+        for result in results {
+            buffered.push_str(result.getText());
+            let messageAppend = result.getBarcodeFormat() == &BarcodeFormat::CODE_128
+                && matches!(
+                    result
+                        .getRXingResultMetadata()
+                        .get(&RXingResultMetadataType::CODE_128_MESSAGE_APPEND),
+                    Some(RXingResultMetadataValue::Code128MessageAppend(true))
+                );
+            if !messageAppend {
+                messages.push(std::mem::take(&mut buffered));
+            }
+        }
+        if !buffered.is_empty() {
+            messages.push(buffered);
+        }
+        messages
+    }
+
     fn findStartPattern(&self, row: &BitArray) -> Result<[usize; 3], Exceptions> {
         let width = row.getSize();
         let rowOffset = row.getNextSet(0);
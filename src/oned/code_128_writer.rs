@@ -18,7 +18,7 @@ use rxing_one_d_proc_derive::OneDWriter;
 
 use crate::BarcodeFormat;
 
-use super::{code_128_reader, OneDimensionalCodeWriter};
+use super::{check_digits, code_128_reader, OneDimensionalCodeWriter};
 
 const CODE_START_A: usize = 103;
 const CODE_START_B: usize = 104;
@@ -91,6 +91,24 @@ impl OneDimensionalCodeWriter for Code128Writer {
     }
 }
 
+impl Code128Writer {
+    /**
+     * Encodes contents as a row of Code 128 modules, without quiet zones. Code sets A, B and C,
+     * including single character shifts, are chosen so that the symbol is as narrow as possible,
+     * which puts runs of digits in code set C. The check symbol and stop pattern are appended.
+     *
+     * @param contents printable ASCII and control characters, where '\u{00f1}' to '\u{00f4}'
+     *  stand for FNC1 to FNC4; a leading FNC1 marks GS1 data and further ones separate its fields
+     * @return the modules of the row, true for bars
+     * @throws IllegalArgumentException if contents is empty, longer than 80 characters or has
+     *  characters Code 128 can't encode
+     */
+    pub fn encode(contents: &str) -> Result<Vec<bool>, Exceptions> {
+        check(contents, &HashMap::new())?;
+        MinimalEncoder::encode(contents)
+    }
+}
+
 fn check(contents: &str, hints: &crate::EncodingHintDictionary) -> Result<i32, Exceptions> {
     let length = contents.chars().count();
     // Check length
@@ -182,9 +200,7 @@ fn check(contents: &str, hints: &crate::EncodingHintDictionary) -> Result<i32, E
 fn encodeFast(contents: &str, forcedCodeSet: i32) -> Result<Vec<bool>, Exceptions> {
     let length = contents.chars().count();
 
-    let mut patterns: Vec<Vec<usize>> = Vec::new(); //new ArrayList<>(); // temporary storage for patterns
-    let mut checkSum = 0;
-    let mut checkWeight = 1;
+    let mut symbols: Vec<u8> = Vec::new(); // temporary storage for symbol values
     let mut codeSet = 0; // selected code (CODE_CODE_B or CODE_CODE_C)
     let mut position = 0; // position in contents
 
@@ -268,34 +284,24 @@ fn encodeFast(contents: &str, forcedCodeSet: i32) -> Result<Vec<bool>, Exception
             codeSet = newCodeSet;
         }
 
-        // Get the pattern
-        patterns.push(
-            code_128_reader::CODE_PATTERNS[patternIndex as usize]
-                .iter()
-                .map(|x| *x as usize)
-                .collect(),
-        );
-
-        // Compute checksum
-        checkSum += patternIndex * checkWeight;
-        if position != 0 {
-            checkWeight += 1;
-        }
+        symbols.push(patternIndex as u8);
     }
 
-    Ok(produceRXingResult(&mut patterns, checkSum as usize))
+    produceRXingResult(&symbols)
 }
 
-fn produceRXingResult(patterns: &mut Vec<Vec<usize>>, checkSum: usize) -> Vec<bool> {
-    // Compute and append checksum
-    let mut checkSum = checkSum;
-    checkSum %= 103;
-    patterns.push(
-        code_128_reader::CODE_PATTERNS[checkSum]
-            .iter()
-            .map(|x| *x as usize)
-            .collect(),
-    );
+fn produceRXingResult(symbols: &[u8]) -> Result<Vec<bool>, Exceptions> {
+    // Get the patterns, then compute and append checksum
+    let mut patterns: Vec<Vec<usize>> = symbols
+        .iter()
+        .chain(std::iter::once(&check_digits::code128_checksum(symbols)?))
+        .map(|symbol| {
+            code_128_reader::CODE_PATTERNS[*symbol as usize]
+                .iter()
+                .map(|x| *x as usize)
+                .collect()
+        })
+        .collect();
 
     // Append stop code
     patterns.push(
@@ -307,7 +313,7 @@ fn produceRXingResult(patterns: &mut Vec<Vec<usize>>, checkSum: usize) -> Vec<bo
 
     // Compute code width
     let mut codeWidth = 0_usize;
-    for pattern in &mut *patterns {
+    for pattern in &patterns {
         // for (int[] pattern : patterns) {
         for width in pattern {
             // for (int width : pattern) {
@@ -320,10 +326,10 @@ fn produceRXingResult(patterns: &mut Vec<Vec<usize>>, checkSum: usize) -> Vec<bo
     let mut pos = 0;
     for pattern in patterns {
         // for (int[] pattern : patterns) {
-        pos += Code128Writer::appendPattern(&mut result, pos, pattern, true) as usize;
+        pos += Code128Writer::appendPattern(&mut result, pos, &pattern, true) as usize;
     }
 
-    result
+    Ok(result)
 }
 
 fn findCType(value: &str, start: usize) -> CType {
@@ -429,7 +435,7 @@ fn chooseCode(value: &str, start: usize, oldCode: usize) -> usize {
 //    minPath:Vec<Vec<Latch>>,
 // }
 mod MinimalEncoder {
-    use crate::{oned::code_128_reader, Exceptions};
+    use crate::Exceptions;
 
     use super::{
         produceRXingResult, CODE_CODE_A, CODE_CODE_B, CODE_CODE_C, CODE_FNC_1, CODE_FNC_2,
@@ -470,9 +476,7 @@ stuvwxyz{|}~\u{007F}\u{00FF}";
 
         encode_with_start_position(contents, Charset::None, 0, &mut memoizedCost, &mut minPath)?;
 
-        let mut patterns: Vec<Vec<usize>> = Vec::new(); //new ArrayList<>();
-        let mut checkSum = vec![0_usize]; //new int[] {0};
-        let mut checkWeight = vec![1]; //new int[] {1};
+        let mut symbols: Vec<u8> = Vec::new(); //new ArrayList<>();
         let mut charset = Charset::None;
         let mut i = 0;
         while i < length {
@@ -483,51 +487,30 @@ stuvwxyz{|}~\u{007F}\u{00FF}";
                 Latch::A => {
                     charset = Charset::A;
                     addPattern(
-                        &mut patterns,
+                        &mut symbols,
                         if i == 0 { CODE_START_A } else { CODE_CODE_A },
-                        &mut checkSum,
-                        &mut checkWeight,
-                        i,
                     );
                 }
                 Latch::B => {
                     charset = Charset::B;
                     addPattern(
-                        &mut patterns,
+                        &mut symbols,
                         if i == 0 { CODE_START_B } else { CODE_CODE_B },
-                        &mut checkSum,
-                        &mut checkWeight,
-                        i,
                     );
                 }
                 Latch::C => {
                     charset = Charset::C;
                     addPattern(
-                        &mut patterns,
+                        &mut symbols,
                         if i == 0 { CODE_START_C } else { CODE_CODE_C },
-                        &mut checkSum,
-                        &mut checkWeight,
-                        i,
                     );
                 }
-                Latch::Shift => addPattern(
-                    &mut patterns,
-                    CODE_SHIFT,
-                    &mut checkSum,
-                    &mut checkWeight,
-                    i,
-                ),
+                Latch::Shift => addPattern(&mut symbols, CODE_SHIFT),
                 Latch::None => { /* skip */ }
             }
             if charset == Charset::C {
                 if contents.chars().nth(i).unwrap() == ESCAPE_FNC_1 {
-                    addPattern(
-                        &mut patterns,
-                        CODE_FNC_1,
-                        &mut checkSum,
-                        &mut checkWeight,
-                        i,
-                    );
+                    addPattern(&mut symbols, CODE_FNC_1);
                 } else {
                     let s: String = contents
                         .char_indices()
@@ -535,13 +518,7 @@ stuvwxyz{|}~\u{007F}\u{00FF}";
                         .take(2)
                         .map(|(_u, c)| c)
                         .collect();
-                    addPattern(
-                        &mut patterns,
-                        s.parse::<usize>().unwrap(),
-                        &mut checkSum,
-                        &mut checkWeight,
-                        i,
-                    );
+                    addPattern(&mut symbols, s.parse::<usize>().unwrap());
                     assert!(i + 1 < length); //the algorithm never leads to a single trailing digit in character set C
                     if i + 1 < length {
                         i += 1;
@@ -570,13 +547,7 @@ stuvwxyz{|}~\u{007F}\u{00FF}";
                 {
                     patternIndex += '`' as isize;
                 }
-                addPattern(
-                    &mut patterns,
-                    patternIndex as usize,
-                    &mut checkSum,
-                    &mut checkWeight,
-                    i,
-                );
+                addPattern(&mut symbols, patternIndex as usize);
             }
 
             i += 1;
@@ -584,26 +555,11 @@ stuvwxyz{|}~\u{007F}\u{00FF}";
         // memoizedCost.clear();
         // minPath.clear();
 
-        Ok(produceRXingResult(&mut patterns, checkSum[0]))
+        produceRXingResult(&symbols)
     }
 
-    fn addPattern(
-        patterns: &mut Vec<Vec<usize>>,
-        patternIndex: usize,
-        checkSum: &mut [usize],
-        checkWeight: &mut [u32],
-        position: usize,
-    ) {
-        patterns.push(
-            code_128_reader::CODE_PATTERNS[patternIndex]
-                .iter()
-                .map(|x| *x as usize)
-                .collect(),
-        );
-        if position != 0 {
-            checkWeight[0] += 1;
-        }
-        checkSum[0] += patternIndex * checkWeight[0] as usize;
+    fn addPattern(symbols: &mut Vec<u8>, patternIndex: usize) {
+        symbols.push(patternIndex as u8);
     }
 
     fn isDigit(c: char) -> bool {
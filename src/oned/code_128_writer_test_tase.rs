@@ -37,9 +37,11 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 
 use crate::{
-    common::{bit_matrix_test_case, BitMatrix},
+    common::{bit_matrix_test_case, BitArray, BitMatrix},
     oned::{Code128Reader, OneDReader},
-    BarcodeFormat, EncodeHintType, EncodeHintValue, EncodingHintDictionary, Exceptions, Writer,
+    BarcodeFormat, DecodeHintType, DecodeHintValue, DecodingHintDictionary, EncodeHintType,
+    EncodeHintValue, EncodingHintDictionary, Exceptions, RXingResultMetadataType,
+    RXingResultMetadataValue, Writer,
 };
 
 use super::Code128Writer;
@@ -143,6 +145,36 @@ fn testRoundtrip() {
     assert_eq!(width, encRXingResult.getWidth() + 11);
 }
 
+#[test]
+fn testFnc1Separator() {
+    let toEncode = concat!("\u{00f1}", "10958", "\u{00f1}", "17160526");
+    let row = WRITER
+        .encode(toEncode, &BarcodeFormat::CODE_128, 0, 0)
+        .expect("encode")
+        .getRow(0);
+
+    let decode = |separator: Option<&str>| {
+        let mut hints: DecodingHintDictionary = HashMap::new();
+        hints.insert(DecodeHintType::ASSUME_GS1, DecodeHintValue::AssumeGs1(true));
+        if let Some(separator) = separator {
+            hints.insert(
+                DecodeHintType::CODE_128_FNC1_SEPARATOR,
+                DecodeHintValue::Code128Fnc1Separator(separator.to_owned()),
+            );
+        }
+        Code128Reader
+            .decodeRow(0, &row, &hints)
+            .expect("decode")
+            .getText()
+            .to_owned()
+    };
+
+    assert_eq!("]C110958\u{1d}17160526", decode(None));
+    assert_eq!("]C110958\u{1d}17160526", decode(Some("\u{1d}")));
+    assert_eq!("]C110958~17160526", decode(Some("~")));
+    assert_eq!("]C11095817160526", decode(Some("")));
+}
+
 #[test]
 fn testLongCompact() {
     //test longest possible input
@@ -469,6 +501,95 @@ fn testEncodeWithForcedCodeSetFailureCodeSetB() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn testEncodeRowPicksCodeSetC() {
+    let row = Code128Writer::encode("00012345678905").expect("encode");
+    let pattern: String = row.iter().map(|bar| if *bar { '1' } else { '0' }).collect();
+    // Start C, 7 digit pairs, check symbol and stop pattern
+    assert!(pattern.starts_with(START_CODE_C));
+    assert!(pattern.ends_with(STOP));
+    assert_eq!(11 + 7 * 11 + 11 + 13, row.len());
+
+    let result = Code128Reader
+        .decodeRow(0, &toBitArray(&row), &HashMap::new())
+        .expect("decode");
+    assert_eq!("00012345678905", result.getText());
+}
+
+#[test]
+fn testEncodeRowGS1() {
+    let row = Code128Writer::encode(concat!("\u{00f1}", "0100012345678905", "\u{00f1}", "10ABC"))
+        .expect("encode");
+    let hints = HashMap::from([(DecodeHintType::ASSUME_GS1, DecodeHintValue::AssumeGs1(true))]);
+    let result = Code128Reader
+        .decodeRow(0, &toBitArray(&row), &hints)
+        .expect("decode");
+    assert_eq!("]C10100012345678905\u{1d}10ABC", result.getText());
+}
+
+#[test]
+fn testEncodeRowRejectsUnsupportedCharacters() {
+    assert!(Code128Writer::encode("caf\u{00e9}").is_err());
+    assert!(Code128Writer::encode("").is_err());
+}
+
+#[test]
+fn testDecodeFnc3SetsReaderInitialization() {
+    let row = Code128Writer::encode("\u{00f3}123").expect("encode");
+    let result = Code128Reader
+        .decodeRow(0, &toBitArray(&row), &HashMap::new())
+        .expect("decode");
+    assert_eq!("123", result.getText());
+    let metadata = result.getRXingResultMetadata();
+    assert_eq!(
+        Some(&RXingResultMetadataValue::Code128ReaderInitialization(true)),
+        metadata.get(&RXingResultMetadataType::CODE_128_READER_INITIALIZATION)
+    );
+    assert!(!metadata.contains_key(&RXingResultMetadataType::CODE_128_MESSAGE_APPEND));
+}
+
+#[test]
+fn testJoinAppendedMessages() {
+    let decode = |contents: &str| {
+        Code128Reader
+            .decodeRow(
+                0,
+                &toBitArray(&Code128Writer::encode(contents).expect("encode")),
+                &HashMap::new(),
+            )
+            .expect("decode")
+    };
+    let first = decode("\u{00f2}PART1");
+    assert_eq!("PART1", first.getText());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::Code128MessageAppend(true)),
+        first
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::CODE_128_MESSAGE_APPEND)
+    );
+
+    let results = [
+        first,
+        decode("\u{00f2}PART2"),
+        decode("END"),
+        decode("SINGLE"),
+    ];
+    assert_eq!(
+        vec!["PART1PART2END", "SINGLE"],
+        Code128Reader::joinAppendedMessages(&results)
+    );
+}
+
+fn toBitArray(row: &[bool]) -> BitArray {
+    let mut bits = BitArray::with_size(row.len() + 20);
+    for (i, bar) in row.iter().enumerate() {
+        if *bar {
+            bits.set(i + 10);
+        }
+    }
+    bits
+}
+
 fn encode(toEncode: &str, compact: bool, expectedLoopback: &str) -> Result<BitMatrix, Exceptions> {
     let mut reader = Code128Reader::default();
 
@@ -14,12 +14,13 @@
  * limitations under the License.
  */
 
-use rxing_one_d_proc_derive::OneDReader;
-
 use crate::common::BitArray;
-use crate::{BarcodeFormat, Exceptions, RXingResult};
+use crate::{
+    BarcodeFormat, DecodeHintType, DecodingHintDictionary, Exceptions, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint,
+};
 
-use super::{one_d_reader, OneDReader};
+use super::{code39_mod43, one_d_reader, OneDReader};
 
 /**
  * <p>Decodes Code 39 barcodes. Supports "Full ASCII Code 39" if USE_CODE_39_EXTENDED_MODE is set.</p>
@@ -27,7 +28,6 @@ use super::{one_d_reader, OneDReader};
  * @author Sean Owen
  * @see Code93Reader
  */
-#[derive(OneDReader)]
 pub struct Code39Reader {
     usingCheckDigit: bool,
     extendedMode: bool,
@@ -44,8 +44,9 @@ impl OneDReader for Code39Reader {
         &mut self,
         rowNumber: u32,
         row: &crate::common::BitArray,
-        _hints: &DecodingHintDictionary,
+        hints: &DecodingHintDictionary,
     ) -> Result<crate::RXingResult, Exceptions> {
+        let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
         // let theCounters = self.counters;
         let mut counters = [0_u32; 9];
         // self.counters.fill(0);
@@ -53,26 +54,49 @@ impl OneDReader for Code39Reader {
         // result.setLength(0);
         self.decodeRowRXingResult.clear();
 
-        let start = Self::findAsteriskPattern(row, &mut counters)?;
+        let relaxQuietZone = one_d_reader::relaxesQuietZone(hints);
+        let start = Self::findAsteriskPattern(row, &mut counters, relaxQuietZone)?;
+        // 6 narrow and 3 wide elements, wide ones being 2 to 3 times as wide
+        if !one_d_reader::matchesXDimension(hints, (start[1] - start[0]) as usize, 12, 15) {
+            return Err(Exceptions::NotFoundException(None));
+        }
         // Read off white space
         let mut nextStart = row.getNextSet(start[1] as usize);
         let end = row.getSize();
 
         let mut decodedChar;
         let mut lastStart;
+        let mut lastPatternSize;
         loop {
             one_d_reader::recordPattern(row, nextStart, &mut counters)?;
             let pattern = Self::toNarrowWidePattern(&counters);
-            if pattern < 0 {
-                return Err(Exceptions::NotFoundException(None));
+            decodedChar = if pattern < 0 {
+                None
+            } else {
+                Self::patternToChar(pattern as u32).ok()
+            };
+            lastPatternSize = counters.iter().sum::<u32>();
+            if decodedChar.is_none() && tryHarder {
+                // The inter-character gap may have been lost, merging our last bar with the
+                // first bar of the next character
+                if let Some((pattern, lastBarAndGap)) = Self::splitMergedGap(&counters) {
+                    decodedChar = Self::patternToChar(pattern).ok();
+                    lastPatternSize = counters[..8].iter().sum::<u32>() + lastBarAndGap;
+                }
+            }
+            if decodedChar.is_none() && tryHarder {
+                // Or a spurious gap may have split one of our bars in two
+                if let Some((character, size)) = Self::mergeSpuriousGap(row, nextStart) {
+                    decodedChar = Some(character);
+                    lastPatternSize = size;
+                }
             }
-            decodedChar = Self::patternToChar(pattern as u32)?;
+            let Some(decodedChar) = decodedChar else {
+                return Err(Exceptions::NotFoundException(None));
+            };
             self.decodeRowRXingResult.push(decodedChar);
             lastStart = nextStart;
-            for counter in &counters {
-                // for (int counter : theCounters) {
-                nextStart += *counter as usize;
-            }
+            nextStart += lastPatternSize as usize;
             // Read off white space
             nextStart = row.getNextSet(nextStart);
 
@@ -84,33 +108,21 @@ impl OneDReader for Code39Reader {
             .truncate(self.decodeRowRXingResult.len() - 1); // remove asterisk
 
         // Look for whitespace after pattern:
-        let mut lastPatternSize = 0;
-        for counter in &counters {
-            // for (int counter : self.counters) {
-            lastPatternSize += *counter;
-        }
         let whiteSpaceAfterEnd = nextStart - lastStart - lastPatternSize as usize;
         // If 50% of last pattern size, following last pattern, is not whitespace, fail
-        // (but if it's whitespace to the very end of the image, that's OK)
-        if nextStart != end && (whiteSpaceAfterEnd * 2) < lastPatternSize as usize {
+        // (but if it's whitespace to the very end of the image, or the quiet zone is relaxed,
+        // that's OK)
+        if nextStart != end
+            && !relaxQuietZone
+            && (whiteSpaceAfterEnd * 2) < lastPatternSize as usize
+        {
             return Err(Exceptions::NotFoundException(None));
         }
 
         if self.usingCheckDigit {
             let max = self.decodeRowRXingResult.chars().count() - 1;
-            let mut total = 0;
-            for i in 0..max {
-                // for (int i = 0; i < max; i++) {
-                if let Some(pos) =
-                    Self::ALPHABET_STRING.find(self.decodeRowRXingResult.chars().nth(i).unwrap())
-                {
-                    total += pos;
-                }
-                // total += Self::ALPHABET_STRING.indexOf(self.decodeRowRXingResult.chars().nth(i).unwrap());
-            }
-            if self.decodeRowRXingResult.chars().nth(max).unwrap()
-                != Self::ALPHABET_STRING.chars().nth(total % 43).unwrap()
-            {
+            let data: String = self.decodeRowRXingResult.chars().take(max).collect();
+            if self.decodeRowRXingResult.chars().nth(max) != code39_mod43(&data).ok() {
                 return Err(Exceptions::NotFoundException(None));
             }
             self.decodeRowRXingResult.truncate(max);
@@ -128,7 +140,7 @@ impl OneDReader for Code39Reader {
         };
 
         let left = (start[1] + start[0]) as f32 / 2.0;
-        let right = (lastStart + lastPatternSize as usize) as f32 / 2.0;
+        let right = lastStart as f32 + lastPatternSize as f32 / 2.0;
 
         let mut resultObject = RXingResult::new(
             &resultString,
@@ -148,6 +160,9 @@ impl OneDReader for Code39Reader {
         Ok(resultObject)
     }
 }
+
+impl_one_d_reader!(Code39Reader);
+
 impl Code39Reader {
     pub const ALPHABET_STRING: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
 
@@ -204,7 +219,11 @@ impl Code39Reader {
         }
     }
 
-    fn findAsteriskPattern(row: &BitArray, counters: &mut [u32]) -> Result<Vec<u32>, Exceptions> {
+    fn findAsteriskPattern(
+        row: &BitArray,
+        counters: &mut [u32],
+        relaxQuietZone: bool,
+    ) -> Result<Vec<u32>, Exceptions> {
         let width = row.getSize();
         let rowOffset = row.getNextSet(0);
 
@@ -219,15 +238,18 @@ impl Code39Reader {
                 counters[counterPosition] += 1;
             } else {
                 if counterPosition == patternLength - 1 {
-                    // Look for whitespace before start pattern, >= 50% of width of start pattern
+                    // Look for whitespace before start pattern, >= 50% of width of start pattern,
+                    // unless the quiet zone is relaxed
                     if Self::toNarrowWidePattern(counters) == (Self::ASTERISK_ENCODING as i32)
-                        && row.isRange(
-                            0.max(
-                                patternStart as isize - ((i as isize - patternStart as isize) / 2),
-                            ) as usize,
-                            patternStart,
-                            false,
-                        )?
+                        && (relaxQuietZone
+                            || row.isRange(
+                                0.max(
+                                    patternStart as isize
+                                        - ((i as isize - patternStart as isize) / 2),
+                                ) as usize,
+                                patternStart,
+                                false,
+                            )?)
                     {
                         return Ok(vec![patternStart as u32, i as u32]);
                         // return new int[]{patternStart, i};
@@ -303,6 +325,80 @@ impl Code39Reader {
         -1
     }
 
+    /**
+     * Attempts to read a character whose final bar has run into the first bar of the next
+     * character, because the inter-character gap between them was lost. The first eight
+     * elements decide whether the last bar must be narrow or wide, since every character has
+     * exactly three wide elements. The widths of the last bar and of the lost gap, nominally
+     * one narrow element, are then estimated from the other elements.
+     *
+     * @param counters the nine element widths, the last of which is too wide
+     * @return the character pattern and the estimated width of its last bar plus the gap, if
+     *  the last element is wide enough to also hold the next character's first bar
+     */
+    fn splitMergedGap(counters: &[u32; 9]) -> Option<(u32, u32)> {
+        let first = &counters[..8];
+        let mut trial = *counters;
+        for lastWide in [false, true] {
+            trial[8] = if lastWide {
+                *first.iter().max()?
+            } else {
+                *first.iter().min()?
+            };
+            let pattern = Self::toNarrowWidePattern(&trial);
+            if pattern < 0 || (pattern & 1 == 1) != lastWide {
+                continue;
+            }
+            let averageWidth = |wide: bool| {
+                let (total, count) = first
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| ((pattern >> (8 - i)) & 1 == 1) == wide)
+                    .fold((0, 0), |(total, count), (_, width)| {
+                        (total + width, count + 1)
+                    });
+                (total + count / 2) / count
+            };
+            let consumed = averageWidth(lastWide) + averageWidth(false);
+            if consumed < counters[8] {
+                return Some((pattern as u32, consumed));
+            }
+        }
+        None
+    }
+
+    /**
+     * Attempts to read a character one of whose bars is split in two by a spurious gap, such as a
+     * scratch or a void in the print, which makes it read as eleven elements rather than nine.
+     * Each bar in turn is tried as the split one, as long as the gap in it is narrower than any
+     * element of the merged character.
+     *
+     * @param row row of black/white values to search
+     * @param start where the character starts
+     * @return the character and the width of all its elements, spurious gap included
+     */
+    fn mergeSpuriousGap(row: &BitArray, start: usize) -> Option<(char, u32)> {
+        let mut counters = [0_u32; 11];
+        one_d_reader::recordPattern(row, start, &mut counters).ok()?;
+        for bar in (0..9).step_by(2) {
+            let mut merged = [0_u32; 9];
+            merged[..bar].copy_from_slice(&counters[..bar]);
+            merged[bar] = counters[bar..bar + 3].iter().sum();
+            merged[bar + 1..].copy_from_slice(&counters[bar + 3..]);
+            if counters[bar + 1] >= *merged.iter().min()? {
+                continue;
+            }
+            let pattern = Self::toNarrowWidePattern(&merged);
+            if pattern < 0 {
+                continue;
+            }
+            if let Ok(character) = Self::patternToChar(pattern as u32) {
+                return Some((character, counters.iter().sum()));
+            }
+        }
+        None
+    }
+
     fn patternToChar(pattern: u32) -> Result<char, Exceptions> {
         for i in 0..Self::CHARACTER_ENCODINGS.len() {
             // for (int i = 0; i < CHARACTER_ENCODINGS.length; i++) {
@@ -421,3 +517,107 @@ mod code_39_extended_mode_test_case {
         assert_eq!(expectedRXingResult, result.getText());
     }
 }
+
+#[cfg(test)]
+mod Code39ReaderTestCase {
+    use std::collections::HashMap;
+
+    use crate::{
+        common::BitArray,
+        oned::{Code39Writer, OneDReader, OneDimensionalCodeWriter},
+        DecodeHintType, DecodeHintValue,
+    };
+
+    use super::Code39Reader;
+
+    const MODULE: usize = 3;
+    const QUIET_ZONE: usize = 15;
+
+    /**
+     * Renders the contents, filling in the inter-character gap which follows the given character.
+     */
+    fn row_without_gap_after(contents: &str, character: usize) -> BitArray {
+        let mut modules = Code39Writer.encode_oned(contents).expect("encode");
+        // Each character, including the start character, is 12 modules followed by a 1 module gap
+        modules[13 * (character + 1) + 12] = true;
+        row_of(modules)
+    }
+
+    fn row_of(modules: Vec<bool>) -> BitArray {
+        let mut row = BitArray::with_size((modules.len() + 2 * QUIET_ZONE) * MODULE);
+        for (i, black) in modules.into_iter().enumerate() {
+            if black {
+                row.setRange((QUIET_ZONE + i) * MODULE, (QUIET_ZONE + i + 1) * MODULE)
+                    .expect("set");
+            }
+        }
+        row
+    }
+
+    #[test]
+    fn testMissingInterCharacterGap() {
+        let row = row_without_gap_after("CODE39", 1);
+        assert!(Code39Reader::default()
+            .decodeRow(0, &row, &HashMap::new())
+            .is_err());
+
+        let hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+        let result = Code39Reader::default()
+            .decodeRow(0, &row, &hints)
+            .expect("decode");
+        assert_eq!("CODE39", result.getText());
+
+        // '1' ends with a wide bar
+        let row = row_without_gap_after("1234", 0);
+        let result = Code39Reader::default()
+            .decodeRow(0, &row, &hints)
+            .expect("decode");
+        assert_eq!("1234", result.getText());
+    }
+
+    /**
+     * Renders the contents, cutting a one pixel gap into the middle of the given wide bar of the
+     * given character, which then reads as two bars with an extra gap between them.
+     */
+    fn row_with_split_bar(contents: &str, character: usize, wideBar: usize) -> BitArray {
+        let modules = Code39Writer.encode_oned(contents).expect("encode");
+        let start = 13 * (character + 1);
+        // Wide elements are two modules, narrow ones one, so a wide bar has a second black module
+        let split = (start..start + 12)
+            .filter(|&i| modules[i] && modules[i + 1] && (i == start || !modules[i - 1]))
+            .nth(wideBar)
+            .expect("wide bar");
+        let mut row = row_of(modules);
+        row.flip((QUIET_ZONE + split + 1) * MODULE);
+        row
+    }
+
+    #[test]
+    fn testExtraGapInCharacter() {
+        let hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+        for character in 0..6 {
+            let row = row_with_split_bar("CODE39", character, 0);
+            assert!(Code39Reader::default()
+                .decodeRow(0, &row, &HashMap::new())
+                .is_err());
+
+            let result = Code39Reader::default()
+                .decodeRow(0, &row, &hints)
+                .expect("decode");
+            assert_eq!("CODE39", result.getText());
+        }
+    }
+
+    #[test]
+    fn testEndPointsAtPatternCenters() {
+        let modules = Code39Writer.encode_oned("CODE39").expect("encode");
+        let length = modules.len();
+        let result = Code39Reader::default()
+            .decodeRow(0, &row_of(modules), &HashMap::new())
+            .expect("decode");
+        let points = result.getRXingResultPoints();
+        // The start and stop characters are 12 modules wide
+        assert_eq!(((QUIET_ZONE + 6) * MODULE) as f32, points[0].x);
+        assert_eq!(((QUIET_ZONE + length - 6) * MODULE) as f32, points[1].x);
+    }
+}
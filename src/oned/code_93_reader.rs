@@ -14,9 +14,10 @@
  * limitations under the License.
  */
 
-use rxing_one_d_proc_derive::OneDReader;
-
-use crate::{common::BitArray, BarcodeFormat, Exceptions, RXingResult};
+use crate::{
+    common::BitArray, BarcodeFormat, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue, RXingResultPoint,
+};
 
 use super::{one_d_reader, OneDReader};
 
@@ -26,7 +27,6 @@ use super::{one_d_reader, OneDReader};
  * @author Sean Owen
  * @see Code39Reader
  */
-#[derive(OneDReader)]
 pub struct Code93Reader {
     decodeRowRXingResult: String,
     counters: [u32; 6],
@@ -46,9 +46,12 @@ impl OneDReader for Code93Reader {
         &mut self,
         rowNumber: u32,
         row: &crate::common::BitArray,
-        _hints: &crate::DecodingHintDictionary,
+        hints: &crate::DecodingHintDictionary,
     ) -> Result<crate::RXingResult, Exceptions> {
         let start = self.findAsteriskPattern(row)?;
+        if !one_d_reader::matchesXDimension(hints, start[1] - start[0], 9, 9) {
+            return Err(Exceptions::NotFoundException(None));
+        }
         // Read off white space
         let mut nextStart = row.getNextSet(start[1]);
         let end = row.getSize();
@@ -129,6 +132,8 @@ impl OneDReader for Code93Reader {
     }
 }
 
+impl_one_d_reader!(Code93Reader);
+
 impl Code93Reader {
     // Note that 'abcd' are dummy characters in place of control characters.
     pub const ALPHABET_STRING: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%abcd*";
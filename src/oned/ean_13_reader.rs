@@ -14,8 +14,6 @@
  * limitations under the License.
  */
 
-use rxing_one_d_proc_derive::{EANReader, OneDReader};
-
 use super::UPCEANReader;
 
 use super::upc_ean_reader;
@@ -31,8 +29,23 @@ use crate::Exceptions;
  * @author Sean Owen
  * @author alasdair@google.com (Alasdair Mackintosh)
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(Default)]
 pub struct EAN13Reader;
+
+impl OneDReader for EAN13Reader {
+    fn decodeRow(
+        &mut self,
+        rowNumber: u32,
+        row: &crate::common::BitArray,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<crate::RXingResult, Exceptions> {
+        let startGuardRange = self.findStartGuardPatternWithHints(row, hints)?;
+        self.decodeRowWithGuardRange(rowNumber, row, &startGuardRange, hints)
+    }
+}
+
+impl_one_d_reader!(EAN13Reader);
+
 impl UPCEANReader for EAN13Reader {
     fn getBarcodeFormat(&self) -> crate::BarcodeFormat {
         BarcodeFormat::EAN_13
@@ -43,7 +56,16 @@ impl UPCEANReader for EAN13Reader {
         row: &crate::common::BitArray,
         startRange: &[usize; 2],
         resultString: &mut String,
-    ) -> Result<usize, crate::Exceptions> {
+    ) -> Result<usize, Exceptions> {
+        Ok(self.decodeMiddleWithGuard(row, startRange, resultString)?.0)
+    }
+
+    fn decodeMiddleWithGuard(
+        &self,
+        row: &crate::common::BitArray,
+        startRange: &[usize; 2],
+        resultString: &mut String,
+    ) -> Result<(usize, Option<[usize; 2]>), crate::Exceptions> {
         let mut counters = [0_u32; 4]; //decodeMiddleCounters;
                                        // counters[0] = 0;
                                        // counters[1] = 0;
@@ -97,7 +119,7 @@ impl UPCEANReader for EAN13Reader {
             x += 1;
         }
 
-        Ok(rowOffset)
+        Ok((rowOffset, Some(middleRange)))
     }
 }
 impl EAN13Reader {
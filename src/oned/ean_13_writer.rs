@@ -32,6 +32,22 @@ use super::{OneDimensionalCodeWriter, UPCEANReader, UPCEANWriter};
 pub struct EAN13Writer;
 impl UPCEANWriter for EAN13Writer {}
 
+impl EAN13Writer {
+    /**
+     * Encodes a GTIN-13 as a row of 95 modules, from the start guard to the end guard, without
+     * quiet zones. The leading digit sets the L and G parities of the left half.
+     *
+     * @param contents 12 digits, to which the check digit is added, or 13 digits with a valid
+     *  check digit
+     * @return the modules of the row, true for bars
+     * @throws IllegalArgumentException if contents has the wrong length, non-digits or a wrong
+     *  check digit
+     */
+    pub fn encode(contents: &str) -> Result<Vec<bool>, crate::Exceptions> {
+        EAN13Writer.encode_oned(contents)
+    }
+}
+
 impl OneDimensionalCodeWriter for EAN13Writer {
     fn encode_oned(&self, contents: &str) -> Result<Vec<bool>, crate::Exceptions> {
         let reader: EAN13Reader = EAN13Reader::default();
@@ -134,7 +150,13 @@ const CODE_WIDTH: usize = 3 + // start guard
  */
 #[cfg(test)]
 mod EAN13WriterTestCase {
-    use crate::{common::bit_matrix_test_case, BarcodeFormat, Writer};
+    use std::collections::HashMap;
+
+    use crate::{
+        common::{bit_matrix_test_case, BitArray},
+        oned::{EAN13Reader, OneDReader},
+        BarcodeFormat, Writer,
+    };
 
     use super::EAN13Writer;
 
@@ -168,6 +190,29 @@ mod EAN13WriterTestCase {
         assert_eq!(testStr, bit_matrix_test_case::matrix_to_string(&result));
     }
 
+    #[test]
+    fn testEncodeRow() {
+        let row = EAN13Writer::encode("4006381333931").expect("encode");
+        assert_eq!(95, row.len());
+        assert_eq!(row, EAN13Writer::encode("400638133393").expect("encode"));
+
+        let mut bits = BitArray::with_size(row.len() + 20);
+        for (i, bar) in row.iter().enumerate() {
+            if *bar {
+                bits.set(i + 10);
+            }
+        }
+        let result = EAN13Reader
+            .decodeRow(0, &bits, &HashMap::new())
+            .expect("decode");
+        assert_eq!("4006381333931", result.getText());
+
+        // Wrong check digit, length and characters
+        assert!(EAN13Writer::encode("4006381333932").is_err());
+        assert!(EAN13Writer::encode("40063813339").is_err());
+        assert!(EAN13Writer::encode("40063813339a").is_err());
+    }
+
     #[test]
     #[should_panic]
     fn testEncodeIllegalCharacters() {
@@ -14,21 +14,33 @@
  * limitations under the License.
  */
 
-use super::OneDReader;
-use crate::{BarcodeFormat, Exceptions};
-use rxing_one_d_proc_derive::{EANReader, OneDReader};
-
 use super::upc_ean_reader;
+use super::OneDReader;
 use super::UPCEANReader;
+use crate::{BarcodeFormat, Exceptions};
 
 /**
  * <p>Implements decoding of the EAN-8 format.</p>
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(Default)]
 pub struct EAN8Reader;
 
+impl OneDReader for EAN8Reader {
+    fn decodeRow(
+        &mut self,
+        rowNumber: u32,
+        row: &crate::common::BitArray,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<crate::RXingResult, Exceptions> {
+        let startGuardRange = self.findStartGuardPatternWithHints(row, hints)?;
+        self.decodeRowWithGuardRange(rowNumber, row, &startGuardRange, hints)
+    }
+}
+
+impl_one_d_reader!(EAN8Reader);
+
 impl UPCEANReader for EAN8Reader {
     fn getBarcodeFormat(&self) -> crate::BarcodeFormat {
         BarcodeFormat::EAN_8
@@ -40,6 +52,15 @@ impl UPCEANReader for EAN8Reader {
         startRange: &[usize; 2],
         resultString: &mut String,
     ) -> Result<usize, Exceptions> {
+        Ok(self.decodeMiddleWithGuard(row, startRange, resultString)?.0)
+    }
+
+    fn decodeMiddleWithGuard(
+        &self,
+        row: &crate::common::BitArray,
+        startRange: &[usize; 2],
+        resultString: &mut String,
+    ) -> Result<(usize, Option<[usize; 2]>), Exceptions> {
         let mut counters = [0_u32; 4]; //decodeMiddleCounters;
                                        // counters[0] = 0;
                                        // counters[1] = 0;
@@ -80,6 +101,6 @@ impl UPCEANReader for EAN8Reader {
             x += 1;
         }
 
-        Ok(rowOffset)
+        Ok((rowOffset, Some(middleRange)))
     }
 }
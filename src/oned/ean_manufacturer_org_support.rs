@@ -75,6 +75,8 @@ impl EANManufacturerOrgSupport {
         if !self.ranges.is_empty() {
             return;
         }
+        // 020-029, 040-049 and 200-299 are for restricted circulation within a company or
+        // region, and 050-059 for coupons, so none of them identify a country
         self.add(vec![0, 19], "US/CA".to_owned());
         self.add(vec![30, 39], "US".to_owned());
         self.add(vec![60, 139], "US/CA".to_owned());
@@ -181,6 +183,8 @@ impl EANManufacturerOrgSupport {
         self.add(vec![940, 949], "AZ".to_owned());
         self.add(vec![955], "MY".to_owned());
         self.add(vec![958], "MO".to_owned());
+        // Books, identified by ISBN rather than by the country of the publisher
+        self.add(vec![978, 979], "Bookland".to_owned());
     }
 }
 
@@ -191,7 +195,13 @@ impl EANManufacturerOrgSupport {
  */
 #[cfg(test)]
 mod EANManufacturerOrgSupportTest {
-    use crate::oned::EANManufacturerOrgSupport;
+    use std::collections::HashMap;
+
+    use crate::{
+        oned::{EAN13Reader, EAN13Writer, EANManufacturerOrgSupport},
+        shared_test_methods::render_bitmap,
+        BarcodeFormat, RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
+    };
 
     #[test]
     fn testLookup() {
@@ -213,5 +223,39 @@ mod EANManufacturerOrgSupportTest {
             "GB",
             support.lookupCountryIdentifier("509000").expect("msg")
         );
+        assert_eq!(
+            "DE",
+            support.lookupCountryIdentifier("400638").expect("msg")
+        );
+        assert_eq!(
+            "Bookland",
+            support.lookupCountryIdentifier("978020").expect("msg")
+        );
+        assert_eq!(
+            "Bookland",
+            support.lookupCountryIdentifier("979100").expect("msg")
+        );
+        // Restricted circulation and coupon prefixes
+        assert!(support.lookupCountryIdentifier("020000").is_none());
+        assert!(support.lookupCountryIdentifier("050000").is_none());
+        assert!(support.lookupCountryIdentifier("250000").is_none());
+    }
+
+    #[test]
+    fn testPossibleCountryMetadata() {
+        for (contents, country) in [("4006381333931", "DE"), ("9780201379624", "Bookland")] {
+            let matrix = EAN13Writer
+                .encode(contents, &BarcodeFormat::EAN_13, 200, 1)
+                .expect("encode");
+            let mut image = render_bitmap(matrix.getWidth(), 20, |x, _| matrix.get(x, 0));
+            let result = EAN13Reader
+                .decode_with_hints(&mut image, &HashMap::new())
+                .expect("decode");
+            assert_eq!(contents, result.getText());
+            assert!(matches!(
+                result.getRXingResultMetadata().get(&RXingResultMetadataType::POSSIBLE_COUNTRY),
+                Some(RXingResultMetadataValue::PossibleCountry(c)) if c == country
+            ));
+        }
     }
 }
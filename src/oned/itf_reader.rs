@@ -14,9 +14,10 @@
  * limitations under the License.
  */
 
-use rxing_one_d_proc_derive::OneDReader;
-
-use crate::{common::BitArray, BarcodeFormat, DecodeHintValue, Exceptions, RXingResult};
+use crate::{
+    common::BitArray, BarcodeFormat, DecodeHintType, DecodeHintValue, Exceptions, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint,
+};
 
 use super::{one_d_reader, OneDReader};
 
@@ -86,16 +87,18 @@ const PATTERNS: [[u32; 5]; 20] = [
  *
  * @author kevin.osullivan@sita.aero, SITA Lab.
  */
-#[derive(OneDReader)]
 pub struct ITFReader {
     // Stores the actual narrow line width of the image being decoded.
     narrowLineWidth: i32,
+    // Whether the {@link DecodeHintType#RELAX_QUIET_ZONE} hint was given for the row being decoded.
+    relaxQuietZone: bool,
 }
 
 impl Default for ITFReader {
     fn default() -> Self {
         Self {
             narrowLineWidth: -1,
+            relaxQuietZone: false,
         }
     }
 }
@@ -107,14 +110,26 @@ impl OneDReader for ITFReader {
         row: &crate::common::BitArray,
         hints: &crate::DecodingHintDictionary,
     ) -> Result<crate::RXingResult, crate::Exceptions> {
+        self.relaxQuietZone = one_d_reader::relaxesQuietZone(hints);
         // Find out where the Middle section (payload) starts & ends
         let mut row = row.clone();
-        let startRange = self.decodeStart(&row)?;
-        let endRange = self.decodeEnd(&mut row)?;
-
-        let mut result = String::with_capacity(20); //new StringBuilder(20);
-        self.decodeMiddle(&row, startRange[1], endRange[0], &mut result)?;
-        let resultString = result; //.toString();
+        let mut searchFrom = Self::skipWhiteSpace(&row)?;
+        let mut lastError = None;
+        let (startRange, endRange, resultString) = loop {
+            // Bearer bars, frames and stray marks ahead of the symbol can look like a start
+            // pattern. If a candidate doesn't lead to a payload, try the next one to its right.
+            let Ok(startRange) = self.decodeStart(&row, searchFrom) else {
+                return Err(lastError.unwrap_or(Exceptions::NotFoundException(None)));
+            };
+            // The start pattern is made up of 4 narrow lines
+            if one_d_reader::matchesXDimension(hints, startRange[1] - startRange[0], 4, 4) {
+                match self.decodeFrom(&mut row, startRange) {
+                    Ok((endRange, resultString)) => break (startRange, endRange, resultString),
+                    Err(e) => lastError = Some(e),
+                }
+            }
+            searchFrom = row.getNextSet(row.getNextUnset(startRange[0]));
+        };
 
         let allowedLengths = if let Some(DecodeHintValue::AllowedLengths(al)) =
             hints.get(&DecodeHintType::ALLOWED_LENGTHS)
@@ -172,7 +187,33 @@ impl OneDReader for ITFReader {
         Ok(resultObject)
     }
 }
+
+impl_one_d_reader!(ITFReader);
+
 impl ITFReader {
+    /**
+     * Finds the end pattern and decodes the payload for a given start pattern candidate.
+     *
+     * @param row        row of black/white values to search
+     * @param startRange start/end offsets of the candidate start pattern
+     * @return the end pattern's offsets and the decoded payload
+     * @throws NotFoundException if decoding could not complete successfully
+     */
+    fn decodeFrom(
+        &mut self,
+        row: &mut BitArray,
+        startRange: [usize; 2],
+    ) -> Result<([usize; 2], String), Exceptions> {
+        let endRange = self.decodeEnd(row)?;
+        if endRange[0] <= startRange[1] {
+            return Err(Exceptions::NotFoundException(None));
+        }
+
+        let mut result = String::with_capacity(20); //new StringBuilder(20);
+        self.decodeMiddle(row, startRange[1], endRange[0], &mut result)?;
+        Ok((endRange, result))
+    }
+
     /**
      * @param row          row of black/white values to search
      * @param payloadStart offset of start pattern
@@ -224,24 +265,31 @@ impl ITFReader {
     }
 
     /**
-     * Identify where the start of the middle / payload section starts.
+     * Identify where the start of the middle / payload section starts. Candidates which are
+     * not preceded by a quiet zone, such as narrow bars inside a bearer frame or noise, are
+     * skipped and the search continues to their right.
      *
      * @param row row of black/white values to search
+     * @param rowOffset position of the black line to start searching at
      * @return Array, containing index of start of 'start block' and end of
      *         'start block'
+     * @throws NotFoundException if no start pattern with a quiet zone is found
      */
-    fn decodeStart(&mut self, row: &BitArray) -> Result<[usize; 2], Exceptions> {
-        let endStart = Self::skipWhiteSpace(row)?;
-        let startPattern = self.findGuardPattern(row, endStart, &START_PATTERN)?;
-
-        // Determine the width of a narrow line in pixels. We can do this by
-        // getting the width of the start pattern and dividing by 4 because its
-        // made up of 4 narrow lines.
-        self.narrowLineWidth = (startPattern[1] - startPattern[0]) as i32 / 4;
-
-        self.validateQuietZone(row, startPattern[0])?;
-
-        Ok(startPattern)
+    fn decodeStart(&mut self, row: &BitArray, rowOffset: usize) -> Result<[usize; 2], Exceptions> {
+        let mut rowOffset = rowOffset;
+        loop {
+            let startPattern = self.findGuardPattern(row, rowOffset, &START_PATTERN)?;
+
+            // Determine the width of a narrow line in pixels. We can do this by
+            // getting the width of the start pattern and dividing by 4 because its
+            // made up of 4 narrow lines.
+            self.narrowLineWidth = (startPattern[1] - startPattern[0]) as i32 / 4;
+
+            if self.validateQuietZone(row, startPattern[0]).is_ok() {
+                return Ok(startPattern);
+            }
+            rowOffset = row.getNextSet(row.getNextUnset(startPattern[0]));
+        }
     }
 
     /**
@@ -255,11 +303,16 @@ impl ITFReader {
      *
      * ref: http://www.barcode-1.net/i25code.html
      *
+     * With the {@link DecodeHintType#RELAX_QUIET_ZONE} hint no quiet zone is required.
+     *
      * @param row bit array representing the scanned barcode.
      * @param startPattern index into row of the start or end pattern.
      * @throws NotFoundException if the quiet zone cannot be found
      */
     fn validateQuietZone(&self, row: &BitArray, startPattern: usize) -> Result<(), Exceptions> {
+        if self.relaxQuietZone {
+            return Ok(());
+        }
         let mut quietCount = self.narrowLineWidth * 10; // expect to find this many pixels of quiet zone
 
         // if there are not so many pixel at all let's try as many as possible
@@ -431,3 +484,78 @@ impl ITFReader {
         }
     }
 }
+
+#[cfg(test)]
+mod ITFReaderTestCase {
+    use std::collections::HashMap;
+
+    use crate::{
+        common::BitArray,
+        oned::{ITFWriter, OneDReader, OneDimensionalCodeWriter},
+    };
+
+    use super::ITFReader;
+
+    const CONTENTS: &str = "30712345000010";
+    const MODULE: usize = 2;
+
+    fn row_of(leading: &[(bool, usize)], trailing: &[(bool, usize)]) -> BitArray {
+        let code = ITFWriter.encode_oned(CONTENTS).expect("encode");
+        let mut pixels = Vec::new();
+        for &(black, modules) in leading {
+            pixels.resize(pixels.len() + modules * MODULE, black);
+        }
+        for black in code {
+            pixels.resize(pixels.len() + MODULE, black);
+        }
+        for &(black, modules) in trailing {
+            pixels.resize(pixels.len() + modules * MODULE, black);
+        }
+        let mut row = BitArray::with_size(pixels.len());
+        for (i, black) in pixels.into_iter().enumerate() {
+            if black {
+                row.set(i);
+            }
+        }
+        row
+    }
+
+    #[test]
+    fn testLeadingNoiseLooksLikeStartPattern() {
+        // Narrow bars which match the start pattern, then a quiet zone before the real symbol
+        let row = row_of(
+            &[
+                (false, 4),
+                (true, 1),
+                (false, 1),
+                (true, 1),
+                (false, 1),
+                (true, 1),
+                (false, 20),
+            ],
+            &[(false, 20)],
+        );
+        let result = ITFReader::default()
+            .decodeRow(0, &row, &HashMap::new())
+            .expect("decode");
+        assert_eq!(CONTENTS, result.getText());
+    }
+
+    #[test]
+    fn testBearerBars() {
+        let row = row_of(&[(true, 12), (false, 15)], &[(false, 15), (true, 12)]);
+        let result = ITFReader::default()
+            .decodeRow(0, &row, &HashMap::new())
+            .expect("decode");
+        assert_eq!(CONTENTS, result.getText());
+    }
+
+    #[test]
+    fn testNoStartPatternWithQuietZone() {
+        // Noise too close to the symbol leaves no acceptable quiet zone
+        let row = row_of(&[(false, 4), (true, 1), (false, 3)], &[(false, 20)]);
+        assert!(ITFReader::default()
+            .decodeRow(0, &row, &HashMap::new())
+            .is_err());
+    }
+}
@@ -1,8 +1,12 @@
+#[macro_use]
 mod one_d_reader;
 pub mod rss;
 
 pub use one_d_reader::*;
 
+#[cfg(test)]
+mod one_d_reader_test_case;
+
 mod ean_manufacturer_org_support;
 pub use ean_manufacturer_org_support::*;
 
@@ -15,6 +19,9 @@ pub use code_39_reader::*;
 mod multi_format_one_d_reader;
 pub use multi_format_one_d_reader::*;
 
+#[cfg(test)]
+mod multi_format_one_d_reader_test_case;
+
 mod code_93_reader;
 pub use code_93_reader::*;
 
@@ -24,6 +31,18 @@ pub use code_128_reader::*;
 mod itf_reader;
 pub use itf_reader::*;
 
+mod pharmacode_reader;
+pub use pharmacode_reader::*;
+
+#[cfg(test)]
+mod pharmacode_reader_test_case;
+
+mod telepen_reader;
+pub use telepen_reader::*;
+
+#[cfg(test)]
+mod telepen_reader_test_case;
+
 mod upc_ean_reader;
 pub use upc_ean_reader::*;
 
@@ -56,6 +75,9 @@ pub use coda_bar_writer::*;
 mod multi_format_upc_ean_reader;
 pub use multi_format_upc_ean_reader::*;
 
+#[cfg(test)]
+mod multi_format_upc_ean_reader_test_case;
+
 mod code_39_writer;
 pub use code_39_writer::*;
 
@@ -85,3 +107,9 @@ pub use ean_8_writer::*;
 
 mod upc_e_writer;
 pub use upc_e_writer::*;
+
+mod check_digits;
+pub use check_digits::*;
+
+#[cfg(test)]
+mod check_digits_test_case;
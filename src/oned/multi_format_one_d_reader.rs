@@ -23,6 +23,8 @@ use super::Code93Reader;
 use super::ITFReader;
 use super::MultiFormatUPCEANReader;
 use super::OneDReader;
+use super::PharmacodeReader;
+use super::TelepenReader;
 use crate::BarcodeFormat;
 use crate::DecodeHintValue;
 use crate::Exceptions;
@@ -53,48 +55,55 @@ impl OneDReader for MultiFormatOneDReader {
 
         Err(Exceptions::NotFoundException(None))
     }
+
+    #[cfg(feature = "diagnostics")]
+    fn decode_row_with_errors(
+        &mut self,
+        rowNumber: u32,
+        row: &crate::common::BitArray,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<crate::RXingResult, crate::Exceptions> {
+        let mut error = Exceptions::NotFoundException(None);
+        for reader in self.0.iter_mut() {
+            match reader.decode_row_with_errors(rowNumber, row, hints) {
+                Ok(res) => return Ok(res),
+                Err(e) => error = super::one_d_reader::moreSpecificError(error, e),
+            }
+        }
+
+        Err(error)
+    }
 }
 impl MultiFormatOneDReader {
     // private static final OneDReader[] EMPTY_ONED_ARRAY = new OneDReader[0];
 
     // private final OneDReader[] readers;
 
+    /**
+     * The formats read when {@link DecodeHintType#POSSIBLE_FORMATS} names none of the 1D ones, by
+     * the readers {@link #new(Map)} falls back to.
+     */
+    pub(crate) const DEFAULT_FORMATS: [BarcodeFormat; 12] = [
+        BarcodeFormat::EAN_13,
+        BarcodeFormat::UPC_A,
+        BarcodeFormat::EAN_8,
+        BarcodeFormat::UPC_E,
+        BarcodeFormat::CODE_39,
+        BarcodeFormat::CODE_93,
+        BarcodeFormat::CODE_128,
+        BarcodeFormat::ITF,
+        BarcodeFormat::CODABAR,
+        BarcodeFormat::RSS_14,
+        BarcodeFormat::RSS_EXPANDED,
+        BarcodeFormat::TELEPEN,
+    ];
+
     pub fn new(hints: &DecodingHintDictionary) -> Self {
-        let useCode39CheckDigit = hints.contains_key(&DecodeHintType::ASSUME_CODE_39_CHECK_DIGIT);
-        let mut readers: Vec<Box<dyn OneDReader>> = Vec::new();
+        let mut readers = Vec::new();
         if let Some(DecodeHintValue::PossibleFormats(possibleFormats)) =
             hints.get(&DecodeHintType::POSSIBLE_FORMATS)
         {
-            if possibleFormats.contains(&BarcodeFormat::EAN_13)
-                || possibleFormats.contains(&BarcodeFormat::UPC_A)
-                || possibleFormats.contains(&BarcodeFormat::EAN_8)
-                || possibleFormats.contains(&BarcodeFormat::UPC_E)
-            {
-                readers.push(Box::new(MultiFormatUPCEANReader::new(hints)));
-            }
-            if possibleFormats.contains(&BarcodeFormat::CODE_39) {
-                readers.push(Box::new(Code39Reader::with_use_check_digit(
-                    useCode39CheckDigit,
-                )));
-            }
-            if possibleFormats.contains(&BarcodeFormat::CODE_93) {
-                readers.push(Box::<Code93Reader>::default());
-            }
-            if possibleFormats.contains(&BarcodeFormat::CODE_128) {
-                readers.push(Box::<Code128Reader>::default());
-            }
-            if possibleFormats.contains(&BarcodeFormat::ITF) {
-                readers.push(Box::<ITFReader>::default());
-            }
-            if possibleFormats.contains(&BarcodeFormat::CODABAR) {
-                readers.push(Box::<CodaBarReader>::default());
-            }
-            if possibleFormats.contains(&BarcodeFormat::RSS_14) {
-                readers.push(Box::<RSS14Reader>::default());
-            }
-            if possibleFormats.contains(&BarcodeFormat::RSS_EXPANDED) {
-                readers.push(Box::<RSSExpandedReader>::default());
-            }
+            readers = Self::readersFor(possibleFormats, hints);
         }
         if readers.is_empty() {
             readers.push(Box::new(MultiFormatUPCEANReader::new(hints)));
@@ -105,88 +114,68 @@ impl MultiFormatOneDReader {
             readers.push(Box::<ITFReader>::default());
             readers.push(Box::<RSS14Reader>::default());
             readers.push(Box::<RSSExpandedReader>::default());
+            readers.push(Box::<TelepenReader>::default());
         }
 
         Self(readers)
     }
+
+    fn readersFor(
+        possibleFormats: &HashSet<BarcodeFormat>,
+        hints: &DecodingHintDictionary,
+    ) -> Vec<Box<dyn OneDReader>> {
+        let useCode39CheckDigit = hints.contains_key(&DecodeHintType::ASSUME_CODE_39_CHECK_DIGIT);
+        let mut readers: Vec<Box<dyn OneDReader>> = Vec::new();
+        if possibleFormats.contains(&BarcodeFormat::EAN_13)
+            || possibleFormats.contains(&BarcodeFormat::UPC_A)
+            || possibleFormats.contains(&BarcodeFormat::EAN_8)
+            || possibleFormats.contains(&BarcodeFormat::UPC_E)
+        {
+            readers.push(Box::new(MultiFormatUPCEANReader::new(hints)));
+        }
+        if possibleFormats.contains(&BarcodeFormat::CODE_39) {
+            readers.push(Box::new(Code39Reader::with_use_check_digit(
+                useCode39CheckDigit,
+            )));
+        }
+        if possibleFormats.contains(&BarcodeFormat::CODE_93) {
+            readers.push(Box::<Code93Reader>::default());
+        }
+        if possibleFormats.contains(&BarcodeFormat::CODE_128) {
+            readers.push(Box::<Code128Reader>::default());
+        }
+        if possibleFormats.contains(&BarcodeFormat::ITF) {
+            readers.push(Box::<ITFReader>::default());
+        }
+        if possibleFormats.contains(&BarcodeFormat::CODABAR) {
+            readers.push(Box::<CodaBarReader>::default());
+        }
+        if possibleFormats.contains(&BarcodeFormat::RSS_14) {
+            readers.push(Box::<RSS14Reader>::default());
+        }
+        if possibleFormats.contains(&BarcodeFormat::RSS_EXPANDED) {
+            readers.push(Box::<RSSExpandedReader>::default());
+        }
+        if possibleFormats.contains(&BarcodeFormat::TELEPEN) {
+            readers.push(Box::<TelepenReader>::default());
+        }
+        // Any few evenly spaced bars read as Pharmacode, so it's only tried when asked for
+        if possibleFormats.contains(&BarcodeFormat::PHARMA_CODE) {
+            readers.push(Box::<PharmacodeReader>::default());
+        }
+        readers
+    }
 }
 
 use crate::DecodeHintType;
 use crate::DecodingHintDictionary;
-use crate::RXingResultMetadataType;
-use crate::RXingResultMetadataValue;
-use crate::Reader;
-use std::collections::HashMap;
+use std::collections::HashSet;
 
-impl Reader for MultiFormatOneDReader {
-    fn decode(
-        &mut self,
-        image: &mut crate::BinaryBitmap,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        self.decode_with_hints(image, &HashMap::new())
-    }
-
-    // Note that we don't try rotation without the try harder flag, even if rotation was supported.
-    fn decode_with_hints(
-        &mut self,
-        image: &mut crate::BinaryBitmap,
-        hints: &DecodingHintDictionary,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        if let Ok(res) = self.doDecode(image, hints) {
-            Ok(res)
-        } else {
-            let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
-            if tryHarder && image.isRotateSupported() {
-                let mut rotatedImage = image.rotateCounterClockwise();
-                let mut result = self.doDecode(&mut rotatedImage, hints)?;
-                // Record that we found it rotated 90 degrees CCW / 270 degrees CW
-                let metadata = result.getRXingResultMetadata();
-                let mut orientation = 270;
-                if metadata.contains_key(&RXingResultMetadataType::ORIENTATION) {
-                    // But if we found it reversed in doDecode(), add in that result here:
-                    orientation = (orientation
-                        + if let Some(crate::RXingResultMetadataValue::Orientation(or)) =
-                            metadata.get(&RXingResultMetadataType::ORIENTATION)
-                        {
-                            *or
-                        } else {
-                            0
-                        })
-                        % 360;
-                }
-                result.putMetadata(
-                    RXingResultMetadataType::ORIENTATION,
-                    RXingResultMetadataValue::Orientation(orientation),
-                );
-                // Update result points
-                // let points = result.getRXingResultPoints();
-                // if points != null {
-                let height = rotatedImage.getHeight();
-                // for point in result.getRXingResultPointsMut().iter_mut() {
-                let total_points = result.getRXingResultPoints().len();
-                let points = result.getRXingResultPointsMut();
-                for point in points.iter_mut().take(total_points) {
-                    // for i in 0..total_points {
-                    // for (int i = 0; i < points.length; i++) {
-                    std::mem::swap(&mut point.x, &mut point.y);
-                    point.x = height as f32 - point.x - 1.0;
-                    // points[i] = RXingResultPoint::new(
-                    //     height as f32 - points[i].getY() - 1.0,
-                    //     points[i].getX(),
-                    // );
-                }
-                // }
-
-                Ok(result)
-            } else {
-                Err(Exceptions::NotFoundException(None))
-            }
-        }
-    }
+impl_one_d_reader!(MultiFormatOneDReader {
     fn reset(&mut self) {
         for reader in self.0.iter_mut() {
             // for (Reader reader : readers) {
             reader.reset();
         }
     }
-}
+});
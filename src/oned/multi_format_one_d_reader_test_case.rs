@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitMatrix, shared_test_methods::render_bitmap, BarcodeFormat, BinaryBitmap,
+    DecodeHintType, DecodeHintValue, DecodingHintDictionary, EncodeHintType, EncodeHintValue,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
+};
+
+use super::{
+    CodaBarWriter, Code128Writer, Code39Writer, EAN13Reader, EAN13Writer, ITFWriter,
+    MultiFormatOneDReader, MultiFormatUPCEANReader,
+};
+
+/**
+ * Renders the matrix turned 90 degrees clockwise, so that its bars run horizontally.
+ */
+fn vertical_bitmap(matrix: &BitMatrix) -> BinaryBitmap {
+    render_bitmap(matrix.getHeight(), matrix.getWidth(), |x, y| {
+        matrix.get(y, matrix.getHeight() - 1 - x)
+    })
+}
+
+#[test]
+fn test_vertical_code_128() {
+    let matrix = Code128Writer
+        .encode("VERTICAL-128", &BarcodeFormat::CODE_128, 300, 60)
+        .expect("encode");
+    let mut image = vertical_bitmap(&matrix);
+
+    let mut reader = MultiFormatOneDReader::new(&HashMap::new());
+    assert!(reader.decode(&mut image).is_err());
+
+    let hints = HashMap::from([(
+        DecodeHintType::TRY_VERTICAL,
+        DecodeHintValue::TryVertical(true),
+    )]);
+    let mut reader = MultiFormatOneDReader::new(&hints);
+    let result = reader
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    assert_eq!("VERTICAL-128", result.getText());
+    assert_eq!(&BarcodeFormat::CODE_128, result.getBarcodeFormat());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::Orientation(270)),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ORIENTATION)
+    );
+    // Result points are mapped back into the unrotated image, along the barcode's column
+    for point in result.getRXingResultPoints() {
+        assert!(point.x < 60.0 && point.y < 300.0);
+    }
+}
+
+#[test]
+fn test_relax_quiet_zone_ean_13() {
+    // Rendered without margin, so the guard bars touch both edges of the image
+    let matrix = EAN13Writer
+        .encode_with_hints(
+            "5901234123457",
+            &BarcodeFormat::EAN_13,
+            1,
+            1,
+            &HashMap::from([(
+                EncodeHintType::MARGIN,
+                EncodeHintValue::Margin("0".to_owned()),
+            )]),
+        )
+        .expect("encode");
+    let image = || render_bitmap(matrix.getWidth() * 2, 40, |x, _| matrix.get(x / 2, 0));
+
+    let mut reader = MultiFormatOneDReader::new(&HashMap::new());
+    assert!(reader.decode(&mut image()).is_err());
+
+    let hints = HashMap::from([(
+        DecodeHintType::RELAX_QUIET_ZONE,
+        DecodeHintValue::RelaxQuietZone(true),
+    )]);
+    let mut reader = MultiFormatOneDReader::new(&hints);
+    let result = reader
+        .decode_with_hints(&mut image(), &hints)
+        .expect("decode");
+    assert_eq!("5901234123457", result.getText());
+    assert_eq!(&BarcodeFormat::EAN_13, result.getBarcodeFormat());
+}
+
+#[test]
+fn test_upc_ean_guard_points() {
+    let matrix = EAN13Writer
+        .encode_with_hints(
+            "5901234123457",
+            &BarcodeFormat::EAN_13,
+            1,
+            1,
+            &HashMap::from([(
+                EncodeHintType::MARGIN,
+                EncodeHintValue::Margin("0".to_owned()),
+            )]),
+        )
+        .expect("encode");
+    // The right half is printed wider than the left, so the middle guard isn't halfway along
+    let moduleWidth = |module: u32| if module < 47 { 2 } else { 3 };
+    let offset = |module: u32| 20 + (0..module).map(moduleWidth).sum::<u32>();
+    let mut row = vec![false; offset(matrix.getWidth()) as usize + 20];
+    for module in 0..matrix.getWidth() {
+        row[offset(module) as usize..offset(module + 1) as usize].fill(matrix.get(module, 0));
+    }
+    let image = || render_bitmap(row.len() as u32, 30, |x, _| row[x as usize]);
+
+    let mut reader = MultiFormatOneDReader::new(&HashMap::new());
+    let result = reader.decode(&mut image()).expect("decode");
+    assert_eq!(2, result.getRXingResultPoints().len());
+
+    let hints = HashMap::from([(
+        DecodeHintType::RETURN_UPC_EAN_GUARD_POINTS,
+        DecodeHintValue::ReturnUpcEanGuardPoints(true),
+    )]);
+    let mut reader = MultiFormatOneDReader::new(&hints);
+    let result = reader
+        .decode_with_hints(&mut image(), &hints)
+        .expect("decode");
+    assert_eq!("5901234123457", result.getText());
+    let points = result.getRXingResultPoints();
+    assert_eq!(5, points.len());
+    for pair in points.windows(2) {
+        assert!(pair[0].x < pair[1].x);
+        assert_eq!(pair[0].y, pair[1].y);
+    }
+    // The middle guard spans modules 45 to 49, after the start guard and six digits
+    let middle = (offset(45) + offset(50)) as f32 / 2.0;
+    assert!((points[2].x - middle).abs() <= 1.0);
+    assert!((points[2].x - (points[0].x + points[4].x) / 2.0).abs() > 5.0);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_append_upc_ean_extension() {
+    let image = image::io::Reader::open("test_resources/blackbox/upcean-extension-1/1.png")
+        .expect("image must open")
+        .decode()
+        .expect("must decode")
+        .into_luma8();
+    let bitmap = |pixels: Vec<u8>, width: u32| {
+        let height = pixels.len() as u32 / width;
+        BinaryBitmap::from_luma8(pixels, width, height).expect("bitmap")
+    };
+
+    let mut reader = MultiFormatOneDReader::new(&HashMap::new());
+    let result = reader
+        .decode(&mut bitmap(image.to_vec(), image.width()))
+        .expect("decode");
+    assert_eq!("9780735200449", result.getText());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::UpcEanExtension(
+            "51299".to_owned()
+        )),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::UPC_EAN_EXTENSION)
+    );
+
+    let hints = HashMap::from([(
+        DecodeHintType::APPEND_UPC_EAN_EXTENSION,
+        DecodeHintValue::AppendUpcEanExtension(true),
+    )]);
+    let mut reader = MultiFormatOneDReader::new(&hints);
+    let appended = reader
+        .decode_with_hints(&mut bitmap(image.to_vec(), image.width()), &hints)
+        .expect("decode");
+    assert_eq!("9780735200449 51299", appended.getText());
+    assert_eq!(&BarcodeFormat::EAN_13, appended.getBarcodeFormat());
+
+    // Pull the supplement away from the symbol by half the symbol's width
+    let points = result.getRXingResultPoints();
+    let split = ((points[1].x + points[2].x) / 2.0) as u32;
+    let extra = ((points[1].x - points[0].x) / 2.0) as u32;
+    let width = image.width() + extra;
+    let mut pixels = Vec::with_capacity((width * image.height()) as usize);
+    for row in image.rows() {
+        for (x, pixel) in row.enumerate() {
+            if x as u32 == split {
+                pixels.extend(std::iter::repeat_n(255, extra as usize));
+            }
+            pixels.push(pixel.0[0]);
+        }
+    }
+    let mut reader = MultiFormatOneDReader::new(&HashMap::new());
+    let apart = reader
+        .decode(&mut bitmap(pixels.clone(), width))
+        .expect("decode");
+    assert!(apart
+        .getRXingResultMetadata()
+        .contains_key(&RXingResultMetadataType::UPC_EAN_EXTENSION));
+    let mut reader = MultiFormatOneDReader::new(&hints);
+    let apart = reader
+        .decode_with_hints(&mut bitmap(pixels, width), &hints)
+        .expect("decode");
+    assert_eq!("9780735200449", apart.getText());
+    assert!(!apart
+        .getRXingResultMetadata()
+        .contains_key(&RXingResultMetadataType::UPC_EAN_EXTENSION));
+}
+
+#[test]
+fn test_rotation_attempts() {
+    let matrix = EAN13Writer
+        .encode("5901234123457", &BarcodeFormat::EAN_13, 190, 60)
+        .expect("encode");
+    // Printed vertically, the barcode is only reached by turning the image
+    let (width, height) = (matrix.getHeight(), matrix.getWidth());
+    let tryHarder = (DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true));
+    let noRotation = HashMap::from([
+        tryHarder.clone(),
+        (
+            DecodeHintType::ROTATION_ATTEMPTS,
+            DecodeHintValue::RotationAttempts(0),
+        ),
+    ]);
+    let oneRotation = HashMap::from([tryHarder]);
+
+    // Every 1D reader turns the image the same way, not only the multi-format one
+    let readers: Vec<Box<dyn Reader>> = vec![
+        Box::new(MultiFormatOneDReader::new(&noRotation)),
+        Box::new(MultiFormatUPCEANReader::new(&noRotation)),
+        Box::<EAN13Reader>::default(),
+    ];
+    for mut reader in readers {
+        // No attempts wins over TRY_HARDER
+        assert!(reader
+            .decode_with_hints(&mut vertical_bitmap(&matrix), &noRotation)
+            .is_err());
+
+        let result = reader
+            .decode_with_hints(&mut vertical_bitmap(&matrix), &oneRotation)
+            .expect("decode");
+        assert_eq!("5901234123457", result.getText());
+        assert_eq!(
+            Some(&RXingResultMetadataValue::Orientation(270)),
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::ORIENTATION)
+        );
+        // Result points are mapped back into the unrotated image
+        for point in result.getRXingResultPoints() {
+            assert!(point.x < width as f32 && point.y < height as f32);
+        }
+    }
+}
+
+#[test]
+fn test_one_rotation_attempt_covers_every_quadrant() {
+    let matrix = EAN13Writer
+        .encode("5901234123457", &BarcodeFormat::EAN_13, 190, 60)
+        .expect("encode");
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    let attempts = |attempts| {
+        HashMap::from([(
+            DecodeHintType::ROTATION_ATTEMPTS,
+            DecodeHintValue::RotationAttempts(attempts),
+        )])
+    };
+    let orientation = |image: &mut BinaryBitmap, hints: &DecodingHintDictionary| {
+        let result = MultiFormatOneDReader::new(hints)
+            .decode_with_hints(image, hints)
+            .expect("decode");
+        assert_eq!("5901234123457", result.getText());
+        match result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ORIENTATION)
+        {
+            Some(RXingResultMetadataValue::Orientation(orientation)) => *orientation,
+            _ => 0,
+        }
+    };
+
+    // Rows are read in both directions, so an upside down barcode needs no turn of the image
+    let mut upside_down = render_bitmap(width, height, |x, y| {
+        matrix.get(width - 1 - x, height - 1 - y)
+    });
+    assert_eq!(180, orientation(&mut upside_down, &attempts(0)));
+    assert_eq!(180, orientation(&mut upside_down, &attempts(3)));
+
+    // Printed vertically either way, a single turn finds it, so further turns add nothing
+    let mut downward = vertical_bitmap(&matrix);
+    assert_eq!(270, orientation(&mut downward, &attempts(1)));
+    let mut upward = render_bitmap(height, width, |x, y| matrix.get(width - 1 - y, x));
+    assert_eq!(90, orientation(&mut upward, &attempts(1)));
+    let mut upward = render_bitmap(height, width, |x, y| matrix.get(width - 1 - y, x));
+    assert_eq!(90, orientation(&mut upward, &attempts(3)));
+}
+
+#[test]
+fn test_row_band() {
+    let ean = EAN13Writer
+        .encode("5901234123457", &BarcodeFormat::EAN_13, 190, 1)
+        .expect("encode");
+    let code128 = Code128Writer
+        .encode("IN-BAND", &BarcodeFormat::CODE_128, 190, 1)
+        .expect("encode");
+    // One barcode near the top of the image and one near the bottom, neither in the middle
+    let (width, height) = (ean.getWidth().max(code128.getWidth()), 200);
+    let image = || {
+        render_bitmap(width, height, |x, y| match y {
+            20..=50 => ean.try_get(x, 0) == Some(true),
+            150..=180 => code128.try_get(x, 0) == Some(true),
+            _ => false,
+        })
+    };
+    let decode = |band: Option<(f32, f32)>| {
+        let mut hints = HashMap::new();
+        if let Some((top, bottom)) = band {
+            hints.insert(
+                DecodeHintType::ONED_ROW_BAND,
+                DecodeHintValue::OneDRowBand(top, bottom),
+            );
+        }
+        MultiFormatOneDReader::new(&hints)
+            .decode_with_hints(&mut image(), &hints)
+            .map(|result| result.getText().to_owned())
+    };
+
+    // By default only the middle of the image is scanned
+    assert!(decode(None).is_err());
+    assert!(decode(Some((0.4, 0.6))).is_err());
+    assert_eq!("5901234123457", decode(Some((0.0, 0.3))).expect("decode"));
+    assert_eq!("IN-BAND", decode(Some((0.7, 0.95))).expect("decode"));
+    // The band may be given bottom first
+    assert_eq!("IN-BAND", decode(Some((0.95, 0.7))).expect("decode"));
+}
+
+/**
+ * Renders the row of the matrix with a dark block two modules away from either end, as when a
+ * symbol is laid out tightly against other content.
+ */
+fn tightly_laid_out_bitmap(matrix: &BitMatrix) -> BinaryBitmap {
+    const MODULE: u32 = 3;
+    let modules = 10 + 3 + 2 + matrix.getWidth() + 2 + 3 + 10;
+    render_bitmap(modules * MODULE, 40, |x, _| match x / MODULE {
+        10..=12 => true,
+        m if m >= modules - 13 && m < modules - 10 => true,
+        m if (15..15 + matrix.getWidth()).contains(&m) => matrix.get(m - 15, 0),
+        _ => false,
+    })
+}
+
+#[test]
+fn test_relax_quiet_zone_code_39_itf_codabar() {
+    let margin = HashMap::from([(
+        EncodeHintType::MARGIN,
+        EncodeHintValue::Margin("0".to_owned()),
+    )]);
+    let symbols: [(&dyn Writer, &str, &str, BarcodeFormat); 3] = [
+        (&Code39Writer, "RELAX39", "RELAX39", BarcodeFormat::CODE_39),
+        (&ITFWriter, "12345678", "12345678", BarcodeFormat::ITF),
+        (&CodaBarWriter, "A123456B", "123456", BarcodeFormat::CODABAR),
+    ];
+    for (writer, contents, text, format) in symbols {
+        let matrix = writer
+            .encode_with_hints(contents, &format, 1, 1, &margin)
+            .expect("encode");
+        let formats = HashMap::from([(
+            DecodeHintType::POSSIBLE_FORMATS,
+            DecodeHintValue::PossibleFormats([format].into()),
+        )]);
+        let mut reader = MultiFormatOneDReader::new(&formats);
+        assert!(
+            reader
+                .decode_with_hints(&mut tightly_laid_out_bitmap(&matrix), &formats)
+                .is_err(),
+            "{format:?} read without its quiet zone"
+        );
+
+        let mut hints = formats.clone();
+        hints.insert(
+            DecodeHintType::RELAX_QUIET_ZONE,
+            DecodeHintValue::RelaxQuietZone(true),
+        );
+        let mut reader = MultiFormatOneDReader::new(&hints);
+        let result = reader
+            .decode_with_hints(&mut tightly_laid_out_bitmap(&matrix), &hints)
+            .expect("decode");
+        assert_eq!(text, result.getText());
+        assert_eq!(&format, result.getBarcodeFormat());
+    }
+}
@@ -18,7 +18,6 @@ use crate::BarcodeFormat;
 use crate::DecodeHintValue;
 use crate::Exceptions;
 use crate::RXingResult;
-use crate::Reader;
 
 use super::EAN13Reader;
 use super::EAN8Reader;
@@ -124,7 +123,7 @@ impl OneDReader for MultiFormatUPCEANReader {
         hints: &crate::DecodingHintDictionary,
     ) -> Result<crate::RXingResult, crate::Exceptions> {
         // Compute this location once and reuse it on multiple implementations
-        let startGuardPattern = STAND_IN.findStartGuardPattern(row)?;
+        let startGuardPattern = STAND_IN.findStartGuardPatternWithHints(row, hints)?;
         for reader in &self.0 {
             // for (UPCEANReader reader : readers) {
             let try_result =
@@ -136,83 +135,35 @@ impl OneDReader for MultiFormatUPCEANReader {
 
         Err(Exceptions::NotFoundException(None))
     }
-}
-
-use crate::DecodeHintType;
-use crate::DecodingHintDictionary;
-use crate::RXingResultMetadataType;
-use crate::RXingResultMetadataValue;
-use std::collections::HashMap;
-
-impl Reader for MultiFormatUPCEANReader {
-    fn decode(
-        &mut self,
-        image: &mut crate::BinaryBitmap,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        self.decode_with_hints(image, &HashMap::new())
-    }
 
-    // Note that we don't try rotation without the try harder flag, even if rotation was supported.
-    fn decode_with_hints(
+    #[cfg(feature = "diagnostics")]
+    fn decode_row_with_errors(
         &mut self,
-        image: &mut crate::BinaryBitmap,
-        hints: &DecodingHintDictionary,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        if let Ok(res) = self.doDecode(image, hints) {
-            Ok(res)
-        } else {
-            let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
-            if tryHarder && image.isRotateSupported() {
-                let mut rotatedImage = image.rotateCounterClockwise();
-                let mut result = self.doDecode(&mut rotatedImage, hints)?;
-                // Record that we found it rotated 90 degrees CCW / 270 degrees CW
-                let metadata = result.getRXingResultMetadata();
-                let mut orientation = 270;
-                if metadata.contains_key(&RXingResultMetadataType::ORIENTATION) {
-                    // But if we found it reversed in doDecode(), add in that result here:
-                    orientation = (orientation
-                        + if let Some(crate::RXingResultMetadataValue::Orientation(or)) =
-                            metadata.get(&RXingResultMetadataType::ORIENTATION)
-                        {
-                            *or
-                        } else {
-                            0
-                        })
-                        % 360;
-                }
-                result.putMetadata(
-                    RXingResultMetadataType::ORIENTATION,
-                    RXingResultMetadataValue::Orientation(orientation),
-                );
-                // Update result points
-                // let points = result.getRXingResultPoints();
-                // if points != null {
-                let height = rotatedImage.getHeight();
-                // for point in result.getRXingResultPointsMut().iter_mut() {
-                let total_points = result.getRXingResultPoints().len();
-                let points = result.getRXingResultPointsMut();
-                for point in points.iter_mut().take(total_points) {
-                    // for i in 0..total_points {
-                    // for (int i = 0; i < points.length; i++) {
-                    std::mem::swap(&mut point.x, &mut point.y);
-                    point.x = height as f32 - point.x - 1.0;
-                    // points[i] = RXingResultPoint::new(
-                    //     height as f32 - points[i].getY() - 1.0,
-                    //     points[i].getX(),
-                    // );
-                }
-                // }
-
-                Ok(result)
-            } else {
-                Err(Exceptions::NotFoundException(None))
+        rowNumber: u32,
+        row: &crate::common::BitArray,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<crate::RXingResult, crate::Exceptions> {
+        let startGuardPattern = STAND_IN.findStartGuardPatternWithHints(row, hints)?;
+        let mut error = Exceptions::NotFoundException(None);
+        for reader in &self.0 {
+            match self.try_decode_function(reader, rowNumber, row, hints, &startGuardPattern) {
+                Ok(result) => return Ok(result),
+                Err(e) => error = super::one_d_reader::moreSpecificError(error, e),
             }
         }
+
+        Err(error)
     }
+}
+
+use crate::DecodeHintType;
+use crate::DecodingHintDictionary;
+
+impl_one_d_reader!(MultiFormatUPCEANReader {
     fn reset(&mut self) {
         for reader in self.0.iter_mut() {
             // for (Reader reader : readers) {
             reader.reset();
         }
     }
-}
+});
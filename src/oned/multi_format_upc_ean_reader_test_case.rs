@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitArray, shared_test_methods::bitmap_for, BarcodeFormat, DecodeHintType,
+    DecodeHintValue, Reader, Writer,
+};
+
+use super::{
+    EAN13Writer, EAN8Writer, MultiFormatOneDReader, MultiFormatUPCEANReader, OneDReader,
+    UPCAWriter, UPCEWriter,
+};
+
+fn row(writer: &dyn Writer, contents: &str, format: BarcodeFormat) -> BitArray {
+    writer
+        .encode(contents, &format, 200, 1)
+        .expect("encode")
+        .getRow(0)
+}
+
+fn possible_formats(formats: &[BarcodeFormat]) -> HashMap<DecodeHintType, DecodeHintValue> {
+    HashMap::from([(
+        DecodeHintType::POSSIBLE_FORMATS,
+        DecodeHintValue::PossibleFormats(formats.iter().copied().collect()),
+    )])
+}
+
+#[test]
+fn test_decode_each_format() {
+    let hints = HashMap::new();
+    let mut reader = MultiFormatUPCEANReader::new(&hints);
+    for (writer, contents, format) in [
+        (
+            &EAN13Writer as &dyn Writer,
+            "5901234123457",
+            BarcodeFormat::EAN_13,
+        ),
+        (&EAN8Writer, "96385074", BarcodeFormat::EAN_8),
+        (&UPCAWriter::default(), "123456789012", BarcodeFormat::UPC_A),
+        (&UPCEWriter, "05096893", BarcodeFormat::UPC_E),
+    ] {
+        let result = reader
+            .decodeRow(0, &row(writer, contents, format), &hints)
+            .expect("decode");
+        assert_eq!(contents, result.getText());
+        assert_eq!(&format, result.getBarcodeFormat());
+    }
+}
+
+#[test]
+fn test_upc_a_read_as_ean_13() {
+    // The same bars as EAN-13 0123456789012
+    let upca = row(&UPCAWriter::default(), "123456789012", BarcodeFormat::UPC_A);
+
+    let hints = possible_formats(&[BarcodeFormat::EAN_13]);
+    let result = MultiFormatUPCEANReader::new(&hints)
+        .decodeRow(0, &upca, &hints)
+        .expect("decode");
+    assert_eq!("0123456789012", result.getText());
+    assert_eq!(&BarcodeFormat::EAN_13, result.getBarcodeFormat());
+
+    let hints = possible_formats(&[BarcodeFormat::EAN_13, BarcodeFormat::UPC_A]);
+    let result = MultiFormatUPCEANReader::new(&hints)
+        .decodeRow(0, &upca, &hints)
+        .expect("decode");
+    assert_eq!("123456789012", result.getText());
+    assert_eq!(&BarcodeFormat::UPC_A, result.getBarcodeFormat());
+
+    let hints = possible_formats(&[BarcodeFormat::UPC_A]);
+    let result = MultiFormatUPCEANReader::new(&hints)
+        .decodeRow(0, &upca, &hints)
+        .expect("decode");
+    assert_eq!("123456789012", result.getText());
+    assert_eq!(&BarcodeFormat::UPC_A, result.getBarcodeFormat());
+
+    // Only EAN-13 codes starting with 0 are UPC-A
+    let ean13 = row(&EAN13Writer, "5901234123457", BarcodeFormat::EAN_13);
+    assert!(MultiFormatUPCEANReader::new(&hints)
+        .decodeRow(0, &ean13, &hints)
+        .is_err());
+}
+
+#[test]
+fn test_decode_ean_13_image() {
+    let matrix = EAN13Writer
+        .encode("4006381333931", &BarcodeFormat::EAN_13, 200, 50)
+        .expect("encode");
+    let mut image = bitmap_for(&matrix);
+
+    let result = MultiFormatOneDReader::new(&HashMap::new())
+        .decode(&mut image)
+        .expect("decode");
+    assert_eq!("4006381333931", result.getText());
+    assert_eq!(&BarcodeFormat::EAN_13, result.getBarcodeFormat());
+}
@@ -15,11 +15,48 @@
  */
 
 use crate::{
-    common::BitArray, BinaryBitmap, DecodeHintType, DecodingHintDictionary, Exceptions,
-    RXingResult, RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint, Reader,
-    ResultPoint,
+    common::BitArray, BinaryBitmap, DecodeHintType, DecodeHintValue, DecodingHintDictionary,
+    Exceptions, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint,
+    Reader, ResultPoint,
 };
 
+/**
+ * Implements {@link Reader} for a {@link OneDReader}, decoding through
+ * {@link OneDReader#decodeWithRotations(BinaryBitmap, Map)}. Any further items, such as a
+ * {@code reset}, can follow the reader's name in braces.
+ */
+macro_rules! impl_one_d_reader {
+    ($name:ident) => {
+        impl_one_d_reader!($name {});
+    };
+    ($name:ident { $($item:item)* }) => {
+        impl $crate::Reader for $name {
+            fn decode(
+                &mut self,
+                image: &mut $crate::BinaryBitmap,
+            ) -> Result<$crate::RXingResult, $crate::Exceptions> {
+                $crate::Reader::decode_with_hints(self, image, &std::collections::HashMap::new())
+            }
+
+            fn decode_with_hints(
+                &mut self,
+                image: &mut $crate::BinaryBitmap,
+                hints: &$crate::DecodingHintDictionary,
+            ) -> Result<$crate::RXingResult, $crate::Exceptions> {
+                $crate::oned::OneDReader::decodeWithRotations(self, image, hints)
+            }
+
+            $($item)*
+        }
+    };
+}
+
+/**
+ * How far, as a fraction of the {@link DecodeHintType#X_DIMENSION} hint, the module width
+ * measured on a pattern may be off and still match.
+ */
+const X_DIMENSION_TOLERANCE: f32 = 0.25;
+
 /**
  * Encapsulates functionality and implementation that is common to all families
  * of one-dimensional barcodes.
@@ -48,101 +85,190 @@ pub trait OneDReader: Reader {
         hints: &DecodingHintDictionary,
     ) -> Result<RXingResult, Exceptions> {
         let mut hints = hints.clone();
-        let width = image.getWidth();
-        let height = image.getHeight();
-        // let mut row = BitArray::with_size(width);
-
-        let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
-        let rowStep = 1.max(height >> (if tryHarder { 8 } else { 5 }));
-        let maxLines = if tryHarder {
-            height // Look at the whole image, not just the center
-        } else {
-            15 // 15 rows spaced 1/32 apart is roughly the middle half of the image
-        };
 
-        let middle = height / 2;
-        for x in 0..maxLines {
-            // for (int x = 0; x < maxLines; x++) {
-
-            // Scanning from the middle out. Determine which row we're looking at next:
-            let rowStepsAboveOrBelow = (x + 1) / 2;
-            let isAbove = (x & 0x01) == 0; // i.e. is x even?
-            let rowNumber: isize = middle as isize
-                + rowStep as isize
-                    * (if isAbove {
-                        rowStepsAboveOrBelow as isize
-                    } else {
-                        -(rowStepsAboveOrBelow as isize)
-                    });
-            if rowNumber < 0 || rowNumber >= height as isize {
-                // Oops, if we run off the top or bottom, stop
-                break;
+        for rowNumber in rowsToScan(image.getHeight(), &hints) {
+            if let Ok(result) = decodeRowBothWays(self, image, rowNumber, &mut hints) {
+                return Ok(result);
             }
+        }
 
-            // Estimate black point for this row and load it:
-            let mut row = if let Ok(res) = image.getBlackRow(rowNumber as usize) {
-                res
-            } else {
+        Err(Exceptions::NotFoundException(None))
+    }
+
+    /**
+     * Decodes the image as {@link #doDecode(BinaryBitmap, Map)} does and, if no barcode is found
+     * upright, turned 90 degrees counterclockwise if {@link DecodeHintType#ROTATION_ATTEMPTS}
+     * asks for it. Every 1D reader decodes images this way.
+     *
+     * @param image The image to decode
+     * @param hints Any hints that were requested
+     * @return The contents of the decoded barcode, with its orientation if it was turned
+     * @throws NotFoundException if no barcode is found in either orientation
+     */
+    fn decodeWithRotations(
+        &mut self,
+        image: &mut BinaryBitmap,
+        hints: &DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        if let Ok(res) = self.doDecode(image, hints) {
+            return Ok(res);
+        }
+        if rotationAttempts(hints) == 0 || !image.isRotateSupported() {
+            return Err(Exceptions::NotFoundException(None));
+        }
+        let mut rotatedImage = image.rotateCounterClockwise();
+        let mut result = self.doDecode(&mut rotatedImage, hints)?;
+        // Record that we found it rotated 90 degrees CCW / 270 degrees CW
+        let metadata = result.getRXingResultMetadata();
+        let mut orientation = 270;
+        // But if we found it reversed in doDecode(), add in that result here:
+        if let Some(RXingResultMetadataValue::Orientation(or)) =
+            metadata.get(&RXingResultMetadataType::ORIENTATION)
+        {
+            orientation += *or;
+        }
+        result.putMetadata(
+            RXingResultMetadataType::ORIENTATION,
+            RXingResultMetadataValue::Orientation(orientation % 360),
+        );
+        // Update result points
+        let height = rotatedImage.getHeight();
+        for point in result.getRXingResultPointsMut().iter_mut() {
+            std::mem::swap(&mut point.x, &mut point.y);
+            point.x = height as f32 - point.x - 1.0;
+        }
+
+        Ok(result)
+    }
+
+    /**
+     * Like {@link #doDecode(BinaryBitmap, Map)}, but instead of stopping at the first row which
+     * decodes, scans every candidate row and returns each distinct decode found, best first.
+     *
+     * Candidates are ranked by how many scanned rows agreed on them, so a damaged or ambiguous
+     * symbol which reads one way on most rows and another way on a few will report both, with
+     * the majority read first. Ties keep the order in which the candidates were first seen, which
+     * favours reads nearer the middle of the image.
+     *
+     * @param image The image to decode
+     * @param hints Any hints that were requested
+     * @return the distinct decoded results, most agreed upon first
+     * @throws NotFoundException if no row could be decoded
+     */
+    fn decode_candidates(
+        &mut self,
+        image: &mut BinaryBitmap,
+        hints: &DecodingHintDictionary,
+    ) -> Result<Vec<RXingResult>, Exceptions> {
+        let mut hints = hints.clone();
+        hints.remove(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
+
+        let mut candidates: Vec<(RXingResult, usize)> = Vec::new();
+        for rowNumber in rowsToScan(image.getHeight(), &hints) {
+            // A row which also reads upside down only counts once
+            let Ok(result) = decodeRowBothWays(self, image, rowNumber, &mut hints) else {
                 continue;
             };
-            // try {
-            //   row = image.getBlackRow(rowNumber, row);
-            // } catch (NotFoundException ignored) {
-            //   continue;
-            // }
-
-            // While we have the image data in a BitArray, it's fairly cheap to reverse it in place to
-            // handle decoding upside down barcodes.
-            for attempt in 0..2 {
-                // for (int attempt = 0; attempt < 2; attempt++) {
-                if attempt == 1 {
-                    // trying again?
-                    row.to_mut().reverse(); // reverse the row and continue
-                                            // This means we will only ever draw result points *once* in the life of this method
-                                            // since we want to avoid drawing the wrong points after flipping the row, and,
-                                            // don't want to clutter with noise from every single row scan -- just the scans
-                                            // that start on the center line.
-                    if hints.contains_key(&DecodeHintType::NEED_RESULT_POINT_CALLBACK) {
-                        // let newHints = HashMap::new();
-                        // newHints.putAll(hints);
-                        // newHints.remove(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
-                        hints.remove(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
-                        // hints = newHints;
+            if let Some((_, votes)) = candidates.iter_mut().find(|(seen, _)| {
+                seen.getText() == result.getText()
+                    && seen.getBarcodeFormat() == result.getBarcodeFormat()
+            }) {
+                *votes += 1;
+            } else {
+                candidates.push((result, 1));
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(Exceptions::NotFoundException(None));
+        }
+        // sort_by is stable, so equally ranked candidates keep their scan order
+        candidates.sort_by(|(_, a), (_, b)| b.cmp(a));
+        Ok(candidates.into_iter().map(|(result, _)| result).collect())
+    }
+
+    /**
+     * Diagnostic counterpart of {@link #doDecode(BinaryBitmap, Map)}: attempts to decode every row
+     * of the image, right side up and then upside down, and reports the outcome per row. Plotting
+     * the result shows which parts of a barcode are readable.
+     *
+     * @param image The image to decode
+     * @param hints Any hints that were requested
+     * @return one entry per row of the image, holding the result if that row decoded
+     */
+    #[cfg(feature = "diagnostics")]
+    fn decode_row_heatmap(
+        &mut self,
+        image: &mut BinaryBitmap,
+        hints: &DecodingHintDictionary,
+    ) -> Vec<Option<RXingResult>> {
+        let mut hints = hints.clone();
+        hints.remove(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
+
+        (0..image.getHeight())
+            .map(|rowNumber| decodeRowBothWays(self, image, rowNumber, &mut hints).ok())
+            .collect()
+    }
+
+    /**
+     * Diagnostic counterpart of {@link #decodeWithRotations(BinaryBitmap, Map)}: decodes the image
+     * the same way, but if nothing decodes, scans the same rows again to tell why. The error
+     * reports a barcode which was found on some row but failed its checksum, or else one whose
+     * format was invalid, rather than NotFoundException.
+     *
+     * @param image The image to decode
+     * @param hints Any hints that were requested
+     * @return The contents of the decoded barcode
+     * @throws NotFoundException if no row holds a barcode
+     * @throws ChecksumException if a barcode was found on some row but failed its checksum
+     * @throws FormatException if a barcode was found on some row but its format is invalid
+     */
+    #[cfg(feature = "diagnostics")]
+    fn decode_with_row_errors(
+        &mut self,
+        image: &mut BinaryBitmap,
+        hints: &DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        if let Ok(result) = self.decodeWithRotations(image, hints) {
+            return Ok(result);
+        }
+        let mut hints = hints.clone();
+        hints.remove(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
+
+        let rotatedImage = (rotationAttempts(&hints) > 0 && image.isRotateSupported())
+            .then(|| image.rotateCounterClockwise());
+        let mut error = Exceptions::NotFoundException(None);
+        for image in std::iter::once(&*image).chain(rotatedImage.as_ref()) {
+            for rowNumber in rowsToScan(image.getHeight(), &hints) {
+                let Ok(mut row) = image.getBlackRow(rowNumber) else {
+                    continue;
+                };
+                for attempt in 0..2 {
+                    if attempt == 1 {
+                        row.to_mut().reverse();
                     }
-                }
-                //try {
-                // Look for a barcode
-                let Ok(mut result) = self.decodeRow(rowNumber as u32, &row, &hints) else {
-            continue
-          };
-                // We found our barcode
-                if attempt == 1 {
-                    // But it was upside down, so note that
-                    result.putMetadata(
-                        RXingResultMetadataType::ORIENTATION,
-                        RXingResultMetadataValue::Orientation(180),
-                    );
-                    // And remember to flip the result points horizontally.
-                    let points = result.getRXingResultPointsMut();
-                    if !points.is_empty() && points.len() >= 2 {
-                        points[0] = RXingResultPoint::new(
-                            width as f32 - points[0].getX() - 1.0,
-                            points[0].getY(),
-                        );
-                        points[1] = RXingResultPoint::new(
-                            width as f32 - points[1].getX() - 1.0,
-                            points[1].getY(),
-                        );
+                    if let Err(e) = self.decode_row_with_errors(rowNumber as u32, &row, &hints) {
+                        error = moreSpecificError(error, e);
                     }
                 }
-                return Ok(result);
-                // } catch (ReaderException re) {
-                //   // continue -- just couldn't decode this row
-                // }
             }
         }
+        Err(error)
+    }
 
-        Err(Exceptions::NotFoundException(None))
+    /**
+     * Diagnostic counterpart of {@link #decodeRow(int, BitArray, Map)}, for
+     * {@link #decode_with_row_errors(BinaryBitmap, Map)}. Readers which try several other readers
+     * on the row return the most telling error of any of them, rather than NotFoundException.
+     */
+    #[cfg(feature = "diagnostics")]
+    fn decode_row_with_errors(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        hints: &DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        self.decodeRow(rowNumber, row, hints)
     }
 
     /**
@@ -165,6 +291,200 @@ pub trait OneDReader: Reader {
     ) -> Result<RXingResult, Exceptions>;
 }
 
+/**
+ * Decodes one row of the image, right side up and then reversed, to handle upside down barcodes.
+ * Rows which are entirely white or entirely black are skipped, as they hold no bars.
+ *
+ * @param hints Any hints that were requested. {@link DecodeHintType#NEED_RESULT_POINT_CALLBACK}
+ *  is removed once a row is reversed: result points are only drawn for scans that start on the
+ *  center line, not with the wrong coordinates after flipping the row or as noise from every row.
+ * @return the result, with the orientation and flipped points of an upside down barcode
+ * @throws NotFoundException if the row holds no barcode
+ */
+fn decodeRowBothWays<R: OneDReader + ?Sized>(
+    reader: &mut R,
+    image: &BinaryBitmap,
+    rowNumber: usize,
+    hints: &mut DecodingHintDictionary,
+) -> Result<RXingResult, Exceptions> {
+    // Estimate black point for this row and load it:
+    let mut row = image.getBlackRow(rowNumber)?;
+    let blackPixels = row.count_ones();
+    if blackPixels == 0 || blackPixels == row.getSize() {
+        return Err(Exceptions::NotFoundException(None));
+    }
+
+    if let Ok(result) = reader.decodeRow(rowNumber as u32, &row, hints) {
+        return Ok(result);
+    }
+
+    // While we have the image data in a BitArray, it's fairly cheap to reverse it in place
+    row.to_mut().reverse();
+    hints.remove(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
+    let Ok(mut result) = reader.decodeRow(rowNumber as u32, &row, hints) else {
+        return Err(Exceptions::NotFoundException(None));
+    };
+    // But it was upside down, so note that
+    result.putMetadata(
+        RXingResultMetadataType::ORIENTATION,
+        RXingResultMetadataValue::Orientation(180),
+    );
+    // And remember to flip the result points horizontally.
+    flipRXingResultPoints(&mut result, image.getWidth());
+    Ok(result)
+}
+
+/**
+ * @return how many 90 degree turns of the image to try: 1 if the
+ *  {@link DecodeHintType#ROTATION_ATTEMPTS} hint asks for any, or without it with
+ *  {@link DecodeHintType#TRY_HARDER} or {@link DecodeHintType#TRY_VERTICAL}, and 0 otherwise.
+ *  Attempts 2 and 3 would turn the image 180 and 270 degrees, which gives the same rows as
+ *  upright or turned 90 degrees, each reversed, and
+ *  {@link OneDReader#doDecode(BinaryBitmap, Map)} already reads every row both ways.
+ */
+fn rotationAttempts(hints: &DecodingHintDictionary) -> u32 {
+    if let Some(DecodeHintValue::RotationAttempts(attempts)) =
+        hints.get(&DecodeHintType::ROTATION_ATTEMPTS)
+    {
+        (*attempts).min(1)
+    } else if hints.contains_key(&DecodeHintType::TRY_HARDER)
+        || hints.contains_key(&DecodeHintType::TRY_VERTICAL)
+    {
+        1
+    } else {
+        0
+    }
+}
+
+/**
+ * Lists the rows to examine, from the middle outward, searching alternately above and below the
+ * middle, and farther out each time. See {@link OneDReader#doDecode(BinaryBitmap, Map)}.
+ */
+fn rowsToScan(height: usize, hints: &DecodingHintDictionary) -> Vec<usize> {
+    // Only the rows of the band, if one is given, spaced relative to its height
+    let (top, bottom) = rowBand(height, hints);
+    let height = bottom - top;
+
+    // Stacked symbols need all of their rows, wherever they are
+    let wholeImage = hints.contains_key(&DecodeHintType::TRY_HARDER)
+        || matches!(
+            hints.get(&DecodeHintType::RSS_STACKED),
+            Some(DecodeHintValue::RssStacked(true))
+        );
+    let rowStep = 1.max(height >> (if wholeImage { 8 } else { 5 }));
+    let maxLines = if wholeImage {
+        height // Look at the whole image, not just the center
+    } else {
+        15 // 15 rows spaced 1/32 apart is roughly the middle half of the image
+    };
+
+    let middle = height / 2;
+    let mut rows = Vec::with_capacity(maxLines);
+    for x in 0..maxLines {
+        // for (int x = 0; x < maxLines; x++) {
+
+        // Scanning from the middle out. Determine which row we're looking at next:
+        let rowStepsAboveOrBelow = (x + 1) / 2;
+        let isAbove = (x & 0x01) == 0; // i.e. is x even?
+        let rowNumber: isize = middle as isize
+            + rowStep as isize
+                * (if isAbove {
+                    rowStepsAboveOrBelow as isize
+                } else {
+                    -(rowStepsAboveOrBelow as isize)
+                });
+        if rowNumber < 0 || rowNumber >= height as isize {
+            // Oops, if we run off the top or bottom, stop
+            break;
+        }
+        rows.push(top + rowNumber as usize);
+    }
+    rows
+}
+
+/**
+ * @return the first row and the row after the last of the {@link DecodeHintType#ONED_ROW_BAND}
+ *  hint, clamped to the image and at least a row high, or else the whole image
+ */
+fn rowBand(height: usize, hints: &DecodingHintDictionary) -> (usize, usize) {
+    let Some(DecodeHintValue::OneDRowBand(from, to)) = hints.get(&DecodeHintType::ONED_ROW_BAND)
+    else {
+        return (0, height);
+    };
+    let row = |fraction: f32| (fraction.clamp(0.0, 1.0) * height as f32).round() as usize;
+    let top = row(from.min(*to)).min(height.saturating_sub(1));
+    let bottom = row(from.max(*to)).clamp(top + 1, height.max(1));
+    (top, bottom)
+}
+
+/**
+ * @return whichever of two errors from decoding a row tells more: a barcode which was found but
+ *  failed its checksum, or else one whose format was invalid, over one which wasn't found at all
+ */
+#[cfg(feature = "diagnostics")]
+pub(crate) fn moreSpecificError(current: Exceptions, candidate: Exceptions) -> Exceptions {
+    let rank = |e: &Exceptions| match e {
+        Exceptions::ChecksumException(_) => 2,
+        Exceptions::FormatException(_) => 1,
+        _ => 0,
+    };
+    if rank(&candidate) > rank(&current) {
+        candidate
+    } else {
+        current
+    }
+}
+
+/**
+ * Mirrors the first two result points horizontally, for a result which was read from a reversed
+ * row.
+ */
+fn flipRXingResultPoints(result: &mut RXingResult, width: usize) {
+    let points = result.getRXingResultPointsMut();
+    if !points.is_empty() && points.len() >= 2 {
+        points[0] = RXingResultPoint::new(width as f32 - points[0].getX() - 1.0, points[0].getY());
+        points[1] = RXingResultPoint::new(width as f32 - points[1].getX() - 1.0, points[1].getY());
+    }
+}
+
+/**
+ * Checks the width of a pattern against the module width given with the
+ * {@link DecodeHintType#X_DIMENSION} hint. Readers check their start pattern with this, so that
+ * symbols printed at another scale are rejected before decoding any further.
+ *
+ * @param hints decode hints
+ * @param width width of the pattern in pixels
+ * @param minModules fewest modules the pattern spans
+ * @param maxModules most modules the pattern spans, which differs from minModules for formats
+ *  with a variable wide to narrow ratio
+ * @return true if the width fits the hint, or if there is no hint
+ */
+pub fn matchesXDimension(
+    hints: &DecodingHintDictionary,
+    width: usize,
+    minModules: u32,
+    maxModules: u32,
+) -> bool {
+    let Some(DecodeHintValue::XDimension(xDimension)) = hints.get(&DecodeHintType::X_DIMENSION)
+    else {
+        return true;
+    };
+    let width = width as f32;
+    width >= minModules as f32 * xDimension * (1.0 - X_DIMENSION_TOLERANCE)
+        && width <= maxModules as f32 * xDimension * (1.0 + X_DIMENSION_TOLERANCE)
+}
+
+/**
+ * @return true if the {@link DecodeHintType#RELAX_QUIET_ZONE} hint allows symbols without the
+ *  quiet zone their specification requires
+ */
+pub fn relaxesQuietZone(hints: &DecodingHintDictionary) -> bool {
+    matches!(
+        hints.get(&DecodeHintType::RELAX_QUIET_ZONE),
+        Some(DecodeHintValue::RelaxQuietZone(true))
+    )
+}
+
 /**
  * Determines how closely a set of observed counts of runs of black/white values matches a given
  * target pattern. This is reported as the ratio of the total variance from the expected pattern
@@ -281,3 +601,28 @@ pub fn recordPatternInReverse(
 
     Ok(())
 }
+
+/**
+ * Records every run of the row, beginning with its first black pixel, for readers of symbols
+ * without a fixed pattern length. The last run may be white, up to the end of the row.
+ *
+ * @param row row to measure
+ * @return the position and the width in pixels of each run
+ */
+pub fn recordRuns(row: &BitArray) -> (Vec<usize>, Vec<usize>) {
+    let size = row.getSize();
+    let mut starts = Vec::new();
+    let mut widths = Vec::new();
+    let mut position = row.getNextSet(0);
+    while position < size {
+        let end = if row.get(position) {
+            row.getNextUnset(position)
+        } else {
+            row.getNextSet(position)
+        };
+        starts.push(position);
+        widths.push(end - position);
+        position = end;
+    }
+    (starts, widths)
+}
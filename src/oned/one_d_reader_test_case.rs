@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitArray, shared_test_methods::render_bitmap, BarcodeFormat, BinaryBitmap,
+    DecodeHintType, DecodeHintValue, DecodingHintDictionary, Exceptions, RXingResult, Reader,
+    Writer,
+};
+
+use super::{Code128Reader, Code128Writer, OneDReader};
+
+const WIDTH: u32 = 300;
+const HEIGHT: u32 = 320;
+
+/**
+ * Stacks two Code 128 symbols of the same width on top of each other, switching from the first
+ * to the second at the given row, so that different scan lines read different contents.
+ */
+fn stacked_bitmap(top: &str, bottom: &str, split: u32) -> BinaryBitmap {
+    let top = Code128Writer
+        .encode(top, &BarcodeFormat::CODE_128, WIDTH as i32, 1)
+        .expect("encode");
+    let bottom = Code128Writer
+        .encode(bottom, &BarcodeFormat::CODE_128, WIDTH as i32, 1)
+        .expect("encode");
+    render_bitmap(WIDTH, HEIGHT, |x, y| {
+        let symbol = if y < split { &top } else { &bottom };
+        symbol.get(x, 0)
+    })
+}
+
+#[test]
+fn test_decode_candidates_ranked_by_agreement() {
+    // Rows 90..=230 are scanned, 10 apart: 7 land on "ABC-123" and 8 on "ABC-128"
+    let mut image = stacked_bitmap("ABC-123", "ABC-128", 155);
+    let candidates = Code128Reader
+        .decode_candidates(&mut image, &HashMap::new())
+        .expect("decode");
+    let texts: Vec<&str> = candidates.iter().map(|r| r.getText().as_str()).collect();
+    assert_eq!(vec!["ABC-128", "ABC-123"], texts);
+
+    let mut image = stacked_bitmap("ABC-123", "ABC-128", 185);
+    let candidates = Code128Reader
+        .decode_candidates(&mut image, &HashMap::new())
+        .expect("decode");
+    let texts: Vec<&str> = candidates.iter().map(|r| r.getText().as_str()).collect();
+    assert_eq!(vec!["ABC-123", "ABC-128"], texts);
+}
+
+#[test]
+fn test_decode_candidates_single_symbol() {
+    let mut image = stacked_bitmap("SAME", "SAME", HEIGHT / 2);
+    let candidates = Code128Reader
+        .decode_candidates(&mut image, &HashMap::new())
+        .expect("decode");
+    assert_eq!(1, candidates.len());
+    assert_eq!("SAME", candidates[0].getText());
+}
+
+#[test]
+fn test_decode_candidates_not_found() {
+    let mut image = render_bitmap(WIDTH, HEIGHT, |_, _| false);
+    assert!(matches!(
+        Code128Reader.decode_candidates(&mut image, &HashMap::new()),
+        Err(Exceptions::NotFoundException(_))
+    ));
+}
+
+#[test]
+fn test_x_dimension_rejects_symbol_at_other_scale() {
+    // A small label printed with 1 pixel modules across the middle of the image, which is scanned
+    // first, and the wanted symbol with 2 pixel modules above it.
+    let wanted = Code128Writer
+        .encode("ABC-123", &BarcodeFormat::CODE_128, 1, 1)
+        .expect("encode");
+    let spurious = Code128Writer
+        .encode("SPURIOUS", &BarcodeFormat::CODE_128, 1, 1)
+        .expect("encode");
+    let image = || {
+        render_bitmap(WIDTH, HEIGHT, |x, y| {
+            let (symbol, xDimension) = if y < 130 {
+                (&wanted, 2)
+            } else {
+                (&spurious, 1)
+            };
+            let module = x / xDimension;
+            module < symbol.getWidth() && symbol.get(module, 0)
+        })
+    };
+    let hints = |xDimension: f32| {
+        HashMap::from([(
+            DecodeHintType::X_DIMENSION,
+            DecodeHintValue::XDimension(xDimension),
+        )])
+    };
+
+    let result = Code128Reader
+        .decode_with_hints(&mut image(), &HashMap::new())
+        .expect("decode");
+    assert_eq!("SPURIOUS", result.getText());
+
+    let result = Code128Reader
+        .decode_with_hints(&mut image(), &hints(2.0))
+        .expect("decode");
+    assert_eq!("ABC-123", result.getText());
+
+    let result = Code128Reader
+        .decode_with_hints(&mut image(), &hints(1.0))
+        .expect("decode");
+    assert_eq!("SPURIOUS", result.getText());
+
+    assert!(matches!(
+        Code128Reader.decode_with_hints(&mut image(), &hints(4.0)),
+        Err(Exceptions::NotFoundException(_))
+    ));
+}
+
+/**
+ * Never finds anything, but records which rows it was asked to decode.
+ */
+#[derive(Default)]
+struct RowRecordingReader {
+    rows: Vec<u32>,
+}
+
+impl Reader for RowRecordingReader {
+    fn decode(&mut self, image: &mut BinaryBitmap) -> Result<RXingResult, Exceptions> {
+        self.decode_with_hints(image, &HashMap::new())
+    }
+
+    fn decode_with_hints(
+        &mut self,
+        image: &mut BinaryBitmap,
+        hints: &DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        self.doDecode(image, hints)
+    }
+}
+
+impl OneDReader for RowRecordingReader {
+    fn decodeRow(
+        &mut self,
+        rowNumber: u32,
+        _row: &BitArray,
+        _hints: &DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        self.rows.push(rowNumber);
+        Err(Exceptions::NotFoundException(None))
+    }
+}
+
+#[test]
+fn test_do_decode_skips_blank_rows() {
+    // A white image with a single black square in rows 150..170
+    let mut image = render_bitmap(WIDTH, HEIGHT, |x, y| {
+        (150..170).contains(&y) && (100..200).contains(&x)
+    });
+    let hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+
+    let mut reader = RowRecordingReader::default();
+    assert!(matches!(
+        reader.decode_with_hints(&mut image, &hints),
+        Err(Exceptions::NotFoundException(_))
+    ));
+    // Only the rows crossing the square were looked at, both ways round
+    assert!(!reader.rows.is_empty());
+    assert!(reader.rows.iter().all(|row| (150..170).contains(row)));
+    assert_eq!(0, reader.rows.len() % 2);
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn test_decode_row_heatmap_marks_barcode_rows() {
+    // Blank bands above and below a Code 128 symbol spanning rows 80..240
+    let symbol = Code128Writer
+        .encode("HEATMAP", &BarcodeFormat::CODE_128, WIDTH as i32, 1)
+        .expect("encode");
+    let mut image = render_bitmap(WIDTH, HEIGHT, |x, y| {
+        (80..240).contains(&y) && symbol.get(x, 0)
+    });
+
+    let heatmap = Code128Reader.decode_row_heatmap(&mut image, &HashMap::new());
+    assert_eq!(HEIGHT as usize, heatmap.len());
+    for (row, result) in heatmap.iter().enumerate() {
+        if (100..220).contains(&row) {
+            assert_eq!("HEATMAP", result.as_ref().expect("central row").getText());
+        } else if !(70..250).contains(&row) {
+            assert!(result.is_none(), "row {row} is blank");
+        }
+    }
+}
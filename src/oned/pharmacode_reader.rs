@@ -0,0 +1,147 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    common::BitArray, BarcodeFormat, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue, RXingResultPoint,
+};
+
+use super::{one_d_reader, OneDReader};
+
+/**
+ * <p>Decodes one-track Pharmacode, as used on pharmaceutical packaging.</p>
+ *
+ * <p>The symbol is a row of 2 to 16 narrow or wide bars with equal spaces between them,
+ * encoding a value from 3 to 131070. Read from the right, each bar is worth its width, 1 for a
+ * narrow and 2 for a wide bar, times a power of 2 which doubles from one bar to the next.</p>
+ *
+ * <p>There is neither a check digit nor a start or stop pattern, so the symbol reads as another
+ * valid value when scanned backwards. The value read left to right is returned. Unless both
+ * directions agree, both values are reported with
+ * {@link RXingResultMetadataType#PHARMACODE_CANDIDATES}.</p>
+ */
+#[derive(Default)]
+pub struct PharmacodeReader;
+
+impl OneDReader for PharmacodeReader {
+    fn decodeRow(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        _hints: &crate::DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        let (starts, widths) = one_d_reader::recordRuns(row);
+
+        let mut index = 0;
+        while index + 2 < widths.len() {
+            if let Some((bars, end)) = Self::findSymbol(&starts, &widths, index) {
+                let forward = bars.iter().fold(0, |value, bar| value * 2 + bar);
+                let backward = bars.iter().rev().fold(0, |value, bar| value * 2 + bar);
+
+                let left = starts[index] as f32 + widths[index] as f32 / 2.0;
+                let right = starts[end] as f32 + widths[end] as f32 / 2.0;
+                let mut result = RXingResult::new(
+                    &forward.to_string(),
+                    Vec::new(),
+                    vec![
+                        RXingResultPoint::new(left, rowNumber as f32),
+                        RXingResultPoint::new(right, rowNumber as f32),
+                    ],
+                    BarcodeFormat::PHARMA_CODE,
+                );
+                if forward != backward {
+                    result.putMetadata(
+                        RXingResultMetadataType::PHARMACODE_CANDIDATES,
+                        RXingResultMetadataValue::PharmacodeCandidates(vec![forward, backward]),
+                    );
+                }
+                return Ok(result);
+            }
+            index += 2;
+        }
+        Err(Exceptions::NotFoundException(None))
+    }
+}
+
+impl_one_d_reader!(PharmacodeReader);
+
+const MIN_BARS: usize = 2;
+const MAX_BARS: usize = 16;
+
+/** How far, as a fraction of the first space, the other spaces may differ from it. */
+const MAX_SPACE_VARIANCE: f32 = 0.5;
+
+/** Blank space required before and after the symbol, as a multiple of the space between bars. */
+const QUIET_ZONE_SPACES: f32 = 3.0;
+
+/** Wide bars are at most this many times as wide as the space between bars. */
+const MAX_WIDE_BAR: f32 = 2.5;
+
+impl PharmacodeReader {
+    /**
+     * Looks for a symbol whose first bar is at the given index: evenly spaced bars, preceded and
+     * followed by a quiet zone.
+     *
+     * @return the weight of each bar, 1 for narrow and 2 for wide, and the index of the last bar
+     */
+    fn findSymbol(starts: &[usize], widths: &[usize], index: usize) -> Option<(Vec<u32>, usize)> {
+        let firstSpace = widths[index + 1] as f32;
+        let quietZone = if index == 0 {
+            starts[0]
+        } else {
+            widths[index - 1]
+        };
+        if (quietZone as f32) < QUIET_ZONE_SPACES * firstSpace {
+            return None;
+        }
+
+        // Collect bars while the spaces between them stay alike
+        let mut end = index;
+        let mut spaces = 0;
+        while end + 1 < widths.len() {
+            let space = widths[end + 1] as f32;
+            if end + 2 >= widths.len() || space >= QUIET_ZONE_SPACES * firstSpace {
+                break;
+            }
+            if (space - firstSpace).abs() > MAX_SPACE_VARIANCE * firstSpace {
+                return None;
+            }
+            spaces += widths[end + 1];
+            end += 2;
+        }
+        // The run after the last bar reaches the end of the row, if there's one
+        let trailingQuietZone = widths.get(end + 1).copied().unwrap_or(0);
+        if (trailingQuietZone as f32) < QUIET_ZONE_SPACES * firstSpace {
+            return None;
+        }
+        let count = (end - index) / 2 + 1;
+        if !(MIN_BARS..=MAX_BARS).contains(&count) {
+            return None;
+        }
+
+        // A narrow bar is about half as wide as a space, a wide one about one and a half times
+        let space = spaces as f32 / (count - 1) as f32;
+        let mut bars = Vec::with_capacity(count);
+        for bar in widths[index..=end].iter().step_by(2) {
+            let bar = *bar as f32;
+            if bar > MAX_WIDE_BAR * space {
+                return None;
+            }
+            bars.push(if bar < space { 1 } else { 2 });
+        }
+        Some((bars, end))
+    }
+}
@@ -0,0 +1,127 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::BitArray, shared_test_methods::render_bitmap, BarcodeFormat, DecodeHintType,
+    DecodeHintValue, RXingResultMetadataType, RXingResultMetadataValue, Reader,
+};
+
+use super::{MultiFormatOneDReader, OneDReader, PharmacodeReader};
+
+const NARROW: usize = 2;
+const WIDE: usize = 6;
+const SPACE: usize = 4;
+const QUIET_ZONE: usize = 24;
+
+/** The bars of 1234, narrow and wide, from left to right. */
+const PATTERN_1234: &str = "NNWWNWNNWW";
+
+fn render(pattern: &str) -> BitArray {
+    let bars: Vec<usize> = pattern
+        .chars()
+        .map(|bar| if bar == 'W' { WIDE } else { NARROW })
+        .collect();
+    let width = bars.iter().sum::<usize>() + (bars.len() - 1) * SPACE + 2 * QUIET_ZONE;
+    let mut row = BitArray::with_size(width);
+    let mut x = QUIET_ZONE;
+    for bar in bars {
+        row.setRange(x, x + bar).expect("set range");
+        x += bar + SPACE;
+    }
+    row
+}
+
+fn candidates(result: &crate::RXingResult) -> Option<&Vec<u32>> {
+    match result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::PHARMACODE_CANDIDATES)
+    {
+        Some(RXingResultMetadataValue::PharmacodeCandidates(values)) => Some(values),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_decode_both_directions() {
+    let mut row = render(PATTERN_1234);
+    let result = PharmacodeReader
+        .decodeRow(0, &row, &HashMap::new())
+        .expect("decode");
+    assert_eq!("1234", result.getText());
+    assert_eq!(&BarcodeFormat::PHARMA_CODE, result.getBarcodeFormat());
+    assert_eq!(Some(&vec![1234, 1835]), candidates(&result));
+
+    // Upside down the same bars read as 1835
+    row.reverse();
+    let result = PharmacodeReader
+        .decodeRow(0, &row, &HashMap::new())
+        .expect("decode");
+    assert_eq!("1835", result.getText());
+    assert_eq!(Some(&vec![1835, 1234]), candidates(&result));
+}
+
+#[test]
+fn test_decode_range() {
+    for (pattern, value) in [("NN", "3"), ("WWWWWWWWWWWWWWWW", "131070")] {
+        let result = PharmacodeReader
+            .decodeRow(0, &render(pattern), &HashMap::new())
+            .expect("decode");
+        assert_eq!(value, result.getText());
+        // The same both ways
+        assert_eq!(None, candidates(&result));
+    }
+    assert!(PharmacodeReader
+        .decodeRow(0, &render("N"), &HashMap::new())
+        .is_err());
+    assert!(PharmacodeReader
+        .decodeRow(0, &render("NNNNNNNNNNNNNNNNN"), &HashMap::new())
+        .is_err());
+}
+
+#[test]
+fn test_requires_trailing_quiet_zone() {
+    let full = render(PATTERN_1234);
+    // Cut the row off a single space after the last bar, then right at it
+    for trailing in [SPACE, 0] {
+        let width = full.getSize() - QUIET_ZONE + trailing;
+        let mut row = BitArray::with_size(width);
+        for x in 0..width {
+            if full.get(x) {
+                row.set(x);
+            }
+        }
+        assert!(PharmacodeReader
+            .decodeRow(0, &row, &HashMap::new())
+            .is_err());
+    }
+}
+
+#[test]
+fn test_multi_format_decodes_pharmacode() {
+    let row = render(PATTERN_1234);
+    let mut image = render_bitmap(row.getSize() as u32, 40, |x, _| row.get(x as usize));
+    let hints = HashMap::from([(
+        DecodeHintType::POSSIBLE_FORMATS,
+        DecodeHintValue::PossibleFormats(HashSet::from([BarcodeFormat::PHARMA_CODE])),
+    )]);
+    let result = MultiFormatOneDReader::new(&hints)
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    assert_eq!("1234", result.getText());
+    assert_eq!(&BarcodeFormat::PHARMA_CODE, result.getBarcodeFormat());
+}
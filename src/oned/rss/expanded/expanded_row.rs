@@ -25,10 +25,24 @@ use super::ExpandedPair;
 pub struct ExpandedRow {
     pairs: Vec<ExpandedPair>,
     rowNumber: u32,
+    span: Option<(usize, usize)>,
 }
 impl ExpandedRow {
     pub fn new(pairs: Vec<ExpandedPair>, rowNumber: u32) -> Self {
-        Self { pairs, rowNumber }
+        let ends = pairs
+            .iter()
+            .filter_map(|pair| pair.getFinderPattern().as_ref())
+            .map(|pattern| pattern.getStartEnd());
+        let span = ends.fold(None, |span: Option<(usize, usize)>, startEnd| {
+            Some(span.map_or((startEnd[0], startEnd[1]), |(start, end)| {
+                (start.min(startEnd[0]), end.max(startEnd[1]))
+            }))
+        });
+        Self {
+            pairs,
+            rowNumber,
+            span,
+        }
     }
 
     pub fn getPairs(&self) -> &[ExpandedPair] {
@@ -44,6 +58,26 @@ impl ExpandedRow {
         self.rowNumber
     }
 
+    /**
+     * @return the horizontal extent of the finder patterns of this row, in pixels, or None if
+     *  none of its pairs has one
+     */
+    pub fn getHorizontalSpan(&self) -> Option<(usize, usize)> {
+        self.span
+    }
+
+    /**
+     * Rows of the same stacked symbol are above one another, so their finder patterns overlap
+     * horizontally. Rows which don't are left apart, as they belong to different symbols. A row
+     * without finder patterns can't be told apart, so it is taken to be aligned.
+     */
+    pub fn isAlignedWith(&self, other: &ExpandedRow) -> bool {
+        match (self.span, other.span) {
+            (Some(span), Some(otherSpan)) => span.0 <= otherSpan.1 && otherSpan.0 <= span.1,
+            _ => true,
+        }
+    }
+
     pub fn isEquivalent(&self, otherPairs: &[ExpandedPair]) -> bool {
         self.pairs == otherPairs
     }
@@ -34,8 +34,8 @@ use std::{collections::HashMap, rc::Rc};
 use crate::{
     client::result::{ExpandedProductParsedRXingResult, ParsedClientResult},
     common::GlobalHistogramBinarizer,
-    oned::{rss::expanded::RSSExpandedReader, OneDReader},
-    BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource,
+    oned::{rss::expanded::RSSExpandedReader, MultiFormatOneDReader, OneDReader},
+    BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, Reader,
 };
 
 /**
@@ -67,6 +67,41 @@ fn testDecodeRow2result2() {
     assertCorrectImage2result("2.png", expected);
 }
 
+#[test]
+fn testDecodeStackedResultThroughMultiFormatReader() {
+    // Two rows of pairs, read one after the other by the 1D dispatcher
+    let image = image::open("test_resources/blackbox/rssexpandedstacked-1/1.png")
+        .expect("image must exist");
+    let mut binaryMap = BinaryBitmap::new(Rc::new(GlobalHistogramBinarizer::new(Box::new(
+        BufferedImageLuminanceSource::new(image),
+    ))));
+    let theRXingResult = MultiFormatOneDReader::new(&HashMap::new())
+        .decode(&mut binaryMap)
+        .expect("must decode");
+    assert_eq!(
+        &BarcodeFormat::RSS_EXPANDED,
+        theRXingResult.getBarcodeFormat()
+    );
+    assert_eq!(
+        "(01)90012345678908(3103)012233(15)991231",
+        theRXingResult.getText()
+    );
+
+    let ParsedClientResult::ExpandedProductResult(result) =
+        crate::client::result::parseRXingResult(&theRXingResult)
+    else {
+        panic!("incorrect result type found");
+    };
+    assert_eq!("90012345678908", result.getProductID());
+    assert_eq!("012233", result.getWeight());
+    assert_eq!(
+        ExpandedProductParsedRXingResult::KILOGRAM,
+        result.getWeightType()
+    );
+    assert_eq!("3", result.getWeightIncrement());
+    assert_eq!("991231", result.getBestBeforeDate());
+}
+
 fn assertCorrectImage2result(fileName: &str, expected: ExpandedProductParsedRXingResult) {
     let path = format!("test_resources/blackbox/rssexpanded-1/{fileName}");
 
@@ -24,8 +24,6 @@
  *   http://www.piramidepse.com/
  */
 
-use std::collections::HashMap;
-
 use crate::{
     common::BitArray,
     oned::{
@@ -36,8 +34,8 @@ use crate::{
         },
         OneDReader,
     },
-    BarcodeFormat, DecodeHintType, DecodingHintDictionary, Exceptions, RXingResult,
-    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    BarcodeFormat, DecodeHintType, DecodeHintValue, Exceptions, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue,
 };
 
 use once_cell::sync::Lazy;
@@ -51,6 +49,11 @@ const FINDER_PAT_D: u32 = 3;
 const FINDER_PAT_E: u32 = 4;
 const FINDER_PAT_F: u32 = 5;
 
+const FINDER_PATTERN_MODULES: f32 = 15.0;
+
+/** Modules between the starts of the finder patterns of adjacent pairs are 49, give or take. */
+const MAX_PAIR_DISTANCE_MODULES: f32 = 49.0 * 1.5;
+
 static FINDER_PATTERN_SEQUENCES: Lazy<Vec<Vec<u32>>> = Lazy::new(|| {
     vec![
         vec![FINDER_PAT_A, FINDER_PAT_A],
@@ -148,6 +151,7 @@ pub struct RSSExpandedReader {
     pub(super) rows: Vec<ExpandedRow>,   // new ArrayList<>();
     startEnd: [u32; 2],                  // new int[2];
     startFromEven: bool,
+    stacked: bool,
 }
 impl AbstractRSSReaderTrait for RSSExpandedReader {}
 impl OneDReader for RSSExpandedReader {
@@ -155,8 +159,12 @@ impl OneDReader for RSSExpandedReader {
         &mut self,
         rowNumber: u32,
         row: &crate::common::BitArray,
-        _hints: &crate::DecodingHintDictionary,
+        hints: &crate::DecodingHintDictionary,
     ) -> Result<crate::RXingResult, crate::Exceptions> {
+        self.stacked = matches!(
+            hints.get(&DecodeHintType::RSS_STACKED),
+            Some(DecodeHintValue::RssStacked(true))
+        );
         // Rows can start with even pattern in case in prev rows there where odd number of patters.
         // So lets try twice
         self.pairs.clear();
@@ -180,77 +188,12 @@ impl OneDReader for RSSExpandedReader {
         Self::constructRXingResult(&self.decodeRow2pairs(rowNumber, row)?)
     }
 }
-impl Reader for RSSExpandedReader {
-    fn decode(
-        &mut self,
-        image: &mut crate::BinaryBitmap,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        self.decode_with_hints(image, &HashMap::new())
-    }
-
-    // Note that we don't try rotation without the try harder flag, even if rotation was supported.
-    fn decode_with_hints(
-        &mut self,
-        image: &mut crate::BinaryBitmap,
-        hints: &DecodingHintDictionary,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        if let Ok(res) = self.doDecode(image, hints) {
-            Ok(res)
-        } else {
-            let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
-            if tryHarder && image.isRotateSupported() {
-                let mut rotatedImage = image.rotateCounterClockwise();
-                let mut result = self.doDecode(&mut rotatedImage, hints)?;
-                // Record that we found it rotated 90 degrees CCW / 270 degrees CW
-                let metadata = result.getRXingResultMetadata();
-                let mut orientation = 270;
-                if metadata.contains_key(&RXingResultMetadataType::ORIENTATION) {
-                    // But if we found it reversed in doDecode(), add in that result here:
-                    orientation = (orientation
-                        + if let Some(crate::RXingResultMetadataValue::Orientation(or)) =
-                            metadata.get(&RXingResultMetadataType::ORIENTATION)
-                        {
-                            *or
-                        } else {
-                            0
-                        })
-                        % 360;
-                }
-                result.putMetadata(
-                    RXingResultMetadataType::ORIENTATION,
-                    RXingResultMetadataValue::Orientation(orientation),
-                );
-                // Update result points
-                // let points = result.getRXingResultPoints();
-                // if points != null {
-                let height = rotatedImage.getHeight();
-                // for point in result.getRXingResultPointsMut().iter_mut() {
-                let total_points = result.getRXingResultPoints().len();
-                let points = result.getRXingResultPointsMut();
-                for point in points.iter_mut().take(total_points) {
-                    // for i in 0..total_points {
-                    // for (int i = 0; i < points.length; i++) {
-                    std::mem::swap(&mut point.x, &mut point.y);
-                    point.x = height as f32 - point.x - 1.0;
-                    // points[i] = RXingResultPoint::new(
-                    //     height as f32 - points[i].getY() - 1.0,
-                    //     points[i].getX(),
-                    // );
-                }
-                // }
-
-                Ok(result)
-            } else {
-                Err(Exceptions::NotFoundException(None))
-            }
-        }
-    }
-
+impl_one_d_reader!(RSSExpandedReader {
     fn reset(&mut self) {
         self.pairs.clear();
         self.rows.clear();
     }
-}
+});
 
 impl RSSExpandedReader {
     pub fn new() -> Self {
@@ -393,6 +336,12 @@ impl RSSExpandedReader {
         for i in currentRow..self.rows.len() {
             // for (int i = currentRow; i < rows.size(); i++) {
             let row = self.rows.get(i).unwrap();
+            if collectedRows
+                .first()
+                .is_some_and(|first| !first.isAlignedWith(row))
+            {
+                continue;
+            }
             self.pairs.clear();
             for collectedRow in &collectedRows.clone() {
                 // for (ExpandedRow collectedRow : collectedRows) {
@@ -677,6 +626,22 @@ impl RSSExpandedReader {
             }
         } //while (keepFinding);
 
+        // The finder of the next pair follows the previous one after two data characters. In an
+        // image of stacked symbols, one much farther away belongs to another symbol next to this
+        // one.
+        if let Some(previous) = previousPairs
+            .last()
+            .and_then(|pair| pair.getFinderPattern().as_ref())
+            .filter(|_| self.stacked)
+        {
+            let startEnd = pattern.as_ref().unwrap().getStartEnd();
+            let moduleWidth = (startEnd[1] - startEnd[0]) as f32 / FINDER_PATTERN_MODULES;
+            let distance = startEnd[0] as f32 - previous.getStartEnd()[0] as f32;
+            if distance > MAX_PAIR_DISTANCE_MODULES * moduleWidth {
+                return Err(Exceptions::NotFoundException(None));
+            }
+        }
+
         // When stacked symbol is split over multiple rows, there's no way to guess if this pair can be last or not.
         // boolean mayBeLast = checkPairSequence(previousPairs, pattern);
 
@@ -24,7 +24,14 @@
  *   http://www.piramidepse.com/
  */
 
-use crate::{oned::rss::expanded::ExpandedPair, Exceptions, Reader};
+use std::collections::HashMap;
+
+use image::GenericImage;
+
+use crate::{
+    oned::{rss::expanded::ExpandedPair, OneDReader},
+    BinaryBitmap, DecodeHintType, DecodeHintValue, Exceptions, Reader,
+};
 
 use super::{test_case_util, RSSExpandedReader};
 
@@ -96,3 +103,60 @@ fn testCompleteDecode() {
     let result = rssExpandedReader.decode(&mut binaryMap).expect("decode");
     assert_eq!("(01)98898765432106(3202)012345(15)991231", result.getText());
 }
+
+#[test]
+fn testDecodeTallStackedSymbol() {
+    let hints = HashMap::from([(
+        DecodeHintType::RSS_STACKED,
+        DecodeHintValue::RssStacked(true),
+    )]);
+
+    // Five rows, of which scanning the middle of the image only finds three
+    let mut binaryMap = test_case_util::getBinaryBitmap("22.png");
+    assert!(RSSExpandedReader::new().decode(&mut binaryMap).is_err());
+
+    let result = RSSExpandedReader::new()
+        .decode_with_hints(&mut binaryMap, &hints)
+        .expect("decode");
+    assert_eq!(
+        "(01)98898765432106(15)991231(3103)001750(10)12A(422)123(21)123456(423)012345678901",
+        result.getText()
+    );
+}
+
+#[test]
+fn testPairsOfNeighbouringSymbolsAreNotJoined() {
+    let left = image::open("test_resources/blackbox/rssexpandedstacked-2/19.png")
+        .expect("load image")
+        .to_luma8();
+    let right = image::open("test_resources/blackbox/rssexpandedstacked-2/20.png")
+        .expect("load image")
+        .to_luma8();
+    // Side by side, with some blank space between them
+    let gap = 100;
+    let mut both = image::GrayImage::from_pixel(
+        left.width() + gap + right.width(),
+        left.height(),
+        image::Luma([255]),
+    );
+    both.copy_from(&left, 0, 0).expect("copy");
+    both.copy_from(&right, left.width() + gap, 0).expect("copy");
+    let binaryMap = BinaryBitmap::from_gray_image(both);
+
+    // Through the first row of both symbols
+    let hints = HashMap::from([(
+        DecodeHintType::RSS_STACKED,
+        DecodeHintValue::RssStacked(true),
+    )]);
+    let mut rssExpandedReader = RSSExpandedReader::new();
+    let rowNumber = binaryMap.getHeight() / 10;
+    let row = binaryMap.getBlackRow(rowNumber).expect("get row");
+    assert!(rssExpandedReader
+        .decodeRow(rowNumber as u32, &row, &hints)
+        .is_err());
+
+    // Only the pairs of the symbol on the left make up the row
+    let expandedRow = &rssExpandedReader.getRows()[0];
+    assert_eq!(2, expandedRow.getPairs().len());
+    assert!(expandedRow.getHorizontalSpan().expect("span").1 < left.width() as usize);
+}
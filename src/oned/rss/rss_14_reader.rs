@@ -14,13 +14,11 @@
  * limitations under the License.
  */
 
-use std::collections::HashMap;
-
 use crate::{
     common::BitArray,
     oned::{one_d_reader, OneDReader},
     BarcodeFormat, DecodeHintType, DecodeHintValue, DecodingHintDictionary, Exceptions,
-    RXingResult, RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint, Reader,
+    RXingResult, RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint,
 };
 
 use super::{
@@ -72,77 +70,12 @@ impl OneDReader for RSS14Reader {
         Err(Exceptions::NotFoundException(None))
     }
 }
-impl Reader for RSS14Reader {
-    fn decode(
-        &mut self,
-        image: &mut crate::BinaryBitmap,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        self.decode_with_hints(image, &HashMap::new())
-    }
-
-    // Note that we don't try rotation without the try harder flag, even if rotation was supported.
-    fn decode_with_hints(
-        &mut self,
-        image: &mut crate::BinaryBitmap,
-        hints: &DecodingHintDictionary,
-    ) -> Result<crate::RXingResult, Exceptions> {
-        if let Ok(res) = self.doDecode(image, hints) {
-            Ok(res)
-        } else {
-            let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
-            if tryHarder && image.isRotateSupported() {
-                let mut rotatedImage = image.rotateCounterClockwise();
-                let mut result = self.doDecode(&mut rotatedImage, hints)?;
-                // Record that we found it rotated 90 degrees CCW / 270 degrees CW
-                let metadata = result.getRXingResultMetadata();
-                let mut orientation = 270;
-                if metadata.contains_key(&RXingResultMetadataType::ORIENTATION) {
-                    // But if we found it reversed in doDecode(), add in that result here:
-                    orientation = (orientation
-                        + if let Some(crate::RXingResultMetadataValue::Orientation(or)) =
-                            metadata.get(&RXingResultMetadataType::ORIENTATION)
-                        {
-                            *or
-                        } else {
-                            0
-                        })
-                        % 360;
-                }
-                result.putMetadata(
-                    RXingResultMetadataType::ORIENTATION,
-                    RXingResultMetadataValue::Orientation(orientation),
-                );
-                // Update result points
-                // let points = result.getRXingResultPoints();
-                // if points != null {
-                let height = rotatedImage.getHeight();
-                // for point in result.getRXingResultPointsMut().iter_mut() {
-                let total_points = result.getRXingResultPoints().len();
-                let points = result.getRXingResultPointsMut();
-                // for i in 0..total_points {
-                for point in points.iter_mut().take(total_points) {
-                    // for (int i = 0; i < points.length; i++) {
-                    std::mem::swap(&mut point.x, &mut point.y);
-                    point.x = height as f32 - point.x - 1.0
-                    // points[i] = RXingResultPoint::new(
-                    //     height as f32 - points[i].getY() - 1.0,
-                    //     points[i].getX(),
-                    // );
-                }
-                // }
-
-                Ok(result)
-            } else {
-                Err(Exceptions::NotFoundException(None))
-            }
-        }
-    }
-
+impl_one_d_reader!(RSS14Reader {
     fn reset(&mut self) {
         self.possibleLeftPairs.clear();
         self.possibleRightPairs.clear();
     }
-}
+});
 
 impl RSS14Reader {
     const OUTSIDE_EVEN_TOTAL_SUBSET: [u32; 5] = [1, 10, 34, 70, 126];
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitArray, BarcodeFormat, DecodeHintType, DecodeHintValue, RXingResultMetadataType,
+    RXingResultMetadataValue,
+};
+
+use super::{super::MultiFormatOneDReader, super::OneDReader, RSS14Reader};
+
+const MODULE: usize = 3;
+const QUIET_ZONE: usize = 30;
+
+/**
+ * The widths in modules of the bars and spaces of GTIN 0441234567890(9), from its first bar:
+ * the left guard bar, outside left data character, left finder pattern, inside left and inside
+ * right data characters, right finder pattern, outside right data character and right guard.
+ */
+const GTIN_04412345678909: [usize; 45] = [
+    1, //
+    2, 1, 3, 1, 4, 1, 3, 1, //
+    3, 3, 7, 1, 1, //
+    1, 1, 1, 5, 2, 2, 2, 1, //
+    1, 1, 2, 2, 1, 2, 1, 5, //
+    1, 1, 6, 5, 2, //
+    4, 1, 1, 1, 3, 3, 2, 1, //
+    1, 1,
+];
+
+fn render(widths: &[usize]) -> BitArray {
+    let mut row = BitArray::with_size(2 * QUIET_ZONE + widths.iter().sum::<usize>() * MODULE);
+    let mut x = QUIET_ZONE;
+    for (i, width) in widths.iter().enumerate() {
+        let end = x + width * MODULE;
+        if i % 2 == 0 {
+            row.setRange(x, end).expect("set range");
+        }
+        x = end;
+    }
+    row
+}
+
+#[test]
+fn test_decode_row() {
+    assert_eq!(96, 1 + GTIN_04412345678909.iter().sum::<usize>());
+
+    let row = render(&GTIN_04412345678909);
+    let mut reader = RSS14Reader::new();
+    // Each half must be seen on three rows before it's trusted
+    for rowNumber in 0..2 {
+        assert!(reader.decodeRow(rowNumber, &row, &HashMap::new()).is_err());
+    }
+    let result = reader.decodeRow(2, &row, &HashMap::new()).expect("decode");
+    assert_eq!("04412345678909", result.getText());
+    assert_eq!(&BarcodeFormat::RSS_14, result.getBarcodeFormat());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::SymbologyIdentifier(
+            "]e0".to_owned()
+        )),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::SYMBOLOGY_IDENTIFIER)
+    );
+    // Two points on each finder pattern, from left to right
+    let points = result.getRXingResultPoints();
+    assert_eq!(4, points.len());
+    assert!(points[0].x < points[1].x && points[1].x < points[2].x);
+
+    let hints = HashMap::from([(
+        DecodeHintType::POSSIBLE_FORMATS,
+        DecodeHintValue::PossibleFormats([BarcodeFormat::RSS_14].into()),
+    )]);
+    let mut reader = MultiFormatOneDReader::new(&hints);
+    for rowNumber in 0..2 {
+        assert!(reader.decodeRow(rowNumber, &row, &hints).is_err());
+    }
+    let result = reader.decodeRow(2, &row, &hints).expect("decode");
+    assert_eq!("04412345678909", result.getText());
+}
+
+#[test]
+fn test_damaged_data_character() {
+    // Swap a bar and a space of the inside left data character, keeping its width
+    let mut widths = GTIN_04412345678909;
+    widths.swap(17, 18);
+    let row = render(&widths);
+    let mut reader = RSS14Reader::new();
+    for rowNumber in 0..3 {
+        assert!(reader.decodeRow(rowNumber, &row, &HashMap::new()).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    common::BitArray, BarcodeFormat, DecodeHintType, DecodeHintValue, Exceptions, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint,
+};
+
+use super::{one_d_reader, OneDReader};
+
+/**
+ * <p>Decodes Telepen barcodes, in full ASCII mode as well as in the double density numeric
+ * mode.</p>
+ *
+ * <p>Every character is 8 bits, sent least significant bit first: 7 bits of ASCII followed by an
+ * even parity bit. A 1 bit is a narrow bar and a narrow space, and a pair of 0 bits is a wide bar
+ * and a narrow space. A 0 bit followed by 1 bits is paired with the next 0 bit instead: with a
+ * single 1 bit between them they're a wide bar and a wide space, otherwise both 0 bits become a
+ * narrow bar and a wide space, with the 1 bits in between as usual. Wide elements are three
+ * times as wide as narrow ones, which makes every character 16 modules wide.</p>
+ *
+ * <p>The symbol starts with '_' and ends with 'z', preceded by a modulo 127 check character.
+ * In numeric mode each character holds two digits, or a digit followed by 'X'. DLE switches
+ * between the modes, which can also start out numeric with
+ * {@link DecodeHintType#TELEPEN_AS_NUMERIC}.</p>
+ */
+#[derive(Default)]
+pub struct TelepenReader;
+
+impl OneDReader for TelepenReader {
+    fn decodeRow(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        let (starts, widths) = one_d_reader::recordRuns(row);
+
+        // Try every bar as the start of the symbol, until one decodes
+        let mut lastError = Exceptions::NotFoundException(None);
+        let mut index = 0;
+        while index + START_STOP_ELEMENTS <= widths.len() {
+            match Self::decodeFrom(&starts, &widths, index, hints) {
+                Ok((values, stopIndex)) => {
+                    return Self::createResult(
+                        rowNumber, &starts, &widths, index, stopIndex, &values, hints,
+                    )
+                }
+                Err(e) => lastError = e,
+            }
+            index += 2;
+        }
+        Err(lastError)
+    }
+}
+
+impl_one_d_reader!(TelepenReader);
+
+/** Number of bars and spaces in both the start and the stop character. */
+const START_STOP_ELEMENTS: usize = 12;
+
+const START: u8 = b'_';
+const STOP: u8 = b'z';
+
+/** Switches between full ASCII and numeric mode. */
+const DLE: u8 = 0x10;
+
+const MODULES_PER_CHARACTER: u32 = 16;
+
+/** Elements this many modules wide or wider are wide. */
+const WIDE_THRESHOLD: f32 = 2.0;
+
+/** Elements this many modules wide or wider are neither narrow nor wide. */
+const MAX_WIDE: f32 = 4.5;
+
+/** Blank space required before and after the symbol, in modules: half a character. */
+const QUIET_ZONE_MODULES: f32 = 8.0;
+
+impl TelepenReader {
+    /**
+     * Attempts to decode a symbol whose start character begins with the bar at the given index.
+     *
+     * @return the values of the characters between the start and the stop character, check
+     *  character included, and the index of the first bar of the stop character
+     */
+    fn decodeFrom(
+        starts: &[usize],
+        widths: &[usize],
+        index: usize,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<(Vec<u8>, usize), Exceptions> {
+        let startWidth: usize = widths[index..index + START_STOP_ELEMENTS].iter().sum();
+        if !one_d_reader::matchesXDimension(
+            hints,
+            startWidth,
+            MODULES_PER_CHARACTER,
+            MODULES_PER_CHARACTER,
+        ) {
+            return Err(Exceptions::NotFoundException(None));
+        }
+        let mut moduleWidth = startWidth as f32 / MODULES_PER_CHARACTER as f32;
+        let quietZone = if index == 0 {
+            starts[0]
+        } else {
+            widths[index - 1]
+        };
+        if (quietZone as f32) < QUIET_ZONE_MODULES * moduleWidth {
+            return Err(Exceptions::NotFoundException(None));
+        }
+        let Some((START, mut next, _)) = Self::decodeCharacter(widths, index, moduleWidth) else {
+            return Err(Exceptions::NotFoundException(None));
+        };
+
+        let mut values = Vec::new();
+        loop {
+            let (value, end, counted) = Self::decodeCharacter(widths, next, moduleWidth)
+                .ok_or(Exceptions::NotFoundException(None))?;
+            let lastSpace = widths[end - 1] as f32 / moduleWidth;
+            let implied = counted < MODULES_PER_CHARACTER;
+            if implied {
+                // The last space wasn't measured, as it merges into the quiet zone after the
+                // stop character. Anywhere else it must be narrow.
+                if value == STOP && (lastSpace >= QUIET_ZONE_MODULES || end == widths.len()) {
+                    return Ok((values, next));
+                }
+                if lastSpace >= WIDE_THRESHOLD {
+                    return Err(Exceptions::NotFoundException(None));
+                }
+            }
+            let pixels: usize = widths[next..end - implied as usize].iter().sum();
+            moduleWidth = pixels as f32 / counted as f32;
+            values.push(value);
+            next = end;
+        }
+    }
+
+    /**
+     * Decodes the character whose first bar is at the given index.
+     *
+     * @return the character value, the index following the character, and the number of
+     *  modules which were measured, which leaves out a final narrow space that's implied
+     */
+    fn decodeCharacter(
+        widths: &[usize],
+        index: usize,
+        moduleWidth: f32,
+    ) -> Option<(u8, usize, u32)> {
+        let mut elements = Vec::with_capacity(16);
+        let mut total = 0;
+        let mut measured = 0;
+        let mut i = index;
+        while total < MODULES_PER_CHARACTER {
+            let isSpace = (i - index) % 2 == 1;
+            let modules = if isSpace && total == MODULES_PER_CHARACTER - 1 {
+                1
+            } else {
+                let modules = widths.get(i)?.to_owned() as f32 / moduleWidth;
+                if modules >= MAX_WIDE {
+                    return None;
+                }
+                let modules = if modules >= WIDE_THRESHOLD { 3 } else { 1 };
+                measured = total + modules;
+                modules
+            };
+            total += modules;
+            elements.push(modules);
+            i += 1;
+        }
+        if total != MODULES_PER_CHARACTER || i > widths.len() {
+            return None;
+        }
+        Some((Self::toValue(&elements)?, i, measured))
+    }
+
+    /**
+     * Turns the widths, in modules, of the bars and spaces of a character into its value.
+     */
+    fn toValue(elements: &[u32]) -> Option<u8> {
+        let mut bits = Vec::with_capacity(8);
+        let mut pairedZero = false;
+        for pair in elements.chunks_exact(2) {
+            match (pairedZero, pair[0], pair[1]) {
+                (_, 1, 1) => bits.push(1),
+                (false, 3, 1) => bits.extend([0, 0]),
+                (false, 3, 3) => bits.extend([0, 1, 0]),
+                (false, 1, 3) => {
+                    bits.extend([0, 1]);
+                    pairedZero = true;
+                }
+                (true, 1, 3) => {
+                    bits.extend([1, 0]);
+                    pairedZero = false;
+                }
+                _ => return None,
+            }
+        }
+        if pairedZero || bits.len() != 8 || bits.iter().sum::<u8>() % 2 != 0 {
+            return None;
+        }
+        Some(
+            bits[..7]
+                .iter()
+                .enumerate()
+                .fold(0, |value, (i, bit)| value | (bit << i)),
+        )
+    }
+
+    fn createResult(
+        rowNumber: u32,
+        starts: &[usize],
+        widths: &[usize],
+        startIndex: usize,
+        stopIndex: usize,
+        values: &[u8],
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<RXingResult, Exceptions> {
+        // At least one data character and the check character
+        if values.len() < 2 {
+            return Err(Exceptions::NotFoundException(None));
+        }
+        if values.iter().map(|v| *v as u32).sum::<u32>() % 127 != 0 {
+            return Err(Exceptions::ChecksumException(None));
+        }
+
+        let startsNumeric = matches!(
+            hints.get(&DecodeHintType::TELEPEN_AS_NUMERIC),
+            Some(DecodeHintValue::TelepenAsNumeric(true))
+        );
+        let mut numeric = startsNumeric;
+        let mut switched = false;
+        let mut text = String::with_capacity(values.len() * 2);
+        for value in &values[..values.len() - 1] {
+            match (numeric, *value) {
+                (_, DLE) => {
+                    numeric = !numeric;
+                    switched = true;
+                }
+                (true, 27..=126) => text.push_str(&format!("{:02}", value - 27)),
+                (true, 17..=26) => {
+                    text.push(char::from(b'0' + value - 17));
+                    text.push('X');
+                }
+                (true, _) => return Err(Exceptions::FormatException(None)),
+                (false, _) => text.push(char::from(*value)),
+            }
+        }
+
+        let symbologyModifier = match (startsNumeric, switched) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (true, true) => 2,
+            (false, true) => 4,
+        };
+
+        let center = |index: usize| {
+            let width: usize = widths[index..index + START_STOP_ELEMENTS - 1].iter().sum();
+            starts[index] as f32 + width as f32 / 2.0
+        };
+        let mut result = RXingResult::new(
+            &text,
+            Vec::new(),
+            vec![
+                RXingResultPoint::new(center(startIndex), rowNumber as f32),
+                RXingResultPoint::new(center(stopIndex), rowNumber as f32),
+            ],
+            BarcodeFormat::TELEPEN,
+        );
+        result.putMetadata(
+            RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+            RXingResultMetadataValue::SymbologyIdentifier(format!("]B{symbologyModifier}")),
+        );
+        Ok(result)
+    }
+}
@@ -0,0 +1,178 @@
+/*
+ * Copyright 2026 ZXing authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    common::BitArray, shared_test_methods::render_bitmap, BarcodeFormat, DecodeHintType,
+    DecodeHintValue, DecodingHintDictionary, Exceptions, RXingResultMetadataType,
+    RXingResultMetadataValue, Reader,
+};
+
+use super::{MultiFormatOneDReader, OneDReader, TelepenReader};
+
+const MODULE: usize = 2;
+const QUIET_ZONE: usize = 20;
+
+/**
+ * The widths of the bars and spaces of a character in modules, see {@link TelepenReader}.
+ */
+fn character(value: u8) -> Vec<usize> {
+    let mut bits: Vec<bool> = (0..7).map(|i| value & (1 << i) != 0).collect();
+    bits.push(value.count_ones() % 2 == 1);
+
+    let mut elements = Vec::new();
+    let mut i = 0;
+    while i < bits.len() {
+        if bits[i] {
+            elements.extend([1, 1]);
+            i += 1;
+        } else if !bits[i + 1] {
+            elements.extend([3, 1]);
+            i += 2;
+        } else {
+            let ones = bits[i + 1..].iter().take_while(|bit| **bit).count();
+            if ones == 1 {
+                elements.extend([3, 3]);
+            } else {
+                elements.extend([1, 3]);
+                for _ in 1..ones - 1 {
+                    elements.extend([1, 1]);
+                }
+                elements.extend([1, 3]);
+            }
+            i += ones + 2;
+        }
+    }
+    elements
+}
+
+fn check_character(values: &[u8]) -> u8 {
+    ((127 - values.iter().map(|v| *v as u32).sum::<u32>() % 127) % 127) as u8
+}
+
+/**
+ * Renders a Telepen symbol holding the given character values and check character.
+ */
+fn render(values: &[u8], check: u8) -> BitArray {
+    let mut characters = vec![b'_'];
+    characters.extend_from_slice(values);
+    characters.push(check);
+    characters.push(b'z');
+
+    let mut row = BitArray::with_size(QUIET_ZONE * 2 * MODULE + characters.len() * 16 * MODULE);
+    let mut x = QUIET_ZONE * MODULE;
+    for value in characters {
+        for (i, modules) in character(value).into_iter().enumerate() {
+            if i % 2 == 0 {
+                row.setRange(x, x + modules * MODULE).expect("set range");
+            }
+            x += modules * MODULE;
+        }
+    }
+    row
+}
+
+fn encode_row(values: &[u8]) -> BitArray {
+    render(values, check_character(values))
+}
+
+fn symbology_identifier(result: &crate::RXingResult) -> &str {
+    match result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::SYMBOLOGY_IDENTIFIER)
+    {
+        Some(RXingResultMetadataValue::SymbologyIdentifier(id)) => id,
+        _ => panic!("missing symbology identifier"),
+    }
+}
+
+#[test]
+fn test_character_patterns() {
+    // Start and stop characters, and a few from the character table
+    let expected = [
+        (b'_', "111111111133"),
+        (b'z', "331111111111"),
+        (0, "31313131"),
+        (5, "11333131"),
+        (6, "13133131"),
+        (14, "1311133111"),
+        (127, "1111111111111111"),
+    ];
+    for (value, pattern) in expected {
+        let elements: String = character(value).iter().map(|e| e.to_string()).collect();
+        assert_eq!(pattern, elements, "character {value}");
+    }
+}
+
+#[test]
+fn test_decode_ascii_row() {
+    let row = encode_row(b"Telepen 123!");
+    let result = TelepenReader
+        .decodeRow(0, &row, &HashMap::new())
+        .expect("decode");
+    assert_eq!("Telepen 123!", result.getText());
+    assert_eq!(&BarcodeFormat::TELEPEN, result.getBarcodeFormat());
+    assert_eq!("]B0", symbology_identifier(&result));
+}
+
+#[test]
+fn test_decode_numeric_row() {
+    // "12", "34", "56" and "7X" in double density numeric mode
+    let row = encode_row(&[12 + 27, 34 + 27, 56 + 27, 7 + 17]);
+    let hints: DecodingHintDictionary = HashMap::from([(
+        DecodeHintType::TELEPEN_AS_NUMERIC,
+        DecodeHintValue::TelepenAsNumeric(true),
+    )]);
+    let result = TelepenReader.decodeRow(0, &row, &hints).expect("decode");
+    assert_eq!("1234567X", result.getText());
+    assert_eq!("]B1", symbology_identifier(&result));
+
+    // Switching to numeric mode with DLE
+    let row = encode_row(&[b'A', b'B', 0x10, 12 + 27, 34 + 27]);
+    let result = TelepenReader
+        .decodeRow(0, &row, &HashMap::new())
+        .expect("decode");
+    assert_eq!("AB1234", result.getText());
+    assert_eq!("]B4", symbology_identifier(&result));
+}
+
+#[test]
+fn test_bad_check_character() {
+    let check = check_character(b"TELEPEN");
+    assert!(TelepenReader
+        .decodeRow(0, &render(b"TELEPEN", check), &HashMap::new())
+        .is_ok());
+    assert!(matches!(
+        TelepenReader.decodeRow(0, &render(b"TELEPEN", check + 1), &HashMap::new()),
+        Err(Exceptions::ChecksumException(_))
+    ));
+}
+
+#[test]
+fn test_multi_format_decodes_telepen() {
+    let row = encode_row(b"RXING");
+    let mut image = render_bitmap(row.getSize() as u32, 40, |x, _| row.get(x as usize));
+    let hints = HashMap::from([(
+        DecodeHintType::POSSIBLE_FORMATS,
+        DecodeHintValue::PossibleFormats(HashSet::from([BarcodeFormat::TELEPEN])),
+    )]);
+    let result = MultiFormatOneDReader::new(&hints)
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    assert_eq!("RXING", result.getText());
+    assert_eq!(&BarcodeFormat::TELEPEN, result.getBarcodeFormat());
+}
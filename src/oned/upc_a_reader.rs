@@ -69,6 +69,15 @@ impl UPCEANReader for UPCAReader {
         self.0.decodeMiddle(row, startRange, resultString)
     }
 
+    fn decodeMiddleWithGuard(
+        &self,
+        row: &crate::common::BitArray,
+        startRange: &[usize; 2],
+        resultString: &mut String,
+    ) -> Result<(usize, Option<[usize; 2]>), Exceptions> {
+        self.0.decodeMiddleWithGuard(row, startRange, resultString)
+    }
+
     fn decodeRowWithGuardRange(
         &self,
         rowNumber: u32,
@@ -28,6 +28,28 @@ use super::EAN13Writer;
 #[derive(Default)]
 pub struct UPCAWriter(EAN13Writer);
 
+impl UPCAWriter {
+    /**
+     * Encodes a UPC-A code as a row of 95 modules, the same bars as the EAN-13 code with a
+     * leading zero, without quiet zones.
+     *
+     * @param contents 11 digits, to which the check digit is added, or 12 digits with a valid
+     *  check digit
+     * @return the modules of the row, true for bars
+     * @throws IllegalArgumentException if contents has the wrong length, non-digits or a wrong
+     *  check digit
+     */
+    pub fn encode(contents: &str) -> Result<Vec<bool>, Exceptions> {
+        if !(11..=12).contains(&contents.chars().count()) {
+            return Err(Exceptions::IllegalArgumentException(Some(format!(
+                "Requested contents should be 11 or 12 digits long, but got {}",
+                contents.chars().count()
+            ))));
+        }
+        EAN13Writer::encode(&format!("0{contents}"))
+    }
+}
+
 impl Writer for UPCAWriter {
     fn encode(
         &self,
@@ -70,7 +92,13 @@ impl Writer for UPCAWriter {
  */
 #[cfg(test)]
 mod UPCAWriterTestCase {
-    use crate::{common::bit_matrix_test_case, BarcodeFormat, Writer};
+    use std::collections::HashMap;
+
+    use crate::{
+        common::{bit_matrix_test_case, BitArray},
+        oned::{EAN13Writer, OneDReader, UPCAReader},
+        BarcodeFormat, Writer,
+    };
 
     use super::UPCAWriter;
 
@@ -89,6 +117,28 @@ mod UPCAWriterTestCase {
         assert_eq!(testStr, bit_matrix_test_case::matrix_to_string(&result));
     }
 
+    #[test]
+    fn testEncodeRow() {
+        let row = UPCAWriter::encode("036000291452").expect("encode");
+        assert_eq!(95, row.len());
+        assert_eq!(row, EAN13Writer::encode("0036000291452").expect("encode"));
+        assert_eq!(row, UPCAWriter::encode("03600029145").expect("encode"));
+
+        let mut bits = BitArray::with_size(row.len() + 20);
+        for (i, bar) in row.iter().enumerate() {
+            if *bar {
+                bits.set(i + 10);
+            }
+        }
+        let result = UPCAReader::default()
+            .decodeRow(0, &bits, &HashMap::new())
+            .expect("decode");
+        assert_eq!("036000291452", result.getText());
+
+        assert!(UPCAWriter::encode("036000291453").is_err());
+        assert!(UPCAWriter::encode("0036000291452").is_err());
+    }
+
     #[test]
     fn testAddChecksumAndEncode() {
         let testStr =
@@ -16,7 +16,6 @@
 
 use super::{OneDReader, UPCEANReader, L_AND_G_PATTERNS};
 use crate::{BarcodeFormat, Exceptions};
-use rxing_one_d_proc_derive::{EANReader, OneDReader};
 
 /**
  * <p>Implements decoding of the UPC-E format.</p>
@@ -25,9 +24,23 @@ use rxing_one_d_proc_derive::{EANReader, OneDReader};
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(Default)]
 pub struct UPCEReader;
 
+impl OneDReader for UPCEReader {
+    fn decodeRow(
+        &mut self,
+        rowNumber: u32,
+        row: &crate::common::BitArray,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<crate::RXingResult, Exceptions> {
+        let startGuardRange = self.findStartGuardPatternWithHints(row, hints)?;
+        self.decodeRowWithGuardRange(rowNumber, row, &startGuardRange, hints)
+    }
+}
+
+impl_one_d_reader!(UPCEReader);
+
 impl UPCEANReader for UPCEReader {
     fn getBarcodeFormat(&self) -> crate::BarcodeFormat {
         BarcodeFormat::UPC_E
@@ -19,9 +19,12 @@ use crate::{
     RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint, Reader,
 };
 
-use super::{one_d_reader, EANManufacturerOrgSupport, OneDReader, UPCEANExtensionSupport};
+use super::{
+    check_digits, one_d_reader, EANManufacturerOrgSupport, OneDReader, UPCEANExtensionSupport,
+};
 
 use once_cell::sync::Lazy;
+use std::collections::HashMap;
 
 pub static EAN_MANUFACTURER_SUPPORT: Lazy<EANManufacturerOrgSupport> =
     Lazy::new(EANManufacturerOrgSupport::default);
@@ -115,6 +118,20 @@ pub trait UPCEANReader: OneDReader {
     // }
 
     fn findStartGuardPattern(&self, row: &BitArray) -> Result<[usize; 2], Exceptions> {
+        self.findStartGuardPatternWithHints(row, &HashMap::new())
+    }
+
+    /**
+     * Like {@link #findStartGuardPattern(BitArray)}, but with the
+     * {@link DecodeHintType#RELAX_QUIET_ZONE} hint a quiet zone which runs off the left edge of
+     * the image is accepted, as long as the part of it inside the image is white.
+     */
+    fn findStartGuardPatternWithHints(
+        &self,
+        row: &BitArray,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Result<[usize; 2], Exceptions> {
+        let relaxQuietZone = one_d_reader::relaxesQuietZone(hints);
         let mut foundStart = false;
         let mut startRange = [0; 2]; //= null;
         let mut nextStart = 0;
@@ -133,10 +150,12 @@ pub trait UPCEANReader: OneDReader {
             nextStart = startRange[1];
             // Make sure there is a quiet zone at least as big as the start pattern before the barcode.
             // If this check would run off the left edge of the image, do not accept this barcode,
-            // as it is very likely to be a false positive.
+            // as it is very likely to be a false positive, unless the quiet zone is relaxed.
             let quietStart = start as isize - (nextStart as isize - start as isize);
             if quietStart >= 0 {
                 foundStart = row.isRange(quietStart as usize, start, false)?;
+            } else if relaxQuietZone {
+                foundStart = row.isRange(0, start, false)?;
             }
         }
 
@@ -173,6 +192,10 @@ pub trait UPCEANReader: OneDReader {
         let resultPointCallback = hints.get(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
         let mut symbologyIdentifier = 0;
 
+        if !one_d_reader::matchesXDimension(hints, startGuardRange[1] - startGuardRange[0], 3, 3) {
+            return Err(Exceptions::NotFoundException(None));
+        }
+
         if let Some(DecodeHintValue::NeedResultPointCallback(cb)) = resultPointCallback {
             cb(&RXingResultPoint::new(
                 (startGuardRange[0] + startGuardRange[1]) as f32 / 2.0,
@@ -181,7 +204,8 @@ pub trait UPCEANReader: OneDReader {
         }
 
         let mut result = String::new(); //decodeRowStringBuffer;
-        let endStart = self.decodeMiddle(row, startGuardRange, &mut result)?;
+        let (endStart, middleRange) =
+            self.decodeMiddleWithGuard(row, startGuardRange, &mut result)?;
 
         if let Some(DecodeHintValue::NeedResultPointCallback(cb)) = resultPointCallback {
             cb(&RXingResultPoint::new(endStart as f32, rowNumber as f32));
@@ -198,9 +222,27 @@ pub trait UPCEANReader: OneDReader {
 
         // Make sure there is a quiet zone at least as big as the end pattern after the barcode. The
         // spec might want more whitespace, but in practice this is the maximum we can count on.
+        // When the quiet zone is relaxed it may run off the right edge of the image.
         let end = endRange[1];
-        let quietEnd = end + (end - endRange[0]);
-        if quietEnd >= row.getSize() || !row.isRange(end, quietEnd, false)? {
+        let mut quietEnd = end + (end - endRange[0]);
+        if quietEnd >= row.getSize() {
+            if !one_d_reader::relaxesQuietZone(hints) {
+                return Err(Exceptions::NotFoundException(None));
+            }
+            quietEnd = row.getSize();
+        }
+        if !row.isRange(end, quietEnd, false)? {
+            return Err(Exceptions::NotFoundException(None));
+        }
+
+        // The whole symbol, guards included, is a fixed number of modules wide
+        let format = self.getBarcodeFormat();
+        let modules = match format {
+            BarcodeFormat::EAN_8 => 67,
+            BarcodeFormat::UPC_E => 51,
+            _ => 95,
+        };
+        if !one_d_reader::matchesXDimension(hints, end - startGuardRange[0], modules, modules) {
             return Err(Exceptions::NotFoundException(None));
         }
 
@@ -213,25 +255,56 @@ pub trait UPCEANReader: OneDReader {
             return Err(Exceptions::ChecksumException(None));
         }
 
-        let left = (startGuardRange[1] + startGuardRange[0]) as f32 / 2.0;
-        let right: f32 = (endRange[1] + endRange[0]) as f32 / 2.0;
-        let format = self.getBarcodeFormat();
-        let mut decodeRXingResult = RXingResult::new(
-            &resultString,
-            Vec::new(), // no natural byte representation for these barcodes
+        let points = if matches!(
+            hints.get(&DecodeHintType::RETURN_UPC_EAN_GUARD_POINTS),
+            Some(DecodeHintValue::ReturnUpcEanGuardPoints(true))
+        ) {
+            let mut edges = vec![startGuardRange[0] as f32, startGuardRange[1] as f32];
+            // UPC-E has no middle guard pattern
+            if let Some(middleRange) = middleRange {
+                edges.push((middleRange[0] + middleRange[1]) as f32 / 2.0);
+            }
+            edges.extend([endRange[0] as f32, endRange[1] as f32]);
+            edges
+                .into_iter()
+                .map(|x| RXingResultPoint::new(x, rowNumber as f32))
+                .collect()
+        } else {
+            let left = (startGuardRange[1] + startGuardRange[0]) as f32 / 2.0;
+            let right: f32 = (endRange[1] + endRange[0]) as f32 / 2.0;
             vec![
                 RXingResultPoint::new(left, rowNumber as f32),
                 RXingResultPoint::new(right, rowNumber as f32),
-            ],
+            ]
+        };
+        let appendExtension = matches!(
+            hints.get(&DecodeHintType::APPEND_UPC_EAN_EXTENSION),
+            Some(DecodeHintValue::AppendUpcEanExtension(true))
+        );
+        let extension = UPC_EAN_EXTENSION_SUPPORT
+            .decodeRow(rowNumber, row, endRange[1])
+            .ok()
+            .filter(|extensionRXingResult| {
+                !appendExtension
+                    || isExtensionNearby(extensionRXingResult, startGuardRange[0], end, modules)
+            });
+
+        let text = match &extension {
+            Some(extensionRXingResult) if appendExtension => {
+                format!("{} {}", resultString, extensionRXingResult.getText())
+            }
+            _ => resultString.clone(),
+        };
+        let mut decodeRXingResult = RXingResult::new(
+            &text,
+            Vec::new(), // no natural byte representation for these barcodes
+            points,
             format,
         );
 
         let mut extensionLength = 0;
 
-        let mut attempt = || -> Result<(), Exceptions> {
-            let extensionRXingResult =
-                UPC_EAN_EXTENSION_SUPPORT.decodeRow(rowNumber, row, endRange[1])?;
-
+        if let Some(extensionRXingResult) = extension {
             decodeRXingResult.putMetadata(
                 RXingResultMetadataType::UPC_EAN_EXTENSION,
                 RXingResultMetadataValue::UpcEanExtension(extensionRXingResult.getText().clone()),
@@ -240,10 +313,7 @@ pub trait UPCEANReader: OneDReader {
             decodeRXingResult
                 .addRXingResultPoints(&mut extensionRXingResult.getRXingResultPoints().clone());
             extensionLength = extensionRXingResult.getText().chars().count();
-            Ok(())
-        };
-
-        let _try_result = attempt();
+        }
         // if let Err(Exceptions::ReaderException(_)) = try_result {
         // } else if try_result.is_err() {
         //     return Err(try_result.err().unwrap());
@@ -348,32 +418,7 @@ pub trait UPCEANReader: OneDReader {
     }
 
     fn getStandardUPCEANChecksum(&self, s: &str) -> Result<u32, Exceptions> {
-        let length = s.chars().count();
-        let mut sum = 0;
-        let mut i = length as isize - 1;
-        while i >= 0 {
-            // for (int i = length - 1; i >= 0; i -= 2) {
-            let digit = (s.chars().nth(i as usize).unwrap() as i32) - ('0' as i32);
-            if !(0..=9).contains(&digit) {
-                return Err(Exceptions::FormatException(None));
-            }
-            sum += digit;
-
-            i -= 2;
-        }
-        sum *= 3;
-        let mut i = length as isize - 2;
-        while i >= 0 {
-            // for (int i = length - 2; i >= 0; i -= 2) {
-            let digit = (s.chars().nth(i as usize).unwrap() as i32) - ('0' as i32);
-            if !(0..=9).contains(&digit) {
-                return Err(Exceptions::FormatException(None));
-            }
-            sum += digit;
-
-            i -= 2;
-        }
-        Ok(((1000 - sum) % 10) as u32)
+        check_digits::gtin_mod10(s).ok_or(Exceptions::FormatException(None))
     }
 
     fn decodeEnd(&self, row: &BitArray, endStart: usize) -> Result<[usize; 2], Exceptions> {
@@ -519,6 +564,43 @@ pub trait UPCEANReader: OneDReader {
         startRange: &[usize; 2],
         resultString: &mut String,
     ) -> Result<usize, Exceptions>;
+
+    /**
+     * Like {@link #decodeMiddle(BitArray, int[], StringBuilder)}, but also returns the start/end
+     * offsets of the middle guard pattern, for formats which have one.
+     */
+    fn decodeMiddleWithGuard(
+        &self,
+        row: &BitArray,
+        startRange: &[usize; 2],
+        resultString: &mut String,
+    ) -> Result<(usize, Option<[usize; 2]>), Exceptions> {
+        Ok((self.decodeMiddle(row, startRange, resultString)?, None))
+    }
+}
+
+/**
+ * The specification puts 7 to 12 modules between a symbol and its supplement; printed codes
+ * stretch that somewhat, so allow a few more.
+ */
+const MAX_EXTENSION_GAP_MODULES: f32 = 16.0;
+
+/**
+ * @param extension supplement found after the symbol
+ * @param start where the symbol's start guard begins
+ * @param end where the symbol's end guard ends
+ * @param modules width of the symbol in modules
+ * @return true if the supplement starts close enough after the symbol to belong to it
+ */
+fn isExtensionNearby(extension: &RXingResult, start: usize, end: usize, modules: u32) -> bool {
+    let moduleSize = (end - start) as f32 / modules as f32;
+    // The first point is the center of the supplement's 4 module wide start guard
+    extension
+        .getRXingResultPoints()
+        .first()
+        .is_some_and(|point| {
+            point.x - 2.0 * moduleSize - end as f32 <= MAX_EXTENSION_GAP_MODULES * moduleSize
+        })
 }
 
 pub(crate) struct StandInStruct;
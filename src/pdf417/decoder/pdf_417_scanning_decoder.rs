@@ -184,6 +184,7 @@ fn merge<'a, T: DetectionRXingResultRowIndicatorColumn>(
     if leftRowIndicatorColumn.is_none() && rightRowIndicatorColumn.is_none() {
         return Ok(None);
     }
+    dropUnreadableRightRowIndicatorColumn(leftRowIndicatorColumn, rightRowIndicatorColumn);
     let barcodeMetadata = getBarcodeMetadata(leftRowIndicatorColumn, rightRowIndicatorColumn);
     if barcodeMetadata.is_none() {
         return Ok(None);
@@ -199,6 +200,35 @@ fn merge<'a, T: DetectionRXingResultRowIndicatorColumn>(
     )))
 }
 
+/**
+ * Drops the right row indicator column if its votes give no metadata, or metadata at odds with
+ * that of the left column, as happens when the right edge of the symbol is damaged. Decoding then
+ * goes on with the left column only, as if the right one hadn't been found.
+ */
+fn dropUnreadableRightRowIndicatorColumn<T: DetectionRXingResultRowIndicatorColumn>(
+    leftRowIndicatorColumn: &mut Option<T>,
+    rightRowIndicatorColumn: &mut Option<T>,
+) {
+    let Some(leftBarcodeMetadata) = leftRowIndicatorColumn
+        .as_mut()
+        .and_then(|column| column.getBarcodeMetadata())
+    else {
+        return;
+    };
+    let readable = rightRowIndicatorColumn
+        .as_mut()
+        .and_then(|column| column.getBarcodeMetadata())
+        .is_some_and(|rightBarcodeMetadata| {
+            leftBarcodeMetadata.getColumnCount() == rightBarcodeMetadata.getColumnCount()
+                && leftBarcodeMetadata.getErrorCorrectionLevel()
+                    == rightBarcodeMetadata.getErrorCorrectionLevel()
+                && leftBarcodeMetadata.getRowCount() == rightBarcodeMetadata.getRowCount()
+        });
+    if !readable {
+        *rightRowIndicatorColumn = None;
+    }
+}
+
 fn adjustBoundingBox<T: DetectionRXingResultRowIndicatorColumn>(
     rowIndicatorColumn: &mut Option<T>,
 ) -> Result<Option<BoundingBox>, Exceptions> {
@@ -400,7 +430,8 @@ fn adjustCodewordCount(
 fn createDecoderRXingResult(
     detectionRXingResult: &mut DetectionRXingResult,
 ) -> Result<DecoderRXingResult, Exceptions> {
-    let mut barcodeMatrix = createBarcodeMatrix(detectionRXingResult);
+    let mut unplacedCodewords = Vec::new();
+    let mut barcodeMatrix = createBarcodeMatrix(detectionRXingResult, &mut unplacedCodewords);
     adjustCodewordCount(detectionRXingResult, &mut barcodeMatrix)?;
     let mut erasures = Vec::new(); //new ArrayList<>();
     let mut codewords = vec![
@@ -435,13 +466,22 @@ fn createDecoderRXingResult(
     // // for (int i = 0; i < ambiguousIndexValues.length; i++) {
     //   ambiguousIndexValues[i] = ambiguousIndexValuesList.get(i) as u32;
     // }
-    createDecoderRXingResultFromAmbiguousValues(
+    let mut decoderRXingResult = createDecoderRXingResultFromAmbiguousValues(
         detectionRXingResult.getBarcodeECLevel(),
         &mut codewords,
         &mut erasures,
         &mut ambiguousIndexesList,
         &ambiguousIndexValues,
-    )
+    )?;
+    decoderRXingResult.setAmbiguousCodewords(
+        ambiguousIndexesList
+            .into_iter()
+            .zip(ambiguousIndexValues)
+            .collect(),
+    );
+    decoderRXingResult.setUnplacedCodewords(unplacedCodewords);
+
+    Ok(decoderRXingResult)
 }
 
 /**
@@ -503,7 +543,14 @@ fn createDecoderRXingResultFromAmbiguousValues(
     Err(Exceptions::ChecksumException(None))
 }
 
-fn createBarcodeMatrix(detectionRXingResult: &mut DetectionRXingResult) -> Vec<Vec<BarcodeValue>> {
+/**
+ * @param unplacedCodewords receives the values of codewords which were read, but have no valid row
+ * number within the barcode, so that they can't be placed in the matrix
+ */
+fn createBarcodeMatrix(
+    detectionRXingResult: &mut DetectionRXingResult,
+    unplacedCodewords: &mut Vec<u32>,
+) -> Vec<Vec<BarcodeValue>> {
     let mut barcodeMatrix =
         vec![
             vec![BarcodeValue::new(); detectionRXingResult.getBarcodeColumnCount() + 2];
@@ -537,9 +584,12 @@ fn createBarcodeMatrix(detectionRXingResult: &mut DetectionRXingResult) -> Vec<V
                 if rowNumber >= 0 {
                     if rowNumber as usize >= barcodeMatrix.len() {
                         // We have more rows than the barcode metadata allows for, ignore them.
+                        unplacedCodewords.push(codeword.getValue());
                         continue;
                     }
                     barcodeMatrix[rowNumber as usize][column].setValue(codeword.getValue());
+                } else {
+                    unplacedCodewords.push(codeword.getValue());
                 }
                 // }
             }
@@ -837,6 +887,8 @@ fn decodeCodewords(
         decoded_bit_stream_parser::decode(codewords, &ecLevel.to_string())?;
     decoderRXingResult.setErrorsCorrected(correctedErrorsCount);
     decoderRXingResult.setErasures(erasures.len());
+    // verifyCodewordCount() has made sure the length descriptor is in range
+    decoderRXingResult.setDataCodewords(codewords[..codewords[0] as usize].to_vec());
 
     Ok(decoderRXingResult)
 }
@@ -20,7 +20,10 @@
 
 use encoding::EncodingRef;
 
-use crate::Exceptions;
+use crate::{
+    pdf417::pdf_417_common::{MAX_ROWS_IN_BARCODE, MIN_ROWS_IN_BARCODE},
+    Exceptions,
+};
 
 use super::{
     pdf_417_error_correction, pdf_417_high_level_encoder, BarcodeMatrix, BarcodeRow, Compaction,
@@ -273,16 +276,20 @@ impl PDF417 {
         let mut ratio = 0.0;
         let mut dimension = None;
 
+        // Symbols outside the row count limits of the specification can't be read
+        let minRows = self.minRows.max(MIN_ROWS_IN_BARCODE);
+        let maxRows = self.maxRows.min(MAX_ROWS_IN_BARCODE);
+
         for cols in self.minCols..=self.maxCols {
             // for (int cols = minCols; cols <= maxCols; cols++) {
 
             let rows = Self::calculateNumberOfRows(sourceCodeWords, errorCorrectionCodeWords, cols);
 
-            if rows < self.minRows {
+            if rows < minRows {
                 break;
             }
 
-            if rows > self.maxRows {
+            if rows > maxRows {
                 continue;
             }
 
@@ -307,8 +314,8 @@ impl PDF417 {
                 errorCorrectionCodeWords,
                 self.minCols,
             );
-            if rows < self.minRows {
-                dimension = Some([self.minCols, self.minRows]);
+            if rows < minRows {
+                dimension = Some([self.minCols, minRows]);
             }
         }
 
@@ -322,7 +329,8 @@ impl PDF417 {
     }
 
     /**
-     * Sets max/min row/col values
+     * Sets max/min row/col values. Row counts outside of [MIN_ROWS_IN_BARCODE, MAX_ROWS_IN_BARCODE]
+     * are never used, whatever the limits given.
      *
      * @param maxCols maximum allowed columns
      * @param minCols minimum allowed columns
@@ -21,6 +21,7 @@
 use std::{any::TypeId, fmt::Display};
 
 use encoding::EncodingRef;
+use num::{BigUint, ToPrimitive, Zero};
 
 use crate::{
     common::{CharacterSetECI, ECIInput, MinimalECIInput},
@@ -613,8 +614,8 @@ fn encodeBinary(bytes: &[u8], startpos: u32, count: u32, startmode: u32, sb: &mu
 fn encodeNumeric<T: ECIInput + ?Sized>(input: &Box<T>, startpos: u32, count: u32, sb: &mut String) {
     let mut idx = 0;
     let mut tmp = String::with_capacity(count as usize / 3 + 1);
-    let num900: u128 = 900;
-    let num0: u128 = 0;
+    // Up to 44 digits with a leading 1 overflow a u128
+    let num900 = BigUint::from(900u32);
     while idx < count {
         tmp.clear();
         let len = 44.min(count as isize - idx as isize);
@@ -629,12 +630,12 @@ fn encodeNumeric<T: ECIInput + ?Sized>(input: &Box<T>, startpos: u32, count: u32
                 .iter()
                 .collect::<String>()
         );
-        let mut bigint: u128 = part.parse().unwrap();
+        let mut bigint: BigUint = part.parse().unwrap();
         loop {
-            tmp.push(char::from_u32((bigint % num900) as u32).unwrap());
-            bigint /= num900;
+            tmp.push(char::from_u32((&bigint % &num900).to_u32().unwrap()).unwrap());
+            bigint /= &num900;
 
-            if bigint == num0 {
+            if bigint.is_zero() {
                 break;
             }
         } //while (!bigint.equals(num0));
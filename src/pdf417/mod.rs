@@ -10,5 +10,8 @@ pub use pdf_417_result_metadata::*;
 mod pdf_417_reader;
 pub use pdf_417_reader::*;
 
+#[cfg(test)]
+mod pdf_417_reader_test_case;
+
 mod pdf_417_writer;
 pub use pdf_417_writer::*;
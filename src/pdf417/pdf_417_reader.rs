@@ -17,9 +17,9 @@
 use std::collections::HashMap;
 
 use crate::{
-    multi::MultipleBarcodeReader, BarcodeFormat, BinaryBitmap, DecodingHintDictionary, Exceptions,
-    RXingResult, RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint, Reader,
-    ResultPoint,
+    multi::MultipleBarcodeReader, BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue,
+    DecodingHintDictionary, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue, RXingResultPoint, Reader, ResultPoint,
 };
 
 use super::{
@@ -108,6 +108,19 @@ impl PDF417Reader {
                 Self::getMinCodewordWidth(points),
                 Self::getMaxCodewordWidth(points),
             )?;
+            let ambiguousCodewords = decoderRXingResult.getAmbiguousCodewords();
+            let unplacedCodewords = decoderRXingResult.getUnplacedCodewords();
+            if matches!(
+                hints.get(&DecodeHintType::PDF417_STRICT_CODEWORDS),
+                Some(DecodeHintValue::Pdf417StrictCodewords(true))
+            ) && (!ambiguousCodewords.is_empty() || !unplacedCodewords.is_empty())
+            {
+                return Err(Exceptions::FormatException(Some(format!(
+                    "{} ambiguous and {} unplaced codewords",
+                    ambiguousCodewords.len(),
+                    unplacedCodewords.len()
+                ))));
+            }
             let mut result = RXingResult::new(
                 decoderRXingResult.getText(),
                 decoderRXingResult.getRawBytes().clone(),
@@ -139,6 +152,26 @@ impl PDF417Reader {
             //   result.putMetadata(RXingResultMetadataType.PDF417_EXTRA_METADATA, pdf417RXingResultMetadata);
             // }
 
+            let dataCodewords = decoderRXingResult.getDataCodewords();
+            if !dataCodewords.is_empty() {
+                result.putMetadata(
+                    RXingResultMetadataType::DATA_CODEWORDS,
+                    RXingResultMetadataValue::DataCodewords(dataCodewords.clone()),
+                );
+            }
+            if matches!(
+                hints.get(&DecodeHintType::PDF417_REPORT_AMBIGUOUS_CODEWORDS),
+                Some(DecodeHintValue::Pdf417ReportAmbiguousCodewords(true))
+            ) {
+                result.putMetadata(
+                    RXingResultMetadataType::PDF417_AMBIGUOUS_CODEWORDS,
+                    RXingResultMetadataValue::Pdf417AmbiguousCodewords(ambiguousCodewords.clone()),
+                );
+                result.putMetadata(
+                    RXingResultMetadataType::PDF417_UNPLACED_CODEWORDS,
+                    RXingResultMetadataValue::Pdf417UnplacedCodewords(unplacedCodewords.clone()),
+                );
+            }
             result.putMetadata(
                 RXingResultMetadataType::ORIENTATION,
                 RXingResultMetadataValue::Orientation(detectorRXingResult.getRotation() as i32),
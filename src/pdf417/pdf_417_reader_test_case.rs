@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitMatrix,
+    shared_test_methods::{bitmap_for, matrix_pixels},
+    BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue, EncodeHintType, EncodeHintValue,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
+};
+
+use super::{
+    decoder::decoded_bit_stream_parser,
+    encoder::{Dimensions, PDF417},
+    pdf_417_common::{MAX_ROWS_IN_BARCODE, MIN_ROWS_IN_BARCODE},
+    PDF417Reader, PDF417Writer,
+};
+
+#[test]
+fn test_data_codewords_metadata() {
+    let contents = "PDF417 data codewords";
+    let matrix = PDF417Writer::new()
+        .encode(contents, &BarcodeFormat::PDF_417, 400, 200)
+        .expect("encode");
+    let mut image = bitmap_for(&matrix);
+
+    let result = PDF417Reader
+        .decode_with_hints(&mut image, &HashMap::new())
+        .expect("decode");
+    assert_eq!(contents, result.getText());
+    let Some(RXingResultMetadataValue::DataCodewords(codewords)) = result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::DATA_CODEWORDS)
+    else {
+        panic!("missing data codewords");
+    };
+
+    // The symbol length descriptor counts itself and excludes the EC codewords
+    assert_eq!(codewords.len() as u32, codewords[0]);
+    assert!(codewords.iter().all(|c| *c < 929));
+    let reparsed = decoded_bit_stream_parser::decode(codewords, "2").expect("parse");
+    assert_eq!(contents, reparsed.getText());
+}
+
+fn bitmap_of(pixels: Vec<u8>, width: u32, height: u32) -> BinaryBitmap {
+    BinaryBitmap::from_luma8(pixels, width, height).expect("bitmap")
+}
+
+#[test]
+fn test_ambiguous_codewords() {
+    const MARGIN: u32 = 10;
+    const COLUMNS: u32 = 3;
+    const ROWS: u32 = 12;
+    const SCALE: u32 = 3;
+    // Rows are 4 modules high, the writer stacks them from the top
+    const ROW_HEIGHT: u32 = 4 * SCALE;
+    const CODEWORD_WIDTH: u32 = 17 * SCALE;
+
+    let contents = "Ambiguous codewords are left to error correction";
+    let modules = (COLUMNS + 4) * 17 + 1;
+    let matrix = PDF417Writer
+        .encode_with_hints(
+            contents,
+            &BarcodeFormat::PDF_417,
+            (modules * SCALE) as i32,
+            (ROWS * ROW_HEIGHT) as i32,
+            &HashMap::from([
+                (
+                    EncodeHintType::PDF417_DIMENSIONS,
+                    EncodeHintValue::Pdf417Dimensions(Dimensions::new(
+                        COLUMNS as usize,
+                        COLUMNS as usize,
+                        ROWS as usize,
+                        ROWS as usize,
+                    )),
+                ),
+                (
+                    EncodeHintType::MARGIN,
+                    EncodeHintValue::Margin(MARGIN.to_string()),
+                ),
+            ]),
+        )
+        .expect("encode");
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    assert_eq!(modules * SCALE, width - 2 * MARGIN);
+    assert_eq!(ROWS * ROW_HEIGHT, height - 2 * MARGIN);
+    let pixels = matrix_pixels(&matrix);
+
+    let report = HashMap::from([(
+        DecodeHintType::PDF417_REPORT_AMBIGUOUS_CODEWORDS,
+        DecodeHintValue::Pdf417ReportAmbiguousCodewords(true),
+    )]);
+    let strict = HashMap::from([(
+        DecodeHintType::PDF417_STRICT_CODEWORDS,
+        DecodeHintValue::Pdf417StrictCodewords(true),
+    )]);
+
+    // A clean symbol has nothing to report, and passes the strict check
+    let result = PDF417Reader
+        .decode_with_hints(&mut bitmap_of(pixels.clone(), width, height), &report)
+        .expect("decode");
+    assert_eq!(
+        Some(&RXingResultMetadataValue::Pdf417AmbiguousCodewords(
+            Vec::new()
+        )),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::PDF417_AMBIGUOUS_CODEWORDS)
+    );
+    PDF417Reader
+        .decode_with_hints(&mut bitmap_of(pixels.clone(), width, height), &strict)
+        .expect("strict decode");
+
+    // Overwrite the lower half of the codeword in row 1, column 1 with the codeword three rows
+    // further down, which is in the same cluster, so that both are read equally often.
+    let (row, column) = (1, 1);
+    let left = MARGIN + 2 * CODEWORD_WIDTH + column * CODEWORD_WIDTH;
+    let cell = |row: u32, y: u32| {
+        let start = ((MARGIN + row * ROW_HEIGHT + y) * width + left) as usize;
+        start..start + CODEWORD_WIDTH as usize
+    };
+    assert_ne!(pixels[cell(row, 0)], pixels[cell(row + 3, 0)]);
+    let mut crafted = pixels.clone();
+    for y in ROW_HEIGHT / 2..ROW_HEIGHT {
+        crafted.copy_within(cell(row + 3, y), cell(row, y).start);
+    }
+
+    let result = PDF417Reader
+        .decode_with_hints(&mut bitmap_of(crafted.clone(), width, height), &report)
+        .expect("decode");
+    assert_eq!(contents, result.getText());
+    let Some(RXingResultMetadataValue::Pdf417AmbiguousCodewords(ambiguous)) = result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::PDF417_AMBIGUOUS_CODEWORDS)
+    else {
+        panic!("missing ambiguous codewords");
+    };
+    assert_eq!(1, ambiguous.len());
+    let (index, values) = &ambiguous[0];
+    assert_eq!(row * COLUMNS + column, *index);
+    assert_eq!(2, values.len());
+
+    assert!(PDF417Reader
+        .decode_with_hints(&mut bitmap_of(crafted, width, height), &strict)
+        .is_err());
+}
+
+#[test]
+fn test_decode_with_corrupted_right_row_indicators() {
+    const MARGIN: u32 = 10;
+    const COLUMNS: u32 = 3;
+    const ROWS: u32 = 12;
+    const SCALE: u32 = 3;
+    const ROW_HEIGHT: u32 = 4 * SCALE;
+    const CODEWORD_WIDTH: u32 = 17 * SCALE;
+
+    let contents = "Left row indicators only";
+    let modules = (COLUMNS + 4) * 17 + 1;
+    let matrix = PDF417Writer
+        .encode_with_hints(
+            contents,
+            &BarcodeFormat::PDF_417,
+            (modules * SCALE) as i32,
+            (ROWS * ROW_HEIGHT) as i32,
+            &HashMap::from([
+                (
+                    EncodeHintType::PDF417_DIMENSIONS,
+                    EncodeHintValue::Pdf417Dimensions(Dimensions::new(
+                        COLUMNS as usize,
+                        COLUMNS as usize,
+                        ROWS as usize,
+                        ROWS as usize,
+                    )),
+                ),
+                (
+                    EncodeHintType::MARGIN,
+                    EncodeHintValue::Margin(MARGIN.to_string()),
+                ),
+            ]),
+        )
+        .expect("encode");
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    let mut pixels = matrix_pixels(&matrix);
+
+    // Replace every right row indicator with the left one of the same row. Both are valid
+    // codewords of the row's cluster, but the right column now votes for a different symbol size.
+    let left = MARGIN + CODEWORD_WIDTH;
+    let right = MARGIN + (COLUMNS + 2) * CODEWORD_WIDTH;
+    for y in 0..ROWS * ROW_HEIGHT {
+        let row = ((MARGIN + y) * width) as usize;
+        pixels.copy_within(
+            row + left as usize..row + (left + CODEWORD_WIDTH) as usize,
+            row + right as usize,
+        );
+    }
+
+    let result = PDF417Reader
+        .decode_with_hints(&mut bitmap_of(pixels, width, height), &HashMap::new())
+        .expect("decode");
+    assert_eq!(contents, result.getText());
+}
+
+fn render(encoder: &PDF417) -> BitMatrix {
+    // Black is 1 in the barcode matrix, the rows are 3 modules high and come last to first
+    let rows = encoder
+        .getBarcodeMatrix()
+        .as_ref()
+        .expect("generated")
+        .getScaledMatrix(2, 6);
+    let margin = 10;
+    let mut matrix = BitMatrix::new(
+        rows[0].len() as u32 + 2 * margin,
+        rows.len() as u32 + 2 * margin,
+    )
+    .expect("matrix");
+    for (y, row) in rows.iter().enumerate() {
+        for (x, module) in row.iter().enumerate() {
+            if *module == 1 {
+                matrix.set(x as u32 + margin, (rows.len() - 1 - y) as u32 + margin);
+            }
+        }
+    }
+    matrix
+}
+
+#[test]
+fn test_encoder_round_trip() {
+    for (contents, (maxCols, minCols, maxRows, minRows), errorCorrectionLevel) in [
+        ("PDF417 round trip", (30, 2, 30, 2), 2),
+        (
+            "01234567890123456789012345678901234567890123456789",
+            (4, 4, 30, 3),
+            1,
+        ),
+        (
+            "Mixed Case text, with punctuation & digits: 42!",
+            (8, 1, 90, 3),
+            4,
+        ),
+    ] {
+        let mut encoder = PDF417::new();
+        encoder.setDimensions(maxCols, minCols, maxRows, minRows);
+        encoder
+            .generateBarcodeLogic(contents, errorCorrectionLevel)
+            .expect("encode");
+        let matrix = render(&encoder);
+        let mut image = bitmap_of(
+            matrix_pixels(&matrix),
+            matrix.getWidth(),
+            matrix.getHeight(),
+        );
+        let result = PDF417Reader
+            .decode_with_hints(&mut image, &HashMap::new())
+            .expect("decode");
+        assert_eq!(contents, result.getText());
+    }
+}
+
+#[test]
+fn test_encoder_respects_row_limits() {
+    // A single data codeword would fit in fewer rows than a symbol may have
+    let mut encoder = PDF417::new();
+    encoder.setDimensions(30, 30, 30, 1);
+    encoder.generateBarcodeLogic("A", 0).expect("encode");
+    let rows = encoder
+        .getBarcodeMatrix()
+        .as_ref()
+        .expect("generated")
+        .getMatrix()
+        .len() as u32;
+    assert_eq!(MIN_ROWS_IN_BARCODE, rows);
+    let matrix = render(&encoder);
+    let mut image = bitmap_of(
+        matrix_pixels(&matrix),
+        matrix.getWidth(),
+        matrix.getHeight(),
+    );
+    assert_eq!(
+        "A",
+        PDF417Reader
+            .decode_with_hints(&mut image, &HashMap::new())
+            .expect("decode")
+            .getText()
+    );
+
+    // In a single column, this needs more rows than a symbol may have
+    let mut encoder = PDF417::new();
+    encoder.setDimensions(1, 1, 200, 3);
+    assert!(encoder
+        .generateBarcodeLogic(&"0123456789".repeat(30), 2)
+        .is_err());
+    assert!(encoder.getBarcodeMatrix().is_none());
+    encoder
+        .generateBarcodeLogic(&"0123456789".repeat(12), 2)
+        .expect("encode");
+    let rows = encoder
+        .getBarcodeMatrix()
+        .as_ref()
+        .expect("generated")
+        .getMatrix()
+        .len() as u32;
+    assert!(rows <= MAX_ROWS_IN_BARCODE);
+}
@@ -18,7 +18,10 @@ use std::collections::HashMap;
 
 use crate::{
     common::BitSourceBuilder,
-    qrcode::decoder::{decoded_bit_stream_parser, ErrorCorrectionLevel, Version},
+    qrcode::decoder::{
+        decoded_bit_stream_parser, ErrorCorrectionLevel, Mode, QRCodeDecodedSegment, Version,
+    },
+    DecodeHintType, DecodeHintValue,
 };
 
 /**
@@ -68,6 +71,101 @@ fn testSimpleSJIS() {
     assert_eq!("\u{ff61}\u{ff62}\u{ff63}\u{ff90}", result);
 }
 
+#[test]
+fn testPreserveSJISBytes() {
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x04, 4); // Byte mode
+    builder.write(0x04, 8); // 4 bytes
+    builder.write(0xA1, 8);
+    builder.write(0xA2, 8);
+    builder.write(0xA3, 8);
+    builder.write(0xD0, 8);
+    builder.write(0x08, 4); // Kanji mode
+    builder.write(0x01, 8); // 1 character
+    builder.write(0x073F, 13); // 0x073F => 0x097F + 0x8140 = 0x8ABF
+    let hints = HashMap::from([(
+        DecodeHintType::QR_PRESERVE_SHIFT_JIS_BYTES,
+        DecodeHintValue::QrPreserveShiftJisBytes(true),
+    )]);
+    let result = decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        Version::getVersionForNumber(1).expect("unwrap"),
+        ErrorCorrectionLevel::H,
+        &hints,
+    )
+    .expect("unwrap");
+    assert_eq!("\u{ff61}\u{ff62}\u{ff63}\u{ff90}\u{6f22}", result.getText());
+    assert_eq!(
+        &vec![vec![0xA1, 0xA2, 0xA3, 0xD0], vec![0x8A, 0xBF]],
+        result.getShiftJisBytes()
+    );
+
+    // Without the hint nothing extra is retained
+    let result = decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        Version::getVersionForNumber(1).expect("unwrap"),
+        ErrorCorrectionLevel::H,
+        &HashMap::new(),
+    )
+    .expect("unwrap");
+    assert!(result.getShiftJisBytes().is_empty());
+}
+
+#[test]
+fn testReturnDecodedSegments() {
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x01, 4); // Numeric mode
+    builder.write(0x04, 10); // 4 digits
+    builder.write(123, 10); // "123"
+    builder.write(4, 4); // "4"
+    builder.write(0x07, 4); // ECI mode
+    builder.write(0x1A, 8); // ECI 26 = UTF-8 encoding
+    builder.write(0x04, 4); // Byte mode
+    builder.write(0x05, 8); // 5 bytes
+    builder.write(0x61, 8); // "a"
+    builder.write(0xC3, 8); // "\u{e9}"
+    builder.write(0xA9, 8);
+    builder.write(0xC3, 8); // "\u{e9}"
+    builder.write(0xA9, 8);
+    builder.write(0x01, 4); // Numeric mode
+    builder.write(0x01, 10); // 1 digit
+    builder.write(5, 4); // "5"
+    let hints = HashMap::from([(
+        DecodeHintType::QR_RETURN_DECODED_SEGMENTS,
+        DecodeHintValue::QrReturnDecodedSegments(true),
+    )]);
+    let result = decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        Version::getVersionForNumber(1).expect("unwrap"),
+        ErrorCorrectionLevel::H,
+        &hints,
+    )
+    .expect("unwrap");
+    assert_eq!("1234a\u{e9}\u{e9}5", result.getText());
+    // Ranges count characters, not the bytes of the UTF-8 text
+    assert_eq!(
+        &vec![
+            QRCodeDecodedSegment::new(Mode::NUMERIC, 0..4, 0..28, None),
+            QRCodeDecodedSegment::new(Mode::BYTE, 4..7, 40..92, Some(26)),
+            QRCodeDecodedSegment::new(Mode::NUMERIC, 7..8, 92..110, Some(26)),
+        ],
+        result.getQRDecodedSegments()
+    );
+    // The ECI between the first two segments belongs to neither
+    assert_eq!(0..4, result.getQRDecodedSegments()[0].getCodewords());
+    assert_eq!(5..12, result.getQRDecodedSegments()[1].getCodewords());
+
+    // Without the hint nothing extra is retained
+    let result = decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        Version::getVersionForNumber(1).expect("unwrap"),
+        ErrorCorrectionLevel::H,
+        &HashMap::new(),
+    )
+    .expect("unwrap");
+    assert!(result.getQRDecodedSegments().is_empty());
+}
+
 #[test]
 fn testECI() {
     let mut builder = BitSourceBuilder::new();
@@ -91,6 +189,114 @@ fn testECI() {
     assert_eq!("\u{00ed}\u{00f3}\u{00fa}", result);
 }
 
+#[test]
+fn testOverrideECICharset() {
+    // UTF-8 bytes of "\u{00e9}\u{20ac}" behind an ECI wrongly declaring ISO-8859-1
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x07, 4); // ECI mode
+    builder.write(0x03, 8); // ECI 3 = ISO-8859-1 encoding
+    builder.write(0x04, 4); // Byte mode
+    builder.write(0x05, 8); // 5 bytes
+    for byte in [0xC3, 0xA9, 0xE2, 0x82, 0xAC] {
+        builder.write(byte, 8);
+    }
+    let bytes = builder.toByteArray();
+    let decode = |hints: &HashMap<DecodeHintType, DecodeHintValue>| {
+        decoded_bit_stream_parser::decode(
+            bytes,
+            Version::getVersionForNumber(1).expect("unwrap"),
+            ErrorCorrectionLevel::H,
+            hints,
+        )
+        .expect("unwrap")
+        .getText()
+        .to_owned()
+    };
+
+    assert_eq!(
+        "\u{00c3}\u{00a9}\u{00e2}\u{201a}\u{00ac}",
+        decode(&HashMap::new())
+    );
+    // CHARACTER_SET doesn't apply where there is an ECI
+    let hints = HashMap::from([(
+        DecodeHintType::CHARACTER_SET,
+        DecodeHintValue::CharacterSet("UTF-8".to_owned()),
+    )]);
+    assert_eq!("\u{00c3}\u{00a9}\u{00e2}\u{201a}\u{00ac}", decode(&hints));
+    let hints = HashMap::from([(
+        DecodeHintType::OVERRIDE_ECI_CHARSET,
+        DecodeHintValue::OverrideEciCharset("UTF-8".to_owned()),
+    )]);
+    assert_eq!("\u{00e9}\u{20ac}", decode(&hints));
+}
+
+#[test]
+fn testECIWithFNC1FirstPosition() {
+    let data = "10\u{00e9}t\u{001d}21".as_bytes();
+    // ECI precedes FNC1, but FNC1 mode may also be signalled first
+    for eciFirst in [true, false] {
+        let mut builder = BitSourceBuilder::new();
+        if eciFirst {
+            builder.write(0x07, 4); // ECI mode
+            builder.write(26, 8); // ECI 26 = UTF-8
+            builder.write(0x05, 4); // FNC1 first position
+        } else {
+            builder.write(0x05, 4); // FNC1 first position
+            builder.write(0x07, 4); // ECI mode
+            builder.write(26, 8); // ECI 26 = UTF-8
+        }
+        builder.write(0x04, 4); // Byte mode
+        builder.write(data.len() as u32, 8);
+        for byte in data {
+            builder.write(*byte as u32, 8);
+        }
+        let result = decoded_bit_stream_parser::decode(
+            builder.toByteArray(),
+            Version::getVersionForNumber(1).expect("unwrap"),
+            ErrorCorrectionLevel::H,
+            &HashMap::new(),
+        )
+        .expect("unwrap");
+        assert_eq!("10\u{00e9}t\u{001d}21", result.getText());
+        assert_eq!(4, result.getSymbologyModifier());
+    }
+}
+
+#[test]
+fn testFNC1SecondPosition() {
+    for (appIndicator, expected) in [(37, "37"), (5, "05"), (165, "A"), (222, "z")] {
+        let mut builder = BitSourceBuilder::new();
+        builder.write(0x07, 4); // ECI mode
+        builder.write(26, 8); // ECI 26 = UTF-8
+        builder.write(0x09, 4); // FNC1 second position
+        builder.write(appIndicator, 8); // Application indicator
+        builder.write(0x04, 4); // Byte mode
+        builder.write(0x02, 8); // 2 bytes
+        builder.write(0xC3, 8); // U+00E9 in UTF-8
+        builder.write(0xA9, 8);
+        let result = decoded_bit_stream_parser::decode(
+            builder.toByteArray(),
+            Version::getVersionForNumber(1).expect("unwrap"),
+            ErrorCorrectionLevel::H,
+            &HashMap::new(),
+        )
+        .expect("unwrap");
+        assert_eq!(format!("{expected}\u{00e9}"), result.getText());
+        assert_eq!(6, result.getSymbologyModifier());
+    }
+
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x09, 4); // FNC1 second position
+    builder.write(150, 8); // Not a digit pair or a letter
+    assert!(decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        Version::getVersionForNumber(1).expect("unwrap"),
+        ErrorCorrectionLevel::H,
+        &HashMap::new(),
+    )
+    .is_err());
+}
+
 #[test]
 fn testHanzi() {
     let mut builder = BitSourceBuilder::new();
@@ -132,4 +338,111 @@ fn testHanziLevel1() {
     assert_eq!("\u{30a2}", result);
 }
 
+#[test]
+fn testMissingTerminatorWithTryHarder() {
+    let tryHarder = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x04, 4); // Byte mode
+    builder.write(0x03, 8); // 3 bytes
+    builder.write(0xF1, 8);
+    builder.write(0xF2, 8);
+    builder.write(0xF3, 8);
+    // Pad codewords without a terminator, so the next mode reads as 1110
+    builder.write(0xEC, 8);
+    builder.write(0x11, 8);
+    builder.write(0x0E, 4);
+    let bytes = builder.toByteArray();
+    let version = Version::getVersionForNumber(1).expect("unwrap");
+    assert!(decoded_bit_stream_parser::decode(
+        bytes,
+        version,
+        ErrorCorrectionLevel::H,
+        &HashMap::new()
+    )
+    .is_err());
+    let result =
+        decoded_bit_stream_parser::decode(bytes, version, ErrorCorrectionLevel::H, &tryHarder)
+            .expect("unwrap");
+    assert_eq!("\u{00f1}\u{00f2}\u{00f3}", result.getText());
+    assert_eq!(&vec![vec![0xF1, 0xF2, 0xF3]], result.getByteSegments());
+}
+
+#[test]
+fn testPaddingAfterMissingTerminatorWithTryHarder() {
+    let tryHarder = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x01, 4); // Numeric mode
+    builder.write(0x03, 10); // 3 digits
+    builder.write(123, 10);
+    // Pad codewords starting with 00010001, which reads as another numeric segment
+    builder.write(0x11, 8);
+    builder.write(0xEC, 8);
+    builder.write(0x11, 8);
+    let bytes = builder.toByteArray();
+    let version = Version::getVersionForNumber(1).expect("unwrap");
+    assert!(decoded_bit_stream_parser::decode(
+        bytes,
+        version,
+        ErrorCorrectionLevel::H,
+        &HashMap::new()
+    )
+    .is_err());
+    let result =
+        decoded_bit_stream_parser::decode(bytes, version, ErrorCorrectionLevel::H, &tryHarder)
+            .expect("unwrap");
+    assert_eq!("123", result.getText());
+}
+
+#[test]
+fn testBrokenSegmentAfterDataFailsWithTryHarder() {
+    let tryHarder = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+    let version = Version::getVersionForNumber(1).expect("unwrap");
+
+    // A byte segment whose count runs past the end of the data
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x01, 4); // Numeric mode
+    builder.write(0x03, 10); // 3 digits
+    builder.write(123, 10);
+    builder.write(0x04, 4); // Byte mode
+    builder.write(0x10, 8); // 16 bytes
+    builder.write(0xF1, 8);
+    assert!(decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        version,
+        ErrorCorrectionLevel::H,
+        &tryHarder,
+    )
+    .is_err());
+
+    // An invalid ECI designator
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x01, 4); // Numeric mode
+    builder.write(0x03, 10); // 3 digits
+    builder.write(123, 10);
+    builder.write(0x07, 4); // ECI mode
+    builder.write(0xFF, 8); // reserved first byte
+    builder.write(0xFF, 8);
+    assert!(decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        version,
+        ErrorCorrectionLevel::H,
+        &tryHarder,
+    )
+    .is_err());
+
+    // Without a complete data segment there's nothing to recover
+    let mut builder = BitSourceBuilder::new();
+    builder.write(0x0E, 4);
+    builder.write(0x00, 4);
+    assert!(decoded_bit_stream_parser::decode(
+        builder.toByteArray(),
+        version,
+        ErrorCorrectionLevel::H,
+        &tryHarder,
+    )
+    .is_err());
+}
+
 // TODO definitely need more tests here
@@ -16,7 +16,7 @@
 
 use crate::{common::BitMatrix, Exceptions};
 
-use super::{DataMask, FormatInformation, Version, VersionRef};
+use super::{DataMask, ErrorCorrectionLevel, FormatInformation, Version, VersionRef};
 
 /**
  * @author Sean Owen
@@ -26,6 +26,7 @@ pub struct BitMatrixParser {
     parsedVersion: Option<VersionRef>,
     parsedFormatInfo: Option<FormatInformation>,
     mirror: bool,
+    assumedECLevel: Option<ErrorCorrectionLevel>,
 }
 
 impl BitMatrixParser {
@@ -45,6 +46,7 @@ impl BitMatrixParser {
                 parsedVersion: None,
                 parsedFormatInfo: None,
                 mirror: false,
+                assumedECLevel: None,
             })
         }
     }
@@ -90,8 +92,15 @@ impl BitMatrixParser {
             formatInfoBits2 = self.copyBit(i, 8, formatInfoBits2);
         }
 
-        self.parsedFormatInfo =
-            FormatInformation::decodeFormatInformation(formatInfoBits1, formatInfoBits2);
+        self.parsedFormatInfo = if let Some(ecLevel) = self.assumedECLevel {
+            FormatInformation::decodeFormatInformationForECLevel(
+                formatInfoBits1,
+                formatInfoBits2,
+                ecLevel,
+            )
+        } else {
+            FormatInformation::decodeFormatInformation(formatInfoBits1, formatInfoBits2)
+        };
         if let Some(pfi) = &self.parsedFormatInfo {
             return Ok(pfi);
         }
@@ -259,6 +268,28 @@ impl BitMatrixParser {
         // dataMask.unmaskBitMatrix(self.bitMatrix, dimension);
     }
 
+    /**
+     * Restrict format information reading to the given error correction level, instead of
+     * detecting it from the symbol.
+     *
+     * @param ecLevel the level the symbol is known to use, or {@code None} to detect it
+     */
+    pub fn setAssumedECLevel(&mut self, ecLevel: Option<ErrorCorrectionLevel>) {
+        self.parsedFormatInfo = None;
+        self.assumedECLevel = ecLevel;
+    }
+
+    /**
+     * Use the given format information instead of reading it from the symbol, as when it is too
+     * damaged to be read. A data mask removed by {@link #readCodewords()} has to be reverted with
+     * {@link #remask()} before the format information is replaced.
+     *
+     * @param formatInfo format information the symbol is assumed to have
+     */
+    pub fn setFormatInformation(&mut self, formatInfo: FormatInformation) {
+        self.parsedFormatInfo = Some(formatInfo);
+    }
+
     /**
      * Prepare the parser for a mirrored operation.
      * This flag has effect only on the {@link #readFormatInformation()} and the
@@ -275,15 +306,6 @@ impl BitMatrixParser {
 
     /** Mirror the bit matrix in order to attempt a second reading. */
     pub fn mirror(&mut self) {
-        for x in 0..self.bitMatrix.getWidth() {
-            // for (int x = 0; x < bitMatrix.getWidth(); x++) {
-            for y in (x + 1)..self.bitMatrix.getHeight() {
-                // for (int y = x + 1; y < bitMatrix.getHeight(); y++) {
-                if self.bitMatrix.get(x, y) != self.bitMatrix.get(y, x) {
-                    self.bitMatrix.flip_coords(y, x);
-                    self.bitMatrix.flip_coords(x, y);
-                }
-            }
-        }
+        self.bitMatrix.mirror();
     }
 }
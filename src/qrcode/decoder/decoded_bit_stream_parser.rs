@@ -14,15 +14,16 @@
  * limitations under the License.
  */
 
+use std::ops::Range;
+
+use encoding::EncodingRef;
+
 use crate::{
-    common::{BitSource, CharacterSetECI, DecoderRXingResult, StringUtils},
-    DecodingHintDictionary, Exceptions,
+    common::{BitSource, CharacterSetECI, DecoderRXingResult, StringUtils, SHIFT_JIS_CHARSET},
+    DecodeHintType, DecodeHintValue, DecodingHintDictionary, Exceptions,
 };
 
-#[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
-use crate::{DecodeHintType, DecodeHintValue};
-
-use super::{ErrorCorrectionLevel, Mode, VersionRef};
+use super::{ErrorCorrectionLevel, Mode, QRCodeDecodedSegment, VersionRef};
 
 /**
  * <p>QR Codes can encode text as bits in one of several modes, and can use multiple modes
@@ -48,6 +49,20 @@ pub fn decode(
     let mut bits = BitSource::new(bytes.to_owned());
     let mut result = String::with_capacity(50);
     let mut byteSegments = vec![vec![0u8; 0]; 0];
+    let mut shiftJisSegments = if let Some(DecodeHintValue::QrPreserveShiftJisBytes(true)) =
+        hints.get(&DecodeHintType::QR_PRESERVE_SHIFT_JIS_BYTES)
+    {
+        Some(Vec::new())
+    } else {
+        None
+    };
+    let mut decodedSegments = if let Some(DecodeHintValue::QrReturnDecodedSegments(true)) =
+        hints.get(&DecodeHintType::QR_RETURN_DECODED_SEGMENTS)
+    {
+        Some(Vec::new())
+    } else {
+        None
+    };
     let mut symbolSequence = -1i32;
     let mut parityData = -1i32;
     let symbologyModifier;
@@ -57,14 +72,25 @@ pub fn decode(
     let mut fc1InEffect = false;
     let mut hasFNC1first = false;
     let mut hasFNC1second = false;
+    // Buggy encoders leave out the terminator, so padding or garbage follows the last segment.
+    // Trying harder, a segment boundary after some data which doesn't start a valid segment is
+    // taken as the end of the data.
+    let lenient = hints.contains_key(&DecodeHintType::TRY_HARDER);
+    let mut hasData = false;
     let mut mode;
     loop {
+        let segmentStart = bitPosition(&bits);
         // While still another segment to read...
-        if bits.available() < 4 {
+        if bits.available() < 4 || lenient && hasData && onlyPaddingRemains(bytes, segmentStart) {
             // OK, assume we're done. Really, a TERMINATOR mode should have been recorded here
             mode = Mode::TERMINATOR;
         } else {
-            mode = Mode::forBits(bits.readBits(4)? as u8)?; // mode is encoded by 4 bits
+            let modeBits = bits.readBits(4)? as u8; // mode is encoded by 4 bits
+            mode = match Mode::forBits(modeBits) {
+                Ok(mode) => mode,
+                Err(_) if lenient && hasData => Mode::TERMINATOR,
+                Err(e) => return Err(e),
+            };
         }
         match mode {
             Mode::TERMINATOR => {}
@@ -77,6 +103,22 @@ pub fn decode(
                 hasFNC1second = true; // symbology detection
                                       // We do little with FNC1 except alter the parsed result a bit according to the spec
                 fc1InEffect = true;
+                // The mode indicator is followed by an 8 bit application indicator, which is
+                // transmitted ahead of the data (ISO/IEC 18004:2015 7.4.8.3): either two digits
+                // 00-99, or a letter a-z / A-Z encoded as its ASCII value + 100.
+                if bits.available() < 8 {
+                    return Err(Exceptions::FormatException(None));
+                }
+                let appIndicator = bits.readBits(8)?;
+                match appIndicator {
+                    0..=99 => result.push_str(&format!("{appIndicator:02}")),
+                    165..=190 | 197..=222 => result.push(char::from((appIndicator - 100) as u8)),
+                    _ => {
+                        return Err(Exceptions::FormatException(Some(format!(
+                            "invalid FNC1 application indicator {appIndicator}"
+                        ))))
+                    }
+                }
             }
             Mode::STRUCTURED_APPEND => {
                 if bits.available() < 16 {
@@ -106,14 +148,24 @@ pub fn decode(
                 let subset = bits.readBits(4)?;
                 let countHanzi =
                     bits.readBits(mode.getCharacterCountBits(version) as usize)? as usize;
+                let start = decodedSegments.as_ref().map(|_| result.chars().count());
                 if subset == GB2312_SUBSET {
                     decodeHanziSegment(&mut bits, &mut result, countHanzi)?;
                 }
+                recordDecodedSegment(
+                    &mut decodedSegments,
+                    mode,
+                    start,
+                    &result,
+                    segmentStart..bitPosition(&bits),
+                    currentCharacterSetECI,
+                );
             }
             _ => {
                 // "Normal" QR code modes:
                 // How many characters will follow, encoded in this mode?
                 let count = bits.readBits(mode.getCharacterCountBits(version) as usize)? as usize;
+                let start = decodedSegments.as_ref().map(|_| result.chars().count());
                 match mode {
                     Mode::NUMERIC => decodeNumericSegment(&mut bits, &mut result, count)?,
                     Mode::ALPHANUMERIC => {
@@ -125,6 +177,7 @@ pub fn decode(
                         count,
                         currentCharacterSetECI,
                         &mut byteSegments,
+                        &mut shiftJisSegments,
                         hints,
                     )?,
                     Mode::KANJI => decodeKanjiSegment(
@@ -132,16 +185,29 @@ pub fn decode(
                         &mut result,
                         count,
                         currentCharacterSetECI,
+                        &mut shiftJisSegments,
                         hints,
                     )?,
                     _ => return Err(Exceptions::FormatException(None)),
                 }
+                recordDecodedSegment(
+                    &mut decodedSegments,
+                    mode,
+                    start,
+                    &result,
+                    segmentStart..bitPosition(&bits),
+                    currentCharacterSetECI,
+                );
             }
         }
 
         if mode == Mode::TERMINATOR {
             break;
         }
+        hasData |= matches!(
+            mode,
+            Mode::NUMERIC | Mode::ALPHANUMERIC | Mode::BYTE | Mode::KANJI | Mode::HANZI
+        );
     }
 
     if currentCharacterSetECI.is_some() {
@@ -165,7 +231,7 @@ pub fn decode(
     //   throw FormatException.getFormatInstance();
     // }
 
-    Ok(DecoderRXingResult::with_all(
+    let mut decoderResult = DecoderRXingResult::with_all(
         bytes.to_owned(),
         result,
         byteSegments.to_vec(),
@@ -175,7 +241,78 @@ pub fn decode(
         symbologyModifier,
         String::default(),
         false,
-    ))
+    );
+    if let Some(shiftJisSegments) = shiftJisSegments {
+        decoderResult.setShiftJisBytes(shiftJisSegments);
+    }
+    if let Some(decodedSegments) = decodedSegments {
+        decoderResult.setQRDecodedSegments(decodedSegments);
+    }
+
+    Ok(decoderResult)
+}
+
+/**
+ * Keeps a copy of the segment's bytes if they were decoded as Shift_JIS and the caller asked
+ * for them to be preserved.
+ */
+fn preserveShiftJisBytes(
+    encoding: EncodingRef,
+    bytes: &[u8],
+    shiftJisSegments: &mut Option<Vec<Vec<u8>>>,
+) {
+    if let Some(segments) = shiftJisSegments {
+        if encoding.name() == SHIFT_JIS_CHARSET.name() {
+            segments.push(bytes.to_vec());
+        }
+    }
+}
+
+/**
+ * Notes the mode, the characters decoded since {@code start}, the bits read and the ECI of a
+ * data segment if the caller asked for the segments. {@code start} is only counted then, as
+ * counting the characters decoded so far means walking the whole result.
+ */
+fn recordDecodedSegment(
+    decodedSegments: &mut Option<Vec<QRCodeDecodedSegment>>,
+    mode: Mode,
+    start: Option<usize>,
+    result: &str,
+    bits: Range<usize>,
+    currentCharacterSetECI: Option<CharacterSetECI>,
+) {
+    if let (Some(segments), Some(start)) = (decodedSegments, start) {
+        segments.push(QRCodeDecodedSegment::new(
+            mode,
+            start..result.chars().count(),
+            bits,
+            currentCharacterSetECI.map(|eci| eci.getValueSelf()),
+        ));
+    }
+}
+
+/**
+ * @return index of the next bit to read, counted from the start of the data codewords
+ */
+fn bitPosition(bits: &BitSource) -> usize {
+    bits.getByteOffset() * 8 + bits.getBitOffset()
+}
+
+/**
+ * Whether the bits from the given position on are nothing but pad codewords, alternating 11101100
+ * and 00010001, which an encoder left out the terminator in front of.
+ */
+fn onlyPaddingRemains(bytes: &[u8], position: usize) -> bool {
+    const PAD_BITS: u16 = 0xEC11;
+    let totalBits = bytes.len() * 8;
+    if position >= totalBits {
+        return false;
+    }
+    let bit = |i: usize| (bytes[i / 8] >> (7 - i % 8)) & 1 == 1;
+    [0, 8].iter().any(|phase| {
+        (position..totalBits)
+            .all(|i| bit(i) == ((PAD_BITS >> (15 - (i - position + phase) % 16)) & 1 == 1))
+    })
 }
 
 /**
@@ -228,6 +365,7 @@ fn decodeKanjiSegment(
     result: &mut String,
     count: usize,
     currentCharacterSetECI: Option<CharacterSetECI>,
+    shiftJisSegments: &mut Option<Vec<Vec<u8>>>,
     hints: &DecodingHintDictionary,
 ) -> Result<(), Exceptions> {
     // Don't crash trying to read more bits than we have available.
@@ -284,6 +422,7 @@ fn decodeKanjiSegment(
         .unwrap();
 
     result.push_str(&encode_string);
+    preserveShiftJisBytes(encoder, &buffer, shiftJisSegments);
 
     Ok(())
 }
@@ -294,6 +433,7 @@ fn decodeByteSegment(
     count: usize,
     currentCharacterSetECI: Option<CharacterSetECI>,
     byteSegments: &mut Vec<Vec<u8>>,
+    shiftJisSegments: &mut Option<Vec<Vec<u8>>>,
     hints: &DecodingHintDictionary,
 ) -> Result<(), Exceptions> {
     // Don't crash trying to read more bits than we have available.
@@ -308,7 +448,28 @@ fn decodeByteSegment(
         // for (int i = 0; i < count; i++) {
         *byte = bits.readBits(8)? as u8;
     }
-    let encoding = if currentCharacterSetECI.is_none() {
+    let overrideEncoding = if let Some(DecodeHintValue::OverrideEciCharset(charset)) =
+        hints.get(&DecodeHintType::OVERRIDE_ECI_CHARSET)
+    {
+        Some(
+            encoding::label::encoding_from_whatwg_label(charset).ok_or_else(|| {
+                Exceptions::IllegalArgumentException(Some(format!(
+                    "unknown character set: {charset}"
+                )))
+            })?,
+        )
+    } else {
+        None
+    };
+    let currentCharacterSetECI = if overrideEncoding.is_some() {
+        None
+    } else {
+        currentCharacterSetECI
+    };
+
+    let encoding = if let Some(encoding) = overrideEncoding {
+        encoding
+    } else if currentCharacterSetECI.is_none() {
         // The spec isn't clear on this mode; see
         // section 6.4.5: t does not say which encoding to assuming
         // upon decoding. I have seen ISO-8859-1 used as well as
@@ -343,13 +504,14 @@ fn decodeByteSegment(
     } else {
         encoding
             .decode(&readBytes, encoding::DecoderTrap::Strict)
-            .unwrap()
+            .map_err(|e| Exceptions::FormatException(Some(format!("{e}"))))?
     };
 
     // let encode_string = encoding
     //     .decode(&readBytes, encoding::DecoderTrap::Strict)
     //     .unwrap();
     result.push_str(&encode_string);
+    preserveShiftJisBytes(encoding, &readBytes, shiftJisSegments);
     byteSegments.push(readBytes);
 
     Ok(())
@@ -18,6 +18,9 @@ use super::ErrorCorrectionLevel;
 
 const FORMAT_INFO_MASK_QR: u32 = 0x5412;
 
+// How far format info may be from the closest pattern when the error correction level is known
+const MAX_ASSUMED_EC_LEVEL_DIFFERENCE: u32 = 5;
+
 /**
  * See ISO 18004:2006, Annex C, Table C.1
  */
@@ -83,6 +86,16 @@ impl FormatInformation {
         }
     }
 
+    /**
+     * @param ec_level error correction level
+     * @param data_mask data mask pattern reference, in the range [0,7]
+     * @return format information specifying the given level and mask, for symbols whose own
+     *  format information can't be read
+     */
+    pub fn forECLevelAndDataMask(ec_level: ErrorCorrectionLevel, data_mask: u8) -> Self {
+        Self::new((ec_level.get_value() << 3) | (data_mask & 0x07))
+    }
+
     pub fn numBitsDiffering(a: u32, b: u32) -> u32 {
         (a ^ b).count_ones()
         // return Integer.bitCount(a ^ b);
@@ -112,6 +125,71 @@ impl FormatInformation {
         )
     }
 
+    /**
+     * Like {@link #decodeFormatInformation(int, int)}, but only considers the eight format
+     * information patterns for a known error correction level.
+     *
+     * An exact match or one within the usual 3 bits is accepted as before. With fewer candidates
+     * to choose between, the closest pattern is also accepted when it is up to
+     * {@code MAX_ASSUMED_EC_LEVEL_DIFFERENCE} bits away, as long as no other candidate is as
+     * close; the data mask it implies is then checked by error correction of the codewords.
+     *
+     * @param maskedFormatInfo1 format info indicator, with mask still applied
+     * @param maskedFormatInfo2 second copy of same info
+     * @param ecLevel the error correction level the symbol is known to use
+     * @return information about the format it specifies, or {@code null}
+     *  if doesn't seem to match any pattern for that level
+     */
+    pub fn decodeFormatInformationForECLevel(
+        masked_format_info1: u32,
+        masked_format_info2: u32,
+        ec_level: ErrorCorrectionLevel,
+    ) -> Option<FormatInformation> {
+        let formatInfo = Self::doDecodeFormatInformationForECLevel(
+            masked_format_info1,
+            masked_format_info2,
+            ec_level,
+        );
+        if formatInfo.is_some() {
+            return formatInfo;
+        }
+        Self::doDecodeFormatInformationForECLevel(
+            masked_format_info1 ^ FORMAT_INFO_MASK_QR,
+            masked_format_info2 ^ FORMAT_INFO_MASK_QR,
+            ec_level,
+        )
+    }
+
+    fn doDecodeFormatInformationForECLevel(
+        masked_format_info1: u32,
+        masked_format_info2: u32,
+        ec_level: ErrorCorrectionLevel,
+    ) -> Option<FormatInformation> {
+        let mut best_difference = u32::MAX;
+        let mut second_difference = u32::MAX;
+        let mut best_format_info = 0;
+        for decodeInfo in FORMAT_INFO_DECODE_LOOKUP {
+            let formatInfo = FormatInformation::new(decodeInfo[1] as u8);
+            if formatInfo.error_correction_level != ec_level {
+                continue;
+            }
+            let bits_difference = Self::numBitsDiffering(masked_format_info1, decodeInfo[0])
+                .min(Self::numBitsDiffering(masked_format_info2, decodeInfo[0]));
+            if bits_difference < best_difference {
+                second_difference = best_difference;
+                best_difference = bits_difference;
+                best_format_info = decodeInfo[1] as u8;
+            } else if bits_difference < second_difference {
+                second_difference = bits_difference;
+            }
+        }
+        if best_difference <= MAX_ASSUMED_EC_LEVEL_DIFFERENCE && best_difference < second_difference
+        {
+            return Some(FormatInformation::new(best_format_info));
+        }
+        None
+    }
+
     fn doDecodeFormatInformation(
         masked_format_info1: u32,
         masked_format_info2: u32,
@@ -5,6 +5,7 @@ pub mod decoded_bit_stream_parser;
 mod error_correction_level;
 mod format_information;
 mod mode;
+mod qr_code_decoded_segment;
 mod qr_code_decoder_meta_data;
 pub mod qrcode_decoder;
 mod version;
@@ -21,6 +22,8 @@ mod ModeTestCase;
 mod VersionTestCase;
 #[cfg(test)]
 mod data_mask_testcase;
+#[cfg(test)]
+mod qrcode_decoder_testcase;
 
 pub use bit_matrix_parser::*;
 pub use data_block::*;
@@ -28,5 +31,6 @@ pub use data_mask::*;
 pub use error_correction_level::*;
 pub use format_information::*;
 pub use mode::*;
+pub use qr_code_decoded_segment::*;
 pub use qr_code_decoder_meta_data::*;
 pub use version::*;
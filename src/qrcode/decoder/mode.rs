@@ -18,12 +18,16 @@ use crate::Exceptions;
 
 use super::Version;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /**
  * <p>See ISO 18004:2006, 6.4.1, Tables 2 and 3. This enum encapsulates the various modes in which
  * data can be encoded to bits in the QR code standard.</p>
  *
  * @author Sean Owen
  */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Mode {
     TERMINATOR,           //(new int[]{0, 0, 0}, 0x00), // Not really a mode...
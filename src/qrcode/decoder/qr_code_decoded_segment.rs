@@ -0,0 +1,69 @@
+use std::ops::Range;
+
+use super::Mode;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/**
+ * One data segment of a decoded QR Code: the mode it was encoded in, the characters of the
+ * decoded text it produced, where it is in the data codewords and the ECI designator in effect
+ * for it, if any.
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct QRCodeDecodedSegment {
+    mode: Mode,
+    characters: Range<usize>,
+    bits: Range<usize>,
+    eci: Option<u32>,
+}
+
+impl QRCodeDecodedSegment {
+    pub fn new(mode: Mode, characters: Range<usize>, bits: Range<usize>, eci: Option<u32>) -> Self {
+        Self {
+            mode,
+            characters,
+            bits,
+            eci,
+        }
+    }
+
+    /**
+     * @return mode the segment was encoded in: numeric, alphanumeric, byte, Kanji or Hanzi
+     */
+    pub fn getMode(&self) -> Mode {
+        self.mode
+    }
+
+    /**
+     * @return range of the characters (not bytes) of the decoded text the segment produced
+     */
+    pub fn getCharacters(&self) -> &Range<usize> {
+        &self.characters
+    }
+
+    /**
+     * @return range of the bits of the data codewords the segment was read from, starting with its
+     * mode indicator, where bit 0 is the most significant bit of the first data codeword
+     */
+    pub fn getBits(&self) -> &Range<usize> {
+        &self.bits
+    }
+
+    /**
+     * @return range of the data codewords holding the bits of the segment, see
+     * {@link RXingResultMetadataType#DATA_CODEWORDS}. A codeword may be shared with the segments
+     * next to it.
+     */
+    pub fn getCodewords(&self) -> Range<usize> {
+        self.bits.start / 8..self.bits.end.div_ceil(8)
+    }
+
+    /**
+     * @return value of the ECI designator in effect for the segment, if there was one
+     */
+    pub fn getECI(&self) -> Option<u32> {
+        self.eci
+    }
+}
@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::HashMap, rc::Rc, str::FromStr};
 
 /**
  * <p>The main class which implements QR Code decoding -- as opposed to locating and extracting
@@ -29,10 +29,15 @@ use crate::{
         reedsolomon::get_predefined_genericgf, reedsolomon::PredefinedGenericGF,
         reedsolomon::ReedSolomonDecoder, BitMatrix, DecoderRXingResult,
     },
-    DecodingHintDictionary, Exceptions,
+    DecodeHintType, DecodeHintValue, DecodingHintDictionary, Exceptions,
 };
 
-use super::{decoded_bit_stream_parser, BitMatrixParser, DataBlock, QRCodeDecoderMetaData};
+use super::{
+    decoded_bit_stream_parser, BitMatrixParser, DataBlock, ErrorCorrectionLevel, FormatInformation,
+    QRCodeDecoderMetaData,
+};
+
+const MAX_QR_VERSION: u32 = 40;
 
 //rsDecoder = new ReedSolomonDecoder(GenericGF.QR_CODE_FIELD_256);
 static RS_DECODER: Lazy<ReedSolomonDecoder> = Lazy::new(|| {
@@ -79,8 +84,73 @@ pub fn decode_bitmatrix_with_hints(
     bits: &BitMatrix,
     hints: &DecodingHintDictionary,
 ) -> Result<DecoderRXingResult, Exceptions> {
+    let mut result = decode_sampled_bitmatrix(bits, hints)?;
+    let timingPatternMismatches = countTimingPatternMismatches(bits);
+    let timingPatternModules = 2 * bits.getHeight().min(bits.getWidth()).saturating_sub(16);
+    result.setTimingPatternMismatches(Some(timingPatternMismatches));
+    if timingPatternModules > 0 {
+        result.setFixedPatternDamage(Some(
+            timingPatternMismatches as f32 / timingPatternModules as f32,
+        ));
+    }
+    Ok(result)
+}
+
+/**
+ * <p>Counts the modules of the two timing patterns which don't alternate between dark and light
+ * as they should, as a measure of how well the symbol was sampled. A clean grid has none, while
+ * blur or an inaccurate perspective transform shift modules into their neighbours.</p>
+ *
+ * <p>The count doesn't change when the symbol is mirrored, as that swaps the two patterns.</p>
+ *
+ * @param bits booleans representing white/black QR Code modules
+ * @return number of timing pattern modules with the wrong color
+ */
+pub fn countTimingPatternMismatches(bits: &BitMatrix) -> u32 {
+    let dimension = bits.getHeight().min(bits.getWidth());
+    if dimension < 17 {
+        return 0;
+    }
+    // Both patterns run between the separators of the finder patterns, starting dark
+    (8..dimension - 8)
+        .map(|i| {
+            let expected = i % 2 == 0;
+            (bits.get(i, 6) != expected) as u32 + (bits.get(6, i) != expected) as u32
+        })
+        .sum()
+}
+
+fn decode_sampled_bitmatrix(
+    bits: &BitMatrix,
+    hints: &DecodingHintDictionary,
+) -> Result<DecoderRXingResult, Exceptions> {
+    // Reject symbols larger than the caller is willing to handle before doing any real work
+    let maxVersion = if let Some(DecodeHintValue::QrMaxVersion(max_version)) =
+        hints.get(&DecodeHintType::QR_MAX_VERSION)
+    {
+        *max_version
+    } else {
+        MAX_QR_VERSION
+    };
+    let dimension = bits.getHeight();
+    if maxVersion < MAX_QR_VERSION && dimension >= 17 && (dimension - 17) / 4 > maxVersion {
+        return Err(Exceptions::FormatException(Some(format!(
+            "symbol version {} exceeds maximum allowed version {maxVersion}",
+            (dimension - 17) / 4
+        ))));
+    }
+
+    let assumedECLevel = if let Some(DecodeHintValue::QrAssumeEcLevel(ec_level)) =
+        hints.get(&DecodeHintType::QR_ASSUME_EC_LEVEL)
+    {
+        Some(ErrorCorrectionLevel::from_str(ec_level)?)
+    } else {
+        None
+    };
+
     // Construct a parser and read version, error-correction level
     let mut parser = BitMatrixParser::new(bits.clone())?;
+    parser.setAssumedECLevel(assumedECLevel);
     let mut fe = None;
     let mut ce = None;
     match decode_bitmatrix_parser_with_hints(&mut parser, hints) {
@@ -118,6 +188,7 @@ pub fn decode_bitmatrix_with_hints(
 
         // Success! Notify the caller that the code was mirrored.
         result.setOther(Some(Rc::new(QRCodeDecoderMetaData::new(true))));
+        result.setIsMirrored(true);
 
         Ok(result)
     };
@@ -126,6 +197,13 @@ pub fn decode_bitmatrix_with_hints(
         Ok(res) => Ok(res),
         Err(er) => match er {
             Exceptions::FormatException(_) | Exceptions::ChecksumException(_) => {
+                if hints.contains_key(&DecodeHintType::TRY_HARDER) {
+                    if let Ok(result) =
+                        decode_with_guessed_format_information(bits, assumedECLevel, hints)
+                    {
+                        return Ok(result);
+                    }
+                }
                 if let Some(fe) = fe {
                     Err(fe)
                 } else {
@@ -145,6 +223,55 @@ pub fn decode_bitmatrix_with_hints(
     // }
 }
 
+/**
+ * <p>Decodes a QR Code whose format information is damaged by trying each of the eight data
+ * masks in turn. The first guess whose codewords pass error correction is accepted.</p>
+ *
+ * <p>The error correction level cannot be derived from the modules without the format
+ * information, so it has to be known: either assumed, or read from the damaged format
+ * information, which may still give the right level with the wrong mask.</p>
+ *
+ * @param bits booleans representing white/black QR Code modules
+ * @param assumedECLevel the error correction level the symbol is known to use, if any
+ * @param hints decoding hints that should be used to influence decoding
+ * @return text and bytes encoded within the QR Code
+ * @throws FormatException if the error correction level isn't known, or no guess decodes
+ */
+fn decode_with_guessed_format_information(
+    bits: &BitMatrix,
+    assumedECLevel: Option<ErrorCorrectionLevel>,
+    hints: &DecodingHintDictionary,
+) -> Result<DecoderRXingResult, Exceptions> {
+    let mut parser = BitMatrixParser::new(bits.clone())?;
+    parser.setAssumedECLevel(assumedECLevel);
+    parser.readVersion()?;
+    // Damaged format information may still be read, as the wrong level and mask
+    let readFormatInfo = parser.readFormatInformation().ok().map(|formatInfo| {
+        (
+            formatInfo.getErrorCorrectionLevel(),
+            formatInfo.getDataMask(),
+        )
+    });
+    let Some(ecLevel) = assumedECLevel.or(readFormatInfo.map(|(ecLevel, _)| ecLevel)) else {
+        return Err(Exceptions::FormatException(None));
+    };
+
+    for dataMask in 0..8 {
+        if readFormatInfo == Some((ecLevel, dataMask)) {
+            // Already tried
+            continue;
+        }
+        parser.setFormatInformation(FormatInformation::forECLevelAndDataMask(ecLevel, dataMask));
+        let result = decode_bitmatrix_parser_with_hints(&mut parser, hints);
+        if result.is_ok() {
+            return result;
+        }
+        // Put back the data mask removed while reading the codewords
+        parser.remask();
+    }
+    Err(Exceptions::FormatException(None))
+}
+
 fn decode_bitmatrix_parser_with_hints(
     parser: &mut BitMatrixParser,
     hints: &DecodingHintDictionary,
@@ -164,13 +291,22 @@ fn decode_bitmatrix_parser_with_hints(
 
     let mut resultBytes = vec![0u8; totalBytes];
     let mut resultOffset = 0;
+    let mut errorsCorrected = 0;
+    let mut unusedErrorCorrection = 1.0f32;
 
     // Error-correct and copy data blocks together into a stream of bytes
     for dataBlock in &dataBlocks {
         // for (DataBlock dataBlock : dataBlocks) {
         let mut codewordBytes = dataBlock.getCodewords().to_vec();
         let numDataCodewords = dataBlock.getNumDataCodewords() as usize;
-        correctErrors(&mut codewordBytes, numDataCodewords)?;
+        let errors = correctErrors(&mut codewordBytes, numDataCodewords)?;
+        errorsCorrected += errors;
+        // Each error takes two error correction codewords to correct
+        let capacity = (codewordBytes.len() - numDataCodewords) / 2;
+        if capacity > 0 {
+            unusedErrorCorrection =
+                unusedErrorCorrection.min(1.0 - errors as f32 / capacity as f32);
+        }
         for codeword_byte in codewordBytes.iter().take(numDataCodewords) {
             // for i in 0..numDataCodewords {
             // for (int i = 0; i < numDataCodewords; i++) {
@@ -180,7 +316,11 @@ fn decode_bitmatrix_parser_with_hints(
     }
 
     // Decode the contents of that stream of bytes
-    decoded_bit_stream_parser::decode(&resultBytes, version, ecLevel, hints)
+    let mut result = decoded_bit_stream_parser::decode(&resultBytes, version, ecLevel, hints)?;
+    result.setDataCodewords(resultBytes.iter().map(|b| *b as u32).collect());
+    result.setErrorsCorrected(errorsCorrected);
+    result.setUnusedErrorCorrection(Some(unusedErrorCorrection.max(0.0)));
+    Ok(result)
 }
 
 /**
@@ -189,9 +329,10 @@ fn decode_bitmatrix_parser_with_hints(
  *
  * @param codewordBytes data and error correction codewords
  * @param numDataCodewords number of codewords that are data bytes
+ * @return the number of errors corrected
  * @throws ChecksumException if error correction fails
  */
-fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<(), Exceptions> {
+fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<usize, Exceptions> {
     let numCodewords = codewordBytes.len();
     // First read into an array of ints
     let mut codewordsInts = vec![0u8; numCodewords];
@@ -203,14 +344,16 @@ fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()
 
     let mut sending_code_words: Vec<i32> = codewordsInts.iter().map(|x| *x as i32).collect();
 
-    if let Err(Exceptions::ReedSolomonException(error_str)) = RS_DECODER.decode(
+    let errorsCorrected = match RS_DECODER.decode(
         &mut sending_code_words,
         (codewordBytes.len() - numDataCodewords) as i32,
     ) {
-        // if let Exceptions::ReedSolomonException(error_str) = e {
-        return Err(Exceptions::ChecksumException(error_str));
-        // }
-    }
+        Ok(errorsCorrected) => errorsCorrected,
+        Err(Exceptions::ReedSolomonException(error_str)) => {
+            return Err(Exceptions::ChecksumException(error_str))
+        }
+        Err(_) => 0,
+    };
 
     // Copy back into array of bytes -- only need to worry about the bytes that were data
     // We don't care about errors in the error-correction codewords
@@ -222,5 +365,5 @@ fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()
         *code_word = *sent_code_word as u8;
     }
 
-    Ok(())
+    Ok(errorsCorrected)
 }
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitMatrix, qrcode::QRCodeWriter, BarcodeFormat, DecodeHintType, DecodeHintValue,
+    EncodeHintType, EncodeHintValue, Exceptions, Writer,
+};
+
+use super::qrcode_decoder;
+
+fn encode_version(contents: &str, version: u32) -> BitMatrix {
+    let hints = HashMap::from([
+        (
+            EncodeHintType::QR_VERSION,
+            EncodeHintValue::QrVersion(version.to_string()),
+        ),
+        (
+            EncodeHintType::MARGIN,
+            EncodeHintValue::Margin("0".to_owned()),
+        ),
+    ]);
+    QRCodeWriter
+        .encode_with_hints(contents, &BarcodeFormat::QR_CODE, 0, 0, &hints)
+        .expect("encode")
+}
+
+#[test]
+fn test_max_version_rejects_larger_symbol() {
+    let bits = encode_version("version forty", 40);
+    assert_eq!(177, bits.getHeight());
+
+    let hints = HashMap::from([(
+        DecodeHintType::QR_MAX_VERSION,
+        DecodeHintValue::QrMaxVersion(10),
+    )]);
+    assert!(matches!(
+        qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints),
+        Err(Exceptions::FormatException(_))
+    ));
+
+    // Without the cap the same symbol decodes normally
+    let result = qrcode_decoder::decode_bitmatrix(&bits).expect("decode");
+    assert_eq!("version forty", result.getText());
+}
+
+#[test]
+fn test_max_version_accepts_smaller_symbol() {
+    let bits = encode_version("version ten", 10);
+
+    let hints = HashMap::from([(
+        DecodeHintType::QR_MAX_VERSION,
+        DecodeHintValue::QrMaxVersion(10),
+    )]);
+    let result = qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints).expect("decode");
+    assert_eq!("version ten", result.getText());
+}
+
+#[test]
+fn test_preserve_shift_jis_bytes() {
+    let hints = HashMap::from([
+        (
+            EncodeHintType::CHARACTER_SET,
+            EncodeHintValue::CharacterSet("Shift_JIS".to_owned()),
+        ),
+        (
+            EncodeHintType::MARGIN,
+            EncodeHintValue::Margin("0".to_owned()),
+        ),
+    ]);
+    let bits = QRCodeWriter
+        .encode_with_hints("漢字", &BarcodeFormat::QR_CODE, 0, 0, &hints)
+        .expect("encode");
+
+    let hints = HashMap::from([(
+        DecodeHintType::QR_PRESERVE_SHIFT_JIS_BYTES,
+        DecodeHintValue::QrPreserveShiftJisBytes(true),
+    )]);
+    let result = qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints).expect("decode");
+    assert_eq!("漢字", result.getText());
+    assert_eq!(
+        &vec![vec![0x8A, 0xBF, 0x8E, 0x9A]],
+        result.getShiftJisBytes()
+    );
+}
+
+/** Encodes the contents at the given error correction level and mask, without a margin. */
+fn encode_with_ec_level_and_mask(contents: &str, ecLevel: &str, mask: &str) -> BitMatrix {
+    let hints = HashMap::from([
+        (
+            EncodeHintType::ERROR_CORRECTION,
+            EncodeHintValue::ErrorCorrection(ecLevel.to_owned()),
+        ),
+        (
+            EncodeHintType::QR_MASK_PATTERN,
+            EncodeHintValue::QrMaskPattern(mask.to_owned()),
+        ),
+        (
+            EncodeHintType::MARGIN,
+            EncodeHintValue::Margin("0".to_owned()),
+        ),
+    ]);
+    QRCodeWriter
+        .encode_with_hints(contents, &BarcodeFormat::QR_CODE, 0, 0, &hints)
+        .expect("encode")
+}
+
+#[test]
+fn test_assume_ec_level_recovers_damaged_format_info() {
+    let mut bits = encode_with_ec_level_and_mask("ASSUME H", "H", "2");
+    let dimension = bits.getHeight();
+
+    // Flip the four lowest format info bits in both copies, which leaves them more than 3 bits
+    // from every valid pattern
+    for i in 0..4 {
+        bits.flip_coords(8, i);
+        bits.flip_coords(dimension - 1 - i, 8);
+    }
+    assert!(qrcode_decoder::decode_bitmatrix(&bits).is_err());
+    // Trying harder can't guess the mask either, without knowing the level
+    let hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+    assert!(matches!(
+        qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints),
+        Err(Exceptions::FormatException(_))
+    ));
+
+    let hints = HashMap::from([(
+        DecodeHintType::QR_ASSUME_EC_LEVEL,
+        DecodeHintValue::QrAssumeEcLevel("H".to_owned()),
+    )]);
+    let result = qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints).expect("decode");
+    assert_eq!("ASSUME H", result.getText());
+}
+
+#[test]
+fn test_assume_wrong_ec_level() {
+    let bits = encode_version("level L", 1);
+    let hints = HashMap::from([(
+        DecodeHintType::QR_ASSUME_EC_LEVEL,
+        DecodeHintValue::QrAssumeEcLevel("H".to_owned()),
+    )]);
+    assert!(qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints).is_err());
+
+    let hints = HashMap::from([(
+        DecodeHintType::QR_ASSUME_EC_LEVEL,
+        DecodeHintValue::QrAssumeEcLevel("L".to_owned()),
+    )]);
+    let result = qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints).expect("decode");
+    assert_eq!("level L", result.getText());
+}
+
+#[test]
+fn test_try_harder_guesses_erased_format_info() {
+    let mut bits = encode_with_ec_level_and_mask("NO FORMAT INFO", "M", "5");
+    let dimension = bits.getHeight();
+
+    // Zero out both copies of the format information, leaving only the dark module. That still
+    // reads as the right level, M, but with mask 0.
+    for i in 0..9 {
+        if i != 6 {
+            bits.unset(8, i);
+            bits.unset(i, 8);
+        }
+    }
+    for i in 0..8 {
+        bits.unset(dimension - 1 - i, 8);
+        if i != 7 {
+            bits.unset(8, dimension - 1 - i);
+        }
+    }
+    assert!(qrcode_decoder::decode_bitmatrix(&bits).is_err());
+
+    let hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+    let result = qrcode_decoder::decode_bitmatrix_with_hints(&bits, &hints).expect("decode");
+    assert_eq!("NO FORMAT INFO", result.getText());
+}
+
+#[test]
+fn test_timing_pattern_mismatches() {
+    let mut bits = encode_version("timing pattern", 2);
+    let dimension = bits.getHeight();
+    let result = qrcode_decoder::decode_bitmatrix(&bits).expect("decode");
+    assert_eq!(Some(0), result.getTimingPatternMismatches());
+
+    // Blur the horizontal timing pattern, so that every dark module bleeds into the next one
+    for x in (8..dimension - 8).rev() {
+        if bits.get(x - 1, 6) {
+            bits.set(x, 6);
+        }
+    }
+    let blurred = (dimension - 16) / 2;
+    assert_eq!(blurred, qrcode_decoder::countTimingPatternMismatches(&bits));
+    // The timing pattern holds no data, so the symbol still decodes
+    let result = qrcode_decoder::decode_bitmatrix(&bits).expect("decode");
+    assert_eq!("timing pattern", result.getText());
+    assert_eq!(Some(blurred), result.getTimingPatternMismatches());
+}
@@ -6,6 +6,7 @@ use crate::{
         decoder::qrcode_decoder, decoder::ErrorCorrectionLevel, detector::Detector,
         encoder::qrcode_encoder,
     },
+    DecodeHintType, DecodeHintValue, Exceptions,
 };
 
 #[test]
@@ -42,6 +43,56 @@ fn test_encode_decode(value: &str) {
     }
 }
 
+#[test]
+fn test_min_detection_confidence() {
+    let qr_code = qrcode_encoder::encode("value", ErrorCorrectionLevel::L).expect("must encode");
+    let matrix: BitMatrix = qr_code.getMatrix().as_ref().unwrap().clone().into();
+    let hints = HashMap::from([(
+        DecodeHintType::QR_MIN_DETECTION_CONFIDENCE,
+        DecodeHintValue::QrMinDetectionConfidence(0.9),
+    )]);
+
+    // A cleanly printed symbol passes even a high threshold
+    let square = scale(&matrix, 4, 4);
+    assert!(Detector::new(&square).detect_with_hints(&hints).is_ok());
+
+    // A symbol stretched to modules 5:4 is still readable, but its finder patterns no longer
+    // form the expected right isosceles triangle
+    let stretched = scale(&matrix, 5, 4);
+    let detected = Detector::new(&stretched)
+        .detect_with_hints(&HashMap::new())
+        .expect("must detect");
+    let decoded = qrcode_decoder::decode_bitmatrix(detected.getBits()).expect("must decode");
+    assert_eq!(decoded.getText(), "value");
+    assert!(matches!(
+        Detector::new(&stretched).detect_with_hints(&hints),
+        Err(Exceptions::NotFoundException(_))
+    ));
+}
+
+// Scales a bit matrix so that each bit is x_factor wide and y_factor high, with a four module
+// quiet zone around it
+fn scale(input: &BitMatrix, x_factor: u32, y_factor: u32) -> BitMatrix {
+    let width = input.getWidth();
+    let mut output =
+        BitMatrix::new((width + 8) * x_factor, (width + 8) * y_factor).expect("must create");
+    for inputY in 0..width {
+        for inputX in 0..width {
+            if input.get(inputX, inputY) {
+                output
+                    .setRegion(
+                        (inputX + 4) * x_factor,
+                        (inputY + 4) * y_factor,
+                        x_factor,
+                        y_factor,
+                    )
+                    .expect("region set should be ok");
+            }
+        }
+    }
+    output
+}
+
 // Zooms a bit matrix so that each bit is factor x factor
 #[allow(dead_code)]
 fn make_larger(input: &BitMatrix, factor: u32) -> BitMatrix {
@@ -35,12 +35,19 @@ pub struct FinderPatternFinder {
     hasSkipped: bool,
     crossCheckStateCount: [u32; 5],
     resultPointCallback: Option<RXingResultPointCallback>,
+    allowQuietZoneIntrusion: bool,
+    quietZoneIntruded: bool,
 }
 impl FinderPatternFinder {
     pub const CENTER_QUORUM: usize = 2;
     // private static final EstimatedModuleComparator moduleComparator = new EstimatedModuleComparator();
     pub const MIN_SKIP: u32 = 3; // 1 pixel/module times 3 modules/center
     pub const MAX_MODULES: u32 = 97; // support up to version 20 for mobile clients
+    /**
+     * The {@link FinderPatternInfo#getConfidence} three patterns need to be accepted when one of
+     * them only matched by running into the quiet zone.
+     */
+    const MIN_QUIET_ZONE_INTRUSION_CONFIDENCE: f32 = 0.8;
 
     /**
      * <p>Creates a finder that will search the image for three finder patterns.</p>
@@ -61,6 +68,8 @@ impl FinderPatternFinder {
             hasSkipped: false,
             crossCheckStateCount: [0u32; 5],
             resultPointCallback,
+            allowQuietZoneIntrusion: false,
+            quietZoneIntruded: false,
         }
     }
 
@@ -72,11 +81,50 @@ impl FinderPatternFinder {
         &self.possibleCenters
     }
 
+    /**
+     * @return true if the last {@link #find} only succeeded by letting the outer ring of a finder
+     *         pattern run into something printed against the symbol
+     */
+    pub fn hasQuietZoneIntrusion(&self) -> bool {
+        self.quietZoneIntruded
+    }
+
     pub fn find(
         &mut self,
         hints: &DecodingHintDictionary,
     ) -> Result<FinderPatternInfo, Exceptions> {
         let tryHarder = hints.contains_key(&DecodeHintType::TRY_HARDER);
+        self.quietZoneIntruded = false;
+        let error = match self.findBestPatterns(tryHarder) {
+            Ok(patternInfo) => return Ok(Self::orderedPatternInfo(patternInfo)),
+            Err(e) if tryHarder => e,
+            Err(e) => return Err(e),
+        };
+
+        // Captions or graphics printed right against the symbol run into the outer ring of a
+        // finder pattern and spoil its 1:1:3:1:1 ratio. Look again, letting one side of the ring
+        // run into the quiet zone, but only accept three patterns which agree closely with each
+        // other.
+        self.possibleCenters.clear();
+        self.hasSkipped = false;
+        self.allowQuietZoneIntrusion = true;
+        let retried = self.findBestPatterns(tryHarder);
+        self.allowQuietZoneIntrusion = false;
+        match retried.map(Self::orderedPatternInfo) {
+            Ok(info) if info.getConfidence() >= Self::MIN_QUIET_ZONE_INTRUSION_CONFIDENCE => {
+                self.quietZoneIntruded = true;
+                Ok(info)
+            }
+            _ => Err(error),
+        }
+    }
+
+    fn orderedPatternInfo(mut patterns: [FinderPattern; 3]) -> FinderPatternInfo {
+        result_point_utils::orderBestPatterns(&mut patterns);
+        FinderPatternInfo::new(patterns)
+    }
+
+    fn findBestPatterns(&mut self, tryHarder: bool) -> Result<[FinderPattern; 3], Exceptions> {
         let maxI = self.image.getHeight();
         let maxJ = self.image.getWidth();
         // We are looking for black/white/black/white/black modules in
@@ -115,9 +163,10 @@ impl FinderPatternFinder {
                         // Counting black pixels
                         if currentState == 4 {
                             // A winner?
-                            if FinderPatternFinder::foundPatternCross(&stateCount) {
+                            if let Some((counts, trimmed)) = self.matchFinderPattern(&stateCount) {
                                 // Yes
-                                let confirmed = self.handlePossibleCenter(&stateCount, i as u32, j);
+                                let confirmed =
+                                    self.handlePossibleCenter(&counts, i as u32, j - trimmed);
                                 if confirmed {
                                     // Start examining every other line. Checking each line turned out to be too
                                     // expensive and didn't improve performance.
@@ -167,10 +216,10 @@ impl FinderPatternFinder {
                 }
                 j += 1;
             }
-            if FinderPatternFinder::foundPatternCross(&stateCount) {
-                let confirmed = self.handlePossibleCenter(&stateCount, i as u32, maxJ);
+            if let Some((counts, trimmed)) = self.matchFinderPattern(&stateCount) {
+                let confirmed = self.handlePossibleCenter(&counts, i as u32, maxJ - trimmed);
                 if confirmed {
-                    iSkip = stateCount[0];
+                    iSkip = counts[0];
                     if self.hasSkipped {
                         // Found a third one
                         done = self.haveMultiplyConfirmedCenters();
@@ -181,10 +230,7 @@ impl FinderPatternFinder {
             i += iSkip as i32;
         }
 
-        let mut patternInfo = self.selectBestPatterns()?;
-        result_point_utils::orderBestPatterns(&mut patternInfo);
-
-        Ok(FinderPatternInfo::new(patternInfo))
+        self.selectBestPatterns()
     }
 
     /**
@@ -252,6 +298,58 @@ impl FinderPatternFinder {
             && (moduleSize - stateCount[4] as f64).abs() < maxVariance
     }
 
+    /**
+     * Matches a count of black/white/black/white/black pixels against the 1:1:3:1:1 ratios,
+     * allowing for an intruding quiet zone if that was asked for.
+     *
+     * @return the counts to use for the pattern, and how many pixels were trimmed from the end
+     *         of the last run, or None if there is no match
+     */
+    fn matchFinderPattern(&self, stateCount: &[u32; 5]) -> Option<([u32; 5], u32)> {
+        if Self::foundPatternCross(stateCount) {
+            Some((*stateCount, 0))
+        } else if self.allowQuietZoneIntrusion {
+            Self::trimQuietZoneIntrusion(stateCount)
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Checks whether a count of black/white/black/white/black pixels is a finder pattern whose
+     * outer ring runs into something dark on one side, as when a caption is printed against
+     * the symbol. The inner three runs must match the 1:3:1 ratios and the outer run on the
+     * other side must be a single module.
+     *
+     * @return the counts with the overlong run cut back to one module, and how many pixels were
+     *         cut from the end of the last run, or None if this isn't such a pattern
+     */
+    fn trimQuietZoneIntrusion(stateCount: &[u32; 5]) -> Option<([u32; 5], u32)> {
+        if stateCount.contains(&0) {
+            return None;
+        }
+        let moduleSize = (stateCount[1] + stateCount[2] + stateCount[3]) as f64 / 5.0;
+        let maxVariance = moduleSize / 2.0;
+        let fits = |count: u32, modules: f64| {
+            (modules * moduleSize - count as f64).abs() < modules * maxVariance
+        };
+        if !fits(stateCount[1], 1.0) || !fits(stateCount[2], 3.0) || !fits(stateCount[3], 1.0) {
+            return None;
+        }
+        let overlong = |count: u32| count as f64 >= moduleSize + maxVariance;
+        let module = (moduleSize.round() as u32).max(1);
+        let mut trimmed = *stateCount;
+        if fits(stateCount[0], 1.0) && overlong(stateCount[4]) {
+            trimmed[4] = module;
+            Some((trimmed, stateCount[4] - module))
+        } else if overlong(stateCount[0]) && fits(stateCount[4], 1.0) {
+            trimmed[0] = module;
+            Some((trimmed, 0))
+        } else {
+            None
+        }
+    }
+
     fn getCrossCheckStateCount(&mut self) -> &[u32; 5] {
         FinderPatternFinder::doClearCounts(&mut self.crossCheckStateCount);
         &self.crossCheckStateCount
@@ -346,7 +444,12 @@ impl FinderPatternFinder {
             return false;
         }
 
-        Self::foundPatternDiagonal(&self.crossCheckStateCount)
+        if Self::foundPatternDiagonal(&self.crossCheckStateCount) {
+            return true;
+        }
+        self.allowQuietZoneIntrusion
+            && Self::trimQuietZoneIntrusion(&self.crossCheckStateCount)
+                .is_some_and(|(counts, _)| Self::foundPatternDiagonal(&counts))
     }
 
     /**
@@ -399,7 +502,7 @@ impl FinderPatternFinder {
             self.crossCheckStateCount[0] += 1;
             i -= 1;
         }
-        if self.crossCheckStateCount[0] > maxCount {
+        if self.crossCheckStateCount[0] > maxCount && !self.allowQuietZoneIntrusion {
             return f32::NAN;
         }
 
@@ -429,28 +532,25 @@ impl FinderPatternFinder {
             self.crossCheckStateCount[4] += 1;
             i += 1;
         }
-        if self.crossCheckStateCount[4] >= maxCount {
+        if self.crossCheckStateCount[4] >= maxCount && !self.allowQuietZoneIntrusion {
             return f32::NAN;
         }
 
+        let Some((stateCount, trimmed)) = self.matchFinderPattern(&self.crossCheckStateCount)
+        else {
+            return f32::NAN;
+        };
+
         // If we found a finder-pattern-like section, but its size is more than 40% different than
         // the original, assume it's a false positive
-        let stateCountTotal = self.crossCheckStateCount[0]
-            + self.crossCheckStateCount[1]
-            + self.crossCheckStateCount[2]
-            + self.crossCheckStateCount[3]
-            + self.crossCheckStateCount[4];
+        let stateCountTotal: u32 = stateCount.iter().sum();
         if 5 * (stateCountTotal as i64 - originalStateCountTotal as i64)
             >= 2 * originalStateCountTotal as i64
         {
             return f32::NAN;
         }
 
-        if Self::foundPatternCross(&self.crossCheckStateCount) {
-            Self::centerFromEnd(&self.crossCheckStateCount, i as u32)
-        } else {
-            f32::NAN
-        }
+        Self::centerFromEnd(&stateCount, i as u32 - trimmed)
     }
 
     /**
@@ -495,7 +595,7 @@ impl FinderPatternFinder {
             self.crossCheckStateCount[0] += 1;
             j -= 1;
         }
-        if self.crossCheckStateCount[0] > maxCount {
+        if self.crossCheckStateCount[0] > maxCount && !self.allowQuietZoneIntrusion {
             return f32::NAN;
         }
 
@@ -524,28 +624,25 @@ impl FinderPatternFinder {
             self.crossCheckStateCount[4] += 1;
             j += 1;
         }
-        if self.crossCheckStateCount[4] >= maxCount {
+        if self.crossCheckStateCount[4] >= maxCount && !self.allowQuietZoneIntrusion {
             return f32::NAN;
         }
 
+        let Some((stateCount, trimmed)) = self.matchFinderPattern(&self.crossCheckStateCount)
+        else {
+            return f32::NAN;
+        };
+
         // If we found a finder-pattern-like section, but its size is significantly different than
         // the original, assume it's a false positive
-        let stateCountTotal = self.crossCheckStateCount[0]
-            + self.crossCheckStateCount[1]
-            + self.crossCheckStateCount[2]
-            + self.crossCheckStateCount[3]
-            + self.crossCheckStateCount[4];
+        let stateCountTotal: u32 = stateCount.iter().sum();
         if 5 * (stateCountTotal as i64 - originalStateCountTotal as i64)
             >= originalStateCountTotal as i64
         {
             return f32::NAN;
         }
 
-        if Self::foundPatternCross(&self.crossCheckStateCount) {
-            Self::centerFromEnd(&self.crossCheckStateCount, j as u32)
-        } else {
-            f32::NAN
-        }
+        Self::centerFromEnd(&stateCount, j as u32 - trimmed)
     }
 
     /**
@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use crate::result_point_utils;
+
 use super::FinderPattern;
 
 /**
@@ -22,6 +24,7 @@ use super::FinderPattern;
  *
  * @author Sean Owen
  */
+#[derive(Clone)]
 pub struct FinderPatternInfo {
     bottomLeft: FinderPattern,
     topLeft: FinderPattern,
@@ -49,4 +52,34 @@ impl FinderPatternInfo {
     pub fn getTopRight(&self) -> &FinderPattern {
         &self.topRight
     }
+
+    /**
+     * Estimates how likely the three patterns are to really be the finder patterns of one symbol,
+     * in the range [0,1]. The patterns returned by the finder were all confirmed by at least
+     * CENTER_QUORUM scans, and clean symbols rarely get more as the search stops early, so the
+     * estimate instead looks at how well the patterns agree with each other.
+     *
+     * @return product of the ratio of smallest to largest module size, the ratio of the two sides
+     *  meeting at the top left pattern, and how close the diagonal is to what those sides imply
+     */
+    pub fn getConfidence(&self) -> f32 {
+        let patterns = [&self.bottomLeft, &self.topLeft, &self.topRight];
+        let moduleSizes = patterns.map(|p| p.getEstimatedModuleSize());
+        let minModuleSize = moduleSizes.iter().copied().fold(f32::MAX, f32::min);
+        let maxModuleSize = moduleSizes.iter().copied().fold(0.0, f32::max);
+
+        let top = result_point_utils::distance(&self.topLeft, &self.topRight);
+        let left = result_point_utils::distance(&self.topLeft, &self.bottomLeft);
+        let diagonal = result_point_utils::distance(&self.bottomLeft, &self.topRight);
+        let expectedDiagonal = (top * top + left * left).sqrt();
+
+        let ratio = |a: f32, b: f32| {
+            if a.max(b) > 0.0 {
+                a.min(b) / a.max(b)
+            } else {
+                0.0
+            }
+        };
+        ratio(minModuleSize, maxModuleSize) * ratio(top, left) * ratio(diagonal, expectedDiagonal)
+    }
 }
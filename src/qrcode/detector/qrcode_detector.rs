@@ -22,7 +22,7 @@ use crate::{
     },
     qrcode::decoder::Version,
     result_point_utils, DecodeHintType, DecodeHintValue, DecodingHintDictionary, Exceptions,
-    RXingResultPointCallback, ResultPoint,
+    RXingResultPoint, RXingResultPointCallback, ResultPoint,
 };
 
 use super::{
@@ -39,6 +39,8 @@ use super::{
 pub struct Detector<'a> {
     image: &'a BitMatrix,
     resultPointCallback: Option<RXingResultPointCallback>,
+    relaxQuietZone: bool,
+    quietZoneIntruded: bool,
 }
 
 impl<'a> Detector<'_> {
@@ -46,6 +48,8 @@ impl<'a> Detector<'_> {
         Detector {
             image,
             resultPointCallback: None,
+            relaxQuietZone: false,
+            quietZoneIntruded: false,
         }
     }
 
@@ -101,8 +105,34 @@ impl<'a> Detector<'_> {
             self.resultPointCallback.clone(),
         );
         let info = finder.find(hints)?;
+        self.quietZoneIntruded = finder.hasQuietZoneIntrusion();
 
-        self.processFinderPatternInfo(info)
+        if let Some(DecodeHintValue::QrMinDetectionConfidence(minConfidence)) =
+            hints.get(&DecodeHintType::QR_MIN_DETECTION_CONFIDENCE)
+        {
+            if info.getConfidence() < *minConfidence {
+                return Err(Exceptions::NotFoundException(Some(format!(
+                    "finder pattern confidence {} is below {}",
+                    info.getConfidence(),
+                    minConfidence
+                ))));
+            }
+        }
+
+        self.relaxQuietZone = false;
+        let result = self.processFinderPatternInfo(info.clone());
+        if result.is_err()
+            && matches!(
+                hints.get(&DecodeHintType::RELAX_QUIET_ZONE),
+                Some(DecodeHintValue::RelaxQuietZone(true))
+            )
+        {
+            // Finder patterns at the very edge of the image may have been cut by it, which throws
+            // off the module size; measure them again from their intact half.
+            self.relaxQuietZone = true;
+            return self.processFinderPatternInfo(info);
+        }
+        result
     }
 
     pub fn processFinderPatternInfo(
@@ -185,7 +215,18 @@ impl<'a> Detector<'_> {
             ]
         };
 
-        Ok(QRCodeDetectorResult::new(bits, points))
+        // The outer corners of the symbol, in module coordinates
+        let size = dimension as f32;
+        let mut corners = [0.0, 0.0, size, 0.0, size, size, 0.0, size];
+        transform.transform_points_single(&mut corners);
+        let bounds = [
+            RXingResultPoint::new(corners[0], corners[1]),
+            RXingResultPoint::new(corners[2], corners[3]),
+            RXingResultPoint::new(corners[4], corners[5]),
+            RXingResultPoint::new(corners[6], corners[7]),
+        ];
+
+        Ok(QRCodeDetectorResult::new(bits, points, bounds))
     }
 
     fn createTransform<T: ResultPoint, X: ResultPoint>(
@@ -339,13 +380,16 @@ impl<'a> Detector<'_> {
         // Now count other way -- don't run off image though of course
         let mut scale = 1.0;
         let mut otherToX = fromX as i32 - (toX as i32 - fromX as i32);
+        let mut clipped = false;
         if otherToX < 0 {
             scale = fromX as f32 / (fromX as i32 - otherToX) as f32;
             otherToX = 0;
+            clipped = true;
         } else if otherToX as u32 >= self.image.getWidth() {
             scale = (self.image.getWidth() as i32 - 1 - fromX as i32) as f32
                 / (otherToX - fromX as i32) as f32;
             otherToX = self.image.getWidth() as i32 - 1;
+            clipped = true;
         }
         let mut otherToY = (fromY as f32 - (toY as f32 - fromY as f32) * scale).floor() as i32;
 
@@ -353,14 +397,31 @@ impl<'a> Detector<'_> {
         if otherToY < 0 {
             scale = fromY as f32 / (fromY as i32 - otherToY) as f32;
             otherToY = 0;
+            clipped = true;
         } else if otherToY as u32 >= self.image.getHeight() {
             scale = (self.image.getHeight() as i32 - 1 - fromY as i32) as f32
                 / (otherToY - fromY as i32) as f32;
             otherToY = self.image.getHeight() as i32 - 1;
+            clipped = true;
         }
         otherToX = (fromX as f32 + (otherToX as f32 - fromX as f32) * scale).floor() as i32;
 
-        result += self.sizeOfBlackWhiteBlackRun(fromX, fromY, otherToX as u32, otherToY as u32);
+        let otherWay =
+            self.sizeOfBlackWhiteBlackRun(fromX, fromY, otherToX as u32, otherToY as u32);
+        // Without a quiet zone the run towards the border may be cut short by the edge of the
+        // image. The finder pattern is symmetric, so the run the other way is as good a measure.
+        if self.relaxQuietZone
+            && clipped
+            && otherWay < result
+            && otherWay >= MathUtils::distance_int(fromX as i32, fromY as i32, otherToX, otherToY)
+        {
+            result += result;
+        } else if self.quietZoneIntruded && (otherWay > 1.5 * result || result > 1.5 * otherWay) {
+            // Something printed against the symbol lengthened the run on one side
+            result = 2.0 * result.min(otherWay);
+        } else {
+            result += otherWay;
+        }
 
         // Middle pixel is double-counted this way; subtract 1
         result - 1.0
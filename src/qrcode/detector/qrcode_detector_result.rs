@@ -6,15 +6,28 @@ use crate::{
 pub struct QRCodeDetectorResult {
     bit_source: BitMatrix,
     result_points: Vec<RXingResultPoint>,
+    bounds: [RXingResultPoint; 4],
 }
 
 impl QRCodeDetectorResult {
-    pub fn new(bit_source: BitMatrix, result_points: Vec<RXingResultPoint>) -> Self {
+    pub fn new(
+        bit_source: BitMatrix,
+        result_points: Vec<RXingResultPoint>,
+        bounds: [RXingResultPoint; 4],
+    ) -> Self {
         Self {
             bit_source,
             result_points,
+            bounds,
         }
     }
+
+    /**
+     * @return the corners of the symbol in the image, clockwise from its top left corner
+     */
+    pub fn getBounds(&self) -> &[RXingResultPoint; 4] {
+        &self.bounds
+    }
 }
 
 impl DetectorRXingResult for QRCodeDetectorResult {
@@ -20,9 +20,9 @@ use crate::{
     common::BitArray,
     qrcode::{
         decoder::{ErrorCorrectionLevel, Mode, Version},
-        encoder::{qrcode_encoder, MinimalEncoder},
+        encoder::{qrcode_encoder, ByteMatrix, MinimalEncoder},
     },
-    EncodeHintType, EncodeHintValue,
+    CapacityExceeded, EncodeHintType, EncodeHintValue, Exceptions,
 };
 use encoding::EncodingRef;
 use once_cell::sync::Lazy;
@@ -1103,6 +1103,97 @@ fn testMinimalEncoder44() {
     );
 }
 
+#[test]
+fn testEncodeIntoReusedMatrix() {
+    let hints = HashMap::new();
+    let mut buffer = ByteMatrix::new(21, 21);
+    let row_storage = buffer.getArray()[0].as_ptr();
+
+    for content in ["ABCDEF", "HELLO WORLD"] {
+        qrcode_encoder::encode_into(content, ErrorCorrectionLevel::L, &hints, &mut buffer)
+            .expect("encode_into");
+        let expected =
+            qrcode_encoder::encode_with_hints(content, ErrorCorrectionLevel::L, &hints).unwrap();
+        assert_eq!(expected.getMatrix().as_ref().unwrap(), &buffer);
+        assert_eq!(row_storage, buffer.getArray()[0].as_ptr());
+    }
+}
+
+#[test]
+fn testEncodeIntoWrongSize() {
+    let mut buffer = ByteMatrix::new(25, 25);
+    assert!(matches!(
+        qrcode_encoder::encode_into(
+            "ABCDEF",
+            ErrorCorrectionLevel::L,
+            &HashMap::new(),
+            &mut buffer
+        ),
+        Err(Exceptions::WriterException(_))
+    ));
+}
+
+#[test]
+fn testEncodeWithSegmentsMatchesMinimal() {
+    let content = "12345678901234567890123456789012345678901234567890 is a long number";
+    let minimal =
+        MinimalEncoder::encode_with_details(content, None, None, false, ErrorCorrectionLevel::L)
+            .expect("encode");
+    assert_eq!(
+        "NUMERIC(12345678901234567890123456789012345678901234567890),BYTE( is a long number)",
+        minimal.to_string()
+    );
+
+    let compact = qrcode_encoder::encode_with_hints(
+        content,
+        ErrorCorrectionLevel::L,
+        &HashMap::from([(
+            EncodeHintType::QR_COMPACT,
+            EncodeHintValue::QrCompact("true".to_owned()),
+        )]),
+    )
+    .expect("encode");
+    let segmented = qrcode_encoder::encode_with_hints(
+        content,
+        ErrorCorrectionLevel::L,
+        &HashMap::from([(
+            EncodeHintType::QR_SEGMENTS,
+            EncodeHintValue::QrSegments(vec![(Mode::NUMERIC, 0..50), (Mode::BYTE, 50..67)]),
+        )]),
+    )
+    .expect("encode");
+    assert_eq!(compact.to_string(), segmented.to_string());
+}
+
+#[test]
+fn testEncodeWithSegmentsRejectsInvalidSegmentation() {
+    let encode = |segments: Vec<(Mode, std::ops::Range<usize>)>| {
+        qrcode_encoder::encode_with_hints(
+            "123ABC",
+            ErrorCorrectionLevel::L,
+            &HashMap::from([(
+                EncodeHintType::QR_SEGMENTS,
+                EncodeHintValue::QrSegments(segments),
+            )]),
+        )
+    };
+    assert!(encode(vec![(Mode::NUMERIC, 0..3), (Mode::ALPHANUMERIC, 3..6)]).is_ok());
+    // Content that doesn't fit the declared mode
+    assert!(matches!(
+        encode(vec![(Mode::NUMERIC, 0..4), (Mode::ALPHANUMERIC, 4..6)]),
+        Err(Exceptions::WriterException(_))
+    ));
+    // Gaps and incomplete coverage
+    assert!(matches!(
+        encode(vec![(Mode::NUMERIC, 0..2), (Mode::ALPHANUMERIC, 3..6)]),
+        Err(Exceptions::WriterException(_))
+    ));
+    assert!(matches!(
+        encode(vec![(Mode::NUMERIC, 0..3)]),
+        Err(Exceptions::WriterException(_))
+    ));
+}
+
 fn verifyMinimalEncoding(
     input: &str,
     expectedRXingResult: &str,
@@ -1196,3 +1287,198 @@ fn shiftJISString(bytes: &[u8]) -> String {
         .expect("decode should be ok")
     // return new String(bytes, StringUtils.SHIFT_JIS_CHARSET);
 }
+
+#[test]
+fn testCapacityExceededReportsOverflow() {
+    // Version 40-L holds 7089 digits: 4 mode bits, 14 length bits and 10 bits per 3 digits
+    let content = "1".repeat(7090);
+    assert_eq!(
+        Err(Exceptions::CapacityExceededException(Some(
+            CapacityExceeded {
+                required_bits: 4 + 14 + 2363 * 10 + 4,
+                available_bits: 2956 * 8,
+            }
+        ))),
+        qrcode_encoder::encode(&content, ErrorCorrectionLevel::L).map(|_| ())
+    );
+    assert!(qrcode_encoder::encode(&content[1..], ErrorCorrectionLevel::L).is_ok());
+
+    // The minimal encoder reports the same way, here for version 9-H, the largest one with 10
+    // length bits for numeric segments
+    assert_eq!(
+        Err(Exceptions::CapacityExceededException(Some(
+            CapacityExceeded {
+                required_bits: 4 + 10 + 100 * 10,
+                available_bits: 100 * 8,
+            }
+        ))),
+        MinimalEncoder::encode_with_details(
+            &content[..300],
+            Some(Version::getVersionForNumber(1).unwrap()),
+            None,
+            false,
+            ErrorCorrectionLevel::H,
+        )
+        .map(|_| ())
+    );
+
+    // 20 digits take 4 + 10 + 67 bits, more than the 9 data bytes of version 1-H
+    let version_1 = HashMap::from([(
+        EncodeHintType::QR_VERSION,
+        EncodeHintValue::QrVersion("1".to_owned()),
+    )]);
+    let digits = "12345678901234567890";
+    assert_eq!(
+        Err(Exceptions::CapacityExceededException(Some(
+            CapacityExceeded {
+                required_bits: 81,
+                available_bits: 72,
+            }
+        ))),
+        qrcode_encoder::encode_with_hints(digits, ErrorCorrectionLevel::H, &version_1).map(|_| ())
+    );
+    // so a caller can lower the error correction level instead
+    assert!(qrcode_encoder::encode_with_hints(digits, ErrorCorrectionLevel::L, &version_1).is_ok());
+}
+
+#[test]
+fn test_encode_structured_append() {
+    let content: String = "STRUCTURED APPEND 0123456789 "
+        .chars()
+        .cycle()
+        .take(400)
+        .collect();
+    let parity = content.bytes().fold(0, |parity, byte| parity ^ byte) as i32;
+    let hints = HashMap::from([(
+        EncodeHintType::QR_VERSION,
+        EncodeHintValue::QrVersion("5".to_owned()),
+    )]);
+
+    let symbols = qrcode_encoder::encode_structured_append_with_hints(
+        &content,
+        ErrorCorrectionLevel::L,
+        16,
+        &hints,
+    )
+    .expect("encode");
+    assert_eq!(3, symbols.len());
+
+    let mut decoded = String::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        assert_eq!(5, symbol.getVersion().unwrap().getVersionNumber());
+        let result = crate::qrcode::decoder::qrcode_decoder::decode_bitmatrix(
+            &symbol.getMatrix().as_ref().unwrap().clone().into(),
+        )
+        .expect("decode");
+        assert!(result.hasStructuredAppend());
+        // Position in the high nibble, number of symbols less one in the low nibble
+        assert_eq!(
+            ((index as i32) << 4) | 2,
+            result.getStructuredAppendSequenceNumber()
+        );
+        assert_eq!(parity, result.getStructuredAppendParity());
+        decoded.push_str(result.getText());
+    }
+    assert_eq!(content, decoded);
+
+    // Too long for two symbols of version 5
+    assert!(qrcode_encoder::encode_structured_append_with_hints(
+        &content,
+        ErrorCorrectionLevel::L,
+        2,
+        &hints,
+    )
+    .is_err());
+    assert!(
+        qrcode_encoder::encode_structured_append(&content, ErrorCorrectionLevel::L, 0).is_err()
+    );
+
+    // Short content takes a single symbol, in the smallest version
+    let symbols = qrcode_encoder::encode_structured_append("hello", ErrorCorrectionLevel::M, 4)
+        .expect("encode");
+    assert_eq!(1, symbols.len());
+    let result = crate::qrcode::decoder::qrcode_decoder::decode_bitmatrix(
+        &symbols[0].getMatrix().as_ref().unwrap().clone().into(),
+    )
+    .expect("decode");
+    assert_eq!("hello", result.getText());
+    assert_eq!(0, result.getStructuredAppendSequenceNumber());
+}
+
+#[test]
+fn test_encode_kanji_without_character_set() {
+    // "Nihongo no bunsho wo fugouka suru" and more, all Kanji mode characters in Shift_JIS
+    let content =
+        "\u{65e5}\u{672c}\u{8a9e}\u{6587}\u{66f8}\u{7b26}\u{53f7}\u{5316}\u{6f22}\u{5b57}"
+            .repeat(3);
+    let kanji = qrcode_encoder::encode(&content, ErrorCorrectionLevel::L).expect("encode");
+    assert_eq!(Mode::KANJI, kanji.getMode().unwrap());
+    let decoded = crate::qrcode::decoder::qrcode_decoder::decode_bitmatrix(
+        &kanji.getMatrix().as_ref().unwrap().clone().into(),
+    )
+    .expect("decode");
+    assert_eq!(content, decoded.getText());
+
+    let hints = HashMap::from([(
+        EncodeHintType::CHARACTER_SET,
+        EncodeHintValue::CharacterSet("UTF-8".to_owned()),
+    )]);
+    let byte = qrcode_encoder::encode_with_hints(&content, ErrorCorrectionLevel::L, &hints)
+        .expect("encode");
+    assert_eq!(Mode::BYTE, byte.getMode().unwrap());
+    // 13 bits per character instead of 24
+    assert!(
+        kanji.getMatrix().as_ref().unwrap().getWidth()
+            < byte.getMatrix().as_ref().unwrap().getWidth()
+    );
+
+    // Kanji mixed with other characters stays in byte mode
+    let mixed =
+        qrcode_encoder::encode("\u{65e5}\u{672c}a", ErrorCorrectionLevel::L).expect("encode");
+    assert_eq!(Mode::BYTE, mixed.getMode().unwrap());
+}
+
+#[test]
+fn test_encode_with_charset_eci() {
+    let content = "grinning \u{1f600}";
+    let decode = |qrCode: &QRCode| {
+        crate::qrcode::decoder::qrcode_decoder::decode_bitmatrix(
+            &qrCode.getMatrix().as_ref().unwrap().clone().into(),
+        )
+        .expect("decode")
+    };
+
+    let qrCode =
+        qrcode_encoder::encode_with_charset(content, ErrorCorrectionLevel::L, Some("UTF-8"))
+            .expect("encode");
+    let result = decode(&qrCode);
+    assert_eq!(content, result.getText());
+    // ECI mode 0111 and designator 26 for UTF-8, then byte mode 0100
+    let raw = result.getRawBytes();
+    assert_eq!(0x71, raw[0]);
+    assert_eq!(0xA4, raw[1]);
+
+    // The default character set needs no ECI
+    let qrCode = qrcode_encoder::encode_with_charset(
+        "caf\u{e9}",
+        ErrorCorrectionLevel::L,
+        Some("ISO-8859-1"),
+    )
+    .expect("encode");
+    let result = decode(&qrCode);
+    assert_eq!("caf\u{e9}", result.getText());
+    assert_eq!(0x40, result.getRawBytes()[0] & 0xF0);
+
+    assert!(qrcode_encoder::encode_with_charset(
+        content,
+        ErrorCorrectionLevel::L,
+        Some("no-such-charset")
+    )
+    .is_err());
+    assert!(qrcode_encoder::encode_with_charset(
+        content,
+        ErrorCorrectionLevel::L,
+        Some("ISO-8859-1")
+    )
+    .is_err());
+}
@@ -74,7 +74,7 @@ impl ByteMatrix {
 
     pub fn clear(&mut self, value: u8) {
         for row in self.bytes.iter_mut() {
-            *row = vec![value; row.len()];
+            row.fill(value);
         }
         // for (byte[] aByte : bytes) {
         //   Arrays.fill(aByte, value);
@@ -155,14 +155,13 @@ impl MinimalEncoder {
         if let Some(version) = version {
             // compute minimal encoding for a given version
             let result = self.encodeSpecificVersion(version)?;
-            if !qrcode_encoder::willFit(
-                result.getSize(),
-                Self::getVersion(Self::getVersionSize(result.getVersion())),
-                &self.ecLevel,
-            ) {
-                return Err(Exceptions::WriterException(Some(format!(
-                    "Data too big for version {version}"
-                ))));
+            let largest = Self::getVersion(Self::getVersionSize(result.getVersion()));
+            if !qrcode_encoder::willFit(result.getSize(), largest, &self.ecLevel) {
+                return Err(qrcode_encoder::capacityExceeded(
+                    result.getSize(),
+                    largest,
+                    &self.ecLevel,
+                ));
             }
             Ok(result)
         } else {
@@ -189,9 +188,11 @@ impl MinimalEncoder {
                 }
             }
             if smallestRXingResult < 0 {
-                return Err(Exceptions::WriterException(Some(
-                    "Data too big for any version".to_owned(),
-                )));
+                return Err(qrcode_encoder::capacityExceeded(
+                    results[2].getSize(),
+                    versions[2],
+                    &self.ecLevel,
+                ));
             }
             Ok(results[smallestRXingResult as usize].clone())
         }
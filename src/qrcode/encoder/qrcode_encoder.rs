@@ -17,7 +17,7 @@
  * @author satorux@google.com (Satoru Takabayashi) - creator
  * @author dswitkin@google.com (Daniel Switkin) - ported from C++
  */
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use encoding::EncodingRef;
 
@@ -30,7 +30,7 @@ use crate::{
         BitArray, CharacterSetECI,
     },
     qrcode::decoder::{ErrorCorrectionLevel, Mode, Version, VersionRef},
-    EncodeHintType, EncodeHintValue, EncodingHintDictionary, Exceptions,
+    CapacityExceeded, EncodeHintType, EncodeHintValue, EncodingHintDictionary, Exceptions,
 };
 
 use super::{mask_util, matrix_util, BlockPair, ByteMatrix, MinimalEncoder, QRCode};
@@ -70,11 +70,247 @@ pub fn encode(content: &str, ecLevel: ErrorCorrectionLevel) -> Result<QRCode, Ex
     encode_with_hints(content, ecLevel, &HashMap::new())
 }
 
+/**
+ * @param content text to encode
+ * @param ec_level error correction level to use
+ * @param charset the character set for byte mode, such as "UTF-8" or "Shift_JIS", or None to use
+ *   ISO-8859-1 where it can represent the content. A byte mode segment in any other character set
+ *   is preceded by an ECI designating it.
+ * @throws WriterException if the character set is unknown or can't represent the content, or if
+ *   encoding can't succeed otherwise
+ */
+pub fn encode_with_charset(
+    content: &str,
+    ec_level: ErrorCorrectionLevel,
+    charset: Option<&str>,
+) -> Result<QRCode, Exceptions> {
+    let mut hints = HashMap::new();
+    if let Some(charset) = charset {
+        // The WHATWG label "ISO-8859-1" resolves to windows-1252
+        let encoding = if charset.eq_ignore_ascii_case("ISO-8859-1") {
+            DEFAULT_BYTE_MODE_ENCODING
+        } else {
+            encoding::label::encoding_from_whatwg_label(charset).ok_or_else(|| {
+                Exceptions::WriterException(Some(format!("Unsupported character set: {charset}")))
+            })?
+        };
+        if encoding
+            .encode(content, encoding::EncoderTrap::Strict)
+            .is_err()
+        {
+            return Err(Exceptions::WriterException(Some(format!(
+                "{charset} can't encode the content"
+            ))));
+        }
+        // The default needs no ECI
+        if encoding.name() != DEFAULT_BYTE_MODE_ENCODING.name() {
+            hints.insert(
+                EncodeHintType::CHARACTER_SET,
+                EncodeHintValue::CharacterSet(charset.to_owned()),
+            );
+        }
+    }
+    encode_with_hints(content, ec_level, &hints)
+}
+
 pub fn encode_with_hints(
     content: &str,
     ec_level: ErrorCorrectionLevel,
     hints: &EncodingHintDictionary,
 ) -> Result<QRCode, Exceptions> {
+    let (mut qrCode, final_bits) = encodeBits(content, ec_level, hints)?;
+
+    let dimension = qrCode.getVersion().unwrap().getDimensionForVersion();
+    let mut matrix = ByteMatrix::new(dimension, dimension);
+    buildMaskedMatrix(&mut qrCode, &final_bits, hints, &mut matrix)?;
+    qrCode.setMatrix(matrix);
+
+    Ok(qrCode)
+}
+
+/**
+ * Encodes into a caller supplied matrix instead of allocating a new one, so that a buffer can be
+ * reused across many codes of the same version.
+ *
+ * @param content text to encode
+ * @param ec_level error correction level to use
+ * @param hints encoding hints, as for {@link #encode_with_hints}
+ * @param out matrix to write the modules into; its previous contents are overwritten
+ * @throws WriterException if encoding can't succeed, or if the dimensions of {@code out} do not
+ *   match the version chosen for the content
+ */
+pub fn encode_into(
+    content: &str,
+    ec_level: ErrorCorrectionLevel,
+    hints: &EncodingHintDictionary,
+    out: &mut ByteMatrix,
+) -> Result<(), Exceptions> {
+    let (mut qrCode, final_bits) = encodeBits(content, ec_level, hints)?;
+
+    let version = qrCode.getVersion().unwrap();
+    let dimension = version.getDimensionForVersion();
+    if out.getWidth() != dimension || out.getHeight() != dimension {
+        return Err(Exceptions::WriterException(Some(format!(
+            "Matrix is {}x{} but version {} requires {}x{}",
+            out.getWidth(),
+            out.getHeight(),
+            version.getVersionNumber(),
+            dimension,
+            dimension
+        ))));
+    }
+    buildMaskedMatrix(&mut qrCode, &final_bits, hints, out)
+}
+
+/**
+ * Splits content across several QR Codes linked by structured append, for payloads too long for
+ * a single symbol. Each symbol starts with a structured append header: its position in the
+ * sequence, the number of symbols and a parity byte, the XOR of all bytes of the content. The
+ * content is divided at character boundaries into as few symbols as it fits in, with about the
+ * same number of characters in each.
+ *
+ * @param content text to encode
+ * @param ec_level error correction level to use for every symbol
+ * @param max_symbols the most symbols to use, from 1 to 16
+ * @return the symbols, in sequence order
+ * @throws WriterException if the content doesn't fit in {@code max_symbols} symbols
+ */
+pub fn encode_structured_append(
+    content: &str,
+    ec_level: ErrorCorrectionLevel,
+    max_symbols: usize,
+) -> Result<Vec<QRCode>, Exceptions> {
+    encode_structured_append_with_hints(content, ec_level, max_symbols, &HashMap::new())
+}
+
+/**
+ * Like {@link #encode_structured_append}, honoring the CHARACTER_SET, QR_VERSION and
+ * QR_MASK_PATTERN hints; with QR_VERSION, every symbol has that version and the content is
+ * split into as many symbols as needed to fit it.
+ */
+pub fn encode_structured_append_with_hints(
+    content: &str,
+    ec_level: ErrorCorrectionLevel,
+    max_symbols: usize,
+    hints: &EncodingHintDictionary,
+) -> Result<Vec<QRCode>, Exceptions> {
+    if !(1..=16).contains(&max_symbols) {
+        return Err(Exceptions::WriterException(Some(format!(
+            "Structured append links 1 to 16 symbols, not {max_symbols}"
+        ))));
+    }
+
+    // Same choice of encoding as for a single symbol
+    let (encoding, has_encoding_hint) =
+        if let Some(EncodeHintValue::CharacterSet(v)) = hints.get(&EncodeHintType::CHARACTER_SET) {
+            let encoding = encoding::label::encoding_from_whatwg_label(v).ok_or_else(|| {
+                Exceptions::WriterException(Some(format!("Unsupported character set: {v}")))
+            })?;
+            (encoding, true)
+        } else {
+            chooseDefaultEncoding(content)
+        };
+    let mode = chooseModeWithEncoding(content, encoding);
+
+    // The parity is over the whole message, so it is the same in every symbol
+    let parity = encoding
+        .encode(content, encoding::EncoderTrap::Strict)
+        .map_err(|e| Exceptions::WriterException(Some(format!("{e}"))))?
+        .iter()
+        .fold(0, |parity, byte| parity ^ byte);
+
+    let version =
+        if let Some(EncodeHintValue::QrVersion(v)) = hints.get(&EncodeHintType::QR_VERSION) {
+            Some(Version::getVersionForNumber(v.parse().unwrap_or(0))?)
+        } else {
+            None
+        };
+
+    let chars: Vec<char> = content.chars().collect();
+    let mut last_error = None;
+    for total in 1..=max_symbols.min(chars.len().max(1)) {
+        let mut symbols = Vec::with_capacity(total);
+        for index in 0..total {
+            let part: String = chars
+                [chars.len() * index / total..chars.len() * (index + 1) / total]
+                .iter()
+                .collect();
+
+            let mut header_bits = BitArray::new();
+            appendModeInfo(Mode::STRUCTURED_APPEND, &mut header_bits)?;
+            header_bits.appendBits(index as u32, 4)?;
+            header_bits.appendBits(total as u32 - 1, 4)?;
+            header_bits.appendBits(parity as u32, 8)?;
+            if mode == Mode::BYTE && has_encoding_hint {
+                if let Some(eci) = CharacterSetECI::getCharacterSetECI(encoding) {
+                    appendECI(&eci, &mut header_bits)?;
+                }
+            }
+            appendModeInfo(mode, &mut header_bits)?;
+
+            let mut data_bits = BitArray::new();
+            appendBytes(&part, mode, &mut data_bits, encoding)?;
+
+            let symbol_version = match version {
+                Some(version) => {
+                    let bits_needed = calculateBitsNeeded(mode, &header_bits, &data_bits, version);
+                    if !willFit(bits_needed, version, &ec_level) {
+                        Err(capacityExceeded(bits_needed, version, &ec_level))
+                    } else {
+                        Ok(version)
+                    }
+                }
+                None => recommendVersion(&ec_level, mode, &header_bits, &data_bits),
+            };
+            let symbol_version = match symbol_version {
+                Ok(symbol_version) => symbol_version,
+                Err(e) => {
+                    last_error = Some(e);
+                    break;
+                }
+            };
+
+            let num_letters = if mode == Mode::BYTE {
+                data_bits.getSizeInBytes()
+            } else {
+                part.chars().count()
+            };
+            let mut header_and_data_bits = header_bits;
+            appendLengthInfo(
+                num_letters as u32,
+                symbol_version,
+                mode,
+                &mut header_and_data_bits,
+            )?;
+            header_and_data_bits.appendBitArray(data_bits);
+
+            let (mut qrCode, final_bits) =
+                finishBits(header_and_data_bits, mode, symbol_version, ec_level)?;
+            let dimension = symbol_version.getDimensionForVersion();
+            let mut matrix = ByteMatrix::new(dimension, dimension);
+            buildMaskedMatrix(&mut qrCode, &final_bits, hints, &mut matrix)?;
+            qrCode.setMatrix(matrix);
+            symbols.push(qrCode);
+        }
+        if symbols.len() == total {
+            return Ok(symbols);
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| Exceptions::WriterException(Some("Data too big".to_owned()))))
+}
+
+/**
+ * Does everything short of laying out the matrix: picks the mode and version, and produces the
+ * final interleaved data and error correction bits.
+ *
+ * @return a {@link QRCode} with mode, EC level and version set, and the bits to place in it
+ */
+fn encodeBits(
+    content: &str,
+    ec_level: ErrorCorrectionLevel,
+    hints: &EncodingHintDictionary,
+) -> Result<(QRCode, BitArray), Exceptions> {
     let version;
     let mut header_and_data_bits;
     let mode;
@@ -102,12 +338,27 @@ pub fn encode_with_hints(
     if has_encoding_hint {
         if let EncodeHintValue::CharacterSet(v) = hints.get(&EncodeHintType::CHARACTER_SET).unwrap()
         {
-            encoding = Some(encoding::label::encoding_from_whatwg_label(v).unwrap())
+            encoding = Some(
+                encoding::label::encoding_from_whatwg_label(v).ok_or_else(|| {
+                    Exceptions::WriterException(Some(format!("Unsupported character set: {v}")))
+                })?,
+            )
         }
         // encoding = encoding::label::encoding_from_whatwg_label(hints.get(&EncodeHintType::CHARACTER_SET).unwrap());
     }
 
-    if has_compaction_hint {
+    if let Some(EncodeHintValue::QrSegments(segments)) = hints.get(&EncodeHintType::QR_SEGMENTS) {
+        mode = Mode::BYTE;
+
+        (header_and_data_bits, version) = encodeSegments(
+            content,
+            segments,
+            encoding,
+            has_gs1_format_hint,
+            ec_level,
+            hints,
+        )?;
+    } else if has_compaction_hint {
         mode = Mode::BYTE;
 
         // dbg!("consider this a huge risk, not sure if it should be defaulting to default");
@@ -127,13 +378,10 @@ pub fn encode_with_hints(
         //Switch to default encoding
         let encoding = if let Some(encoding) = encoding {
             encoding
-        } else if let Ok(_encs) =
-            DEFAULT_BYTE_MODE_ENCODING.encode(content, encoding::EncoderTrap::Strict)
-        {
-            DEFAULT_BYTE_MODE_ENCODING
         } else {
-            has_encoding_hint = true;
-            encoding::all::UTF_8
+            let (encoding, needs_eci) = chooseDefaultEncoding(content);
+            has_encoding_hint = needs_eci;
+            encoding
         };
 
         // Pick an encoding mode appropriate for the content. Note that this will not attempt to use
@@ -182,9 +430,7 @@ pub fn encode_with_hints(
             version = Version::getVersionForNumber(versionNumber)?;
             let bitsNeeded = calculateBitsNeeded(mode, &header_bits, &data_bits, version);
             if !willFit(bitsNeeded, version, &ec_level) {
-                return Err(Exceptions::WriterException(Some(
-                    "Data too big for requested version".to_owned(),
-                )));
+                return Err(capacityExceeded(bitsNeeded, version, &ec_level));
             }
         } else {
             version = recommendVersion(&ec_level, mode, &header_bits, &data_bits)?;
@@ -203,6 +449,21 @@ pub fn encode_with_hints(
         header_and_data_bits.appendBitArray(data_bits);
     }
 
+    finishBits(header_and_data_bits, mode, version, ec_level)
+}
+
+/**
+ * Terminates and pads the header and data bits, and interleaves them with their error correction
+ * bytes.
+ *
+ * @return a {@link QRCode} with mode, EC level and version set, and the bits to place in it
+ */
+fn finishBits(
+    mut header_and_data_bits: BitArray,
+    mode: Mode,
+    version: VersionRef,
+    ec_level: ErrorCorrectionLevel,
+) -> Result<(QRCode, BitArray), Exceptions> {
     let ec_blocks = version.getECBlocksForLevel(ec_level);
     let num_data_bytes = version.getTotalCodewords() - ec_blocks.getTotalECCodewords();
 
@@ -223,9 +484,154 @@ pub fn encode_with_hints(
     qrCode.setMode(mode);
     qrCode.setVersion(version);
 
-    //  Choose the mask pattern and set to "qrCode".
-    let dimension = version.getDimensionForVersion();
-    let mut matrix = ByteMatrix::new(dimension, dimension);
+    Ok((qrCode, final_bits))
+}
+
+/**
+ * Encodes the content using the segmentation supplied with the QR_SEGMENTS hint, in the smallest
+ * version that fits unless a version was requested with QR_VERSION.
+ *
+ * @return the header and data bits, and the version they were laid out for
+ * @throws WriterException if the segments don't cover the content in order, if a segment's content
+ *   can't be represented in its mode, or if the data doesn't fit
+ */
+fn encodeSegments(
+    content: &str,
+    segments: &[(Mode, Range<usize>)],
+    encoding: Option<EncodingRef>,
+    is_gs1: bool,
+    ec_level: ErrorCorrectionLevel,
+    hints: &EncodingHintDictionary,
+) -> Result<(BitArray, VersionRef), Exceptions> {
+    let mut position = 0;
+    for (mode, range) in segments {
+        if range.start != position
+            || range.is_empty()
+            || !content.is_char_boundary(range.start)
+            || !content.is_char_boundary(range.end)
+        {
+            return Err(Exceptions::WriterException(Some(format!(
+                "Segment {range:?} does not continue the content at {position}"
+            ))));
+        }
+        let text = &content[range.clone()];
+        let valid = match mode {
+            Mode::NUMERIC => text.bytes().all(|c| c.is_ascii_digit()),
+            Mode::ALPHANUMERIC => text.bytes().all(|c| getAlphanumericCode(c as u32) != -1),
+            Mode::BYTE => true,
+            Mode::KANJI => isOnlyDoubleByteKanji(text),
+            _ => false,
+        };
+        if !valid {
+            return Err(Exceptions::WriterException(Some(format!(
+                "Segment {range:?} can't be encoded in mode {mode:?}"
+            ))));
+        }
+        position = range.end;
+    }
+    if position != content.len() {
+        return Err(Exceptions::WriterException(Some(format!(
+            "Segments end at {position} but the content is {} bytes long",
+            content.len()
+        ))));
+    }
+
+    // Byte segments use the requested encoding, else the default one if it can represent all of
+    // them, else UTF-8. Anything but the implicit default is announced with an ECI.
+    let byte_segments_fit = |encoding: EncodingRef| {
+        segments
+            .iter()
+            .filter(|(mode, _)| *mode == Mode::BYTE)
+            .all(|(_, range)| {
+                encoding
+                    .encode(&content[range.clone()], encoding::EncoderTrap::Strict)
+                    .is_ok()
+            })
+    };
+    let (encoding, needs_eci) = match encoding {
+        Some(encoding) => (encoding, true),
+        None if byte_segments_fit(DEFAULT_BYTE_MODE_ENCODING) => {
+            (DEFAULT_BYTE_MODE_ENCODING, false)
+        }
+        None => (encoding::all::UTF_8 as EncodingRef, true),
+    };
+
+    let mut header_bits = BitArray::new();
+    if needs_eci && segments.iter().any(|(mode, _)| *mode == Mode::BYTE) {
+        if let Some(eci) = CharacterSetECI::getCharacterSetECI(encoding) {
+            appendECI(&eci, &mut header_bits)?;
+        }
+    }
+    if is_gs1 {
+        appendModeInfo(Mode::FNC1_FIRST_POSITION, &mut header_bits)?;
+    }
+
+    // The data of each segment doesn't depend on the version, only the length fields do.
+    let mut segment_data = Vec::with_capacity(segments.len());
+    for (mode, range) in segments {
+        let text = &content[range.clone()];
+        let mut data_bits = BitArray::new();
+        appendBytes(text, *mode, &mut data_bits, encoding)?;
+        let num_letters = if *mode == Mode::BYTE {
+            data_bits.getSizeInBytes()
+        } else {
+            text.chars().count()
+        };
+        segment_data.push((*mode, num_letters as u32, data_bits));
+    }
+    let bits_for_version = |version: VersionRef| -> Result<BitArray, Exceptions> {
+        let mut bits = header_bits.clone();
+        for (mode, num_letters, data_bits) in &segment_data {
+            appendModeInfo(*mode, &mut bits)?;
+            appendLengthInfo(*num_letters, version, *mode, &mut bits)?;
+            bits.appendBitArray(data_bits.clone());
+        }
+        Ok(bits)
+    };
+
+    if let Some(EncodeHintValue::QrVersion(v)) = hints.get(&EncodeHintType::QR_VERSION) {
+        let version = Version::getVersionForNumber(v.parse::<u32>().unwrap_or(0))?;
+        let bits = bits_for_version(version)?;
+        if !willFit(bits.getSize() as u32, version, &ec_level) {
+            return Err(capacityExceeded(bits.getSize() as u32, version, &ec_level));
+        }
+        return Ok((bits, version));
+    }
+
+    // Length fields only change size at versions 10 and 27, so one layout per range is enough.
+    let mut bits_needed = None;
+    for (first, last) in [(1, 9), (10, 26), (27, 40)] {
+        let Ok(bits) = bits_for_version(Version::getVersionForNumber(last)?) else {
+            continue;
+        };
+        bits_needed = Some(bits.getSize() as u32);
+        for number in first..=last {
+            let version = Version::getVersionForNumber(number)?;
+            if willFit(bits.getSize() as u32, version, &ec_level) {
+                return Ok((bits, version));
+            }
+        }
+    }
+    Err(match bits_needed {
+        Some(bits_needed) => {
+            capacityExceeded(bits_needed, Version::getVersionForNumber(40)?, &ec_level)
+        }
+        // Even the largest versions' length fields can't count a segment
+        None => Exceptions::WriterException(Some("Data too big".to_owned())),
+    })
+}
+
+/**
+ * Chooses the mask pattern, sets it on {@code qrCode}, and lays out the final matrix.
+ */
+fn buildMaskedMatrix(
+    qrCode: &mut QRCode,
+    final_bits: &BitArray,
+    hints: &EncodingHintDictionary,
+    matrix: &mut ByteMatrix,
+) -> Result<(), Exceptions> {
+    let version = qrCode.getVersion().unwrap();
+    let ec_level = qrCode.getECLevel().unwrap();
 
     // Enable manual selection of the pattern to be used via hint
     let mut mask_pattern = -1;
@@ -250,15 +656,12 @@ pub fn encode_with_hints(
     }
 
     if mask_pattern == -1 {
-        mask_pattern = chooseMaskPattern(&final_bits, &ec_level, version, &mut matrix)? as i32;
+        mask_pattern = chooseMaskPattern(final_bits, &ec_level, version, matrix)? as i32;
     }
     qrCode.setMaskPattern(mask_pattern);
 
-    // Build the matrix and set it to "qrCode".
-    matrix_util::buildMatrix(&final_bits, &ec_level, version, mask_pattern, &mut matrix)?;
-    qrCode.setMatrix(matrix);
-
-    Ok(qrCode)
+    // Build the matrix.
+    matrix_util::buildMatrix(final_bits, &ec_level, version, mask_pattern, matrix)
 }
 
 /**
@@ -281,7 +684,9 @@ fn recommendVersion(
         data_bits,
         Version::getVersionForNumber(1)?,
     );
-    let provisional_version = chooseVersion(provisional_bits_needed, ec_level)?;
+    // If even that doesn't fit, measure it for the largest version to report how much is missing
+    let provisional_version = chooseVersion(provisional_bits_needed, ec_level)
+        .or_else(|_| Version::getVersionForNumber(40))?;
 
     // Use that guess to calculate the right version. I am still not sure this works in 100% of cases.
     let bits_needed = calculateBitsNeeded(mode, header_bits, data_bits, provisional_version);
@@ -348,6 +753,31 @@ fn chooseModeWithEncoding(content: &str, encoding: EncodingRef) -> Mode {
     Mode::BYTE
 }
 
+/**
+ * Picks the encoding for content when none was requested: ISO-8859-1 if it can represent the
+ * content, else Shift_JIS if every character is a Kanji mode character, which takes 13 bits
+ * each instead of the 24 of UTF-8, and else UTF-8.
+ *
+ * @return the encoding, and whether an ECI must announce it in byte mode
+ */
+fn chooseDefaultEncoding(content: &str) -> (EncodingRef, bool) {
+    if DEFAULT_BYTE_MODE_ENCODING
+        .encode(content, encoding::EncoderTrap::Strict)
+        .is_ok()
+    {
+        (DEFAULT_BYTE_MODE_ENCODING, false)
+    } else if isOnlyDoubleByteKanji(content) {
+        // Kanji mode is always Shift_JIS, so it needs no ECI
+        (*SHIFT_JIS_CHARSET, false)
+    } else {
+        (encoding::all::UTF_8, true)
+    }
+}
+
+/**
+ * @return true if every character of content is a double byte Shift_JIS character in the
+ *  0x8140 to 0x9FFC or 0xE040 to 0xEBBF ranges, which Kanji mode can encode
+ */
 pub fn isOnlyDoubleByteKanji(content: &str) -> bool {
     let bytes = if let Ok(byt) = SHIFT_JIS_CHARSET.encode(content, encoding::EncoderTrap::Strict) {
         byt
@@ -361,8 +791,8 @@ pub fn isOnlyDoubleByteKanji(content: &str) -> bool {
     let mut i = 0;
     while i < length {
         // for (int i = 0; i < length; i += 2) {
-        let byte1 = bytes[i];
-        if !(0x81..=0x9F).contains(&byte1) && !(0xE0..=0xEB).contains(&byte1) {
+        let code = ((bytes[i] as u16) << 8) | bytes[i + 1] as u16;
+        if !(0x8140..=0x9FFC).contains(&code) && !(0xE040..=0xEBBF).contains(&code) {
             return false;
         }
         i += 2;
@@ -381,9 +811,9 @@ fn chooseMaskPattern(
     // We try all mask patterns to choose the best one.
     for maskPattern in 0..QRCode::NUM_MASK_PATTERNS {
         // for (int maskPattern = 0; maskPattern < QRCode.NUM_MASK_PATTERNS; maskPattern++) {
-        let mut matrix = matrix.clone();
-        matrix_util::buildMatrix(bits, ec_level, version, maskPattern, &mut matrix)?;
-        let penalty = calculateMaskPenalty(&matrix);
+        // buildMatrix() clears the matrix first, so each candidate can reuse the same buffer
+        matrix_util::buildMatrix(bits, ec_level, version, maskPattern, matrix)?;
+        let penalty = calculateMaskPenalty(matrix);
         if penalty < min_penalty {
             min_penalty = penalty;
             best_mask_pattern = maskPattern;
@@ -403,7 +833,11 @@ fn chooseVersion(
             return Ok(version);
         }
     }
-    Err(Exceptions::WriterException(Some("Data too big".to_owned())))
+    Err(capacityExceeded(
+        numInputBits,
+        Version::getVersionForNumber(40)?,
+        ecLevel,
+    ))
 }
 
 /**
@@ -423,15 +857,61 @@ pub fn willFit(numInputBits: u32, version: VersionRef, ecLevel: &ErrorCorrection
     num_data_bytes >= total_input_bytes
 }
 
+/**
+ * @return how many characters of the given mode fit in a code with the specified version and
+ * error correction level, as a single segment, or None for modes which don't encode characters
+ */
+pub fn getMaxCharacterCount(
+    version: VersionRef,
+    ecLevel: &ErrorCorrectionLevel,
+    mode: Mode,
+) -> Option<u32> {
+    let num_data_bytes =
+        version.getTotalCodewords() - version.getECBlocksForLevel(*ecLevel).getTotalECCodewords();
+    let count_bits = mode.getCharacterCountBits(version) as u32;
+    let bits = num_data_bytes * 8 - 4 - count_bits;
+    let characters = match mode {
+        // 3 digits in 10 bits, then 2 in 7 or 1 in 4
+        Mode::NUMERIC => bits / 10 * 3 + [0, 0, 0, 0, 1, 1, 1, 2, 2, 2][(bits % 10) as usize],
+        // 2 characters in 11 bits, then 1 in 6
+        Mode::ALPHANUMERIC => bits / 11 * 2 + u32::from(bits % 11 >= 6),
+        Mode::BYTE => bits / 8,
+        Mode::KANJI => bits / 13,
+        _ => return None,
+    };
+    // The character count field must be able to hold the count
+    Some(characters.min((1 << count_bits) - 1))
+}
+
+/**
+ * @return the error for a number of input bits which doesn't fit in a code with the specified
+ * version and error correction level, carrying both the bits needed and the bits available
+ */
+pub fn capacityExceeded(
+    numInputBits: u32,
+    version: VersionRef,
+    ecLevel: &ErrorCorrectionLevel,
+) -> Exceptions {
+    let num_data_bytes =
+        version.getTotalCodewords() - version.getECBlocksForLevel(*ecLevel).getTotalECCodewords();
+    Exceptions::CapacityExceededException(Some(CapacityExceeded {
+        required_bits: numInputBits,
+        available_bits: num_data_bytes * 8,
+    }))
+}
+
 /**
  * Terminate bits as described in 8.4.8 and 8.4.9 of JISX0510:2004 (p.24).
  */
 pub fn terminateBits(num_data_bytes: u32, bits: &mut BitArray) -> Result<(), Exceptions> {
     let capacity = num_data_bytes * 8;
     if bits.getSize() > capacity as usize {
-        return Err(Exceptions::WriterException(Some(format!(
-            "data bits cannot fit in the QR Code{capacity} > "
-        ))));
+        return Err(Exceptions::CapacityExceededException(Some(
+            CapacityExceeded {
+                required_bits: bits.getSize() as u32,
+                available_bits: capacity,
+            },
+        )));
         // throw new WriterException("data bits cannot fit in the QR Code" + bits.getSize() + " > " +
         //     capacity);
     }
@@ -778,7 +1258,12 @@ pub fn append8BitBytes(
 ) -> Result<(), Exceptions> {
     let bytes = encoding
         .encode(content, encoding::EncoderTrap::Strict)
-        .expect("should encode");
+        .map_err(|e| {
+            Exceptions::WriterException(Some(format!(
+                "{} can't encode the content: {e}",
+                encoding.name()
+            )))
+        })?;
     // let bytes = content.getBytes(encoding);
     for b in bytes {
         // for (byte b : bytes) {
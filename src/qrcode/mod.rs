@@ -11,3 +11,6 @@ pub use qr_code_writer::*;
 #[cfg(test)]
 #[cfg(feature = "image")]
 mod QRCodeWriterTestCase;
+
+#[cfg(test)]
+mod qr_code_reader_testcase;
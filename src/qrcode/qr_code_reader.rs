@@ -14,19 +14,44 @@
  * limitations under the License.
  */
 
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
 use crate::{
-    common::{BitMatrix, DecoderRXingResult, DetectorRXingResult},
-    BarcodeFormat, DecodeHintType, Exceptions, RXingResult, RXingResultMetadataType,
-    RXingResultMetadataValue, RXingResultPoint, Reader,
+    common::{
+        gradePrintQuality, measureSymbolContrast, BitMatrix, DecoderRXingResult,
+        DetectorRXingResult,
+    },
+    result_point_utils, BarcodeFormat, DecodeHintType, DecodeHintValue, Exceptions, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint, Reader, ResultPoint,
 };
 
 use super::{
     decoder::{qrcode_decoder, QRCodeDecoderMetaData},
-    detector::Detector,
+    detector::{Detector, QRCodeDetectorResult},
 };
 
+/** Quiet zone, in modules, kept around the symbol when retrying on a cropped image. */
+const CROP_MARGIN_MODULES: f32 = 2.0;
+
+/**
+ * Images with at least this many pixels are first searched at a lower resolution, unless
+ * overridden with {@link DecodeHintType#QR_COARSE_DETECTION_MIN_PIXELS}.
+ */
+const COARSE_DETECTION_MIN_PIXELS: usize = 4_000_000;
+
+/**
+ * Approximate number of pixels in the scaled down image searched first. Modules of a symbol need
+ * to stay about a pixel wide at this size to be found, which is why the coarse search falls back
+ * to native resolution when it fails.
+ */
+const COARSE_DETECTION_TARGET_PIXELS: usize = 1_000_000;
+
+/**
+ * Margin, in modules, kept around a symbol located at low resolution. It is generous, as the
+ * location is only approximate.
+ */
+const COARSE_MARGIN_MODULES: f32 = 4.0;
+
 /**
  * This implementation can detect and decode QR Codes in an image.
  *
@@ -66,12 +91,46 @@ impl Reader for QRCodeReader {
             let bits = Self::extractPureBits(image.getBlackMatrix())?;
             decoderRXingResult = qrcode_decoder::decode_bitmatrix_with_hints(&bits, hints)?;
             points = Vec::new();
+        } else if let Some((coarseResult, coarsePoints)) = Self::decodeCoarseToFine(image, hints) {
+            decoderRXingResult = coarseResult;
+            points = coarsePoints;
         } else {
             let detectorRXingResult =
                 Detector::new(image.getBlackMatrix()).detect_with_hints(hints)?;
-            decoderRXingResult =
-                qrcode_decoder::decode_bitmatrix_with_hints(detectorRXingResult.getBits(), hints)?;
-            points = detectorRXingResult.getPoints().to_vec();
+            match qrcode_decoder::decode_bitmatrix_with_hints(detectorRXingResult.getBits(), hints)
+            {
+                Ok(result) => {
+                    decoderRXingResult = result;
+                    points = detectorRXingResult.getPoints().to_vec();
+                }
+                Err(e) => {
+                    // The finder patterns were found, so when trying harder, retry on a tight
+                    // crop around them, leaving out any clutter that may have skewed the
+                    // binarization.
+                    let cropped = if hints.contains_key(&DecodeHintType::TRY_HARDER) {
+                        Self::decodeCropped(
+                            image,
+                            detectorRXingResult.getPoints(),
+                            detectorRXingResult.getBits().getWidth(),
+                            CROP_MARGIN_MODULES,
+                            hints,
+                        )
+                    } else {
+                        None
+                    };
+                    match cropped {
+                        Some(cropped) => (decoderRXingResult, points) = cropped,
+                        None if matches!(
+                            hints.get(&DecodeHintType::RETURN_DETECTION_ON_FAILURE),
+                            Some(DecodeHintValue::ReturnDetectionOnFailure(true))
+                        ) =>
+                        {
+                            return Ok(Self::detectionResult(&detectorRXingResult, e));
+                        }
+                        None => return Err(e),
+                    }
+                }
+            }
         }
 
         // If the code was mirrored: swap the bottom-left and the top-right points.
@@ -99,6 +158,27 @@ impl Reader for QRCodeReader {
                 RXingResultMetadataValue::ByteSegments(byteSegments.clone()),
             );
         }
+        let shiftJisBytes = decoderRXingResult.getShiftJisBytes();
+        if !shiftJisBytes.is_empty() {
+            result.putMetadata(
+                RXingResultMetadataType::SHIFT_JIS_BYTES,
+                RXingResultMetadataValue::ShiftJisBytes(shiftJisBytes.clone()),
+            );
+        }
+        let decodedSegments = decoderRXingResult.getQRDecodedSegments();
+        if !decodedSegments.is_empty() {
+            result.putMetadata(
+                RXingResultMetadataType::QR_DECODED_SEGMENTS,
+                RXingResultMetadataValue::QrDecodedSegments(decodedSegments.clone()),
+            );
+        }
+        let dataCodewords = decoderRXingResult.getDataCodewords();
+        if !dataCodewords.is_empty() {
+            result.putMetadata(
+                RXingResultMetadataType::DATA_CODEWORDS,
+                RXingResultMetadataValue::DataCodewords(dataCodewords.clone()),
+            );
+        }
         let ecLevel = decoderRXingResult.getECLevel();
         if !ecLevel.is_empty() {
             result.putMetadata(
@@ -120,6 +200,30 @@ impl Reader for QRCodeReader {
                 ),
             );
         }
+        let mirrored = decoderRXingResult.getIsMirrored();
+        if mirrored {
+            result.putMetadata(
+                RXingResultMetadataType::IS_MIRRORED,
+                RXingResultMetadataValue::IsMirrored(mirrored),
+            );
+        }
+        if let Some(timingPatternMismatches) = decoderRXingResult.getTimingPatternMismatches() {
+            result.putMetadata(
+                RXingResultMetadataType::QR_TIMING_PATTERN_MISMATCHES,
+                RXingResultMetadataValue::QrTimingPatternMismatches(timingPatternMismatches),
+            );
+        }
+        if matches!(
+            hints.get(&DecodeHintType::PRINT_QUALITY_GRADE),
+            Some(DecodeHintValue::PrintQualityGrade(true))
+        ) {
+            let grade =
+                Self::printQualityGrade(image, result.getRXingResultPoints(), &decoderRXingResult);
+            result.putMetadata(
+                RXingResultMetadataType::PRINT_QUALITY_GRADE,
+                RXingResultMetadataValue::PrintQualityGrade(grade),
+            );
+        }
         result.putMetadata(
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier(format!(
@@ -141,6 +245,199 @@ impl QRCodeReader {
         Self {}
     }
 
+    /**
+     * Grades the print quality of a decoded symbol, see
+     * {@link RXingResultMetadataType#PRINT_QUALITY_GRADE}. The contrast is measured between the
+     * finder patterns, or over the whole image when there are no points, as for a pure barcode.
+     */
+    fn printQualityGrade(
+        image: &crate::BinaryBitmap,
+        points: &[RXingResultPoint],
+        decoderRXingResult: &DecoderRXingResult,
+    ) -> char {
+        let (left, top, right, bottom) = if points.len() >= 3 {
+            let minX = points.iter().map(|p| p.getX()).fold(f32::MAX, f32::min);
+            let minY = points.iter().map(|p| p.getY()).fold(f32::MAX, f32::min);
+            let maxX = points.iter().map(|p| p.getX()).fold(f32::MIN, f32::max);
+            let maxY = points.iter().map(|p| p.getY()).fold(f32::MIN, f32::max);
+            (
+                minX.max(0.0) as usize,
+                minY.max(0.0) as usize,
+                maxX.max(0.0) as usize + 1,
+                maxY.max(0.0) as usize + 1,
+            )
+        } else {
+            (0, 0, image.getWidth(), image.getHeight())
+        };
+        let symbolContrast =
+            measureSymbolContrast(image.getLuminanceSource(), left, top, right, bottom);
+        gradePrintQuality(
+            symbolContrast,
+            decoderRXingResult.getFixedPatternDamage().unwrap_or(0.0),
+            decoderRXingResult.getUnusedErrorCorrection().unwrap_or(1.0),
+        )
+    }
+
+    /**
+     * Builds the partial result returned for a symbol which was located but couldn't be decoded,
+     * see {@link DecodeHintType#RETURN_DETECTION_ON_FAILURE}. It has no text.
+     */
+    fn detectionResult(detection: &QRCodeDetectorResult, error: Exceptions) -> RXingResult {
+        let mut result = RXingResult::new(
+            "",
+            Vec::new(),
+            detection.getPoints().to_vec(),
+            BarcodeFormat::QR_CODE,
+        );
+        result.putMetadata(
+            RXingResultMetadataType::DETECTION_BOUNDS,
+            RXingResultMetadataValue::DetectionBounds(detection.getBounds().to_vec()),
+        );
+        result.putMetadata(
+            RXingResultMetadataType::DECODE_FAILURE,
+            RXingResultMetadataValue::DecodeFailure(error.to_string()),
+        );
+        result
+    }
+
+    /**
+     * On images of at least COARSE_DETECTION_MIN_PIXELS pixels, or as many as
+     * {@link DecodeHintType#QR_COARSE_DETECTION_MIN_PIXELS} asks for, first locates the symbol on a copy
+     * scaled down to around COARSE_DETECTION_TARGET_PIXELS pixels, then detects and decodes it
+     * from a full resolution crop of that region. Only the crop is binarized at full resolution,
+     * which is much cheaper than binarizing a multi-megapixel photo in its entirety.
+     *
+     * @return the decoded result and its points, or None if the image is too small for this, or
+     *  the symbol couldn't be found or decoded this way
+     */
+    pub(crate) fn decodeCoarseToFine(
+        image: &mut crate::BinaryBitmap,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Option<(DecoderRXingResult, Vec<RXingResultPoint>)> {
+        let minPixels = match hints.get(&DecodeHintType::QR_COARSE_DETECTION_MIN_PIXELS) {
+            Some(DecodeHintValue::QrCoarseDetectionMinPixels(minPixels)) => *minPixels as usize,
+            _ => COARSE_DETECTION_MIN_PIXELS,
+        };
+        let pixels = image.getWidth() * image.getHeight();
+        if pixels < minPixels || !image.isCropSupported() {
+            return None;
+        }
+        let factor = (pixels as f64 / COARSE_DETECTION_TARGET_PIXELS as f64)
+            .sqrt()
+            .ceil() as usize;
+
+        // Points found on the scaled down copy would be in the wrong coordinates for a callback.
+        let mut coarseHints = hints.clone();
+        coarseHints.remove(&DecodeHintType::NEED_RESULT_POINT_CALLBACK);
+        let mut coarse = image.scaleDown(factor);
+        let coarseDetection = Detector::new(coarse.getBlackMatrix())
+            .detect_with_hints(&coarseHints)
+            .ok()?;
+        let points: Vec<RXingResultPoint> = coarseDetection
+            .getPoints()
+            .iter()
+            .map(|p| RXingResultPoint::new(p.getX() * factor as f32, p.getY() * factor as f32))
+            .collect();
+
+        Self::decodeCropped(
+            image,
+            &points,
+            coarseDetection.getBits().getWidth(),
+            COARSE_MARGIN_MODULES,
+            hints,
+        )
+    }
+
+    /**
+     * Crops the image to the quad spanned by the given finder pattern centers, plus a margin
+     * around the symbol, and runs detection and decoding again on that region alone. Result
+     * points are mapped back into the coordinates of the full image.
+     *
+     * @param points bottom left, top left and top right finder pattern centers, as detected
+     * @param dimension the number of modules on a side of the detected symbol
+     * @param marginModules how many modules to keep around the symbol
+     * @return the decoded result and its points, or None if the retry failed as well
+     */
+    fn decodeCropped(
+        image: &mut crate::BinaryBitmap,
+        points: &[RXingResultPoint],
+        dimension: u32,
+        marginModules: f32,
+        hints: &crate::DecodingHintDictionary,
+    ) -> Option<(DecoderRXingResult, Vec<RXingResultPoint>)> {
+        if !image.isCropSupported() || points.len() < 3 {
+            return None;
+        }
+        let (bottomLeft, topLeft, topRight) = (points[0], points[1], points[2]);
+        // The fourth corner is not detected, so complete the parallelogram.
+        let bottomRight = RXingResultPoint::new(
+            topRight.getX() + bottomLeft.getX() - topLeft.getX(),
+            topRight.getY() + bottomLeft.getY() - topLeft.getY(),
+        );
+        let corners = [bottomLeft, topLeft, topRight, bottomRight];
+
+        // Finder pattern centers sit 3.5 modules in from the edge of the symbol.
+        let dimension = dimension as f32;
+        let moduleSize = (result_point_utils::distance(&topLeft, &topRight)
+            + result_point_utils::distance(&topLeft, &bottomLeft))
+            / 2.0
+            / (dimension - 7.0);
+        let margin = (3.5 + marginModules) * moduleSize;
+
+        let width = image.getWidth() as f32;
+        let height = image.getHeight() as f32;
+        let left = (corners.iter().map(|p| p.getX()).fold(f32::MAX, f32::min) - margin).max(0.0);
+        let top = (corners.iter().map(|p| p.getY()).fold(f32::MAX, f32::min) - margin).max(0.0);
+        let right = (corners.iter().map(|p| p.getX()).fold(f32::MIN, f32::max) + margin).min(width);
+        let bottom =
+            (corners.iter().map(|p| p.getY()).fold(f32::MIN, f32::max) + margin).min(height);
+        if right <= left || bottom <= top {
+            return None;
+        }
+        let (left, top) = (left as usize, top as usize);
+        let (cropWidth, cropHeight) = (right as usize - left, bottom as usize - top);
+        if cropWidth == image.getWidth() && cropHeight == image.getHeight() {
+            // Nothing would be cropped away, so the retry can't do any better.
+            return None;
+        }
+
+        // A callback expects points in the coordinates of the full image, not of the crop.
+        let mut croppedHints = hints.clone();
+        if let Some(DecodeHintValue::NeedResultPointCallback(callback)) =
+            hints.get(&DecodeHintType::NEED_RESULT_POINT_CALLBACK)
+        {
+            let callback = callback.clone();
+            let (offsetX, offsetY) = (left as f32, top as f32);
+            croppedHints.insert(
+                DecodeHintType::NEED_RESULT_POINT_CALLBACK,
+                DecodeHintValue::NeedResultPointCallback(Rc::new(
+                    move |point: &dyn ResultPoint| {
+                        callback(&RXingResultPoint::new(
+                            point.getX() + offsetX,
+                            point.getY() + offsetY,
+                        ))
+                    },
+                )),
+            );
+        }
+
+        let mut cropped = image.crop(left, top, cropWidth, cropHeight);
+        let detectorRXingResult = Detector::new(cropped.getBlackMatrix())
+            .detect_with_hints(&croppedHints)
+            .ok()?;
+        let decoderRXingResult = qrcode_decoder::decode_bitmatrix_with_hints(
+            detectorRXingResult.getBits(),
+            &croppedHints,
+        )
+        .ok()?;
+        let points = detectorRXingResult
+            .getPoints()
+            .iter()
+            .map(|p| RXingResultPoint::new(p.getX() + left as f32, p.getY() + top as f32))
+            .collect();
+        Some((decoderRXingResult, points))
+    }
+
     /**
      * This method detects a code in a "pure" image -- that is, pure monochrome image
      * which contains only an unrotated, unskewed, image of a code, with some white border
@@ -0,0 +1,836 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use crate::{
+    common::{BitArray, BitMatrix, DetectorRXingResult, GlobalHistogramBinarizer, HybridBinarizer},
+    multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+    oned::Code128Writer,
+    qrcode::{
+        decoder::{qrcode_decoder, ErrorCorrectionLevel, Mode},
+        detector::Detector,
+        encoder::{matrix_util, qrcode_encoder, ByteMatrix},
+    },
+    shared_test_methods::{bitmap_for, render, render_bitmap, render_luma, unreadable_qr_pixels},
+    BarcodeFormat, BinaryBitmap, DecodeHintType, DecodeHintValue, DecodingHintDictionary,
+    EncodeHintType, EncodeHintValue, Exceptions, Luma8LuminanceSource, LuminanceSource,
+    MultiFormatReader, RXingResultMetadataType, RXingResultMetadataValue, RXingResultPoint, Reader,
+    ResultPoint, Writer,
+};
+
+use super::{QRCodeReader, QRCodeWriter};
+
+fn encode(contents: &str) -> BitMatrix {
+    let hints = HashMap::from([(
+        EncodeHintType::MARGIN,
+        EncodeHintValue::Margin("4".to_owned()),
+    )]);
+    QRCodeWriter
+        .encode_with_hints(contents, &BarcodeFormat::QR_CODE, 116, 116, &hints)
+        .expect("encode")
+}
+
+#[test]
+fn test_mirrored_read_sets_metadata() {
+    let mut matrix = encode("http://www.example.com/mirror");
+    matrix.mirror();
+
+    let hints = HashMap::from([(
+        DecodeHintType::PURE_BARCODE,
+        DecodeHintValue::PureBarcode(true),
+    )]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&matrix), &hints)
+        .expect("decode");
+    assert_eq!("http://www.example.com/mirror", result.getText());
+    assert_eq!(
+        Some(&RXingResultMetadataValue::IsMirrored(true)),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::IS_MIRRORED)
+    );
+}
+
+#[test]
+fn test_normal_read_has_no_mirror_metadata() {
+    let matrix = encode("http://www.example.com/mirror");
+
+    let hints = HashMap::from([(
+        DecodeHintType::PURE_BARCODE,
+        DecodeHintValue::PureBarcode(true),
+    )]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&matrix), &hints)
+        .expect("decode");
+    assert_eq!("http://www.example.com/mirror", result.getText());
+    assert!(!result
+        .getRXingResultMetadata()
+        .contains_key(&RXingResultMetadataType::IS_MIRRORED));
+}
+
+#[test]
+fn test_clean_read_has_no_timing_pattern_mismatches() {
+    let matrix = encode("http://www.example.com/timing");
+
+    let result = QRCodeReader
+        .decode(&mut bitmap_for(&matrix))
+        .expect("decode");
+    assert_eq!(
+        Some(&RXingResultMetadataValue::QrTimingPatternMismatches(0)),
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::QR_TIMING_PATTERN_MISMATCHES)
+    );
+}
+
+#[test]
+fn test_data_codewords_reencode_to_same_symbol() {
+    let contents = "DATA CODEWORDS 0123456789";
+    let hints = HashMap::from([(
+        DecodeHintType::PURE_BARCODE,
+        DecodeHintValue::PureBarcode(true),
+    )]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&encode(contents)), &hints)
+        .expect("decode");
+    let Some(RXingResultMetadataValue::DataCodewords(codewords)) = result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::DATA_CODEWORDS)
+    else {
+        panic!("missing data codewords");
+    };
+
+    // Interleave the codewords with freshly computed EC codewords and lay out a matrix with the
+    // same version, level and mask as the encoder chose
+    let expected = qrcode_encoder::encode(contents, ErrorCorrectionLevel::L).expect("encode");
+    let version = expected.getVersion().unwrap();
+    let ec_level = expected.getECLevel().unwrap();
+    let ec_blocks = version.getECBlocksForLevel(ec_level);
+    let mut data_bits = BitArray::new();
+    for codeword in codewords {
+        data_bits.appendBits(*codeword, 8).expect("append");
+    }
+    let final_bits = qrcode_encoder::interleaveWithECBytes(
+        &data_bits,
+        version.getTotalCodewords(),
+        codewords.len() as u32,
+        ec_blocks.getNumBlocks(),
+    )
+    .expect("interleave");
+    let dimension = version.getDimensionForVersion();
+    let mut matrix = ByteMatrix::new(dimension, dimension);
+    matrix_util::buildMatrix(
+        &final_bits,
+        &ec_level,
+        version,
+        expected.getMaskPattern(),
+        &mut matrix,
+    )
+    .expect("build");
+    assert_eq!(expected.getMatrix().as_ref().unwrap(), &matrix);
+}
+
+const CLUTTERED_SIZE: u32 = 600;
+const CLUTTERED_MODULE: u32 = 4;
+const CLUTTERED_OFFSET: u32 = 260;
+const CLUTTERED_CONTENTS: &str = "crop and retry";
+
+/**
+ * A symbol in a cluttered image, which only decodes from a crop around its finder patterns. The
+ * symbol is at CLUTTERED_OFFSET in both directions, with modules of CLUTTERED_MODULE pixels.
+ */
+fn cluttered_qr_image() -> BinaryBitmap {
+    const SIZE: u32 = CLUTTERED_SIZE;
+    const MODULE: u32 = CLUTTERED_MODULE;
+    const OFFSET: u32 = CLUTTERED_OFFSET;
+
+    let hints = HashMap::from([(
+        EncodeHintType::MARGIN,
+        EncodeHintValue::Margin("0".to_owned()),
+    )]);
+    let code = QRCodeWriter
+        .encode_with_hints(CLUTTERED_CONTENTS, &BarcodeFormat::QR_CODE, 0, 0, &hints)
+        .expect("encode");
+    let dimension = code.getWidth();
+    let in_finder =
+        |x: u32, y: u32| (x < 7 || x >= dimension - 7) && y < 7 || x < 7 && y >= dimension - 7;
+
+    // A dark texture fills the image outside of a 4 module quiet zone, and everything but the
+    // finder patterns is printed in faded ink. Binarizing the whole image puts the threshold
+    // between the texture tones, which drops the faded modules; the symbol alone binarizes fine.
+    let quiet_start = OFFSET - 4 * MODULE;
+    let quiet_end = OFFSET + (dimension + 4) * MODULE;
+    let mut pixels = Vec::with_capacity((SIZE * SIZE) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let in_quiet_zone =
+                (quiet_start..quiet_end).contains(&x) && (quiet_start..quiet_end).contains(&y);
+            let in_code = (OFFSET..OFFSET + dimension * MODULE).contains(&x)
+                && (OFFSET..OFFSET + dimension * MODULE).contains(&y);
+            let pixel = if in_code {
+                let (mx, my) = ((x - OFFSET) / MODULE, (y - OFFSET) / MODULE);
+                if !code.get(mx, my) {
+                    255
+                } else if in_finder(mx, my) {
+                    0
+                } else {
+                    110
+                }
+            } else if in_quiet_zone {
+                255
+            } else if (x / 2 + y / 2) % 4 == 0 {
+                0
+            } else {
+                90
+            };
+            pixels.push(pixel);
+        }
+    }
+
+    BinaryBitmap::new(Rc::new(GlobalHistogramBinarizer::new(Box::new(
+        Luma8LuminanceSource::new(pixels, SIZE, SIZE),
+    ))))
+}
+
+#[test]
+fn test_crop_and_retry_in_cluttered_image() {
+    const MODULE: u32 = CLUTTERED_MODULE;
+    const OFFSET: u32 = CLUTTERED_OFFSET;
+
+    let mut image = cluttered_qr_image();
+    // Without the crop the finder patterns are found, but the symbol doesn't decode
+    let no_hints = HashMap::new();
+    let detected = Detector::new(image.getBlackMatrix())
+        .detect_with_hints(&no_hints)
+        .expect("detect");
+    assert!(qrcode_decoder::decode_bitmatrix_with_hints(detected.getBits(), &no_hints).is_err());
+
+    let result = QRCodeReader
+        .decode_with_hints(&mut image, &try_harder())
+        .expect("decode");
+    assert_eq!(CLUTTERED_CONTENTS, result.getText());
+
+    // Points are reported in the coordinates of the full image
+    let top_left = &result.getRXingResultPoints()[1];
+    let center = (OFFSET + 7 * MODULE / 2) as f32;
+    assert!((top_left.getX() - center).abs() < MODULE as f32);
+    assert!((top_left.getY() - center).abs() < MODULE as f32);
+}
+
+#[test]
+fn test_crop_and_retry_only_when_trying_harder() {
+    // The crop is another detection and decode, which a default decode doesn't spend
+    assert!(QRCodeReader.decode(&mut cluttered_qr_image()).is_err());
+    assert!(QRCodeReader
+        .decode_with_hints(&mut cluttered_qr_image(), &try_harder())
+        .is_ok());
+}
+
+fn try_harder() -> DecodingHintDictionary {
+    HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))])
+}
+
+/**
+ * Decodes with the given hints and a callback, returning the points passed to it and the points
+ * of the result.
+ */
+fn decode_with_point_callback(
+    image: &mut BinaryBitmap,
+    mut hints: DecodingHintDictionary,
+) -> (Vec<RXingResultPoint>, Vec<RXingResultPoint>) {
+    let found = Rc::new(RefCell::new(Vec::new()));
+    let sink = found.clone();
+    hints.insert(
+        DecodeHintType::NEED_RESULT_POINT_CALLBACK,
+        DecodeHintValue::NeedResultPointCallback(Rc::new(move |point: &dyn ResultPoint| {
+            sink.borrow_mut()
+                .push(RXingResultPoint::new(point.getX(), point.getY()))
+        })),
+    );
+    let result = QRCodeReader
+        .decode_with_hints(image, &hints)
+        .expect("decode");
+    let found = found.borrow().clone();
+    (found, result.getRXingResultPoints().to_vec())
+}
+
+#[test]
+fn test_crop_and_retry_reports_callback_points_in_image_coordinates() {
+    let (found, points) = decode_with_point_callback(&mut cluttered_qr_image(), try_harder());
+
+    // Each finder pattern of the result was passed to the callback where it is in the full image,
+    // by the detection on the crop that decoded
+    for point in &points {
+        assert!(
+            found
+                .iter()
+                .any(|p| (p.getX() - point.getX()).abs() < 1.0
+                    && (p.getY() - point.getY()).abs() < 1.0),
+            "{point} was not passed to the callback"
+        );
+    }
+    // Nothing was reported relative to the crop, which starts a few modules before the symbol
+    let near = (CLUTTERED_OFFSET - 4 * CLUTTERED_MODULE) as f32;
+    assert!(found.iter().all(|p| p.getX() > near && p.getY() > near));
+}
+
+/**
+ * Forwards to another source, adding up how many pixels are read through getMatrix(), which is
+ * how the binarizers read an image whole. Crops share the count.
+ */
+struct MatrixCountingLuminanceSource {
+    source: Box<dyn LuminanceSource>,
+    matrixPixels: Rc<Cell<usize>>,
+}
+
+impl LuminanceSource for MatrixCountingLuminanceSource {
+    fn getRow(&self, y: usize) -> Vec<u8> {
+        self.source.getRow(y)
+    }
+
+    fn getMatrix(&self) -> Vec<u8> {
+        self.matrixPixels
+            .set(self.matrixPixels.get() + self.getWidth() * self.getHeight());
+        self.source.getMatrix()
+    }
+
+    fn getWidth(&self) -> usize {
+        self.source.getWidth()
+    }
+
+    fn getHeight(&self) -> usize {
+        self.source.getHeight()
+    }
+
+    fn invert(&mut self) {
+        self.source.invert()
+    }
+
+    fn isCropSupported(&self) -> bool {
+        true
+    }
+
+    fn crop(
+        &self,
+        left: usize,
+        top: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Box<dyn LuminanceSource>, Exceptions> {
+        Ok(Box::new(Self {
+            source: self.source.crop(left, top, width, height)?,
+            matrixPixels: self.matrixPixels.clone(),
+        }))
+    }
+}
+
+const LARGE_SIZE: u32 = 4000;
+const LARGE_MODULE: u32 = 32;
+const LARGE_LEFT: u32 = 1700;
+const LARGE_TOP: u32 = 2100;
+const LARGE_CONTENTS: &str = "coarse to fine";
+
+/**
+ * A 16 megapixel image of a symbol with modules of LARGE_MODULE pixels, its top left corner at
+ * LARGE_LEFT, LARGE_TOP. Also returns the count of full resolution pixels its binarizers read.
+ */
+fn large_qr_image() -> (BinaryBitmap, Rc<Cell<usize>>) {
+    let code = QRCodeWriter
+        .encode_with_hints(
+            LARGE_CONTENTS,
+            &BarcodeFormat::QR_CODE,
+            0,
+            0,
+            &HashMap::from([(
+                EncodeHintType::MARGIN,
+                EncodeHintValue::Margin("0".to_owned()),
+            )]),
+        )
+        .expect("encode");
+    let pixels = render(LARGE_SIZE, LARGE_SIZE, |x, y| {
+        let (mx, my) = (
+            x.wrapping_sub(LARGE_LEFT) / LARGE_MODULE,
+            y.wrapping_sub(LARGE_TOP) / LARGE_MODULE,
+        );
+        mx < code.getWidth() && my < code.getHeight() && code.get(mx, my)
+    });
+    let matrixPixels = Rc::new(Cell::new(0));
+    let source = MatrixCountingLuminanceSource {
+        source: Box::new(Luma8LuminanceSource::new(pixels, LARGE_SIZE, LARGE_SIZE)),
+        matrixPixels: matrixPixels.clone(),
+    };
+    (
+        BinaryBitmap::new(Rc::new(HybridBinarizer::new(Box::new(source)))),
+        matrixPixels,
+    )
+}
+
+#[test]
+fn test_coarse_to_fine_detection_of_large_image() {
+    let (mut image, matrixPixels) = large_qr_image();
+    let result = QRCodeReader.decode(&mut image).expect("decode");
+    assert_eq!(LARGE_CONTENTS, result.getText());
+    let top_left = &result.getRXingResultPoints()[1];
+    let center = 7.0 * LARGE_MODULE as f32 / 2.0;
+    assert!((top_left.getX() - (LARGE_LEFT as f32 + center)).abs() < LARGE_MODULE as f32);
+    assert!((top_left.getY() - (LARGE_TOP as f32 + center)).abs() < LARGE_MODULE as f32);
+
+    // Only the crop around the symbol was binarized at full resolution, not the whole image
+    let total = (LARGE_SIZE * LARGE_SIZE) as usize;
+    assert!(matrixPixels.get() > 0);
+    assert!(
+        matrixPixels.get() < total / 10,
+        "binarized {} of {total} pixels",
+        matrixPixels.get()
+    );
+
+    // Turned off, the whole image is binarized
+    let (mut image, matrixPixels) = large_qr_image();
+    let hints = HashMap::from([(
+        DecodeHintType::QR_COARSE_DETECTION_MIN_PIXELS,
+        DecodeHintValue::QrCoarseDetectionMinPixels(u32::MAX),
+    )]);
+    assert!(QRCodeReader::decodeCoarseToFine(&mut image, &hints).is_none());
+    let result = QRCodeReader
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    assert_eq!(LARGE_CONTENTS, result.getText());
+    assert!(matrixPixels.get() >= total);
+
+    // Smaller images go straight to native resolution detection
+    let mut small = bitmap_for(&encode(LARGE_CONTENTS));
+    assert!(QRCodeReader::decodeCoarseToFine(&mut small, &HashMap::new()).is_none());
+}
+
+#[test]
+fn test_coarse_to_fine_reports_callback_points_in_image_coordinates() {
+    let (found, points) = decode_with_point_callback(&mut large_qr_image().0, HashMap::new());
+
+    // The finder patterns found on the crop are passed on where they are in the full image, and
+    // none are reported from the scaled down copy
+    for point in &points {
+        assert!(
+            found
+                .iter()
+                .any(|p| (p.getX() - point.getX()).abs() < 1.0
+                    && (p.getY() - point.getY()).abs() < 1.0),
+            "{point} was not passed to the callback"
+        );
+    }
+    assert!(found
+        .iter()
+        .all(|p| p.getX() > LARGE_LEFT as f32 && p.getY() > LARGE_TOP as f32));
+}
+
+#[test]
+fn test_relax_quiet_zone_reads_flush_crop() {
+    // A version 3 symbol with 4 pixel modules, cropped so tightly that half of its outermost
+    // modules are cut off along with the quiet zone
+    const MODULE: u32 = 4;
+    const CROP: u32 = 2;
+    let contents = "abcdefghijabcdefghijabcdefghijabcdefghij";
+    let code = QRCodeWriter
+        .encode_with_hints(
+            contents,
+            &BarcodeFormat::QR_CODE,
+            1,
+            1,
+            &HashMap::from([(
+                EncodeHintType::MARGIN,
+                EncodeHintValue::Margin("0".to_owned()),
+            )]),
+        )
+        .expect("encode");
+    assert_eq!(29, code.getWidth());
+    let size = code.getWidth() * MODULE - 2 * CROP;
+    let mut matrix = BitMatrix::new(size, size).expect("matrix");
+    for y in 0..size {
+        for x in 0..size {
+            if code.get((x + CROP) / MODULE, (y + CROP) / MODULE) {
+                matrix.set(x, y);
+            }
+        }
+    }
+
+    assert!(QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&matrix), &HashMap::new())
+        .is_err());
+
+    let hints = HashMap::from([(
+        DecodeHintType::RELAX_QUIET_ZONE,
+        DecodeHintValue::RelaxQuietZone(true),
+    )]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&matrix), &hints)
+        .expect("decode");
+    assert_eq!(contents, result.getText());
+}
+
+#[test]
+fn test_decode_rotated_by_arbitrary_angle() {
+    const SIZE: u32 = 400;
+    const MODULE: f32 = 6.0;
+
+    let contents = "rotated by thirty seven degrees";
+    let code = QRCodeWriter
+        .encode_with_hints(
+            contents,
+            &BarcodeFormat::QR_CODE,
+            0,
+            0,
+            &HashMap::from([(
+                EncodeHintType::MARGIN,
+                EncodeHintValue::Margin("0".to_owned()),
+            )]),
+        )
+        .expect("encode");
+    let dimension = code.getWidth() as f32;
+
+    // Render the symbol rotated 37 degrees about the image center by mapping each pixel back into
+    // module space
+    let (sin, cos) = 37f32.to_radians().sin_cos();
+    let center = SIZE as f32 / 2.0;
+    let mut image = render_bitmap(SIZE, SIZE, |x, y| {
+        let (dx, dy) = (x as f32 + 0.5 - center, y as f32 + 0.5 - center);
+        let mx = (dx * cos + dy * sin) / MODULE + dimension / 2.0;
+        let my = (-dx * sin + dy * cos) / MODULE + dimension / 2.0;
+        (0.0..dimension).contains(&mx)
+            && (0.0..dimension).contains(&my)
+            && code.get(mx as u32, my as u32)
+    });
+    let detected = Detector::new(image.getBlackMatrix())
+        .detect()
+        .expect("detect");
+    assert_eq!(
+        contents,
+        qrcode_decoder::decode_bitmatrix(detected.getBits())
+            .expect("decode")
+            .getText()
+    );
+
+    // The top left finder pattern is reported where the rotation put it
+    let result = QRCodeReader.decode(&mut image).expect("decode");
+    assert_eq!(contents, result.getText());
+    let offset = (3.5 - dimension / 2.0) * MODULE;
+    let top_left = &result.getRXingResultPoints()[1];
+    assert!((top_left.getX() - (center + offset * cos - offset * sin)).abs() < MODULE);
+    assert!((top_left.getY() - (center + offset * sin + offset * cos)).abs() < MODULE);
+}
+
+#[test]
+fn test_return_decoded_segments() {
+    // A long run of digits is worth a numeric segment of its own in a compact symbol
+    let contents = "0123456789012345678901234567890123456789hello, world";
+    let code = QRCodeWriter
+        .encode_with_hints(
+            contents,
+            &BarcodeFormat::QR_CODE,
+            0,
+            0,
+            &HashMap::from([(
+                EncodeHintType::QR_COMPACT,
+                EncodeHintValue::QrCompact("true".to_owned()),
+            )]),
+        )
+        .expect("encode");
+
+    let hints = HashMap::from([
+        (
+            DecodeHintType::PURE_BARCODE,
+            DecodeHintValue::PureBarcode(true),
+        ),
+        (
+            DecodeHintType::QR_RETURN_DECODED_SEGMENTS,
+            DecodeHintValue::QrReturnDecodedSegments(true),
+        ),
+    ]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&code), &hints)
+        .expect("decode");
+    assert_eq!(contents, result.getText());
+    let Some(RXingResultMetadataValue::QrDecodedSegments(segments)) = result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::QR_DECODED_SEGMENTS)
+    else {
+        panic!("missing decoded segments");
+    };
+    let segments = segments
+        .iter()
+        .map(|segment| (segment.getMode(), segment.getCharacters().clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(Mode::NUMERIC, 0..40), (Mode::BYTE, 40..52)], segments);
+
+    let result = QRCodeReader
+        .decode_with_hints(
+            &mut bitmap_for(&code),
+            &HashMap::from([(
+                DecodeHintType::PURE_BARCODE,
+                DecodeHintValue::PureBarcode(true),
+            )]),
+        )
+        .expect("decode");
+    assert!(!result
+        .getRXingResultMetadata()
+        .contains_key(&RXingResultMetadataType::QR_DECODED_SEGMENTS));
+}
+
+#[test]
+fn test_decoded_segment_positions() {
+    let contents = "0123456789012345678901234567890123456789hello, world";
+    let code = QRCodeWriter
+        .encode_with_hints(
+            contents,
+            &BarcodeFormat::QR_CODE,
+            0,
+            0,
+            &HashMap::from([(
+                EncodeHintType::QR_COMPACT,
+                EncodeHintValue::QrCompact("true".to_owned()),
+            )]),
+        )
+        .expect("encode");
+
+    let hints = HashMap::from([
+        (
+            DecodeHintType::PURE_BARCODE,
+            DecodeHintValue::PureBarcode(true),
+        ),
+        (
+            DecodeHintType::QR_RETURN_DECODED_SEGMENTS,
+            DecodeHintValue::QrReturnDecodedSegments(true),
+        ),
+    ]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&code), &hints)
+        .expect("decode");
+    let metadata = result.getRXingResultMetadata();
+    let Some(RXingResultMetadataValue::QrDecodedSegments(segments)) =
+        metadata.get(&RXingResultMetadataType::QR_DECODED_SEGMENTS)
+    else {
+        panic!("missing decoded segments");
+    };
+    let Some(RXingResultMetadataValue::DataCodewords(codewords)) =
+        metadata.get(&RXingResultMetadataType::DATA_CODEWORDS)
+    else {
+        panic!("missing data codewords");
+    };
+
+    // The numeric segment comes first: its mode indicator, a character count of 40 in the 10
+    // bits of a small version, then 13 groups of three digits and one more digit
+    let numeric = &segments[0];
+    assert_eq!(Mode::NUMERIC, numeric.getMode());
+    assert_eq!(&(0..40), numeric.getCharacters());
+    assert_eq!(&(0..4 + 10 + 13 * 10 + 4), numeric.getBits());
+    assert_eq!(0..19, numeric.getCodewords());
+    let mut bits = BitArray::new();
+    for codeword in &codewords[numeric.getCodewords()] {
+        bits.appendBits(*codeword, 8).expect("append");
+    }
+    let mut header = 0;
+    for i in 0..14 {
+        header = (header << 1) | bits.get(i) as u32;
+    }
+    assert_eq!((0b0001 << 10) | 40, header);
+
+    // The byte segment follows straight on, sharing the codeword the numeric one ends in
+    let byte = &segments[1];
+    assert_eq!(&(40..52), byte.getCharacters());
+    assert_eq!(numeric.getBits().end, byte.getBits().start);
+    assert_eq!(18, byte.getCodewords().start);
+}
+
+#[test]
+fn test_return_detection_on_failure() {
+    let (pixels, size) = unreadable_qr_pixels();
+    let bitmap = || BinaryBitmap::from_luma8(pixels.clone(), size, size).expect("bitmap");
+
+    assert!(QRCodeReader.decode(&mut bitmap()).is_err());
+
+    let hints = HashMap::from([(
+        DecodeHintType::RETURN_DETECTION_ON_FAILURE,
+        DecodeHintValue::ReturnDetectionOnFailure(true),
+    )]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap(), &hints)
+        .expect("partial result");
+    assert!(result.isPartial());
+    assert!(result.getText().is_empty());
+    assert!(result.getRXingResultPoints().len() >= 3);
+    let metadata = result.getRXingResultMetadata();
+    assert!(matches!(
+        metadata.get(&RXingResultMetadataType::DECODE_FAILURE),
+        Some(RXingResultMetadataValue::DecodeFailure(_))
+    ));
+    let Some(RXingResultMetadataValue::DetectionBounds(bounds)) =
+        metadata.get(&RXingResultMetadataType::DETECTION_BOUNDS)
+    else {
+        panic!("missing detection bounds");
+    };
+    // The symbol spans from 4 to 41 modules, clockwise from the top left
+    let (near, far) = (4.0 * 1.5, 41.0 * 1.5);
+    let expected = [(near, near), (far, near), (far, far), (near, far)];
+    assert_eq!(4, bounds.len());
+    for (corner, (x, y)) in bounds.iter().zip(expected) {
+        assert!(
+            (corner.getX() - x).abs() < 3.0 && (corner.getY() - y).abs() < 3.0,
+            "{corner} is not near ({x}, {y})"
+        );
+    }
+}
+
+#[test]
+fn test_partial_result_is_a_last_resort() {
+    let (qr, qr_size) = unreadable_qr_pixels();
+    let code128 = Code128Writer
+        .encode("NOT PARTIAL", &BarcodeFormat::CODE_128, 240, qr_size as i32)
+        .expect("encode");
+    // The unreadable symbol on the left, a readable barcode on the right
+    let (width, height) = (qr_size + 20 + code128.getWidth(), qr_size);
+    let mut pixels = vec![255u8; (width * height) as usize];
+    for y in 0..height {
+        for x in 0..qr_size {
+            pixels[(y * width + x) as usize] = qr[(y * qr_size + x) as usize];
+        }
+        for x in 0..code128.getWidth() {
+            if code128.get(x, y) {
+                pixels[(y * width + qr_size + 20 + x) as usize] = 0;
+            }
+        }
+    }
+    let bitmap = |pixels: &[u8], width: u32, height: u32| {
+        BinaryBitmap::from_luma8(pixels.to_vec(), width, height).expect("bitmap")
+    };
+    // Trying harder, the QR Code reader comes before the 1D readers
+    let hints = HashMap::from([
+        (
+            DecodeHintType::RETURN_DETECTION_ON_FAILURE,
+            DecodeHintValue::ReturnDetectionOnFailure(true),
+        ),
+        (DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true)),
+    ]);
+
+    let mut image = bitmap(&pixels, width, height);
+    assert!(QRCodeReader
+        .decode_with_hints(&mut image, &hints)
+        .expect("partial result")
+        .isPartial());
+    let result = MultiFormatReader::default()
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    assert!(!result.isPartial());
+    assert_eq!("NOT PARTIAL", result.getText());
+
+    let results = GenericMultipleBarcodeReader::new(MultiFormatReader::default())
+        .decode_multiple_with_hints(&mut bitmap(&pixels, width, height), &hints)
+        .expect("decode");
+    assert_eq!(1, results.len());
+    assert_eq!("NOT PARTIAL", results[0].getText());
+
+    // With nothing else to decode, the partial result is returned after all
+    let result = MultiFormatReader::default()
+        .decode_with_hints(&mut bitmap(&qr, qr_size, qr_size), &hints)
+        .expect("partial result");
+    assert!(result.isPartial());
+}
+
+fn print_quality_grade(matrix: &BitMatrix, dark: u8, light: u8) -> Option<char> {
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    let pixels = render_luma(width, height, dark, light, |x, y| matrix.get(x, y));
+    let mut image = BinaryBitmap::from_luma8(pixels, width, height).expect("bitmap");
+    let hints = HashMap::from([(
+        DecodeHintType::PRINT_QUALITY_GRADE,
+        DecodeHintValue::PrintQualityGrade(true),
+    )]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut image, &hints)
+        .expect("decode");
+    match result
+        .getRXingResultMetadata()
+        .get(&RXingResultMetadataType::PRINT_QUALITY_GRADE)
+    {
+        Some(RXingResultMetadataValue::PrintQualityGrade(grade)) => Some(*grade),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_print_quality_grade() {
+    let matrix = encode("http://www.example.com/grade");
+    assert_eq!(Some('A'), print_quality_grade(&matrix, 0, 255));
+    // Gray on gray still decodes, but the contrast is poor
+    assert_eq!(Some('D'), print_quality_grade(&matrix, 100, 170));
+
+    // Only graded when asked for
+    let result = QRCodeReader
+        .decode(&mut bitmap_for(&matrix))
+        .expect("decode");
+    assert!(!result
+        .getRXingResultMetadata()
+        .contains_key(&RXingResultMetadataType::PRINT_QUALITY_GRADE));
+}
+
+#[test]
+fn test_print_quality_grade_counts_corrected_errors() {
+    let mut matrix = encode("http://www.example.com/grade");
+    // Modules are 4 pixels wide with a 4 module quiet zone. Blot out a block of the data region
+    // in the lower right, away from the finder and timing patterns.
+    let dimension = matrix.getWidth() / 4 - 8;
+    for y in dimension - 6..dimension - 1 {
+        for x in dimension - 6..dimension - 1 {
+            for dy in 0..4 {
+                for dx in 0..4 {
+                    matrix.unset((x + 4) * 4 + dx, (y + 4) * 4 + dy);
+                }
+            }
+        }
+    }
+    let grade = print_quality_grade(&matrix, 0, 255).expect("graded");
+    assert!(grade > 'A', "damaged symbol graded {grade}");
+}
+
+#[test]
+fn test_grade_print_quality_takes_worst_parameter() {
+    use crate::common::{gradePrintQuality, gradeUnusedErrorCorrection};
+
+    assert_eq!('A', gradePrintQuality(0.9, 0.0, 1.0));
+    assert_eq!('B', gradePrintQuality(0.6, 0.0, 1.0));
+    assert_eq!('C', gradePrintQuality(0.9, 0.08, 1.0));
+    assert_eq!('F', gradePrintQuality(0.9, 0.0, 0.1));
+    assert_eq!('D', gradeUnusedErrorCorrection(0.25));
+}
+
+/**
+ * A symbol with a black caption banner printed right against its bottom edge, as tall as the
+ * finder patterns. The caption's white lettering is left out, as it never reaches the symbol.
+ */
+fn captioned_symbol(contents: &str) -> BitMatrix {
+    let symbol = encode(contents);
+    let [left, top, width, height] = symbol.getEnclosingRectangle().expect("symbol");
+    let mut matrix = BitMatrix::new(symbol.getWidth(), symbol.getHeight() + 40).unwrap();
+    for y in 0..symbol.getHeight() {
+        for x in 0..symbol.getWidth() {
+            if symbol.get(x, y) {
+                matrix.set(x, y);
+            }
+        }
+    }
+    matrix
+        .setRegion(left, top + height, width, width * 7 / 25)
+        .unwrap();
+    matrix
+}
+
+#[test]
+fn test_caption_touching_symbol() {
+    let matrix = captioned_symbol("http://www.example.com/caption");
+    // The bottom left finder pattern runs into the caption
+    assert!(QRCodeReader.decode(&mut bitmap_for(&matrix)).is_err());
+
+    let hints = HashMap::from([(DecodeHintType::TRY_HARDER, DecodeHintValue::TryHarder(true))]);
+    let result = QRCodeReader
+        .decode_with_hints(&mut bitmap_for(&matrix), &hints)
+        .expect("decode");
+    assert_eq!("http://www.example.com/caption", result.getText());
+}
@@ -148,6 +148,15 @@ impl RXingResult {
         &self.resultMetadata
     }
 
+    /**
+     * @return true for a partial result, of a barcode which was located but couldn't be decoded.
+     *  It has no text; see {@link DecodeHintType#RETURN_DETECTION_ON_FAILURE}.
+     */
+    pub fn isPartial(&self) -> bool {
+        self.resultMetadata
+            .contains_key(&RXingResultMetadataType::DECODE_FAILURE)
+    }
+
     pub fn putMetadata(
         &mut self,
         md_type: RXingResultMetadataType,
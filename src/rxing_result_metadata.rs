@@ -18,7 +18,10 @@
 
 use std::rc::Rc;
 
-use crate::pdf417::PDF417RXingResultMetadata;
+use crate::{
+    common::BitMatrix, pdf417::PDF417RXingResultMetadata, qrcode::decoder::QRCodeDecodedSegment,
+    RXingResultPoint,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -112,6 +115,100 @@ pub enum RXingResultMetadataType {
     IS_MIRRORED,
 
     CONTENT_TYPE,
+
+    /**
+     * The original bytes of each segment which was decoded from Shift_JIS, in order. Only
+     * present when requested with {@link DecodeHintType#QR_PRESERVE_SHIFT_JIS_BYTES}.
+     */
+    SHIFT_JIS_BYTES,
+
+    /**
+     * The error corrected data codewords the symbol was decoded from, without any error
+     * correction codewords. For QR Code these are the data codewords of each block, block after
+     * block, i.e. already de-interleaved, each in the range [0,255]. For PDF417 they are in symbol
+     * order starting with the symbol length descriptor, each in the range [0,928].
+     */
+    DATA_CODEWORDS,
+
+    /**
+     * PDF417 codeword positions which were read with several equally likely values, as the
+     * index of the codeword in symbol order together with the candidate values. Only present
+     * when requested with {@link DecodeHintType#PDF417_REPORT_AMBIGUOUS_CODEWORDS}.
+     */
+    PDF417_AMBIGUOUS_CODEWORDS,
+
+    /**
+     * Values of PDF417 codewords which were read, but whose row couldn't be placed in the symbol,
+     * so that they were left out of decoding. Only present when requested with
+     * {@link DecodeHintType#PDF417_REPORT_AMBIGUOUS_CODEWORDS}.
+     */
+    PDF417_UNPLACED_CODEWORDS,
+
+    /**
+     * Both values a Pharmacode symbol reads as, left to right first and then right to left, when
+     * they differ. Pharmacode has no start or stop pattern to tell the direction apart.
+     */
+    PHARMACODE_CANDIDATES,
+
+    /**
+     * The binarized image the barcode was decoded from, which decodes to the same result. Only
+     * present when requested with {@link DecodeHintType#RETURN_BINARIZED_MATRIX}, and not for 1D
+     * barcodes, which are decoded from single rows.
+     */
+    BINARIZED_MATRIX,
+
+    /**
+     * The data segments of a QR Code in order, each with its mode, the characters of the text it
+     * decoded to, the bits of the data codewords it was read from and its ECI designator, which
+     * maps parts of the text back to the symbol. Only present when requested with
+     * {@link DecodeHintType#QR_RETURN_DECODED_SEGMENTS}.
+     */
+    QR_DECODED_SEGMENTS,
+
+    /**
+     * The corners of the area a barcode was located in, clockwise from the top left corner of the
+     * symbol. Only present on partial results, see
+     * {@link DecodeHintType#RETURN_DETECTION_ON_FAILURE}.
+     */
+    DETECTION_BOUNDS,
+
+    /**
+     * Why a located barcode couldn't be decoded, which marks a result as partial: it has no text.
+     * Only present when requested with {@link DecodeHintType#RETURN_DETECTION_ON_FAILURE}.
+     */
+    DECODE_FAILURE,
+
+    /**
+     * The index of the image the barcode was decoded from, among the orientations of a frame
+     * passed to {@link MultiFormatReader#decode_rotations}.
+     */
+    ROTATION_INDEX,
+
+    /**
+     * The number of modules of the timing patterns of a QR Code which were sampled with the wrong
+     * color, out of twice the symbol dimension less 16. A high count points to blur or a poor
+     * perspective transform, even when error correction still recovered the data.
+     */
+    QR_TIMING_PATTERN_MISMATCHES,
+
+    /**
+     * Set when a Code 128 symbol contains FNC2, which asks for its text to be buffered and
+     * prepended to the text of the next symbol. See {@link Code128Reader#joinAppendedMessages}.
+     */
+    CODE_128_MESSAGE_APPEND,
+
+    /**
+     * Set when a Code 128 symbol contains FNC3, which marks it as reader initialization or
+     * programming data rather than a message to pass on.
+     */
+    CODE_128_READER_INITIALIZATION,
+
+    /**
+     * A simplified ISO/IEC 15415 style print quality grade of a QR Code, 'A' to 'D' or 'F', the
+     * worst of the grades for symbol contrast, timing pattern damage and unused error correction.
+     * Only reported when asked for with {@link DecodeHintType#PRINT_QUALITY_GRADE}.
+     */
+    PRINT_QUALITY_GRADE,
 }
 
 impl From<String> for RXingResultMetadataType {
@@ -141,6 +238,36 @@ impl From<String> for RXingResultMetadataType {
             }
             "IS_MIRRORED" | "ISMIRRORED" => RXingResultMetadataType::IS_MIRRORED,
             "CONTENT_TYPE" | "CONTENTTYPE" => RXingResultMetadataType::CONTENT_TYPE,
+            "SHIFT_JIS_BYTES" | "SHIFTJISBYTES" => RXingResultMetadataType::SHIFT_JIS_BYTES,
+            "DATA_CODEWORDS" | "DATACODEWORDS" => RXingResultMetadataType::DATA_CODEWORDS,
+            "PDF417_AMBIGUOUS_CODEWORDS" | "PDF417AMBIGUOUSCODEWORDS" => {
+                RXingResultMetadataType::PDF417_AMBIGUOUS_CODEWORDS
+            }
+            "PDF417_UNPLACED_CODEWORDS" | "PDF417UNPLACEDCODEWORDS" => {
+                RXingResultMetadataType::PDF417_UNPLACED_CODEWORDS
+            }
+            "PHARMACODE_CANDIDATES" | "PHARMACODECANDIDATES" => {
+                RXingResultMetadataType::PHARMACODE_CANDIDATES
+            }
+            "BINARIZED_MATRIX" | "BINARIZEDMATRIX" => RXingResultMetadataType::BINARIZED_MATRIX,
+            "QR_DECODED_SEGMENTS" | "QRDECODEDSEGMENTS" => {
+                RXingResultMetadataType::QR_DECODED_SEGMENTS
+            }
+            "DETECTION_BOUNDS" | "DETECTIONBOUNDS" => RXingResultMetadataType::DETECTION_BOUNDS,
+            "DECODE_FAILURE" | "DECODEFAILURE" => RXingResultMetadataType::DECODE_FAILURE,
+            "ROTATION_INDEX" | "ROTATIONINDEX" => RXingResultMetadataType::ROTATION_INDEX,
+            "QR_TIMING_PATTERN_MISMATCHES" | "QRTIMINGPATTERNMISMATCHES" => {
+                RXingResultMetadataType::QR_TIMING_PATTERN_MISMATCHES
+            }
+            "CODE_128_MESSAGE_APPEND" | "CODE128MESSAGEAPPEND" => {
+                RXingResultMetadataType::CODE_128_MESSAGE_APPEND
+            }
+            "CODE_128_READER_INITIALIZATION" | "CODE128READERINITIALIZATION" => {
+                RXingResultMetadataType::CODE_128_READER_INITIALIZATION
+            }
+            "PRINT_QUALITY_GRADE" | "PRINTQUALITYGRADE" => {
+                RXingResultMetadataType::PRINT_QUALITY_GRADE
+            }
             _ => RXingResultMetadataType::OTHER,
         }
     }
@@ -229,4 +356,98 @@ pub enum RXingResultMetadataValue {
     IsMirrored(bool),
 
     ContentType(String),
+
+    /**
+     * The original bytes of each segment which was decoded from Shift_JIS, in order. Only
+     * present when requested with {@link DecodeHintType#QR_PRESERVE_SHIFT_JIS_BYTES}.
+     */
+    ShiftJisBytes(Vec<Vec<u8>>),
+
+    /**
+     * The error corrected data codewords the symbol was decoded from, without any error
+     * correction codewords. For QR Code these are the data codewords of each block, block after
+     * block, i.e. already de-interleaved, each in the range [0,255]. For PDF417 they are in symbol
+     * order starting with the symbol length descriptor, each in the range [0,928].
+     */
+    DataCodewords(Vec<u32>),
+
+    /**
+     * PDF417 codeword positions which were read with several equally likely values, as the
+     * index of the codeword in symbol order together with the candidate values. Only present
+     * when requested with {@link DecodeHintType#PDF417_REPORT_AMBIGUOUS_CODEWORDS}.
+     */
+    Pdf417AmbiguousCodewords(Vec<(u32, Vec<u32>)>),
+
+    /**
+     * Values of PDF417 codewords which were read, but whose row couldn't be placed in the symbol,
+     * so that they were left out of decoding. Only present when requested with
+     * {@link DecodeHintType#PDF417_REPORT_AMBIGUOUS_CODEWORDS}.
+     */
+    Pdf417UnplacedCodewords(Vec<u32>),
+
+    /**
+     * Both values a Pharmacode symbol reads as, left to right first and then right to left, when
+     * they differ. Pharmacode has no start or stop pattern to tell the direction apart.
+     */
+    PharmacodeCandidates(Vec<u32>),
+
+    /**
+     * The binarized image the barcode was decoded from, which decodes to the same result. Only
+     * present when requested with {@link DecodeHintType#RETURN_BINARIZED_MATRIX}, and not for 1D
+     * barcodes, which are decoded from single rows.
+     */
+    BinarizedMatrix(BitMatrix),
+
+    /**
+     * The data segments of a QR Code in order, each with its mode, the characters of the text it
+     * decoded to, the bits of the data codewords it was read from and its ECI designator, which
+     * maps parts of the text back to the symbol. Only present when requested with
+     * {@link DecodeHintType#QR_RETURN_DECODED_SEGMENTS}.
+     */
+    QrDecodedSegments(Vec<QRCodeDecodedSegment>),
+
+    /**
+     * The corners of the area a barcode was located in, clockwise from the top left corner of the
+     * symbol. Only present on partial results, see
+     * {@link DecodeHintType#RETURN_DETECTION_ON_FAILURE}.
+     */
+    DetectionBounds(Vec<RXingResultPoint>),
+
+    /**
+     * Why a located barcode couldn't be decoded, which marks a result as partial: it has no text.
+     * Only present when requested with {@link DecodeHintType#RETURN_DETECTION_ON_FAILURE}.
+     */
+    DecodeFailure(String),
+
+    /**
+     * The index of the image the barcode was decoded from, among the orientations of a frame
+     * passed to {@link MultiFormatReader#decode_rotations}.
+     */
+    RotationIndex(usize),
+
+    /**
+     * The number of modules of the timing patterns of a QR Code which were sampled with the wrong
+     * color, out of twice the symbol dimension less 16. A high count points to blur or a poor
+     * perspective transform, even when error correction still recovered the data.
+     */
+    QrTimingPatternMismatches(u32),
+
+    /**
+     * Set when a Code 128 symbol contains FNC2, which asks for its text to be buffered and
+     * prepended to the text of the next symbol. See {@link Code128Reader#joinAppendedMessages}.
+     */
+    Code128MessageAppend(bool),
+
+    /**
+     * Set when a Code 128 symbol contains FNC3, which marks it as reader initialization or
+     * programming data rather than a message to pass on.
+     */
+    Code128ReaderInitialization(bool),
+
+    /**
+     * A simplified ISO/IEC 15415 style print quality grade of a QR Code, 'A' to 'D' or 'F', the
+     * worst of the grades for symbol contrast, timing pattern damage and unused error correction.
+     * Only reported when asked for with {@link DecodeHintType#PRINT_QUALITY_GRADE}.
+     */
+    PrintQualityGrade(char),
 }
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::BitMatrix, qrcode::QRCodeWriter, BarcodeFormat, BinaryBitmap, EncodeHintType,
+    EncodeHintValue, Writer,
+};
+
+/**
+ * Renders an image of the given size as 8 bit grayscale pixels in row major order, using the
+ * dark gray level where isDark(x, y) holds and the light one elsewhere.
+ */
+pub(crate) fn render_luma(
+    width: u32,
+    height: u32,
+    dark: u8,
+    light: u8,
+    isDark: impl Fn(u32, u32) -> bool,
+) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.push(if isDark(x, y) { dark } else { light });
+        }
+    }
+    pixels
+}
+
+/**
+ * Renders an image of the given size black on white, black where isDark(x, y) holds.
+ */
+pub(crate) fn render(width: u32, height: u32, isDark: impl Fn(u32, u32) -> bool) -> Vec<u8> {
+    render_luma(width, height, 0, 255, isDark)
+}
+
+/**
+ * Renders the matrix black on white, one pixel per bit.
+ */
+pub(crate) fn matrix_pixels(matrix: &BitMatrix) -> Vec<u8> {
+    render(matrix.getWidth(), matrix.getHeight(), |x, y| {
+        matrix.get(x, y)
+    })
+}
+
+/**
+ * Renders an image like {@link #render} and binarizes it with a {@link HybridBinarizer}.
+ */
+pub(crate) fn render_bitmap(
+    width: u32,
+    height: u32,
+    isDark: impl Fn(u32, u32) -> bool,
+) -> BinaryBitmap {
+    BinaryBitmap::from_luma8(render(width, height, isDark), width, height).expect("bitmap")
+}
+
+/**
+ * Renders the matrix like {@link #matrix_pixels} and binarizes it with a {@link HybridBinarizer}.
+ */
+pub(crate) fn bitmap_for(matrix: &BitMatrix) -> BinaryBitmap {
+    render_bitmap(matrix.getWidth(), matrix.getHeight(), |x, y| {
+        matrix.get(x, y)
+    })
+}
+
+/**
+ * Renders a QR Code whose finder patterns can be found but whose data can't be read, as 8 bit
+ * grayscale pixels of a square image. Also returns the size of its sides.
+ */
+pub(crate) fn unreadable_qr_pixels() -> (Vec<u8>, u32) {
+    let code = QRCodeWriter
+        .encode_with_hints(
+            "The quick brown fox jumps over the lazy dog, again and again and again: 0123456789",
+            &BarcodeFormat::QR_CODE,
+            0,
+            0,
+            &HashMap::from([(
+                EncodeHintType::MARGIN,
+                EncodeHintValue::Margin("4".to_owned()),
+            )]),
+        )
+        .expect("encode");
+    let modules = code.getWidth();
+    assert_eq!(45, modules);
+
+    // Render it at 6 pixels per module, then average blocks of 4 by 4 pixels: at 1.5 pixels per
+    // module the finder patterns can still be found, but the data can't be read.
+    let (scale, factor) = (6, 4);
+    let size = modules * scale / factor;
+    let mut pixels = Vec::with_capacity((size * size) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let mut sum = 0;
+            for dy in 0..factor {
+                for dx in 0..factor {
+                    let px = (x * factor + dx) / scale;
+                    let py = (y * factor + dy) / scale;
+                    sum += if code.get(px, py) { 0 } else { 255 };
+                }
+            }
+            pixels.push((sum / (factor * factor)) as u8);
+        }
+    }
+    (pixels, size)
+}
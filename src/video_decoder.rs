@@ -0,0 +1,101 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    common::HybridBinarizer,
+    multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+    BarcodeFormat, BinaryBitmap, DecodingHintDictionary, LuminanceSource, MultiFormatReader,
+    RXingResult,
+};
+
+/**
+ * Decodes the frames of a video or camera feed, reporting each barcode once while it stays in
+ * view. A barcode is reported again only after it has been missing from the frames for longer
+ * than the dedup window.
+ */
+pub struct VideoDecoder {
+    reader: GenericMultipleBarcodeReader<MultiFormatReader>,
+    hints: DecodingHintDictionary,
+    dedupWindow: Duration,
+    lastSeen: HashMap<(String, BarcodeFormat), Instant>,
+}
+
+impl VideoDecoder {
+    /**
+     * @param hints The hints to decode every frame with
+     * @param dedupWindow How long a barcode must be out of view before it's reported again
+     */
+    pub fn new(hints: DecodingHintDictionary, dedupWindow: Duration) -> Self {
+        Self {
+            reader: GenericMultipleBarcodeReader::new(MultiFormatReader::default()),
+            hints,
+            dedupWindow,
+            lastSeen: HashMap::new(),
+        }
+    }
+
+    /**
+     * Decodes a frame captured now.
+     *
+     * @return the barcodes in the frame which weren't seen within the dedup window
+     */
+    pub fn process_frame(&mut self, source: Box<dyn LuminanceSource>) -> Vec<RXingResult> {
+        self.process_frame_at(source, Instant::now())
+    }
+
+    /**
+     * Decodes a frame captured at the given time, for frames which carry their own timestamps.
+     * A frame stamped before the last sighting of a barcode counts as seeing it again at once.
+     *
+     * @param timestamp The capture time of the frame
+     * @return the barcodes in the frame which weren't seen within the dedup window
+     */
+    pub fn process_frame_at(
+        &mut self,
+        source: Box<dyn LuminanceSource>,
+        timestamp: Instant,
+    ) -> Vec<RXingResult> {
+        let window = self.dedupWindow;
+        self.lastSeen
+            .retain(|_, seen| timestamp.saturating_duration_since(*seen) <= window);
+
+        let mut image = BinaryBitmap::new(Rc::new(HybridBinarizer::new(source)));
+        // a frame without any barcode is the common case, not an error
+        let Ok(results) = self
+            .reader
+            .decode_multiple_with_hints(&mut image, &self.hints)
+        else {
+            return Vec::new();
+        };
+
+        let mut newResults = Vec::new();
+        for result in results {
+            if result.isPartial() {
+                continue;
+            }
+            let key = (result.getText().to_owned(), *result.getBarcodeFormat());
+            match self.lastSeen.entry(key) {
+                // a late frame mustn't move the last sighting back
+                Entry::Occupied(mut seen) => {
+                    let seen = seen.get_mut();
+                    *seen = (*seen).max(timestamp);
+                }
+                Entry::Vacant(seen) => {
+                    seen.insert(timestamp);
+                    newResults.push(result);
+                }
+            }
+        }
+        newResults
+    }
+
+    /**
+     * Forgets the barcodes seen so far, so the next frame reports everything in it.
+     */
+    pub fn reset(&mut self) {
+        self.lastSeen.clear();
+    }
+}
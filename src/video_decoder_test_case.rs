@@ -0,0 +1,113 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    qrcode::QRCodeWriter, shared_test_methods::matrix_pixels, BarcodeFormat, Luma8LuminanceSource,
+    LuminanceSource, VideoDecoder, Writer,
+};
+
+fn qr_frame(contents: &str) -> Box<dyn LuminanceSource> {
+    let matrix = QRCodeWriter
+        .encode(contents, &BarcodeFormat::QR_CODE, 200, 200)
+        .expect("encode");
+    Box::new(Luma8LuminanceSource::new(
+        matrix_pixels(&matrix),
+        matrix.getWidth(),
+        matrix.getHeight(),
+    ))
+}
+
+/// The time a number of milliseconds after the given start
+fn at(start: Instant, millis: u64) -> Instant {
+    start + Duration::from_millis(millis)
+}
+
+fn blank_frame() -> Box<dyn LuminanceSource> {
+    Box::new(Luma8LuminanceSource::new(vec![255; 200 * 200], 200, 200))
+}
+
+#[test]
+fn test_consecutive_frames_reported_once() {
+    let mut decoder = VideoDecoder::new(HashMap::new(), Duration::from_millis(500));
+    let start = Instant::now();
+
+    let first = decoder.process_frame_at(qr_frame("video frame"), at(start, 0));
+    assert_eq!(1, first.len());
+    assert_eq!("video frame", first[0].getText());
+    assert_eq!(&BarcodeFormat::QR_CODE, first[0].getBarcodeFormat());
+
+    for timestamp in [33, 66, 100, 400, 800] {
+        assert!(decoder
+            .process_frame_at(qr_frame("video frame"), at(start, timestamp))
+            .is_empty());
+    }
+
+    // a frame stamped out of order is still within the window
+    assert!(decoder
+        .process_frame_at(qr_frame("video frame"), at(start, 200))
+        .is_empty());
+
+    let other = decoder.process_frame_at(qr_frame("another code"), at(start, 833));
+    assert_eq!(1, other.len());
+    assert_eq!("another code", other[0].getText());
+}
+
+#[test]
+fn test_out_of_order_frame_keeps_latest_sighting() {
+    let mut decoder = VideoDecoder::new(HashMap::new(), Duration::from_millis(500));
+    let start = Instant::now();
+
+    assert_eq!(
+        1,
+        decoder
+            .process_frame_at(qr_frame("video frame"), at(start, 0))
+            .len()
+    );
+    for timestamp in [400, 800, 200] {
+        assert!(decoder
+            .process_frame_at(qr_frame("video frame"), at(start, timestamp))
+            .is_empty());
+    }
+
+    // last seen at 800, not at the late 200 frame
+    assert!(decoder
+        .process_frame_at(qr_frame("video frame"), at(start, 833))
+        .is_empty());
+}
+
+#[test]
+fn test_reported_again_after_window() {
+    let mut decoder = VideoDecoder::new(HashMap::new(), Duration::from_millis(500));
+    let start = Instant::now();
+
+    assert_eq!(
+        1,
+        decoder
+            .process_frame_at(qr_frame("video frame"), at(start, 0))
+            .len()
+    );
+    assert!(decoder
+        .process_frame_at(blank_frame(), at(start, 300))
+        .is_empty());
+    assert!(decoder
+        .process_frame_at(qr_frame("video frame"), at(start, 400))
+        .is_empty());
+
+    // out of view for longer than the window since it was last seen at 400
+    assert_eq!(
+        1,
+        decoder
+            .process_frame_at(qr_frame("video frame"), at(start, 1000))
+            .len()
+    );
+
+    decoder.reset();
+    assert_eq!(
+        1,
+        decoder
+            .process_frame_at(qr_frame("video frame"), at(start, 1033))
+            .len()
+    );
+}
@@ -25,9 +25,10 @@ use std::{
 
 use encoding::Encoding;
 use rxing::{
-    common::HybridBinarizer, pdf417::PDF417RXingResultMetadata, BarcodeFormat, BinaryBitmap,
-    BufferedImageLuminanceSource, DecodeHintType, DecodeHintValue, RXingResultMetadataType,
-    RXingResultMetadataValue, Reader,
+    common::{BitMatrix, HybridBinarizer},
+    pdf417::PDF417RXingResultMetadata,
+    BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintType, DecodeHintValue,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 use super::TestRXingResult;
@@ -235,11 +236,71 @@ impl<T: Reader> AbstractBlackBoxTestCase<T> {
                         RXingResultMetadataValue::SymbologyIdentifier(v)
                     }
                     RXingResultMetadataType::IS_MIRRORED => {
-                        RXingResultMetadataValue::IsMirrored(v.parse().unwrap())
+                        RXingResultMetadataValue::IsMirrored(v.parse().expect("is mirrored"))
                     }
                     RXingResultMetadataType::CONTENT_TYPE => {
                         RXingResultMetadataValue::ContentType(v)
                     }
+                    RXingResultMetadataType::SHIFT_JIS_BYTES => {
+                        RXingResultMetadataValue::ShiftJisBytes(vec![v.into_bytes()])
+                    }
+                    RXingResultMetadataType::DATA_CODEWORDS => {
+                        RXingResultMetadataValue::DataCodewords(
+                            v.split(',')
+                                .map(|c| c.trim().parse().expect("data codeword"))
+                                .collect(),
+                        )
+                    }
+                    RXingResultMetadataType::PDF417_AMBIGUOUS_CODEWORDS
+                    | RXingResultMetadataType::QR_DECODED_SEGMENTS
+                    | RXingResultMetadataType::DETECTION_BOUNDS => {
+                        panic!("expected {new_k:?} metadata can't be given in a properties file")
+                    }
+                    RXingResultMetadataType::PDF417_UNPLACED_CODEWORDS => {
+                        RXingResultMetadataValue::Pdf417UnplacedCodewords(
+                            v.split(',')
+                                .map(|c| c.trim().parse().expect("unplaced codeword"))
+                                .collect(),
+                        )
+                    }
+                    RXingResultMetadataType::PHARMACODE_CANDIDATES => {
+                        RXingResultMetadataValue::PharmacodeCandidates(
+                            v.split(',')
+                                .map(|c| c.trim().parse().expect("pharmacode candidate"))
+                                .collect(),
+                        )
+                    }
+                    RXingResultMetadataType::BINARIZED_MATRIX => {
+                        RXingResultMetadataValue::BinarizedMatrix(
+                            BitMatrix::parse_strings(&v, "X", " ").expect("binarized matrix"),
+                        )
+                    }
+                    RXingResultMetadataType::DECODE_FAILURE => {
+                        RXingResultMetadataValue::DecodeFailure(v)
+                    }
+                    RXingResultMetadataType::ROTATION_INDEX => {
+                        RXingResultMetadataValue::RotationIndex(v.parse().expect("rotation index"))
+                    }
+                    RXingResultMetadataType::QR_TIMING_PATTERN_MISMATCHES => {
+                        RXingResultMetadataValue::QrTimingPatternMismatches(
+                            v.parse().expect("timing pattern mismatches"),
+                        )
+                    }
+                    RXingResultMetadataType::CODE_128_MESSAGE_APPEND => {
+                        RXingResultMetadataValue::Code128MessageAppend(
+                            v.parse().expect("message append"),
+                        )
+                    }
+                    RXingResultMetadataType::CODE_128_READER_INITIALIZATION => {
+                        RXingResultMetadataValue::Code128ReaderInitialization(
+                            v.parse().expect("reader initialization"),
+                        )
+                    }
+                    RXingResultMetadataType::PRINT_QUALITY_GRADE => {
+                        RXingResultMetadataValue::PrintQualityGrade(
+                            v.parse().expect("print quality grade"),
+                        )
+                    }
                 };
                 expected_metadata.insert(new_k, new_v);
             }